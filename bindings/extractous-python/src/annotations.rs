@@ -0,0 +1,45 @@
+use crate::ecore;
+use pyo3::prelude::*;
+
+/// A comment or annotation of a document, returned by `Extractor.extract_file_annotations`
+#[pyclass(get_all)]
+#[derive(Clone)]
+pub struct Annotation {
+    /// Kind of the annotation: "comment" for the comments of office documents, and the snake case
+    /// subtype of pdf annotations, e.g. "note", "highlight" or "free_text"
+    pub kind: String,
+    /// Name of the author of the annotation
+    pub author: Option<String>,
+    /// Date of the annotation, in ISO 8601 when the document has a valid date
+    pub date: Option<String>,
+    /// Text of the annotation
+    pub text: String,
+    /// The commented text of docx documents, the text under the pdf highlights and the cell
+    /// reference of xlsx comments, e.g. "Sheet1!B2"
+    pub anchor_text: Option<String>,
+    /// Page of pdf annotations, slide of pptx comments and sheet of xlsx comments, starting at 1
+    pub page: Option<u32>,
+}
+
+impl From<ecore::Annotation> for Annotation {
+    fn from(annotation: ecore::Annotation) -> Self {
+        Self {
+            kind: annotation.kind,
+            author: annotation.author,
+            date: annotation.date,
+            text: annotation.text,
+            anchor_text: annotation.anchor_text,
+            page: annotation.page,
+        }
+    }
+}
+
+#[pymethods]
+impl Annotation {
+    fn __repr__(&self) -> String {
+        format!(
+            "Annotation(kind={:?}, author={:?}, date={:?}, text={:?}, anchor_text={:?}, page={:?})",
+            self.kind, self.author, self.date, self.text, self.anchor_text, self.page
+        )
+    }
+}
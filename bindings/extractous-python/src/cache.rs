@@ -0,0 +1,54 @@
+use crate::ecore;
+use pyo3::exceptions::PyIOError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+/// On-disk cache of the extractions to string, backed by a local directory
+///
+/// Set it with `Extractor.set_cache`. The extractions are keyed by the hash of the document
+/// bytes and of the extractor configuration. Once the entries exceed `max_size` bytes
+/// (1 GiB by default), the least recently used ones are removed.
+#[pyclass]
+#[derive(Clone)]
+pub struct ExtractionCache(ecore::ExtractionCache);
+
+impl From<ExtractionCache> for ecore::ExtractionCache {
+    fn from(cache: ExtractionCache) -> Self {
+        cache.0
+    }
+}
+
+#[pymethods]
+impl ExtractionCache {
+    /// Creates a cache backed by the directory, which is created if missing
+    #[new]
+    #[pyo3(signature = (dir, max_size=None))]
+    pub fn new(dir: std::path::PathBuf, max_size: Option<u64>) -> PyResult<Self> {
+        let mut cache = ecore::ExtractionCache::new(dir)
+            .map_err(|e| PyErr::new::<PyIOError, _>(format!("{}", e)))?;
+        if let Some(max_size) = max_size {
+            cache = cache.set_max_size(max_size);
+        }
+        Ok(Self(cache))
+    }
+
+    /// The hits, misses and evictions of the cache, and the number of entries and their size
+    /// in bytes, as a dict
+    pub fn stats<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let stats = self.0.stats();
+        let dict = PyDict::new(py);
+        dict.set_item("hits", stats.hits)?;
+        dict.set_item("misses", stats.misses)?;
+        dict.set_item("evictions", stats.evictions)?;
+        dict.set_item("entries", stats.entries)?;
+        dict.set_item("size", stats.size)?;
+        Ok(dict)
+    }
+
+    /// Removes all the entries of the cache
+    pub fn clear(&self) -> PyResult<()> {
+        self.0
+            .clear()
+            .map_err(|e| PyErr::new::<PyIOError, _>(format!("{}", e)))
+    }
+}
@@ -1,4 +1,5 @@
-use pyo3::{pyclass, pymethods, PyResult};
+use pyo3::exceptions::PyValueError;
+use pyo3::{pyclass, pymethods, PyErr, PyResult};
 
 use crate::ecore;
 
@@ -26,6 +27,90 @@ impl From<PdfOcrStrategy> for ecore::PdfOcrStrategy {
     }
 }
 
+/// Color type of the images pages are rendered to before OCR
+#[pyclass(eq, eq_int)]
+#[derive(Clone, PartialEq)]
+#[allow(non_camel_case_types)]
+pub enum PdfOcrImageType {
+    BINARY,
+    GRAY,
+    RGB,
+    ARGB,
+}
+
+impl From<PdfOcrImageType> for ecore::PdfOcrImageType {
+    fn from(image_type: PdfOcrImageType) -> Self {
+        match image_type {
+            PdfOcrImageType::BINARY => ecore::PdfOcrImageType::BINARY,
+            PdfOcrImageType::GRAY => ecore::PdfOcrImageType::GRAY,
+            PdfOcrImageType::RGB => ecore::PdfOcrImageType::RGB,
+            PdfOcrImageType::ARGB => ecore::PdfOcrImageType::ARGB,
+        }
+    }
+}
+
+/// File format of the images pages are rendered to before OCR
+#[pyclass(eq, eq_int)]
+#[derive(Clone, PartialEq)]
+#[allow(non_camel_case_types)]
+pub enum PdfOcrImageFormat {
+    PNG,
+    TIFF,
+    JPEG,
+}
+
+impl From<PdfOcrImageFormat> for ecore::PdfOcrImageFormat {
+    fn from(format: PdfOcrImageFormat) -> Self {
+        match format {
+            PdfOcrImageFormat::PNG => ecore::PdfOcrImageFormat::PNG,
+            PdfOcrImageFormat::TIFF => ecore::PdfOcrImageFormat::TIFF,
+            PdfOcrImageFormat::JPEG => ecore::PdfOcrImageFormat::JPEG,
+        }
+    }
+}
+
+/// Which elements of a page are rendered before OCR
+#[pyclass(eq, eq_int)]
+#[derive(Clone, PartialEq)]
+#[allow(non_camel_case_types)]
+pub enum PdfOcrRenderingStrategy {
+    NO_TEXT,
+    TEXT_ONLY,
+    VECTOR_GRAPHICS_ONLY,
+    ALL,
+}
+
+impl From<PdfOcrRenderingStrategy> for ecore::PdfOcrRenderingStrategy {
+    fn from(strategy: PdfOcrRenderingStrategy) -> Self {
+        match strategy {
+            PdfOcrRenderingStrategy::NO_TEXT => ecore::PdfOcrRenderingStrategy::NO_TEXT,
+            PdfOcrRenderingStrategy::TEXT_ONLY => ecore::PdfOcrRenderingStrategy::TEXT_ONLY,
+            PdfOcrRenderingStrategy::VECTOR_GRAPHICS_ONLY => {
+                ecore::PdfOcrRenderingStrategy::VECTOR_GRAPHICS_ONLY
+            }
+            PdfOcrRenderingStrategy::ALL => ecore::PdfOcrRenderingStrategy::ALL,
+        }
+    }
+}
+
+/// Output of the Tesseract OCR
+#[pyclass(eq, eq_int)]
+#[derive(Clone, PartialEq)]
+#[allow(non_camel_case_types)]
+pub enum OcrOutputType {
+    TXT,
+    HOCR,
+}
+
+impl From<OcrOutputType> for ecore::OcrOutputType {
+    fn from(output_type: OcrOutputType) -> Self {
+        match output_type {
+            OcrOutputType::TXT => ecore::OcrOutputType::TXT,
+            OcrOutputType::HOCR => ecore::OcrOutputType::HOCR,
+        }
+    }
+}
+
 /// PDF parsing configuration settings
 ///
 /// These settings are used to configure the behavior of the PDF parsing.
@@ -97,6 +182,129 @@ impl PdfParserConfig {
         Ok(Self(inner))
     }
 
+    /// If true, sort the text tokens by their x/y position before extracting the text. This
+    /// may be needed for PDFs whose content stream is not in reading order, e.g. with columns.
+    /// Default: false.
+    pub fn set_sort_by_position(&self, val: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_sort_by_position(val);
+        Ok(Self(inner))
+    }
+
+    /// Sets the minimum whitespace, as a multiple of the max height of the current characters,
+    /// beyond which a space is inserted between two characters.
+    /// Default: 0.5.
+    pub fn set_spacing_tolerance(&self, val: f32) -> PyResult<Self> {
+        let inner = self.0.clone().set_spacing_tolerance(val);
+        Ok(Self(inner))
+    }
+
+    /// Sets the average character width tolerance, as a multiple of the current character
+    /// width, beyond which a space is inserted between two characters.
+    /// Default: 0.3.
+    pub fn set_average_char_tolerance(&self, val: f32) -> PyResult<Self> {
+        let inner = self.0.clone().set_average_char_tolerance(val);
+        Ok(Self(inner))
+    }
+
+    /// If true, insert spaces between words that are rendered without a space character.
+    /// Default: true.
+    pub fn set_enable_auto_space(&self, val: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_enable_auto_space(val);
+        Ok(Self(inner))
+    }
+
+    /// If true, remove the duplicate text that some PDF writers render several times at
+    /// slightly different positions to simulate bold text. Slows down the extraction.
+    /// Default: false.
+    pub fn set_suppress_duplicate_overlapping_text(&self, val: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_suppress_duplicate_overlapping_text(val);
+        Ok(Self(inner))
+    }
+
+    /// If true, extract the content of the AcroForm fields, i.e. the interactive form fields.
+    /// Default: true.
+    pub fn set_extract_acro_form_content(&self, val: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_extract_acro_form_content(val);
+        Ok(Self(inner))
+    }
+
+    /// If true, extract the text of the bookmarks, i.e. the document outline.
+    /// Default: true.
+    pub fn set_extract_bookmarks_text(&self, val: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_extract_bookmarks_text(val);
+        Ok(Self(inner))
+    }
+
+    /// If true, extract the actions of the document, such as javascript or links triggered
+    /// when a page is opened or a form field is changed.
+    /// Default: false.
+    pub fn set_extract_actions(&self, val: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_extract_actions(val);
+        Ok(Self(inner))
+    }
+
+    /// Sets the DPI at which the pages are rendered before OCR.
+    /// Default: 300.
+    pub fn set_ocr_dpi(&self, val: i32) -> PyResult<Self> {
+        let inner = self.0.clone().set_ocr_dpi(val);
+        Ok(Self(inner))
+    }
+
+    /// Sets the color type of the images the pages are rendered to before OCR.
+    /// Default: GRAY.
+    pub fn set_ocr_image_type(&self, val: PdfOcrImageType) -> PyResult<Self> {
+        let inner = self.0.clone().set_ocr_image_type(val.into());
+        Ok(Self(inner))
+    }
+
+    /// Sets the file format of the images the pages are rendered to before OCR.
+    /// Default: PNG.
+    pub fn set_ocr_image_format(&self, val: PdfOcrImageFormat) -> PyResult<Self> {
+        let inner = self.0.clone().set_ocr_image_format(val.into());
+        Ok(Self(inner))
+    }
+
+    /// Sets which elements of the pages are rendered before OCR. NO_TEXT avoids running OCR
+    /// on text that is extracted anyway when using the OCR_AND_TEXT_EXTRACTION strategy.
+    /// Default: ALL.
+    pub fn set_ocr_rendering_strategy(&self, val: PdfOcrRenderingStrategy) -> PyResult<Self> {
+        let inner = self.0.clone().set_ocr_rendering_strategy(val.into());
+        Ok(Self(inner))
+    }
+
+    /// Sets the maximum main memory in bytes the PDF parser may use before it falls back to
+    /// temporary files. Set to -1 for no limit.
+    /// Default: 512 MB.
+    pub fn set_max_main_memory_bytes(&self, val: i64) -> PyResult<Self> {
+        let inner = self.0.clone().set_max_main_memory_bytes(val);
+        Ok(Self(inner))
+    }
+
+    /// If true, exceptions thrown while parsing a page are recorded in the metadata and the
+    /// remaining pages are parsed. If false, the first exception stops the parsing.
+    /// Default: true.
+    pub fn set_catch_intermediate_io_exceptions(&self, val: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_catch_intermediate_io_exceptions(val);
+        Ok(Self(inner))
+    }
+
+    /// Extracts only the pages from start to end, both included. Pages are numbered from 1.
+    /// Parsing stops once the end page is extracted, so the following pages are neither
+    /// parsed nor OCRed.
+    /// Default: all pages.
+    pub fn set_page_range(&self, start: u32, end: u32) -> PyResult<Self> {
+        let inner = self.0.clone().set_page_range(start..=end);
+        Ok(Self(inner))
+    }
+
+    /// Extracts only the given pages, numbered from 1. Parsing stops once the last given page
+    /// is extracted.
+    /// Default: all pages.
+    pub fn set_pages(&self, pages: Vec<u32>) -> PyResult<Self> {
+        let inner = self.0.clone().set_pages(&pages);
+        Ok(Self(inner))
+    }
+
     fn __repr__(&self) -> String {
         format!("{:?}", self.0)
     }
@@ -207,6 +415,15 @@ impl OfficeParserConfig {
         Ok(Self(inner))
     }
 
+    /// Whether to include the comments of docx documents, the comments of pptx slides and the
+    /// cell comments of xlsx sheets in the extracted text. Set to false to keep the comments
+    /// out of the text and read them with `Extractor.extract_file_annotations` instead.
+    /// Default: true
+    pub fn set_include_comments(&self, val: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_include_comments(val);
+        Ok(Self(inner))
+    }
+
     fn __repr__(&self) -> String {
         format!("{:?}", self.0)
     }
@@ -278,7 +495,341 @@ impl TesseractOcrConfig {
         Ok(Self(inner))
     }
 
+    /// Sets the tesseract page segmentation mode, from 0 to 13, e.g. 6 assumes a single
+    /// uniform block of text.
+    /// Default: 1.
+    pub fn set_page_seg_mode(&self, val: i32) -> PyResult<Self> {
+        let inner = self.0.clone().set_page_seg_mode(val);
+        Ok(Self(inner))
+    }
+
+    /// Sets the tesseract OCR engine mode, from 0 to 3: 0 legacy engine only, 1 LSTM engine
+    /// only, 2 legacy and LSTM engines, 3 default, based on what is available.
+    /// Default: tesseract's default.
+    pub fn set_ocr_engine_mode(&self, val: i32) -> PyResult<Self> {
+        let inner = self.0.clone().set_ocr_engine_mode(val);
+        Ok(Self(inner))
+    }
+
+    /// If true, tesseract preserves the spacing between words.
+    /// Default: false.
+    pub fn set_preserve_interword_spacing(&self, val: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_preserve_interword_spacing(val);
+        Ok(Self(inner))
+    }
+
+    /// Sets the minimum size in bytes of an image to be OCRed.
+    /// Default: 0.
+    pub fn set_min_file_size_to_ocr(&self, val: i64) -> PyResult<Self> {
+        let inner = self.0.clone().set_min_file_size_to_ocr(val);
+        Ok(Self(inner))
+    }
+
+    /// Sets the maximum size in bytes of an image to be OCRed.
+    /// Default: 2147483647.
+    pub fn set_max_file_size_to_ocr(&self, val: i64) -> PyResult<Self> {
+        let inner = self.0.clone().set_max_file_size_to_ocr(val);
+        Ok(Self(inner))
+    }
+
+    /// Sets the directory containing the tesseract executable.
+    /// Default: tesseract is looked up on the PATH.
+    pub fn set_tesseract_path(&self, val: &str) -> PyResult<Self> {
+        let inner = self.0.clone().set_tesseract_path(val);
+        Ok(Self(inner))
+    }
+
+    /// Sets the directory containing the tessdata language files.
+    /// Default: tesseract's default tessdata directory.
+    pub fn set_tessdata_path(&self, val: &str) -> PyResult<Self> {
+        let inner = self.0.clone().set_tessdata_path(val);
+        Ok(Self(inner))
+    }
+
+    /// Sets the directory containing the ImageMagick executables used to preprocess images.
+    /// Default: ImageMagick is looked up on the PATH.
+    pub fn set_image_magick_path(&self, val: &str) -> PyResult<Self> {
+        let inner = self.0.clone().set_image_magick_path(val);
+        Ok(Self(inner))
+    }
+
+    /// Sets the ImageMagick colorspace the images are converted to during preprocessing.
+    /// Default: "gray".
+    pub fn set_colorspace(&self, val: &str) -> PyResult<Self> {
+        let inner = self.0.clone().set_colorspace(val);
+        Ok(Self(inner))
+    }
+
+    /// Sets the ImageMagick filter used to resize the images during preprocessing.
+    /// Default: "triangle".
+    pub fn set_filter(&self, val: &str) -> PyResult<Self> {
+        let inner = self.0.clone().set_filter(val);
+        Ok(Self(inner))
+    }
+
+    /// Sets the percentage, from 100 to 900 in steps of 100, the images are resized to
+    /// during preprocessing.
+    /// Default: 200.
+    pub fn set_resize(&self, val: i32) -> PyResult<Self> {
+        let inner = self.0.clone().set_resize(val);
+        Ok(Self(inner))
+    }
+
+    /// Adds a tesseract config variable, passed as `-c key=value` to tesseract.
+    pub fn add_other_tesseract_config(&self, key: &str, value: &str) -> PyResult<Self> {
+        let inner = self.0.clone().add_other_tesseract_config(key, value);
+        Ok(Self(inner))
+    }
+
+    /// Sets the output of tesseract. With HOCR, the xml output contains the hOCR elements of
+    /// the OCRed images.
+    /// Default: TXT.
+    pub fn set_output_type(&self, val: OcrOutputType) -> PyResult<Self> {
+        let inner = self.0.clone().set_output_type(val.into());
+        Ok(Self(inner))
+    }
+
+    /// Checks that all the settings have valid values, raises a ValueError otherwise.
+    /// The config is also validated before every extraction.
+    pub fn validate(&self) -> PyResult<()> {
+        self.0
+            .validate()
+            .map_err(|e| PyErr::new::<PyValueError, _>(format!("{}", e)))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
+
+/// Url fetching configuration settings
+///
+/// These settings are used to configure how `extract_url` functions download http(s) urls.
+#[pyclass]
+#[derive(Clone, PartialEq)]
+pub struct UrlFetchConfig(ecore::UrlFetchConfig);
+
+impl From<UrlFetchConfig> for ecore::UrlFetchConfig {
+    fn from(config: UrlFetchConfig) -> Self {
+        config.0
+    }
+}
+
+#[pymethods]
+impl UrlFetchConfig {
+    /// Creates a new instance of UrlFetchConfig with default settings.
+    #[new]
+    pub fn new() -> Self {
+        Self(ecore::UrlFetchConfig::new())
+    }
+
+    /// Adds a header that is sent with every request. Can be called multiple times.
+    /// Default: no extra headers.
+    pub fn set_header(&self, name: &str, value: &str) -> PyResult<Self> {
+        let inner = self.0.clone().set_header(name, value);
+        Ok(Self(inner))
+    }
+
+    /// Sets the `User-Agent` header.
+    /// Default: "extractous/{crate version}".
+    pub fn set_user_agent(&self, val: &str) -> PyResult<Self> {
+        let inner = self.0.clone().set_user_agent(val);
+        Ok(Self(inner))
+    }
+
+    /// Sends an `Authorization: Bearer {token}` header with every request.
+    /// Default: None.
+    pub fn set_bearer_auth(&self, token: &str) -> PyResult<Self> {
+        let inner = self.0.clone().set_bearer_auth(token);
+        Ok(Self(inner))
+    }
+
+    /// Sends an `Authorization: Basic` header built from the username and optional password.
+    /// Default: None.
+    #[pyo3(signature = (username, password=None))]
+    pub fn set_basic_auth(&self, username: &str, password: Option<&str>) -> PyResult<Self> {
+        let inner = self.0.clone().set_basic_auth(username, password);
+        Ok(Self(inner))
+    }
+
+    /// Sets the total time in seconds allowed for a request, including reading the body.
+    /// Default: 120.
+    pub fn set_timeout_seconds(&self, val: u64) -> PyResult<Self> {
+        let inner = self.0.clone().set_timeout_seconds(val);
+        Ok(Self(inner))
+    }
+
+    /// Sets the time in seconds allowed to establish a connection.
+    /// Default: 30.
+    pub fn set_connect_timeout_seconds(&self, val: u64) -> PyResult<Self> {
+        let inner = self.0.clone().set_connect_timeout_seconds(val);
+        Ok(Self(inner))
+    }
+
+    /// Routes all requests through the given proxy url, e.g. "http://proxy.local:3128".
+    /// Default: None, the `HTTP_PROXY`/`HTTPS_PROXY` environment variables are honored.
+    pub fn set_proxy(&self, val: &str) -> PyResult<Self> {
+        let inner = self.0.clone().set_proxy(val);
+        Ok(Self(inner))
+    }
+
+    /// Sets the maximum number of redirects to follow. Set to 0 to disable redirects.
+    /// Default: 10.
+    pub fn set_max_redirects(&self, val: usize) -> PyResult<Self> {
+        let inner = self.0.clone().set_max_redirects(val);
+        Ok(Self(inner))
+    }
+
+    /// If true, TLS certificates are not verified. Only use this against trusted hosts.
+    /// Default: false.
+    pub fn set_accept_invalid_certs(&self, val: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_accept_invalid_certs(val);
+        Ok(Self(inner))
+    }
+
+    /// Adds a PEM encoded root certificate file that is trusted in addition to the
+    /// built-in roots. Can be called multiple times.
+    /// Default: no extra certificates.
+    pub fn add_root_certificate(&self, pem_file_path: &str) -> PyResult<Self> {
+        let inner = self.0.clone().add_root_certificate(pem_file_path);
+        Ok(Self(inner))
+    }
+
+    /// Sets the maximum size in bytes of a response body. Larger responses fail instead of
+    /// being loaded into memory.
+    /// Default: None (no limit).
+    pub fn set_max_body_bytes(&self, val: u64) -> PyResult<Self> {
+        let inner = self.0.clone().set_max_body_bytes(val);
+        Ok(Self(inner))
+    }
+
     fn __repr__(&self) -> String {
         format!("{:?}", self.0)
     }
 }
+
+/// Unicode normalization form of `TextNormalizer.set_unicode_form`
+#[pyclass(eq, eq_int)]
+#[derive(Clone, PartialEq)]
+#[allow(non_camel_case_types)]
+pub enum UnicodeForm {
+    NFC,
+    NFKC,
+}
+
+impl From<UnicodeForm> for ecore::UnicodeForm {
+    fn from(form: UnicodeForm) -> Self {
+        match form {
+            UnicodeForm::NFC => ecore::UnicodeForm::NFC,
+            UnicodeForm::NFKC => ecore::UnicodeForm::NFKC,
+        }
+    }
+}
+
+/// Text normalization settings
+///
+/// These settings are used to clean up the extracted text, all the steps are disabled by default.
+#[pyclass]
+#[derive(Clone, PartialEq)]
+pub struct TextNormalizer(ecore::TextNormalizer);
+
+impl From<TextNormalizer> for ecore::TextNormalizer {
+    fn from(normalizer: TextNormalizer) -> Self {
+        normalizer.0
+    }
+}
+
+#[pymethods]
+impl TextNormalizer {
+    /// Creates a new instance of TextNormalizer with all the steps disabled.
+    #[new]
+    pub fn new() -> Self {
+        Self(ecore::TextNormalizer::new())
+    }
+
+    /// Removes the control characters, except the tabs and line breaks.
+    /// Default: false.
+    pub fn set_strip_control_chars(&self, val: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_strip_control_chars(val);
+        Ok(Self(inner))
+    }
+
+    /// Expands the Latin typographic ligatures, such as "ﬁ" or "ﬂ", to their letters.
+    /// Default: false.
+    pub fn set_expand_ligatures(&self, val: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_expand_ligatures(val);
+        Ok(Self(inner))
+    }
+
+    /// Normalizes the text to the Unicode normalization form, or not at all if None.
+    /// Default: None.
+    #[pyo3(signature = (val=None))]
+    pub fn set_unicode_form(&self, val: Option<UnicodeForm>) -> PyResult<Self> {
+        let inner = self.0.clone().set_unicode_form(val.map(Into::into));
+        Ok(Self(inner))
+    }
+
+    /// Joins the words hyphenated at the end of a line when the next line starts with a
+    /// lowercase letter, and removes the soft hyphens.
+    /// Default: false.
+    pub fn set_dehyphenate(&self, val: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_dehyphenate(val);
+        Ok(Self(inner))
+    }
+
+    /// Replaces the runs of spaces and tabs with a single space, trims the lines and keeps at
+    /// most one blank line between two lines.
+    /// Default: false.
+    pub fn set_collapse_whitespace(&self, val: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_collapse_whitespace(val);
+        Ok(Self(inner))
+    }
+
+    /// Normalizes the text with the enabled steps
+    pub fn normalize(&self, text: &str) -> String {
+        self.0.normalize(text)
+    }
+}
+
+/// Header and footer detection settings, used by `Extractor.set_remove_headers_and_footers`
+#[pyclass]
+#[derive(Clone, PartialEq)]
+pub struct HeaderFooterConfig(ecore::HeaderFooterConfig);
+
+impl From<HeaderFooterConfig> for ecore::HeaderFooterConfig {
+    fn from(config: HeaderFooterConfig) -> Self {
+        config.0
+    }
+}
+
+#[pymethods]
+impl HeaderFooterConfig {
+    /// Creates a new instance of HeaderFooterConfig with the default settings.
+    #[new]
+    pub fn new() -> Self {
+        Self(ecore::HeaderFooterConfig::new())
+    }
+
+    /// The minimum fraction of the pages, from 0.0 to 1.0, a line must be repeated on to be a
+    /// header or a footer. A line must be repeated on at least two pages.
+    /// Default: 0.5.
+    pub fn set_min_page_ratio(&self, val: f64) -> PyResult<Self> {
+        let inner = self.0.clone().set_min_page_ratio(val);
+        Ok(Self(inner))
+    }
+
+    /// The number of non blank lines at the top and at the bottom of every page that can be
+    /// headers, footers or page numbers.
+    /// Default: 3.
+    pub fn set_max_lines(&self, val: usize) -> PyResult<Self> {
+        let inner = self.0.clone().set_max_lines(val);
+        Ok(Self(inner))
+    }
+
+    /// Removes the page numbers found at the top or at the bottom of the pages.
+    /// Default: true.
+    pub fn set_remove_page_numbers(&self, val: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_remove_page_numbers(val);
+        Ok(Self(inner))
+    }
+}
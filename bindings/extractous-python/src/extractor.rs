@@ -1,8 +1,14 @@
-use crate::{ecore, OfficeParserConfig, PdfParserConfig, TesseractOcrConfig};
-use pyo3::exceptions::PyTypeError;
+use crate::{
+    ecore, Annotation, DocumentRevisions, ExtractionCache, HeaderFooterConfig, Link, OcrWord,
+    OfficeParserConfig, OutlineEntry, PdfParserConfig, TesseractOcrConfig, TextNormalizer,
+    UrlFetchConfig,
+};
+use pyo3::exceptions::{PyTypeError, PyValueError};
 use pyo3::prelude::*;
 use pyo3::types::PyByteArray;
+use pyo3::types::PyBytes;
 use pyo3::types::PyDict;
+use pyo3::types::PyTuple;
 use std::collections::HashMap;
 use std::io::Read;
 
@@ -46,8 +52,10 @@ impl StreamReader {
         // Resize the buffer to the requested size
         self.buffer.resize(size, 0);
 
-        // Perform the read operation into the internal buffer
-        match self.reader.read(&mut self.buffer) {
+        // Perform the read operation into the internal buffer. The GIL is released while
+        // reading, so that a progress callback called from the parsing thread can run
+        let (reader, buffer) = (&mut self.reader, &mut self.buffer);
+        match py.allow_threads(|| reader.read(buffer)) {
             Ok(bytes_read) => unsafe {
                 // Truncate buffer to actual read size.
                 self.buffer.truncate(bytes_read);
@@ -80,16 +88,33 @@ impl StreamReader {
 
     /// Reads into the specified buffer
     pub fn readinto<'py>(&mut self, buf: Bound<'py, PyByteArray>) -> PyResult<usize> {
-        let bs = unsafe { buf.as_bytes_mut() };
-
-        let bytes_read = self
-            .reader
-            .read(bs)
+        // Read into the internal buffer without the GIL, like `read`
+        self.buffer.resize(buf.len(), 0);
+        let (reader, buffer) = (&mut self.reader, &mut self.buffer);
+        let bytes_read = buf
+            .py()
+            .allow_threads(|| reader.read(buffer))
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("{}", e)))?;
+
+        let bs = unsafe { buf.as_bytes_mut() };
+        bs[..bytes_read].copy_from_slice(&self.buffer[..bytes_read]);
         Ok(bytes_read)
     }
+
+    /// The stats of the extraction as a dict, when collected with `set_collect_stats`. The
+    /// stats of the documents parsed by tika are known once the stream is read to its end,
+    /// None before
+    pub fn stats<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        self.reader
+            .stats()
+            .map(|stats| stats_to_pydict(py, stats))
+            .transpose()
+    }
 }
 
+/// An embedded document as a (name, bytes, metadata) tuple
+type PyEmbeddedDocument = (String, PyObject, PyObject);
+
 /// `Extractor` is the entry for all extract APIs
 ///
 /// Create a new `Extractor` with the default configuration.
@@ -110,6 +135,14 @@ impl Extractor {
         Self(inner)
     }
 
+    /// Set the maximum number of pages of pdf documents or slides of presentations to extract.
+    /// Parsing stops once the last page is extracted. Set to -1 for no limit.
+    /// Default: -1
+    pub fn set_max_pages(&self, max_pages: i32) -> Self {
+        let inner = self.0.clone().set_max_pages(max_pages);
+        Self(inner)
+    }
+
     /// Set the encoding to use for when extracting text to a stream.
     /// Not used for extract_to_string functions.
     /// Default: CharSet::UTF_8
@@ -136,12 +169,127 @@ impl Extractor {
         Ok(Self(inner))
     }
 
+    /// Set the configuration used to download http(s) urls in the `extract_url` functions
+    pub fn set_url_fetch_config(&self, config: UrlFetchConfig) -> PyResult<Self> {
+        let inner = self.0.clone().set_url_fetch_config(config.into());
+        Ok(Self(inner))
+    }
+
+    /// Set the text normalizer cleaning up the extracted text of the string and stream outputs,
+    /// e.g. to join the hyphenated words and expand the ligatures of pdf documents
+    pub fn set_text_normalizer(&self, normalizer: TextNormalizer) -> PyResult<Self> {
+        let inner = self.0.clone().set_text_normalizer(normalizer.into());
+        Ok(Self(inner))
+    }
+
+    /// Set to true to remove the running headers, footers and page numbers of the pdf pages
+    /// from the text extracted to string. The removed lines are reported in the metadata under
+    /// the `X-EXTRACTOUS:removed_headers`, `X-EXTRACTOUS:removed_footers` and
+    /// `X-EXTRACTOUS:removed_page_numbers` keys. The streams and the xml output are not changed
+    pub fn set_remove_headers_and_footers(&self, val: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_remove_headers_and_footers(val);
+        Ok(Self(inner))
+    }
+
+    /// Set the configuration of the detection of the headers and footers
+    pub fn set_header_footer_config(&self, config: HeaderFooterConfig) -> PyResult<Self> {
+        let inner = self.0.clone().set_header_footer_config(config.into());
+        Ok(Self(inner))
+    }
+
+    /// Set to true to extract only the main content of html documents, without the navigation,
+    /// banners, sidebars, comments and footers. The title, byline and publication date of the
+    /// article are added to the metadata as `dc:title`, `dc:creator` and `dcterms:created`
+    pub fn set_html_main_content(&self, val: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_html_main_content(val);
+        Ok(Self(inner))
+    }
+
+    /// Set the url the relative links returned by the extract_*_links functions are resolved
+    /// against, e.g. "https://intranet.example.com/docs/"
+    pub fn set_link_base_url(&self, base_url: &str) -> PyResult<Self> {
+        let inner = self.0.clone().set_link_base_url(base_url);
+        Ok(Self(inner))
+    }
+
     /// Set the configuration for the parse as xml
     pub fn set_xml_output(&self, xml_output: bool) -> PyResult<Self> {
         let inner = self.0.clone().set_xml_output(xml_output);
         Ok(Self(inner))
     }
 
+    /// Set to True to parse all the documents with tika, e.g. to compare the results of the
+    /// native parsers with tika. Otherwise plain text, markdown, csv, tsv, json, xml and html
+    /// documents are parsed natively, without going through the JVM
+    pub fn set_force_tika(&self, force_tika: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_force_tika(force_tika);
+        Ok(Self(inner))
+    }
+
+    /// Set a callback called with the progress of the extractions, a dict with the `kind` of
+    /// progress and its values:
+    /// {"kind": "document_started", "content_type": str},
+    /// {"kind": "page_done", "page": int, "total": int or None},
+    /// {"kind": "embedded_document_started", "resource_name": str or None},
+    /// {"kind": "ocr_started", "page": int}, {"kind": "ocr_finished", "page": int},
+    /// {"kind": "output_produced", "bytes": int} and {"kind": "document_finished"}.
+    /// Exceptions raised by the callback are reported as unraisable and do not stop the
+    /// extraction
+    pub fn set_progress_callback(&self, callback: PyObject) -> PyResult<Self> {
+        let inner = self
+            .0
+            .clone()
+            .set_progress_callback(move |progress: &ecore::Progress| {
+                Python::with_gil(|py| {
+                    let result = progress_to_pydict(py, progress)
+                        .and_then(|dict| callback.call1(py, (dict,)));
+                    if let Err(e) = result {
+                        e.write_unraisable(py, None);
+                    }
+                })
+            });
+        Ok(Self(inner))
+    }
+
+    /// Set to true to add the stats of every extraction to its metadata, under the
+    /// `X-EXTRACTOUS:` keys: the time spent detecting, parsing, OCRing and copying the results
+    /// from the JVM, the bytes in and characters out, the embedded documents, the JVM heap used
+    /// and whether an OCR ran. The stats of the streams are returned by `StreamReader.stats()`.
+    /// Default: False
+    pub fn set_collect_stats(&self, collect_stats: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_collect_stats(collect_stats);
+        Ok(Self(inner))
+    }
+
+    /// Set to true to add the fingerprints of every extraction to string to its metadata: the
+    /// SHA-256 of the document bytes under `X-EXTRACTOUS:input_sha256`, the SHA-256 of the
+    /// normalized text under `X-EXTRACTOUS:text_sha256` and the SimHash of the text under
+    /// `X-EXTRACTOUS:simhash`. Compare the SimHashes with `simhash_similarity`.
+    /// Default: False
+    pub fn set_compute_fingerprints(&self, compute_fingerprints: bool) -> PyResult<Self> {
+        let inner = self
+            .0
+            .clone()
+            .set_compute_fingerprints(compute_fingerprints);
+        Ok(Self(inner))
+    }
+
+    /// Set the `ExtractionCache` checked by the extract to string functions before parsing a
+    /// document. The extractions read from the cache have no stats.
+    /// Default: None
+    pub fn set_cache(&self, cache: ExtractionCache) -> PyResult<Self> {
+        let inner = self.0.clone().set_cache(cache.into());
+        Ok(Self(inner))
+    }
+
+    /// Checks that the extractor can OCR documents: the OCR config is valid, tesseract can be
+    /// run and the OCR languages are installed. Raises a ValueError otherwise.
+    pub fn validate(&self) -> PyResult<()> {
+        self.0
+            .validate()
+            .map_err(|e| PyErr::new::<PyValueError, _>(format!("{}", e)))
+    }
+
     /// Extracts text from a file path. Returns a tuple with stream of the extracted text
     /// the stream is decoded using the extractor's `encoding` and tika metadata.
     pub fn extract_file<'py>(
@@ -248,6 +396,125 @@ impl Extractor {
         Ok((content, py_metadata.into()))
     }
 
+    /// Extracts text from a file path with the words recognized by the OCR. Returns a tuple
+    /// with the text, the list of `OcrWord` with their bounding box and confidence, and the
+    /// metadata as dict. The OCR always runs with the HOCR output type.
+    pub fn extract_file_to_ocr_words<'py>(
+        &self,
+        filename: &str,
+        py: Python<'py>,
+    ) -> PyResult<(String, Vec<OcrWord>, PyObject)> {
+        let (content, words, metadata) = self
+            .0
+            .extract_file_to_ocr_words(filename)
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+
+        let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
+        let words = words.into_iter().map(OcrWord::from).collect();
+        Ok((content, words, py_metadata.into()))
+    }
+
+    /// Same as `extract_file_to_ocr_words` for a bytearray
+    pub fn extract_bytes_to_ocr_words<'py>(
+        &self,
+        buffer: &Bound<'_, PyByteArray>,
+        py: Python<'py>,
+    ) -> PyResult<(String, Vec<OcrWord>, PyObject)> {
+        let (content, words, metadata) = self
+            .0
+            .extract_bytes_to_ocr_words(&buffer.to_vec())
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+
+        let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
+        let words = words.into_iter().map(OcrWord::from).collect();
+        Ok((content, words, py_metadata.into()))
+    }
+
+    /// Extracts text from a bytearray and from all the documents embedded in it, such as email
+    /// attachments or the files of an archive. Returns a list with the metadata dict of every
+    /// document, the container first, with its content under the "X-TIKA:content" key.
+    /// The optional metadata hints, e.g. {"resourceName": ["report.pdf"]}, help detecting the type
+    #[pyo3(signature = (buffer, metadata_hints=None))]
+    pub fn extract_bytes_recursive<'py>(
+        &self,
+        buffer: &Bound<'_, PyByteArray>,
+        metadata_hints: Option<HashMap<String, Vec<String>>>,
+        py: Python<'py>,
+    ) -> PyResult<Vec<PyObject>> {
+        let metadata_list = self
+            .0
+            .extract_bytes_recursive(&buffer.to_vec(), &metadata_hints.unwrap_or_default())
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+
+        metadata_list
+            .iter()
+            .map(|metadata| Ok(metadata_hashmap_to_pydict(py, metadata)?.into()))
+            .collect()
+    }
+
+    /// Extracts text from a bytearray and the raw bytes of the documents embedded in it.
+    /// Returns a tuple with the text of the container, its metadata as dict and a list of
+    /// (name, bytes, metadata) tuples of the embedded documents.
+    #[pyo3(signature = (buffer, metadata_hints=None))]
+    pub fn unpack_bytes<'py>(
+        &self,
+        buffer: &Bound<'_, PyByteArray>,
+        metadata_hints: Option<HashMap<String, Vec<String>>>,
+        py: Python<'py>,
+    ) -> PyResult<(String, PyObject, Vec<PyEmbeddedDocument>)> {
+        let (content, metadata, embedded) = self
+            .0
+            .unpack_bytes(&buffer.to_vec(), &metadata_hints.unwrap_or_default())
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+
+        let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
+        let py_embedded = embedded
+            .into_iter()
+            .map(|document| {
+                let py_data = PyBytes::new_bound(py, &document.data);
+                let py_document_metadata = metadata_hashmap_to_pydict(py, &document.metadata)?;
+                Ok((document.name, py_data.into(), py_document_metadata.into()))
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        Ok((content, py_metadata.into(), py_embedded))
+    }
+
+    /// Extracts a file as SAX-style events sent to the handler, an object with any of the
+    /// methods start_element(name, attributes), end_element(name), characters(text),
+    /// embedded_start(metadata), embedded_end() and metadata(metadata). An exception raised by
+    /// the handler stops the extraction. Returns the metadata of the file as dict.
+    pub fn extract_file_events<'py>(
+        &self,
+        filename: &str,
+        handler: &Bound<'py, PyAny>,
+        py: Python<'py>,
+    ) -> PyResult<PyObject> {
+        let mut py_handler = PyContentHandler::new(handler);
+        let result = self.0.extract_file_events(filename, &mut py_handler);
+        let metadata = py_handler.finish(result)?;
+        Ok(metadata_hashmap_to_pydict(py, &metadata)?.into())
+    }
+
+    /// Same as `extract_file_events` for a bytearray. The optional metadata hints, e.g.
+    /// {"resourceName": ["report.pdf"]}, help detecting the type
+    #[pyo3(signature = (buffer, handler, metadata_hints=None))]
+    pub fn extract_bytes_events<'py>(
+        &self,
+        buffer: &Bound<'_, PyByteArray>,
+        handler: &Bound<'py, PyAny>,
+        metadata_hints: Option<HashMap<String, Vec<String>>>,
+        py: Python<'py>,
+    ) -> PyResult<PyObject> {
+        let mut py_handler = PyContentHandler::new(handler);
+        let result = self.0.extract_bytes_events(
+            &buffer.to_vec(),
+            &metadata_hints.unwrap_or_default(),
+            &mut py_handler,
+        );
+        let metadata = py_handler.finish(result)?;
+        Ok(metadata_hashmap_to_pydict(py, &metadata)?.into())
+    }
+
     /// Extracts text from a URL. Returns a tuple with string that is of maximum length
     /// of the extractor's `extract_string_max_length` and the metadata as dict.
     pub fn extract_url_to_string<'py>(
@@ -264,11 +531,325 @@ impl Extractor {
         Ok((content, py_metadata.into()))
     }
 
+    /// Extracts the hyperlinks of a file: the links of html pages and emails, the link
+    /// annotations of pdfs and the hyperlinks of office documents, including the ones of its
+    /// embedded documents. Returns a tuple with the list of `Link` in document order and the
+    /// metadata as dict.
+    pub fn extract_file_links<'py>(
+        &self,
+        filename: &str,
+        py: Python<'py>,
+    ) -> PyResult<(Vec<Link>, PyObject)> {
+        let (links, metadata) = self
+            .0
+            .extract_file_links(filename)
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+
+        let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
+        Ok((
+            links.into_iter().map(Link::from).collect(),
+            py_metadata.into(),
+        ))
+    }
+
+    /// Same as `extract_file_links` for a bytearray
+    pub fn extract_bytes_links<'py>(
+        &self,
+        buffer: &Bound<'_, PyByteArray>,
+        py: Python<'py>,
+    ) -> PyResult<(Vec<Link>, PyObject)> {
+        let (links, metadata) = self
+            .0
+            .extract_bytes_links(&buffer.to_vec())
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+
+        let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
+        Ok((
+            links.into_iter().map(Link::from).collect(),
+            py_metadata.into(),
+        ))
+    }
+
+    /// Same as `extract_file_links` for an url. Without a link base url, the relative links
+    /// are resolved against the url
+    pub fn extract_url_links<'py>(
+        &self,
+        url: &str,
+        py: Python<'py>,
+    ) -> PyResult<(Vec<Link>, PyObject)> {
+        let (links, metadata) = self
+            .0
+            .extract_url_links(url)
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+
+        let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
+        Ok((
+            links.into_iter().map(Link::from).collect(),
+            py_metadata.into(),
+        ))
+    }
+
+    /// Extracts the outline of a file as a list of `OutlineEntry` trees: the bookmarks of pdfs
+    /// with the page they point to, the navigation document of epubs, or else the headings of
+    /// the document, such as the heading styles of docx and odt documents.
+    pub fn extract_file_outline(&self, filename: &str) -> PyResult<Vec<OutlineEntry>> {
+        let outline = self
+            .0
+            .extract_file_outline(filename)
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        Ok(outline.into_iter().map(OutlineEntry::from).collect())
+    }
+
+    /// Same as `extract_file_outline` for a bytearray
+    pub fn extract_bytes_outline(
+        &self,
+        buffer: &Bound<'_, PyByteArray>,
+    ) -> PyResult<Vec<OutlineEntry>> {
+        let outline = self
+            .0
+            .extract_bytes_outline(&buffer.to_vec())
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        Ok(outline.into_iter().map(OutlineEntry::from).collect())
+    }
+
+    /// Extracts the comments and annotations of a file as a list of `Annotation`: the
+    /// annotations of the pdf pages, such as the notes, highlights and free texts, the comments
+    /// of docx documents and pptx slides and the cell comments of xlsx sheets.
+    pub fn extract_file_annotations(&self, filename: &str) -> PyResult<Vec<Annotation>> {
+        let annotations = self
+            .0
+            .extract_file_annotations(filename)
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        Ok(annotations.into_iter().map(Annotation::from).collect())
+    }
+
+    /// Same as `extract_file_annotations` for a bytearray
+    pub fn extract_bytes_annotations(
+        &self,
+        buffer: &Bound<'_, PyByteArray>,
+    ) -> PyResult<Vec<Annotation>> {
+        let annotations = self
+            .0
+            .extract_bytes_annotations(&buffer.to_vec())
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        Ok(annotations.into_iter().map(Annotation::from).collect())
+    }
+
+    /// Extracts the tracked changes of the body of a docx file as `DocumentRevisions`: its
+    /// insertions, deletions, moves and formatting changes, with their author and date, and the
+    /// text of the document with the text of all its revisions, from which its original and
+    /// final text are rebuilt.
+    pub fn extract_file_revisions(&self, filename: &str) -> PyResult<DocumentRevisions> {
+        let revisions = self
+            .0
+            .extract_file_revisions(filename)
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        Ok(revisions.into())
+    }
+
+    /// Same as `extract_file_revisions` for a bytearray
+    pub fn extract_bytes_revisions(
+        &self,
+        buffer: &Bound<'_, PyByteArray>,
+    ) -> PyResult<DocumentRevisions> {
+        let revisions = self
+            .0
+            .extract_bytes_revisions(&buffer.to_vec())
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        Ok(revisions.into())
+    }
+
+    /// Detects the mime type of a file. Returns a tuple with the mime type and the metadata as dict.
+    pub fn detect_file<'py>(
+        &self,
+        filename: &str,
+        py: Python<'py>,
+    ) -> PyResult<(String, PyObject)> {
+        let (mime_type, metadata) = self
+            .0
+            .detect_file(filename)
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+
+        let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
+        Ok((mime_type, py_metadata.into()))
+    }
+
+    /// Detects the mime type of a bytearray. Returns a tuple with the mime type and the metadata as dict.
+    pub fn detect_bytes<'py>(
+        &self,
+        buffer: &Bound<'_, PyByteArray>,
+        py: Python<'py>,
+    ) -> PyResult<(String, PyObject)> {
+        let (mime_type, metadata) = self
+            .0
+            .detect_bytes(&buffer.to_vec())
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+
+        let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
+        Ok((mime_type, py_metadata.into()))
+    }
+
+    /// Detects the mime type of a http(s) url. Returns a tuple with the mime type and the metadata as dict.
+    pub fn detect_url<'py>(&self, url: &str, py: Python<'py>) -> PyResult<(String, PyObject)> {
+        let (mime_type, metadata) = self
+            .0
+            .detect_url(url)
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+
+        let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
+        Ok((mime_type, py_metadata.into()))
+    }
+
     fn __repr__(&self) -> String {
         format!("{:?}", self.0)
     }
 }
 
+/// Converts a progress to a dict with its `kind` and values
+fn progress_to_pydict<'py>(
+    py: Python<'py>,
+    progress: &ecore::Progress,
+) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    match progress {
+        ecore::Progress::DocumentStarted { content_type } => {
+            dict.set_item("kind", "document_started")?;
+            dict.set_item("content_type", content_type)?;
+        }
+        ecore::Progress::PageDone { page, total } => {
+            dict.set_item("kind", "page_done")?;
+            dict.set_item("page", page)?;
+            dict.set_item("total", total)?;
+        }
+        ecore::Progress::EmbeddedDocumentStarted { resource_name } => {
+            dict.set_item("kind", "embedded_document_started")?;
+            dict.set_item("resource_name", resource_name)?;
+        }
+        ecore::Progress::OcrStarted { page } => {
+            dict.set_item("kind", "ocr_started")?;
+            dict.set_item("page", page)?;
+        }
+        ecore::Progress::OcrFinished { page } => {
+            dict.set_item("kind", "ocr_finished")?;
+            dict.set_item("page", page)?;
+        }
+        ecore::Progress::OutputProduced { bytes } => {
+            dict.set_item("kind", "output_produced")?;
+            dict.set_item("bytes", bytes)?;
+        }
+        ecore::Progress::DocumentFinished => {
+            dict.set_item("kind", "document_finished")?;
+        }
+    }
+    Ok(dict)
+}
+
+/// Converts the stats of an extraction to a dict, with the times in seconds
+fn stats_to_pydict<'py>(
+    py: Python<'py>,
+    stats: &ecore::ExtractionStats,
+) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("total_time", stats.total_time.as_secs_f64())?;
+    dict.set_item("detect_time", stats.detect_time.as_secs_f64())?;
+    dict.set_item("parse_time", stats.parse_time.as_secs_f64())?;
+    dict.set_item("ocr_time", stats.ocr_time.as_secs_f64())?;
+    dict.set_item("jni_copy_time", stats.jni_copy_time.as_secs_f64())?;
+    dict.set_item("bytes_in", stats.bytes_in)?;
+    dict.set_item("chars_out", stats.chars_out)?;
+    dict.set_item("embedded_documents", stats.embedded_documents)?;
+    dict.set_item("heap_used_before", stats.heap_used_before)?;
+    dict.set_item("heap_used_after", stats.heap_used_after)?;
+    dict.set_item("ocr_ran", stats.ocr_ran)?;
+    Ok(dict)
+}
+
+/// Calls the methods of a python handler object with the events of the extracted document
+struct PyContentHandler<'a, 'py> {
+    handler: &'a Bound<'py, PyAny>,
+    /// The exception raised by the handler, which stopped the extraction
+    error: Option<PyErr>,
+}
+
+impl<'a, 'py> PyContentHandler<'a, 'py> {
+    fn new(handler: &'a Bound<'py, PyAny>) -> Self {
+        Self {
+            handler,
+            error: None,
+        }
+    }
+
+    /// Calls the method of the handler if it has one
+    fn call(
+        &mut self,
+        method: &str,
+        args: impl IntoPyObject<'py, Target = PyTuple>,
+    ) -> ecore::ExtractResult<()> {
+        let result = match self.handler.hasattr(method) {
+            Ok(true) => self.handler.call_method1(method, args).map(|_| ()),
+            Ok(false) => Ok(()),
+            Err(e) => Err(e),
+        };
+        result.map_err(|e| {
+            let message = e.to_string();
+            self.error = Some(e);
+            ecore::Error::Unknown(message)
+        })
+    }
+
+    fn metadata_dict(
+        &self,
+        metadata: &ecore::Metadata,
+    ) -> ecore::ExtractResult<Bound<'py, PyDict>> {
+        metadata_hashmap_to_pydict(self.handler.py(), metadata)
+            .map_err(|e| ecore::Error::Unknown(e.to_string()))
+    }
+
+    /// Returns the exception raised by the handler, otherwise the extraction result
+    fn finish<T>(self, result: ecore::ExtractResult<T>) -> PyResult<T> {
+        match self.error {
+            Some(error) => Err(error),
+            None => result.map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e))),
+        }
+    }
+}
+
+impl ecore::ContentHandler for PyContentHandler<'_, '_> {
+    fn start_element(
+        &mut self,
+        name: &str,
+        attributes: &[(String, String)],
+    ) -> ecore::ExtractResult<()> {
+        let attributes: HashMap<&str, &str> = attributes
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect();
+        self.call("start_element", (name, attributes))
+    }
+
+    fn end_element(&mut self, name: &str) -> ecore::ExtractResult<()> {
+        self.call("end_element", (name,))
+    }
+
+    fn characters(&mut self, text: &str) -> ecore::ExtractResult<()> {
+        self.call("characters", (text,))
+    }
+
+    fn embedded_start(&mut self, metadata: &ecore::Metadata) -> ecore::ExtractResult<()> {
+        let metadata = self.metadata_dict(metadata)?;
+        self.call("embedded_start", (metadata,))
+    }
+
+    fn embedded_end(&mut self) -> ecore::ExtractResult<()> {
+        self.call("embedded_end", ())
+    }
+
+    fn metadata(&mut self, metadata: &ecore::Metadata) -> ecore::ExtractResult<()> {
+        let metadata = self.metadata_dict(metadata)?;
+        self.call("metadata", (metadata,))
+    }
+}
+
 /// Converts HashMap<String, Vec<String> to PyDict
 fn metadata_hashmap_to_pydict<'py>(
     py: Python<'py>,
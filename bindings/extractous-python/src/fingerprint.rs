@@ -0,0 +1,16 @@
+use crate::ecore;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// The similarity of the texts of two documents, from 0.0 to 1.0, computed from the SimHashes
+/// of their `X-EXTRACTOUS:simhash` metadata, as 16 hex digits. Identical texts have a
+/// similarity of 1.0, near duplicates usually above 0.9 and unrelated texts around 0.5
+#[pyfunction]
+pub fn simhash_similarity(a: &str, b: &str) -> PyResult<f64> {
+    let parse = |simhash: &str| {
+        u64::from_str_radix(simhash, 16).map_err(|e| {
+            PyErr::new::<PyValueError, _>(format!("Invalid simhash {:?}: {}", simhash, e))
+        })
+    };
+    Ok(ecore::fingerprint::simhash_similarity(parse(a)?, parse(b)?))
+}
@@ -12,6 +12,20 @@ mod extractor;
 pub use extractor::*;
 mod config;
 pub use config::*;
+mod ocr;
+pub use ocr::*;
+mod links;
+pub use links::*;
+mod outline;
+pub use outline::*;
+mod annotations;
+pub use annotations::*;
+mod revisions;
+pub use revisions::*;
+mod cache;
+pub use cache::*;
+mod fingerprint;
+pub use fingerprint::*;
 
 /// Extractous is a library that extracts text from various file formats.
 /// * Supports many file formats such as Word, Excel, PowerPoint, PDF, and many more.
@@ -56,12 +70,30 @@ fn _extractous(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<CharSet>()?;
     m.add_class::<StreamReader>()?;
     m.add_class::<Extractor>()?;
+    m.add_class::<OcrWord>()?;
+    m.add_class::<OcrDiagnostics>()?;
+    m.add_class::<Link>()?;
+    m.add_class::<OutlineEntry>()?;
+    m.add_class::<Annotation>()?;
+    m.add_class::<Revision>()?;
+    m.add_class::<DocumentRevisions>()?;
+    m.add_class::<ExtractionCache>()?;
+    m.add_function(wrap_pyfunction!(diagnose_ocr, m)?)?;
+    m.add_function(wrap_pyfunction!(simhash_similarity, m)?)?;
 
     // Config
     m.add_class::<PdfOcrStrategy>()?;
+    m.add_class::<PdfOcrImageType>()?;
+    m.add_class::<PdfOcrImageFormat>()?;
+    m.add_class::<PdfOcrRenderingStrategy>()?;
+    m.add_class::<OcrOutputType>()?;
     m.add_class::<PdfParserConfig>()?;
     m.add_class::<OfficeParserConfig>()?;
     m.add_class::<TesseractOcrConfig>()?;
+    m.add_class::<UrlFetchConfig>()?;
+    m.add_class::<UnicodeForm>()?;
+    m.add_class::<TextNormalizer>()?;
+    m.add_class::<HeaderFooterConfig>()?;
 
     Ok(())
 }
@@ -0,0 +1,37 @@
+use crate::ecore;
+use pyo3::prelude::*;
+
+/// A hyperlink of a document, returned by `Extractor.extract_file_links`
+#[pyclass(get_all)]
+#[derive(Clone)]
+pub struct Link {
+    /// Target of the link, resolved against the base url if it is relative
+    pub href: String,
+    /// Text of the link, with its runs of whitespace collapsed to a single space
+    pub anchor_text: String,
+    /// The rel attribute of the link, e.g. "nofollow"
+    pub rel: Option<String>,
+    /// Page or slide of the link, starting at 1, for the pdfs and presentations
+    pub source_page: Option<u32>,
+}
+
+impl From<ecore::Link> for Link {
+    fn from(link: ecore::Link) -> Self {
+        Self {
+            href: link.href,
+            anchor_text: link.anchor_text,
+            rel: link.rel,
+            source_page: link.source_page,
+        }
+    }
+}
+
+#[pymethods]
+impl Link {
+    fn __repr__(&self) -> String {
+        format!(
+            "Link(href={:?}, anchor_text={:?}, rel={:?}, source_page={:?})",
+            self.href, self.anchor_text, self.rel, self.source_page
+        )
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{ecore, TesseractOcrConfig};
+use pyo3::prelude::*;
+
+/// A word recognized by the OCR, returned by `Extractor.extract_file_to_ocr_words`
+#[pyclass(get_all)]
+#[derive(Clone)]
+pub struct OcrWord {
+    /// Text of the word
+    pub text: String,
+    /// Bounding box of the word in pixels as a (x0, y0, x1, y1) tuple, (x0, y0) is the top
+    /// left corner
+    pub bbox: (u32, u32, u32, u32),
+    /// Confidence of the OCR in the recognized text, from 0 to 100
+    pub confidence: f32,
+    /// Page of the word, starting at 1
+    pub page: u32,
+    /// Line of the word in its page, starting at 1
+    pub line: u32,
+}
+
+impl From<ecore::OcrWord> for OcrWord {
+    fn from(word: ecore::OcrWord) -> Self {
+        Self {
+            text: word.text,
+            bbox: (word.bbox.x0, word.bbox.y0, word.bbox.x1, word.bbox.y1),
+            confidence: word.confidence,
+            page: word.page,
+            line: word.line,
+        }
+    }
+}
+
+#[pymethods]
+impl OcrWord {
+    fn __repr__(&self) -> String {
+        format!(
+            "OcrWord(text={:?}, bbox={:?}, confidence={}, page={}, line={})",
+            self.text, self.bbox, self.confidence, self.page, self.line
+        )
+    }
+}
+
+/// The tesseract installation found by `diagnose_ocr`
+#[pyclass(get_all)]
+#[derive(Clone)]
+pub struct OcrDiagnostics {
+    /// The tesseract executable that was run
+    pub tesseract: String,
+    /// True if the tesseract executable could be run
+    pub tesseract_found: bool,
+    /// Version of tesseract, e.g. "5.3.0"
+    pub version: Option<String>,
+    /// The installed tessdata languages, e.g. ["eng", "osd"]
+    pub languages: Vec<String>,
+    /// The reason tesseract or its languages could not be found
+    pub error: Option<String>,
+}
+
+impl From<ecore::ocr::OcrDiagnostics> for OcrDiagnostics {
+    fn from(diagnostics: ecore::ocr::OcrDiagnostics) -> Self {
+        Self {
+            tesseract: diagnostics.tesseract.display().to_string(),
+            tesseract_found: diagnostics.tesseract_found,
+            version: diagnostics.version,
+            languages: diagnostics.languages,
+            error: diagnostics.error,
+        }
+    }
+}
+
+#[pymethods]
+impl OcrDiagnostics {
+    fn __repr__(&self) -> String {
+        format!(
+            "OcrDiagnostics(tesseract={:?}, tesseract_found={}, version={:?}, languages={:?}, error={:?})",
+            self.tesseract, self.tesseract_found, self.version, self.languages, self.error
+        )
+    }
+}
+
+/// Checks the tesseract installation: runs tesseract, from the tesseract path of the optional
+/// config or the PATH, to get its version and the installed languages.
+#[pyfunction]
+#[pyo3(signature = (config=None))]
+pub fn diagnose_ocr(config: Option<TesseractOcrConfig>) -> OcrDiagnostics {
+    let config: ecore::TesseractOcrConfig = config.map(Into::into).unwrap_or_default();
+    ecore::ocr::diagnose_config(&config).into()
+}
@@ -0,0 +1,40 @@
+use crate::ecore;
+use pyo3::prelude::*;
+
+/// An entry of the outline of a document, returned by `Extractor.extract_file_outline`
+#[pyclass(get_all)]
+#[derive(Clone)]
+pub struct OutlineEntry {
+    /// Title of the entry
+    pub title: String,
+    /// Level of the entry, starting at 1. For headings the level of the heading, e.g. 2 for h2
+    pub level: u32,
+    /// Page the entry points to, starting at 1, for the bookmarks of pdfs
+    pub page: Option<u32>,
+    /// The list of `OutlineEntry` nested under this entry
+    pub children: Vec<OutlineEntry>,
+}
+
+impl From<ecore::OutlineEntry> for OutlineEntry {
+    fn from(entry: ecore::OutlineEntry) -> Self {
+        Self {
+            title: entry.title,
+            level: entry.level,
+            page: entry.page,
+            children: entry.children.into_iter().map(OutlineEntry::from).collect(),
+        }
+    }
+}
+
+#[pymethods]
+impl OutlineEntry {
+    fn __repr__(&self) -> String {
+        format!(
+            "OutlineEntry(title={:?}, level={}, page={:?}, children={})",
+            self.title,
+            self.level,
+            self.page,
+            self.children.len()
+        )
+    }
+}
@@ -0,0 +1,85 @@
+use crate::ecore;
+use pyo3::prelude::*;
+
+/// A tracked change of a docx document, returned in `DocumentRevisions.revisions`
+#[pyclass(get_all)]
+#[derive(Clone)]
+pub struct Revision {
+    /// Kind of the revision: "Insert", "Delete", "MoveFrom", "MoveTo" or "Format"
+    pub kind: String,
+    /// Name of the author of the revision
+    pub author: Option<String>,
+    /// Date of the revision, in ISO 8601
+    pub date: Option<String>,
+    /// Text of the revision
+    pub text: String,
+    /// Offset of the text of the revision in `DocumentRevisions.text`, in characters
+    pub offset: usize,
+}
+
+impl From<ecore::Revision> for Revision {
+    fn from(revision: ecore::Revision) -> Self {
+        Self {
+            kind: revision.kind.to_string(),
+            author: revision.author,
+            date: revision.date,
+            text: revision.text,
+            offset: revision.offset,
+        }
+    }
+}
+
+#[pymethods]
+impl Revision {
+    fn __repr__(&self) -> String {
+        format!(
+            "Revision(kind={:?}, author={:?}, date={:?}, text={:?}, offset={})",
+            self.kind, self.author, self.date, self.text, self.offset
+        )
+    }
+}
+
+/// The tracked changes of a docx document, returned by `Extractor.extract_file_revisions`
+#[pyclass]
+pub struct DocumentRevisions(ecore::DocumentRevisions);
+
+impl From<ecore::DocumentRevisions> for DocumentRevisions {
+    fn from(revisions: ecore::DocumentRevisions) -> Self {
+        Self(revisions)
+    }
+}
+
+#[pymethods]
+impl DocumentRevisions {
+    /// Text of the body of the document with the text of all its revisions, both the inserted
+    /// and the deleted text, in document order
+    #[getter]
+    fn text(&self) -> String {
+        self.0.text.clone()
+    }
+
+    /// The list of `Revision` with text, in document order
+    #[getter]
+    fn revisions(&self) -> Vec<Revision> {
+        self.0
+            .revisions
+            .iter()
+            .cloned()
+            .map(Revision::from)
+            .collect()
+    }
+
+    /// The text of the document before its revisions
+    fn original_text(&self) -> String {
+        self.0.original_text()
+    }
+
+    /// The text of the document with all its revisions accepted
+    fn final_text(&self) -> String {
+        self.0.final_text()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("DocumentRevisions(revisions={})", self.0.revisions.len())
+    }
+}
@@ -1,4 +1,7 @@
+import io
 import json
+import zipfile
+
 import pytest
 
 from extractous import Extractor
@@ -139,3 +142,96 @@ def test_extract_bytes_to_stream_as_xml(file_name, target_dist, metadata_dist):
     percent_similarity = calculate_similarity_percent(metadata, expected_metadata)
     assert percent_similarity >= metadata_dist, \
         f"The metadata similarity is lower than expected. Current {percent_similarity}% | filename: {file_name}"
+
+
+def test_extract_bytes_recursive_and_unpack():
+    file_bytes = read_file_to_bytearray("../../test_files/documents/category-level.docx")
+    hints = {"resourceName": ["category-level.docx"]}
+
+    extractor = Extractor()
+    metadata_list = extractor.extract_bytes_recursive(file_bytes, hints)
+    assert len(metadata_list) >= 1
+    assert "X-TIKA:content" in metadata_list[0]
+
+    content, metadata, embedded = extractor.unpack_bytes(file_bytes, hints)
+    assert len(content) > 0
+    assert len(embedded) == len(metadata_list) - 1
+    for name, data, document_metadata in embedded:
+        assert len(name) > 0
+        assert isinstance(data, bytes)
+
+
+def test_extract_bytes_html_main_content():
+    html = ("<html><head><meta name=\"author\" content=\"Jane Doe\"></head><body>"
+            "<nav><a href=\"/\">Home</a> <a href=\"/news\">News</a></nav>"
+            "<article><h1>Big news today</h1>"
+            "<p>The first paragraph of the article, with enough text to be scored.</p>"
+            "<p>The second paragraph, which also has commas, clauses, and length.</p></article>"
+            "<footer><p>Copyright The Daily, all rights reserved, since 1900.</p></footer>"
+            "</body></html>")
+
+    extractor = Extractor().set_html_main_content(True)
+    content, metadata = extractor.extract_bytes_to_string(bytearray(html.encode("utf-8")))
+    assert "The first paragraph of the article" in content
+    assert "Home" not in content
+    assert "Copyright" not in content
+    assert metadata["dc:title"] == ["Big news today"]
+    assert metadata["dc:creator"] == ["Jane Doe"]
+
+
+def test_extract_bytes_links():
+    html = ("<html><body><p>See the <a href=\"guides/setup.html\">setup guide</a> and the "
+            "<a href=\"https://example.com/\" rel=\"nofollow\">vendor site</a>.</p></body></html>")
+    buffer = bytearray(html.encode("utf-8"))
+
+    extractor = Extractor().set_link_base_url("https://intranet.example.com/docs/")
+    links, metadata = extractor.extract_bytes_links(buffer)
+    assert [link.href for link in links] == ["https://intranet.example.com/docs/guides/setup.html",
+                                             "https://example.com/"]
+    assert links[0].anchor_text == "setup guide"
+    assert links[1].rel == "nofollow"
+    assert links[1].source_page is None
+
+
+def test_extract_bytes_annotations():
+    relationships = ('<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">'
+                     '<Relationship Id="rId1" Target="comments.xml" '
+                     'Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/comments"/>'
+                     '</Relationships>')
+    document = ('<w:document xmlns:w="w"><w:body><w:p><w:commentRangeStart w:id="0"/>'
+                '<w:r><w:t>shall indemnify</w:t></w:r><w:commentRangeEnd w:id="0"/></w:p></w:body></w:document>')
+    comments = ('<w:comments xmlns:w="w"><w:comment w:id="0" w:author="Jane Roe" w:date="2024-03-01T10:00:00Z">'
+                '<w:p><w:r><w:t>Too broad</w:t></w:r></w:p></w:comment></w:comments>')
+    docx = io.BytesIO()
+    with zipfile.ZipFile(docx, "w") as archive:
+        archive.writestr("word/document.xml", document)
+        archive.writestr("word/_rels/document.xml.rels", relationships)
+        archive.writestr("word/comments.xml", comments)
+
+    annotations = Extractor().extract_bytes_annotations(bytearray(docx.getvalue()))
+    assert len(annotations) == 1
+    assert annotations[0].kind == "comment"
+    assert annotations[0].author == "Jane Roe"
+    assert annotations[0].date == "2024-03-01T10:00:00Z"
+    assert annotations[0].text == "Too broad"
+    assert annotations[0].anchor_text == "shall indemnify"
+    assert annotations[0].page is None
+
+
+def test_extract_bytes_revisions():
+    document = ('<w:document xmlns:w="w"><w:body><w:p><w:r><w:t xml:space="preserve">The supplier </w:t></w:r>'
+                '<w:del w:id="1" w:author="Jane Roe"><w:r><w:delText>may</w:delText></w:r></w:del>'
+                '<w:ins w:id="2" w:author="Jane Roe" w:date="2024-03-01T10:00:00Z"><w:r><w:t>shall</w:t></w:r></w:ins>'
+                '<w:r><w:t xml:space="preserve"> indemnify.</w:t></w:r></w:p></w:body></w:document>')
+    docx = io.BytesIO()
+    with zipfile.ZipFile(docx, "w") as archive:
+        archive.writestr("word/document.xml", document)
+
+    revisions = Extractor().extract_bytes_revisions(bytearray(docx.getvalue()))
+    assert [(r.kind, r.text, r.offset) for r in revisions.revisions] == [("Delete", "may", 13),
+                                                                         ("Insert", "shall", 16)]
+    assert revisions.revisions[1].author == "Jane Roe"
+    assert revisions.revisions[1].date == "2024-03-01T10:00:00Z"
+    assert revisions.text == "The supplier mayshall indemnify.\n"
+    assert revisions.original_text() == "The supplier may indemnify.\n"
+    assert revisions.final_text() == "The supplier shall indemnify.\n"
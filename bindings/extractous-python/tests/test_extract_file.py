@@ -1,7 +1,7 @@
 import json
 import pytest
 
-from extractous import Extractor
+from extractous import ExtractionCache, Extractor, TextNormalizer, UnicodeForm, simhash_similarity
 from utils import calculate_similarity_percent, cosine_similarity, is_expected_metadata_contained, read_to_string, extract_body_text
 
 TEST_CASES = [
@@ -135,3 +135,144 @@ def test_extract_file_to_stream_as_xml(file_name, target_dist, metadata_dist):
     percent_similarity = calculate_similarity_percent(metadata, expected_metadata)
     assert percent_similarity >= metadata_dist, \
         f"The metadata similarity is lower than expected. Current {percent_similarity}% | filename: {file_name}"
+
+
+def test_detect_file():
+    extractor = Extractor()
+    mime_type, metadata = extractor.detect_file("../../test_files/documents/2022_Q3_AAPL.pdf")
+
+    assert mime_type == "application/pdf"
+    assert len(metadata.keys()) > 0
+
+
+def test_extract_csv_file_natively_or_with_tika():
+    file_path = "../../test_files/documents/table-multi-row-column-cells-actual.csv"
+
+    result, metadata = Extractor().extract_file_to_string(file_path)
+    assert "Low Vision" in result
+    assert metadata["csv:num_rows"] == ["6"]
+
+    tika_result, tika_metadata = Extractor().set_force_tika(True).extract_file_to_string(file_path)
+    assert cosine_similarity(result, tika_result) > 0.9
+    assert is_expected_metadata_contained(tika_metadata, metadata)
+
+
+class CellCollector:
+    def __init__(self):
+        self.cells = []
+        self.in_cell = False
+        self.metadata = None
+
+    def start_element(self, name, attributes):
+        self.in_cell = name == "td"
+        if self.in_cell:
+            self.cells.append("")
+
+    def end_element(self, name):
+        self.in_cell = False
+
+    def characters(self, text):
+        if self.in_cell:
+            self.cells[-1] += text
+
+    def metadata(self, metadata):
+        self.metadata = metadata
+
+
+def test_extract_file_events():
+    file_path = "../../test_files/documents/table-multi-row-column-cells-actual.csv"
+
+    handler = CellCollector()
+    metadata = Extractor().extract_file_events(file_path, handler)
+    assert "Low Vision" in handler.cells
+    assert handler.metadata == metadata
+    assert metadata["csv:num_rows"] == ["6"]
+
+
+def test_extract_file_events_handler_exception_stops_extraction():
+    class StopHandler:
+        def start_element(self, name, attributes):
+            if name == "td":
+                raise ValueError("stop")
+
+    with pytest.raises(ValueError, match="stop"):
+        Extractor().extract_file_events(
+            "../../test_files/documents/table-multi-row-column-cells-actual.csv", StopHandler())
+
+
+def test_progress_callback():
+    progress = []
+    extractor = Extractor().set_max_pages(2).set_progress_callback(progress.append)
+
+    reader, metadata = extractor.extract_file("../../test_files/documents/2022_Q3_AAPL.pdf")
+    while reader.read(4096):
+        pass
+
+    assert progress[0] == {"kind": "document_started", "content_type": "application/pdf"}
+    assert progress[-1] == {"kind": "document_finished"}
+    pages = [p["page"] for p in progress if p["kind"] == "page_done"]
+    assert pages == [1, 2]
+    assert any(p["kind"] == "output_produced" and p["bytes"] > 0 for p in progress)
+
+
+def test_collect_stats():
+    extractor = Extractor().set_collect_stats(True)
+
+    content, metadata = extractor.extract_file_to_string("../../test_files/documents/2022_Q3_AAPL.pdf")
+    assert metadata["X-EXTRACTOUS:chars_out"] == [str(len(content))]
+    assert int(metadata["X-EXTRACTOUS:total_time_micros"][0]) > 0
+
+    reader, metadata = extractor.extract_file("../../test_files/documents/simple.odt")
+    assert reader.stats() is None
+    while reader.read(4096):
+        pass
+    stats = reader.stats()
+    assert stats["chars_out"] > 0
+    assert stats["total_time"] >= stats["parse_time"]
+    assert stats["ocr_ran"] is False
+
+
+def test_extraction_cache(tmp_path):
+    cache = ExtractionCache(str(tmp_path / "cache"))
+    extractor = Extractor().set_cache(cache)
+
+    result = extractor.extract_file_to_string("../../test_files/documents/simple.odt")
+    assert extractor.extract_file_to_string("../../test_files/documents/simple.odt") == result
+    stats = cache.stats()
+    assert (stats["hits"], stats["misses"], stats["entries"]) == (1, 1, 1)
+
+    cache.clear()
+    assert cache.stats()["entries"] == 0
+
+
+def test_compute_fingerprints():
+    extractor = Extractor().set_compute_fingerprints(True)
+
+    _content, odt = extractor.extract_file_to_string("../../test_files/documents/simple.odt")
+    _content, doc = extractor.extract_file_to_string("../../test_files/documents/simple.doc")
+    assert len(odt["X-EXTRACTOUS:input_sha256"][0]) == 64
+    assert odt["X-EXTRACTOUS:input_sha256"] != doc["X-EXTRACTOUS:input_sha256"]
+    assert simhash_similarity(odt["X-EXTRACTOUS:simhash"][0], odt["X-EXTRACTOUS:simhash"][0]) == 1.0
+
+
+def test_text_normalizer():
+    normalizer = (TextNormalizer()
+                  .set_expand_ligatures(True)
+                  .set_unicode_form(UnicodeForm.NFC)
+                  .set_dehyphenate(True)
+                  .set_collapse_whitespace(True))
+    assert normalizer.normalize("the ﬁnal extrac-\ntion  of   é") == "the final extraction of é"
+
+    extractor = Extractor().set_text_normalizer(normalizer)
+    content, _metadata = extractor.extract_file_to_string("../../test_files/documents/simple.odt")
+    assert "  " not in content
+    assert "\n\n\n" not in content
+
+
+def test_extract_file_outline():
+    outline = Extractor().extract_file_outline("../../test_files/documents/winter-sports.epub")
+    chapter = next(entry for entry in outline if entry.title == "CHAPTER II RINKS AND SKATERS")
+    assert chapter.level == 1
+    assert chapter.page is None
+    assert chapter.children[0].title == "THIRD-CLASS TEST"
+    assert chapter.children[0].level == 2
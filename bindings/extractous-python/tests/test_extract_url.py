@@ -1,4 +1,4 @@
-from extractous import Extractor
+from extractous import Extractor, UrlFetchConfig
 from utils import read_to_string
 
 def test_extract_url_to_stream():
@@ -26,3 +26,12 @@ def test_extract_url_to_string_as_xml():
 
     assert "Google" in content
     assert len(metadata.keys()) > 0
+
+def test_extract_url_with_fetch_config():
+    fetch_config = UrlFetchConfig().set_user_agent("extractous-test").set_header("Accept-Language", "en")
+    extractor = Extractor().set_url_fetch_config(fetch_config)
+
+    content, metadata  = extractor.extract_url_to_string("https://www.google.com")
+
+    assert "Google" in content
+    assert metadata["http-header:status-code"] == ["200"]
@@ -2,7 +2,7 @@ import sys
 
 import pytest
 
-from extractous import Extractor, PdfOcrStrategy, PdfParserConfig, TesseractOcrConfig
+from extractous import Extractor, PdfOcrStrategy, PdfParserConfig, TesseractOcrConfig, diagnose_ocr
 from utils import cosine_similarity
 
 
@@ -52,3 +52,61 @@ def test_test_extract_file_to_string_no_ocr_strategy_deu_ocr_pdf():
     result, metadata = extractor.extract_file_to_string(test_file)
 
     assert result.strip() == ""
+
+
+def test_ocr_config_validation():
+    config = TesseractOcrConfig().set_page_seg_mode(6).set_resize(300).add_other_tesseract_config("tessedit_do_invert", "0")
+    config.validate()
+
+    with pytest.raises(ValueError):
+        TesseractOcrConfig().set_page_seg_mode(14).validate()
+    with pytest.raises(ValueError):
+        TesseractOcrConfig().set_filter("bilinear").validate()
+
+
+@pytest.mark.skipif(sys.platform.startswith("win"), reason="Test not supported on Windows")
+def test_ara_ocr_png_page_seg_mode():
+    ocr_config = TesseractOcrConfig().set_language("ara").set_page_seg_mode(3).set_preserve_interword_spacing(True)
+    extractor = Extractor().set_ocr_config(ocr_config)
+    result, metadata = extractor.extract_file_to_string("../../test_files/documents/ara-ocr.png")
+
+    with open("../../test_files/expected_result/ara-ocr.png.txt", "r", encoding="utf8") as file:
+        expected = file.read()
+
+    assert cosine_similarity(result, expected) > 0.9
+
+
+@pytest.mark.skipif(sys.platform.startswith("win"), reason="Test not supported on Windows")
+def test_ara_ocr_png_words():
+    extractor = Extractor().set_ocr_config(TesseractOcrConfig().set_language("ara"))
+    result, words, metadata = extractor.extract_file_to_ocr_words("../../test_files/documents/ara-ocr.png")
+
+    with open("../../test_files/expected_result/ara-ocr.png.txt", "r", encoding="utf8") as file:
+        expected = file.read()
+
+    assert cosine_similarity(result, expected) > 0.9
+    assert len(words) > 0
+    for word in words:
+        x0, y0, x1, y1 = word.bbox
+        assert x0 < x1 and y0 < y1
+        assert 0 <= word.confidence <= 100
+        assert word.page == 1
+        assert word.line >= 1
+
+
+@pytest.mark.skipif(sys.platform.startswith("win"), reason="Test not supported on Windows")
+def test_diagnose_ocr_and_validate():
+    diagnostics = diagnose_ocr()
+    assert diagnostics.tesseract_found, diagnostics.error
+    assert diagnostics.version is not None
+    assert "ara" in diagnostics.languages
+
+    Extractor().set_ocr_config(TesseractOcrConfig().set_language("ara")).validate()
+    with pytest.raises(ValueError):
+        Extractor().set_ocr_config(TesseractOcrConfig().set_language("xyz")).validate()
+
+
+def test_diagnose_ocr_missing_tesseract():
+    diagnostics = diagnose_ocr(TesseractOcrConfig().set_tesseract_path("/extractous/missing"))
+    assert not diagnostics.tesseract_found
+    assert diagnostics.error is not None
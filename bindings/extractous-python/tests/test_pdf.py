@@ -1,4 +1,4 @@
-from extractous import Extractor
+from extractous import Extractor, HeaderFooterConfig, PdfOcrRenderingStrategy, PdfParserConfig
 from utils import read_to_string, extract_body_text
 
 
@@ -58,3 +58,39 @@ def test_extract_bytes_as_xml():
     print(f"test_pdf:test_extract_bytes_as_xml result = {result_xml}")
     result_text = extract_body_text(result_xml)
     assert result_text.strip() == expected_result().strip()
+
+def test_extract_file_with_layout_config():
+    pdf_config = (PdfParserConfig()
+                  .set_sort_by_position(True)
+                  .set_spacing_tolerance(0.4)
+                  .set_enable_auto_space(True)
+                  .set_suppress_duplicate_overlapping_text(True)
+                  .set_ocr_rendering_strategy(PdfOcrRenderingStrategy.NO_TEXT)
+                  .set_max_main_memory_bytes(-1))
+    extractor = Extractor().set_pdf_config(pdf_config)
+    result, metadata = extractor.extract_file_to_string("tests/quarkus.pdf")
+
+    print(f"test_pdf:test_extract_file_with_layout_config result = {result}")
+    assert result.strip() == expected_result().strip()
+
+def test_extract_file_page_range():
+    extractor = Extractor().set_xml_output(True)
+    extractor = extractor.set_pdf_config(PdfParserConfig().set_page_range(1, 2))
+    result, metadata = extractor.extract_file_to_string("../../test_files/documents/2022_Q3_AAPL.pdf")
+    assert result.count('<div class="page">') == 2
+
+    extractor = Extractor().set_xml_output(True).set_max_pages(1)
+    result, metadata = extractor.extract_file_to_string("../../test_files/documents/2022_Q3_AAPL.pdf")
+    assert result.count('<div class="page">') == 1
+
+
+def test_remove_headers_and_footers():
+    extractor = Extractor().set_remove_headers_and_footers(True)
+    result, metadata = extractor.extract_file_to_string("../../test_files/documents/2022_Q3_AAPL.pdf")
+    assert "Form 10-Q | 2" not in result
+    assert any(footer.startswith("Apple Inc. | Q3 2022 Form 10-Q |")
+               for footer in metadata["X-EXTRACTOUS:removed_footers"])
+
+    extractor = extractor.set_header_footer_config(HeaderFooterConfig().set_remove_page_numbers(False))
+    result, metadata = extractor.extract_file_to_string("../../test_files/documents/2022_Q3_AAPL.pdf")
+    assert metadata["X-EXTRACTOUS:removed_page_numbers"] == ["0"]
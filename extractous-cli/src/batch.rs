@@ -0,0 +1,198 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+use extractous::Extractor;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde_json::{json, Value};
+use walkdir::WalkDir;
+
+use crate::cli::BatchArgs;
+use crate::commands::extract_content;
+use crate::error::{CliError, CliResult};
+use crate::input::{open_output, Input};
+
+/// The `batch` command. Every file is written as a json line, either
+/// `{"path", "content", "metadata"}` or `{"path", "error", "error_kind"}` when it fails.
+/// Lines are written in the order the extractions finish
+pub fn run(args: &BatchArgs) -> CliResult<()> {
+    let extractor = args.options.extractor()?;
+    let files = collect_files(args)?;
+    let jobs = args
+        .jobs
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()))
+        .clamp(1, files.len().max(1));
+
+    let mut out = open_output(args.output.as_deref())?;
+    let next_file = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    let mut failures = 0;
+    let mut first_error = None;
+
+    thread::scope(|scope| -> CliResult<()> {
+        let (sender, receiver) = mpsc::channel();
+        for _ in 0..jobs {
+            let sender = sender.clone();
+            let (files, next_file, stop, extractor) = (&files, &next_file, &stop, &extractor);
+            scope.spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    let Some(path) = files.get(next_file.fetch_add(1, Ordering::Relaxed)) else {
+                        break;
+                    };
+                    let result = extract_record(path, extractor, args);
+                    if sender.send((path, result)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        // Only the workers hold senders now, so the loop ends when they are all done
+        drop(sender);
+
+        for (path, result) in receiver {
+            let record = match result {
+                Ok(record) => record,
+                Err(e) => {
+                    failures += 1;
+                    let record = json!({
+                        "path": path.to_string_lossy(),
+                        "error": e.to_string(),
+                        "error_kind": e.kind(),
+                    });
+                    if args.fail_fast {
+                        stop.store(true, Ordering::Relaxed);
+                        first_error.get_or_insert(e);
+                    }
+                    record
+                }
+            };
+            if let Err(e) = writeln!(out, "{}", record) {
+                stop.store(true, Ordering::Relaxed);
+                return Err(e.into());
+            }
+        }
+        Ok(())
+    })?;
+
+    out.flush()?;
+    match first_error {
+        Some(e) => Err(e),
+        None if failures > 0 => Err(CliError::BatchFailures(failures, files.len())),
+        None => Ok(()),
+    }
+}
+
+fn extract_record(path: &Path, extractor: &Extractor, args: &BatchArgs) -> CliResult<Value> {
+    let input = Input::File(path.to_string_lossy().into_owned());
+    let (content, metadata) = extract_content(&input, extractor, args.format.into())?;
+    Ok(json!({
+        "path": input.name(),
+        "content": content,
+        "metadata": metadata,
+    }))
+}
+
+pub fn build_glob_set(patterns: &[String]) -> CliResult<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns.iter() {
+        let glob = Glob::new(pattern)
+            .map_err(|e| CliError::Usage(format!("Invalid glob {:?}: {}", pattern, e)))?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .map_err(|e| CliError::Usage(format!("Invalid globs: {}", e)))
+}
+
+/// Walks the given paths and returns the files matching the include and exclude globs.
+/// Globs are matched against the path relative to the walked directory
+fn collect_files(args: &BatchArgs) -> CliResult<Vec<PathBuf>> {
+    let include = build_glob_set(&args.include)?;
+    let exclude = build_glob_set(&args.exclude)?;
+    let is_selected = |relative: &Path| {
+        (include.is_empty() || include.is_match(relative)) && !exclude.is_match(relative)
+    };
+
+    let mut files = Vec::new();
+    for root in args.paths.iter().map(Path::new) {
+        if root.is_file() {
+            // Files given explicitly are always extracted
+            files.push(root.to_path_buf());
+            continue;
+        }
+        if !root.is_dir() {
+            return Err(CliError::Io(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("No such file or directory: {}", root.display()),
+            )));
+        }
+
+        let mut walker = WalkDir::new(root)
+            .follow_links(args.follow_links)
+            .sort_by_file_name();
+        if let Some(max_depth) = args.max_depth {
+            walker = walker.max_depth(max_depth);
+        }
+        for entry in walker {
+            let entry = entry.map_err(|e| CliError::Io(e.into()))?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let relative = entry.path().strip_prefix(root).unwrap_or(entry.path());
+            if is_selected(relative) {
+                files.push(entry.into_path());
+            }
+        }
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{Cli, Command};
+    use clap::Parser;
+
+    fn batch_args(args: &[&str]) -> BatchArgs {
+        let cli = Cli::try_parse_from([&["extractous", "batch"], args].concat()).unwrap();
+        match cli.command {
+            Command::Batch(args) => args,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn collect_files_with_globs_test() {
+        let args = batch_args(&[
+            "../test_files/documents",
+            "--include",
+            "*.pdf",
+            "--include",
+            "*.docx",
+            "--exclude",
+            "bug_*",
+        ]);
+        let files = collect_files(&args).unwrap();
+        let names: Vec<_> = files
+            .iter()
+            .map(|f| f.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "2022_Q3_AAPL.pdf",
+                "category-level.docx",
+                "deu-ocr.pdf",
+                "eng-ocr.pdf"
+            ]
+        );
+    }
+
+    #[test]
+    fn collect_files_missing_path_test() {
+        let args = batch_args(&["../test_files/does-not-exist"]);
+        assert!(matches!(collect_files(&args), Err(CliError::Io(_))));
+    }
+}
@@ -0,0 +1,313 @@
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+use crate::error::EXIT_CODES_HELP;
+use crate::options::ExtractorOptions;
+
+/// Extract text, metadata and mime types from all kind of documents
+#[derive(Parser, Debug)]
+#[command(name = "extractous", version, about, after_help = EXIT_CODES_HELP)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Extract the content of a document
+    Extract(ExtractArgs),
+    /// Detect the mime type of documents
+    Detect(DetectArgs),
+    /// Extract the metadata of a document as json
+    Metadata(MetadataArgs),
+    /// Extract the hyperlinks of a document
+    Links(LinksArgs),
+    /// Extract the outline of a document: its bookmarks, navigation document or headings
+    Outline(OutlineArgs),
+    /// Extract the comments and annotations of a pdf, docx, pptx or xlsx document
+    Annotations(AnnotationsArgs),
+    /// Extract the tracked changes of a docx document, or its original or final text
+    Revisions(RevisionsArgs),
+    /// Extract all the documents of directories to json lines
+    Batch(BatchArgs),
+    /// Poll a directory and extract its created and modified documents to json lines
+    Watch(WatchArgs),
+}
+
+/// Output format of the extracted content
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// Plain text
+    #[default]
+    Text,
+    /// Tika xhtml
+    Xml,
+    /// Markdown converted from the xhtml
+    Markdown,
+    /// Json object with the text content and the metadata
+    Json,
+}
+
+#[derive(Args, Debug)]
+#[command(after_help = EXIT_CODES_HELP)]
+pub struct ExtractArgs {
+    /// File path, http(s) url, or - to read from stdin
+    #[arg(value_name = "INPUT")]
+    pub input: String,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+
+    /// Write the output to a file instead of stdout
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<String>,
+
+    #[command(flatten)]
+    pub options: ExtractorOptions,
+}
+
+#[derive(Args, Debug)]
+#[command(after_help = EXIT_CODES_HELP)]
+pub struct DetectArgs {
+    /// File paths, http(s) urls, or - to read from stdin
+    #[arg(value_name = "INPUT", required = true)]
+    pub inputs: Vec<String>,
+
+    /// Print a json line per input with the path and the mime type
+    #[arg(long)]
+    pub json: bool,
+
+    #[command(flatten)]
+    pub options: ExtractorOptions,
+}
+
+#[derive(Args, Debug)]
+#[command(after_help = EXIT_CODES_HELP)]
+pub struct MetadataArgs {
+    /// File path, http(s) url, or - to read from stdin
+    #[arg(value_name = "INPUT")]
+    pub input: String,
+
+    /// Print the json on a single line
+    #[arg(long)]
+    pub compact: bool,
+
+    /// Write the output to a file instead of stdout
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<String>,
+
+    #[command(flatten)]
+    pub options: ExtractorOptions,
+}
+
+#[derive(Args, Debug)]
+#[command(after_help = EXIT_CODES_HELP)]
+pub struct LinksArgs {
+    /// File path, http(s) url, or - to read from stdin
+    #[arg(value_name = "INPUT")]
+    pub input: String,
+
+    /// Resolve the relative links against this url. The links of an url input are resolved
+    /// against the url by default
+    #[arg(long, value_name = "URL")]
+    pub base_url: Option<String>,
+
+    /// Print a json line per link with its href, anchor text, rel and page instead of its href
+    #[arg(long)]
+    pub json: bool,
+
+    /// Write the output to a file instead of stdout
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<String>,
+
+    #[command(flatten)]
+    pub options: ExtractorOptions,
+}
+
+#[derive(Args, Debug)]
+#[command(after_help = EXIT_CODES_HELP)]
+pub struct OutlineArgs {
+    /// File path or - to read from stdin
+    #[arg(value_name = "INPUT")]
+    pub input: String,
+
+    /// Print the outline as a json array of nested entries instead of an indented list
+    #[arg(long)]
+    pub json: bool,
+
+    /// Write the output to a file instead of stdout
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<String>,
+
+    #[command(flatten)]
+    pub options: ExtractorOptions,
+}
+
+#[derive(Args, Debug)]
+#[command(after_help = EXIT_CODES_HELP)]
+pub struct AnnotationsArgs {
+    /// File path or - to read from stdin
+    #[arg(value_name = "INPUT")]
+    pub input: String,
+
+    /// Print a json line per annotation with its kind, author, date, text, anchor text and page
+    /// instead of a line with its kind, page, author and text
+    #[arg(long)]
+    pub json: bool,
+
+    /// Write the output to a file instead of stdout
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<String>,
+
+    #[command(flatten)]
+    pub options: ExtractorOptions,
+}
+
+/// Version of the text of a document with tracked changes
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextVersion {
+    /// The text before the tracked changes
+    Original,
+    /// The text with all the tracked changes accepted
+    Final,
+}
+
+#[derive(Args, Debug)]
+#[command(after_help = EXIT_CODES_HELP)]
+pub struct RevisionsArgs {
+    /// File path or - to read from stdin
+    #[arg(value_name = "INPUT")]
+    pub input: String,
+
+    /// Print a json line per revision with its kind, author, date, text and offset instead of a
+    /// line with its kind, author and text
+    #[arg(long, conflicts_with = "text")]
+    pub json: bool,
+
+    /// Print this version of the text of the document instead of its revisions
+    #[arg(long, value_enum, value_name = "VERSION")]
+    pub text: Option<TextVersion>,
+
+    /// Write the output to a file instead of stdout
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<String>,
+
+    #[command(flatten)]
+    pub options: ExtractorOptions,
+}
+
+#[derive(Args, Debug)]
+#[command(after_help = EXIT_CODES_HELP)]
+pub struct BatchArgs {
+    /// Files or directories to extract, directories are walked recursively
+    #[arg(value_name = "PATH", required = true)]
+    pub paths: Vec<String>,
+
+    /// Only extract the files matching this glob, e.g. '*.pdf'. Can be repeated
+    #[arg(long, value_name = "GLOB")]
+    pub include: Vec<String>,
+
+    /// Skip the files matching this glob, e.g. '**/drafts/**'. Can be repeated
+    #[arg(long, value_name = "GLOB")]
+    pub exclude: Vec<String>,
+
+    /// Format of the content field of every json line
+    #[arg(short, long, value_enum, default_value_t = ContentFormat::Text)]
+    pub format: ContentFormat,
+
+    /// Write the json lines to a file instead of stdout
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<String>,
+
+    /// Number of documents extracted in parallel [default: number of cpus]
+    #[arg(short, long, value_name = "N")]
+    pub jobs: Option<usize>,
+
+    /// Follow symbolic links when walking directories
+    #[arg(long)]
+    pub follow_links: bool,
+
+    /// Maximum depth of the directory walk
+    #[arg(long, value_name = "DEPTH")]
+    pub max_depth: Option<usize>,
+
+    /// Stop at the first input that fails instead of writing an error record
+    #[arg(long)]
+    pub fail_fast: bool,
+
+    #[command(flatten)]
+    pub options: ExtractorOptions,
+}
+
+#[derive(Args, Debug)]
+#[command(after_help = EXIT_CODES_HELP)]
+pub struct WatchArgs {
+    /// Directory to watch, walked recursively
+    #[arg(value_name = "DIR")]
+    pub dir: String,
+
+    /// File keeping the state of the extracted files, so that a restarted watch only extracts
+    /// the files changed since it stopped. Without it, all the files are extracted on start
+    #[arg(long, value_name = "FILE")]
+    pub state_file: Option<String>,
+
+    /// Seconds between two scans of the directory
+    #[arg(long, value_name = "SECONDS", default_value_t = 5)]
+    pub interval: u64,
+
+    /// Scan the directory once and exit, e.g. to run the watch from a scheduler
+    #[arg(long)]
+    pub once: bool,
+
+    /// Only extract the files matching this glob, e.g. '*.pdf'. Can be repeated
+    #[arg(long, value_name = "GLOB")]
+    pub include: Vec<String>,
+
+    /// Skip the files matching this glob, e.g. '**/drafts/**'. Can be repeated
+    #[arg(long, value_name = "GLOB")]
+    pub exclude: Vec<String>,
+
+    /// Format of the content field of every json line
+    #[arg(short, long, value_enum, default_value_t = ContentFormat::Text)]
+    pub format: ContentFormat,
+
+    /// Append the json lines to a file instead of writing them to stdout
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<String>,
+
+    #[command(flatten)]
+    pub options: ExtractorOptions,
+}
+
+/// Format of the content embedded in the batch and watch json lines
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ContentFormat {
+    /// Plain text
+    #[default]
+    Text,
+    /// Tika xhtml
+    Xml,
+    /// Markdown converted from the xhtml
+    Markdown,
+}
+
+impl From<ContentFormat> for OutputFormat {
+    fn from(format: ContentFormat) -> Self {
+        match format {
+            ContentFormat::Text => OutputFormat::Text,
+            ContentFormat::Xml => OutputFormat::Xml,
+            ContentFormat::Markdown => OutputFormat::Markdown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn verify_cli_test() {
+        Cli::command().debug_assert();
+    }
+}
@@ -0,0 +1,240 @@
+use std::io::Write;
+
+use extractous::{Annotation, Extractor, Metadata, OutlineEntry};
+use serde_json::{json, Value};
+
+use crate::cli::{
+    AnnotationsArgs, DetectArgs, ExtractArgs, LinksArgs, MetadataArgs, OutlineArgs, OutputFormat,
+    RevisionsArgs, TextVersion,
+};
+use crate::error::CliResult;
+use crate::input::{open_output, Input};
+use crate::markdown::xhtml_to_markdown;
+
+/// Extracts the content of the input in the given format. For the json format only the text
+/// content is returned, the caller is responsible for adding the metadata
+pub fn extract_content(
+    input: &Input,
+    extractor: &Extractor,
+    format: OutputFormat,
+) -> CliResult<(String, Metadata)> {
+    let as_xml = matches!(format, OutputFormat::Xml | OutputFormat::Markdown);
+    let extractor = extractor.clone().set_xml_output(as_xml);
+
+    let (content, metadata) = input.extract_to_string(&extractor)?;
+    if format == OutputFormat::Markdown {
+        return Ok((xhtml_to_markdown(&content)?, metadata));
+    }
+    Ok((content, metadata))
+}
+
+/// The `extract` command
+pub fn extract(args: &ExtractArgs) -> CliResult<()> {
+    let input = Input::parse(&args.input);
+    let extractor = args.options.extractor()?;
+    let mut out = open_output(args.output.as_deref())?;
+
+    match args.format {
+        // Stream the content unless it must be truncated
+        OutputFormat::Text | OutputFormat::Xml if args.options.max_length.is_none() => {
+            let extractor = extractor.set_xml_output(args.format == OutputFormat::Xml);
+            input.extract_to_writer(&extractor, &mut out)?;
+        }
+        OutputFormat::Json => {
+            let (content, metadata) = extract_content(&input, &extractor, args.format)?;
+            let value = json!({ "content": content, "metadata": metadata });
+            serde_json::to_writer_pretty(&mut out, &value)?;
+            writeln!(out)?;
+        }
+        _ => {
+            let (content, _metadata) = extract_content(&input, &extractor, args.format)?;
+            out.write_all(content.as_bytes())?;
+        }
+    }
+
+    out.flush()?;
+    Ok(())
+}
+
+/// The `detect` command
+pub fn detect(args: &DetectArgs) -> CliResult<()> {
+    let extractor = args.options.extractor()?;
+    let mut out = open_output(None)?;
+
+    for input in args.inputs.iter().map(|i| Input::parse(i)) {
+        let mime_type = input.detect(&extractor)?;
+        if args.json {
+            let value = json!({ "path": input.name(), "mime_type": mime_type });
+            writeln!(out, "{}", value)?;
+        } else if args.inputs.len() > 1 {
+            writeln!(out, "{}: {}", input.name(), mime_type)?;
+        } else {
+            writeln!(out, "{}", mime_type)?;
+        }
+    }
+
+    out.flush()?;
+    Ok(())
+}
+
+/// The `metadata` command
+pub fn metadata(args: &MetadataArgs) -> CliResult<()> {
+    let input = Input::parse(&args.input);
+    let extractor = args.options.extractor()?;
+    let mut out = open_output(args.output.as_deref())?;
+
+    let (_content, metadata) = input.extract_to_string(&extractor)?;
+    // Convert to a json value to sort the keys
+    let value = serde_json::to_value(&metadata)?;
+    if args.compact {
+        serde_json::to_writer(&mut out, &value)?;
+    } else {
+        serde_json::to_writer_pretty(&mut out, &value)?;
+    }
+    writeln!(out)?;
+
+    out.flush()?;
+    Ok(())
+}
+
+/// The `links` command
+pub fn links(args: &LinksArgs) -> CliResult<()> {
+    let input = Input::parse(&args.input);
+    let mut extractor = args.options.extractor()?;
+    if let Some(base_url) = &args.base_url {
+        extractor = extractor.set_link_base_url(base_url);
+    }
+    let mut out = open_output(args.output.as_deref())?;
+
+    let (links, _metadata) = input.extract_links(&extractor)?;
+    for link in links {
+        if args.json {
+            let value = json!({
+                "href": link.href,
+                "anchor_text": link.anchor_text,
+                "rel": link.rel,
+                "source_page": link.source_page,
+            });
+            writeln!(out, "{}", value)?;
+        } else {
+            writeln!(out, "{}", link.href)?;
+        }
+    }
+
+    out.flush()?;
+    Ok(())
+}
+
+/// The `outline` command
+pub fn outline(args: &OutlineArgs) -> CliResult<()> {
+    let input = Input::parse(&args.input);
+    let extractor = args.options.extractor()?;
+    let mut out = open_output(args.output.as_deref())?;
+
+    let outline = input.extract_outline(&extractor)?;
+    if args.json {
+        let value: Vec<Value> = outline.iter().map(outline_json).collect();
+        serde_json::to_writer_pretty(&mut out, &value)?;
+        writeln!(out)?;
+    } else {
+        write_outline(&mut out, &outline, 0)?;
+    }
+
+    out.flush()?;
+    Ok(())
+}
+
+fn outline_json(entry: &OutlineEntry) -> Value {
+    json!({
+        "title": entry.title,
+        "level": entry.level,
+        "page": entry.page,
+        "children": entry.children.iter().map(outline_json).collect::<Vec<_>>(),
+    })
+}
+
+/// Writes the entries indented by their depth, with their page if any
+fn write_outline(out: &mut dyn Write, entries: &[OutlineEntry], depth: usize) -> CliResult<()> {
+    for entry in entries {
+        match entry.page {
+            Some(page) => writeln!(out, "{}{} ({})", "  ".repeat(depth), entry.title, page)?,
+            None => writeln!(out, "{}{}", "  ".repeat(depth), entry.title)?,
+        }
+        write_outline(out, &entry.children, depth + 1)?;
+    }
+    Ok(())
+}
+
+/// The `annotations` command
+pub fn annotations(args: &AnnotationsArgs) -> CliResult<()> {
+    let input = Input::parse(&args.input);
+    let extractor = args.options.extractor()?;
+    let mut out = open_output(args.output.as_deref())?;
+
+    for annotation in input.extract_annotations(&extractor)? {
+        if args.json {
+            let value = json!({
+                "kind": annotation.kind,
+                "author": annotation.author,
+                "date": annotation.date,
+                "text": annotation.text,
+                "anchor_text": annotation.anchor_text,
+                "page": annotation.page,
+            });
+            writeln!(out, "{}", value)?;
+        } else {
+            writeln!(out, "{}", annotation_line(&annotation))?;
+        }
+    }
+
+    out.flush()?;
+    Ok(())
+}
+
+/// The annotation on one line, e.g. `highlight p.2 by Jane Roe: Too broad`
+fn annotation_line(annotation: &Annotation) -> String {
+    let mut line = annotation.kind.clone();
+    if let Some(page) = annotation.page {
+        line.push_str(&format!(" p.{}", page));
+    }
+    if let Some(author) = &annotation.author {
+        line.push_str(&format!(" by {}", author));
+    }
+    let text: Vec<&str> = annotation.text.split_whitespace().collect();
+    format!("{}: {}", line, text.join(" "))
+}
+
+/// The `revisions` command
+pub fn revisions(args: &RevisionsArgs) -> CliResult<()> {
+    let input = Input::parse(&args.input);
+    let extractor = args.options.extractor()?;
+    let mut out = open_output(args.output.as_deref())?;
+
+    let revisions = input.extract_revisions(&extractor)?;
+    match args.text {
+        Some(TextVersion::Original) => write!(out, "{}", revisions.original_text())?,
+        Some(TextVersion::Final) => write!(out, "{}", revisions.final_text())?,
+        None if args.json => {
+            for revision in revisions.revisions {
+                let value = json!({
+                    "kind": revision.kind.to_string(),
+                    "author": revision.author,
+                    "date": revision.date,
+                    "text": revision.text,
+                    "offset": revision.offset,
+                });
+                writeln!(out, "{}", value)?;
+            }
+        }
+        None => {
+            for revision in revisions.revisions {
+                let author = revision.author.as_deref().unwrap_or("unknown author");
+                let text: Vec<&str> = revision.text.split_whitespace().collect();
+                writeln!(out, "{} by {}: {}", revision.kind, author, text.join(" "))?;
+            }
+        }
+    }
+
+    out.flush()?;
+    Ok(())
+}
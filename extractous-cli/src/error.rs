@@ -0,0 +1,98 @@
+use std::process::ExitCode;
+
+/// Exit code for errors that do not fall in any other category
+pub const EXIT_UNKNOWN_ERROR: u8 = 1;
+/// Exit code for invalid arguments, also used by clap for usage errors
+pub const EXIT_USAGE_ERROR: u8 = 2;
+/// Exit code when an input could not be read or an output could not be written
+pub const EXIT_IO_ERROR: u8 = 3;
+/// Exit code when a document could not be parsed
+pub const EXIT_PARSE_ERROR: u8 = 4;
+/// Exit code when an url could not be downloaded
+pub const EXIT_FETCH_ERROR: u8 = 5;
+/// Exit code when an url returned a non success http status
+pub const EXIT_HTTP_STATUS_ERROR: u8 = 6;
+/// Exit code for errors raised by the native Tika library
+pub const EXIT_NATIVE_ERROR: u8 = 7;
+/// Exit code of the batch command when at least one of the inputs failed
+pub const EXIT_BATCH_FAILURES: u8 = 8;
+
+/// Exit codes documentation shown at the end of `extractous --help`
+pub const EXIT_CODES_HELP: &str = "\
+Exit codes:
+  0  Success
+  1  Unknown error
+  2  Invalid arguments
+  3  I/O error, e.g. the input file does not exist
+  4  Parse error, the document could not be parsed
+  5  Fetch error, the url could not be downloaded
+  6  The url returned a non success http status
+  7  Native library error
+  8  batch: one or more inputs failed, see the error records in the output";
+
+/// Represent errors returned by the cli commands
+#[derive(thiserror::Error, Debug)]
+pub enum CliError {
+    #[error("{0}")]
+    Extract(#[from] extractous::Error),
+
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("{0}")]
+    Usage(String),
+
+    #[error("Invalid xml output: {0}")]
+    Xml(#[from] quick_xml::Error),
+
+    #[error("{0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("{0} of {1} inputs failed")]
+    BatchFailures(usize, usize),
+}
+
+impl CliError {
+    /// Returns the documented exit code for this error
+    pub fn code(&self) -> u8 {
+        match self {
+            CliError::Extract(e) => match e {
+                extractous::Error::Unknown(_) => EXIT_UNKNOWN_ERROR,
+                extractous::Error::IoError(_) => EXIT_IO_ERROR,
+                extractous::Error::ParseError(_) => EXIT_PARSE_ERROR,
+                extractous::Error::Utf8Error(_) => EXIT_PARSE_ERROR,
+                extractous::Error::JniError(_) => EXIT_NATIVE_ERROR,
+                extractous::Error::JniEnvCall(_) => EXIT_NATIVE_ERROR,
+                extractous::Error::FetchError(_) => EXIT_FETCH_ERROR,
+                extractous::Error::HttpStatusError(_, _) => EXIT_HTTP_STATUS_ERROR,
+                extractous::Error::ConfigError(_) => EXIT_USAGE_ERROR,
+            },
+            CliError::Io(_) => EXIT_IO_ERROR,
+            CliError::Usage(_) => EXIT_USAGE_ERROR,
+            CliError::Xml(_) => EXIT_PARSE_ERROR,
+            CliError::Json(_) => EXIT_UNKNOWN_ERROR,
+            CliError::BatchFailures(_, _) => EXIT_BATCH_FAILURES,
+        }
+    }
+
+    /// Returns a short name of the error category, used in the batch error records
+    pub fn kind(&self) -> &'static str {
+        match self.code() {
+            EXIT_USAGE_ERROR => "usage",
+            EXIT_IO_ERROR => "io",
+            EXIT_PARSE_ERROR => "parse",
+            EXIT_FETCH_ERROR => "fetch",
+            EXIT_HTTP_STATUS_ERROR => "http_status",
+            EXIT_NATIVE_ERROR => "native",
+            EXIT_BATCH_FAILURES => "batch",
+            _ => "unknown",
+        }
+    }
+
+    pub fn exit_code(&self) -> ExitCode {
+        ExitCode::from(self.code())
+    }
+}
+
+/// Result that is a wrapper of Result<T, CliError>
+pub type CliResult<T> = Result<T, CliError>;
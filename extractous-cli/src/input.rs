@@ -0,0 +1,167 @@
+use std::io::{self, Read, Write};
+
+use extractous::{Annotation, DocumentRevisions, Extractor, Link, Metadata, OutlineEntry};
+
+use crate::error::{CliError, CliResult};
+
+/// Source of a document given on the command line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    /// `-` reads the document from stdin
+    Stdin,
+    /// Any argument with an url scheme, e.g. `https://`
+    Url(String),
+    File(String),
+}
+
+impl Input {
+    pub fn parse(input: &str) -> Self {
+        if input == "-" {
+            Input::Stdin
+        } else if input.contains("://") {
+            Input::Url(input.to_string())
+        } else {
+            Input::File(input.to_string())
+        }
+    }
+
+    /// Name of the input as given on the command line
+    pub fn name(&self) -> &str {
+        match self {
+            Input::Stdin => "-",
+            Input::Url(url) => url,
+            Input::File(path) => path,
+        }
+    }
+
+    /// Extracts the whole content and writes it to `out` while it is being parsed
+    pub fn extract_to_writer(
+        &self,
+        extractor: &Extractor,
+        out: &mut dyn Write,
+    ) -> CliResult<Metadata> {
+        let metadata = match self {
+            Input::Stdin => {
+                // The buffer must outlive the reader, which parses the bytes lazily
+                let buffer = read_stdin()?;
+                let (mut reader, metadata) = extractor.extract_bytes(&buffer)?;
+                io::copy(&mut reader, out)?;
+                metadata
+            }
+            Input::Url(url) => {
+                let (mut reader, metadata) = extractor.extract_url(url)?;
+                io::copy(&mut reader, out)?;
+                metadata
+            }
+            Input::File(path) => {
+                let (mut reader, metadata) = extractor.extract_file(path)?;
+                io::copy(&mut reader, out)?;
+                metadata
+            }
+        };
+        Ok(metadata)
+    }
+
+    /// Extracts the content to a string of maximum length of the extractor's
+    /// `extract_string_max_length`. Unlike the stream, the returned metadata is always complete
+    pub fn extract_to_string(&self, extractor: &Extractor) -> CliResult<(String, Metadata)> {
+        let result = match self {
+            Input::Stdin => extractor.extract_bytes_to_string(&read_stdin()?)?,
+            Input::Url(url) => extractor.extract_url_to_string(url)?,
+            Input::File(path) => extractor.extract_file_to_string(path)?,
+        };
+        Ok(result)
+    }
+
+    /// Extracts the hyperlinks of the input
+    pub fn extract_links(&self, extractor: &Extractor) -> CliResult<(Vec<Link>, Metadata)> {
+        let result = match self {
+            Input::Stdin => extractor.extract_bytes_links(&read_stdin()?)?,
+            Input::Url(url) => extractor.extract_url_links(url)?,
+            Input::File(path) => extractor.extract_file_links(path)?,
+        };
+        Ok(result)
+    }
+
+    /// Extracts the outline of the input. Urls are not supported
+    pub fn extract_outline(&self, extractor: &Extractor) -> CliResult<Vec<OutlineEntry>> {
+        let outline = match self {
+            Input::Stdin => extractor.extract_bytes_outline(&read_stdin()?)?,
+            Input::Url(url) => {
+                let message = format!("The outline of urls is not supported: {}", url);
+                return Err(CliError::Usage(message));
+            }
+            Input::File(path) => extractor.extract_file_outline(path)?,
+        };
+        Ok(outline)
+    }
+
+    /// Extracts the comments and annotations of the input. Urls are not supported
+    pub fn extract_annotations(&self, extractor: &Extractor) -> CliResult<Vec<Annotation>> {
+        let annotations = match self {
+            Input::Stdin => extractor.extract_bytes_annotations(&read_stdin()?)?,
+            Input::Url(url) => {
+                let message = format!("The annotations of urls are not supported: {}", url);
+                return Err(CliError::Usage(message));
+            }
+            Input::File(path) => extractor.extract_file_annotations(path)?,
+        };
+        Ok(annotations)
+    }
+
+    /// Extracts the tracked changes of the input. Urls are not supported
+    pub fn extract_revisions(&self, extractor: &Extractor) -> CliResult<DocumentRevisions> {
+        let revisions = match self {
+            Input::Stdin => extractor.extract_bytes_revisions(&read_stdin()?)?,
+            Input::Url(url) => {
+                let message = format!("The revisions of urls are not supported: {}", url);
+                return Err(CliError::Usage(message));
+            }
+            Input::File(path) => extractor.extract_file_revisions(path)?,
+        };
+        Ok(revisions)
+    }
+
+    /// Detects the mime type of the input
+    pub fn detect(&self, extractor: &Extractor) -> CliResult<String> {
+        let (mime_type, _metadata) = match self {
+            Input::Stdin => extractor.detect_bytes(&read_stdin()?)?,
+            Input::Url(url) => extractor.detect_url(url)?,
+            Input::File(path) => extractor.detect_file(path)?,
+        };
+        Ok(mime_type)
+    }
+}
+
+fn read_stdin() -> CliResult<Vec<u8>> {
+    let mut buffer = Vec::new();
+    io::stdin().lock().read_to_end(&mut buffer)?;
+    Ok(buffer)
+}
+
+/// Opens the output file, or stdout if no file is given
+pub fn open_output(output: Option<&str>) -> CliResult<Box<dyn Write>> {
+    let out: Box<dyn Write> = match output {
+        Some(path) => Box::new(io::BufWriter::new(std::fs::File::create(path)?)),
+        None => Box::new(io::BufWriter::new(io::stdout().lock())),
+    };
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_input_test() {
+        assert_eq!(Input::parse("-"), Input::Stdin);
+        assert_eq!(
+            Input::parse("https://example.com/a.pdf"),
+            Input::Url("https://example.com/a.pdf".to_string())
+        );
+        assert_eq!(
+            Input::parse("docs/a.pdf"),
+            Input::File("docs/a.pdf".to_string())
+        );
+    }
+}
@@ -0,0 +1,60 @@
+//! `extractous` command line interface
+//!
+//! Extracts text, metadata and mime types from files, urls or stdin. See `extractous --help`
+//! for the available subcommands and the exit codes.
+//!
+//! ```text
+//! extractous extract report.pdf
+//! extractous extract --format markdown https://example.com/report.pdf
+//! cat report.docx | extractous metadata -
+//! extractous detect *.pdf
+//! extractous links --json https://example.com/
+//! extractous outline book.epub
+//! extractous annotations --json contract.docx
+//! extractous revisions --text original contract.docx
+//! extractous batch ./documents --include '*.pdf' --output documents.jsonl
+//! extractous watch ./documents --state-file documents.state.json --output documents.jsonl
+//! ```
+
+use clap::Parser;
+use std::process::ExitCode;
+
+mod batch;
+mod cli;
+mod commands;
+mod error;
+mod input;
+mod markdown;
+mod options;
+mod watch;
+
+use cli::{Cli, Command};
+use error::CliError;
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    // The logs of tika are printed to stderr when enabled with RUST_LOG, e.g. RUST_LOG=warn
+    env_logger::init();
+
+    let result = match &cli.command {
+        Command::Extract(args) => commands::extract(args),
+        Command::Detect(args) => commands::detect(args),
+        Command::Metadata(args) => commands::metadata(args),
+        Command::Links(args) => commands::links(args),
+        Command::Outline(args) => commands::outline(args),
+        Command::Annotations(args) => commands::annotations(args),
+        Command::Revisions(args) => commands::revisions(args),
+        Command::Batch(args) => batch::run(args),
+        Command::Watch(args) => watch::run(args),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        // The reader of the output went away, e.g. `extractous extract file.pdf | head`
+        Err(CliError::Io(e)) if e.kind() == std::io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("extractous: {}", e);
+            e.exit_code()
+        }
+    }
+}
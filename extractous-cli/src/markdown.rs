@@ -0,0 +1,317 @@
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+/// Converts the xhtml produced by the extractor xml output to markdown.
+///
+/// Headings, paragraphs, lists, links, images, emphasis, preformatted text and tables are
+/// converted, other elements only contribute their text
+pub fn xhtml_to_markdown(xhtml: &str) -> Result<String, quick_xml::Error> {
+    let mut reader = Reader::from_str(xhtml);
+    let mut writer = MarkdownWriter::default();
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) => writer.start(&e),
+            Event::Empty(e) => {
+                writer.start(&e);
+                writer.end(e.local_name().as_ref());
+            }
+            Event::End(e) => writer.end(e.local_name().as_ref()),
+            Event::Text(e) => writer.text(&e.unescape()?),
+            Event::CData(e) => writer.text(&String::from_utf8_lossy(&e)),
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(writer.finish())
+}
+
+#[derive(Default)]
+struct MarkdownWriter {
+    /// The first buffer is the document, table cells are written to their own buffer
+    buffers: Vec<String>,
+    /// Depth inside elements whose text is not part of the content, e.g. `head`
+    skip_depth: usize,
+    pre_depth: usize,
+    /// `None` for unordered lists, the next item number for ordered lists
+    lists: Vec<Option<usize>>,
+    /// The href of the links being written
+    links: Vec<Option<String>>,
+    tables: Vec<Vec<Vec<String>>>,
+}
+
+fn attribute(e: &BytesStart, name: &str) -> Option<String> {
+    e.try_get_attribute(name)
+        .ok()
+        .flatten()
+        .and_then(|a| a.unescape_value().ok().map(|v| v.into_owned()))
+}
+
+impl MarkdownWriter {
+    fn buffer(&mut self) -> &mut String {
+        if self.buffers.is_empty() {
+            self.buffers.push(String::new());
+        }
+        self.buffers.last_mut().unwrap()
+    }
+
+    fn in_cell(&self) -> bool {
+        self.buffers.len() > 1
+    }
+
+    fn push(&mut self, s: &str) {
+        if self.skip_depth == 0 {
+            self.buffer().push_str(s);
+        }
+    }
+
+    /// Starts a new block separated from the previous one by an empty line
+    fn block(&mut self) {
+        if self.skip_depth > 0 {
+            return;
+        }
+        if self.in_cell() {
+            self.push(" ");
+            return;
+        }
+        let buffer = self.buffer();
+        buffer.truncate(buffer.trim_end().len());
+        if !buffer.is_empty() {
+            buffer.push_str("\n\n");
+        }
+    }
+
+    /// Starts a new line without an empty line, used between list items
+    fn line(&mut self) {
+        if self.skip_depth > 0 || self.in_cell() {
+            return;
+        }
+        let buffer = self.buffer();
+        buffer.truncate(buffer.trim_end_matches([' ', '\t']).len());
+        if !buffer.is_empty() && !buffer.ends_with('\n') {
+            buffer.push('\n');
+        }
+    }
+
+    fn start(&mut self, e: &BytesStart) {
+        match e.local_name().as_ref() {
+            b"head" | b"script" | b"style" | b"title" => self.skip_depth += 1,
+            b"p" | b"div" | b"blockquote" | b"section" | b"article" => self.block(),
+            b"h1" | b"h2" | b"h3" | b"h4" | b"h5" | b"h6" => {
+                self.block();
+                let level = (e.local_name().as_ref()[1] - b'0') as usize;
+                self.push(&format!("{} ", "#".repeat(level)));
+            }
+            b"ul" => {
+                self.line();
+                self.lists.push(None);
+            }
+            b"ol" => {
+                self.line();
+                self.lists.push(Some(1));
+            }
+            b"li" => {
+                self.line();
+                let indent = "  ".repeat(self.lists.len().saturating_sub(1));
+                let marker = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}. ", *number - 1)
+                    }
+                    _ => "- ".to_string(),
+                };
+                self.push(&format!("{}{}", indent, marker));
+            }
+            b"b" | b"strong" => self.push("**"),
+            b"i" | b"em" => self.push("*"),
+            b"br" => {
+                if self.in_cell() {
+                    self.push(" ");
+                } else {
+                    self.push("  \n");
+                }
+            }
+            b"a" => {
+                let href = attribute(e, "href").filter(|h| !h.is_empty());
+                if href.is_some() {
+                    self.push("[");
+                }
+                self.links.push(href);
+            }
+            b"img" => {
+                let src = attribute(e, "src").unwrap_or_default();
+                let alt = attribute(e, "alt").unwrap_or_default();
+                self.push(&format!("![{}]({})", alt, src));
+            }
+            b"pre" => {
+                self.block();
+                self.push("```\n");
+                self.pre_depth += 1;
+            }
+            b"table" => {
+                self.block();
+                self.tables.push(Vec::new());
+            }
+            b"tr" => {
+                if let Some(table) = self.tables.last_mut() {
+                    table.push(Vec::new());
+                }
+            }
+            b"td" | b"th" => self.buffers.push(String::new()),
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, name: &[u8]) {
+        match name {
+            b"head" | b"script" | b"style" | b"title" => {
+                self.skip_depth = self.skip_depth.saturating_sub(1)
+            }
+            b"p" | b"div" | b"blockquote" | b"section" | b"article" => self.block(),
+            b"h1" | b"h2" | b"h3" | b"h4" | b"h5" | b"h6" => self.block(),
+            b"ul" | b"ol" => {
+                self.lists.pop();
+                if self.lists.is_empty() {
+                    self.block();
+                }
+            }
+            b"li" => self.line(),
+            b"b" | b"strong" => self.push("**"),
+            b"i" | b"em" => self.push("*"),
+            b"a" => {
+                if let Some(Some(href)) = self.links.pop() {
+                    self.push(&format!("]({})", href));
+                }
+            }
+            b"pre" => {
+                self.pre_depth = self.pre_depth.saturating_sub(1);
+                self.line();
+                self.push("```");
+                self.block();
+            }
+            b"td" | b"th" if self.in_cell() => {
+                let cell = self.buffers.pop().unwrap_or_default();
+                let cell = cell.split_whitespace().collect::<Vec<_>>().join(" ");
+                if let Some(row) = self.tables.last_mut().and_then(|t| t.last_mut()) {
+                    row.push(cell.replace('|', "\\|"));
+                }
+            }
+            b"table" => {
+                if let Some(rows) = self.tables.pop() {
+                    self.write_table(rows);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if self.skip_depth > 0 {
+            return;
+        }
+        if self.pre_depth > 0 {
+            self.push(text);
+            return;
+        }
+
+        // Collapse whitespaces like a browser would do
+        let mut collapsed = String::with_capacity(text.len());
+        let buffer = self.buffer();
+        let mut last_is_space = buffer.is_empty() || buffer.ends_with([' ', '\n']);
+        for c in text.chars() {
+            if c.is_whitespace() {
+                if !last_is_space {
+                    collapsed.push(' ');
+                }
+                last_is_space = true;
+            } else {
+                collapsed.push(c);
+                last_is_space = false;
+            }
+        }
+        self.push(&collapsed);
+    }
+
+    fn write_table(&mut self, rows: Vec<Vec<String>>) {
+        let rows: Vec<_> = rows.into_iter().filter(|r| !r.is_empty()).collect();
+        let columns = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+        if columns == 0 {
+            return;
+        }
+
+        let mut table = String::new();
+        for (i, row) in rows.iter().enumerate() {
+            let mut cells = row.clone();
+            cells.resize(columns, String::new());
+            table.push_str(&format!("| {} |\n", cells.join(" | ")));
+            // Markdown tables need a header, the first row is used as the header
+            if i == 0 {
+                table.push_str(&format!("|{}\n", " --- |".repeat(columns)));
+            }
+        }
+
+        if self.in_cell() {
+            // Nested tables can not be represented, keep their text only
+            let text = rows.concat().join(" ");
+            self.push(&text);
+        } else {
+            self.block();
+            self.push(&table);
+            self.block();
+        }
+    }
+
+    fn finish(mut self) -> String {
+        let buffer = self.buffer();
+        let mut markdown = buffer.trim().to_string();
+        markdown.push('\n');
+        markdown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_markdown(body: &str) -> String {
+        let xhtml = format!(
+            "<html xmlns=\"http://www.w3.org/1999/xhtml\">\
+            <head><meta name=\"Content-Type\" content=\"text/html\"/><title>Title</title></head>\
+            <body>{}</body></html>",
+            body
+        );
+        xhtml_to_markdown(&xhtml).unwrap()
+    }
+
+    #[test]
+    fn blocks_test() {
+        assert_eq!(
+            to_markdown(
+                "<h1>Report</h1><p>First   paragraph\nwith <b>bold</b> text</p><p>Second</p>"
+            ),
+            "# Report\n\nFirst paragraph with **bold** text\n\nSecond\n"
+        );
+    }
+
+    #[test]
+    fn lists_and_links_test() {
+        assert_eq!(
+            to_markdown(
+                "<ul><li>one</li><li><a href=\"https://extractous.yobix.ai\">two</a></li></ul>\
+                <ol><li>first</li><li>second</li></ol>"
+            ),
+            "- one\n- [two](https://extractous.yobix.ai)\n\n1. first\n2. second\n"
+        );
+    }
+
+    #[test]
+    fn table_test() {
+        assert_eq!(
+            to_markdown(
+                "<table><tbody><tr><td>Name</td><td>Value</td></tr>\
+                <tr><td>a|b</td><td>1</td></tr><tr><td>c</td></tr></tbody></table>"
+            ),
+            "| Name | Value |\n| --- | --- |\n| a\\|b | 1 |\n| c |  |\n"
+        );
+    }
+}
@@ -0,0 +1,783 @@
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+
+use clap::Args;
+use extractous::{
+    CharSet, ExtractionCache, Extractor, HeaderFooterConfig, OcrOutputType, OfficeParserConfig,
+    PdfOcrImageFormat, PdfOcrImageType, PdfOcrRenderingStrategy, PdfOcrStrategy, PdfParserConfig,
+    Progress, TesseractOcrConfig, TextNormalizer, UnicodeForm, UrlFetchConfig,
+};
+
+use crate::error::{CliError, CliResult};
+
+/// Calls the config setter for every option that was given on the command line, so that
+/// options that are not given keep the config defaults
+macro_rules! apply_options {
+    ($config:ident, $($opt:expr => $setter:ident),* $(,)?) => {
+        $(
+            if let Some(val) = $opt {
+                $config = $config.$setter(val);
+            }
+        )*
+    };
+}
+
+/// Options shared by all the commands that create an [`Extractor`]
+#[derive(Args, Debug, Clone, Default)]
+pub struct ExtractorOptions {
+    /// Maximum number of characters to extract. By default the whole content is extracted
+    #[arg(long, value_name = "CHARS")]
+    pub max_length: Option<i32>,
+
+    /// Maximum number of pdf pages or presentation slides to extract. By default all the pages
+    /// are extracted
+    #[arg(long, value_name = "PAGES")]
+    pub max_pages: Option<i32>,
+
+    /// Encoding of the extracted text stream: UTF_8, US_ASCII or UTF_16BE [default: UTF_8]
+    #[arg(long, value_name = "CHARSET")]
+    pub encoding: Option<CharSet>,
+
+    /// Parse text, markdown, csv, json, xml and html documents with tika instead of the native
+    /// parsers [default: false]
+    #[arg(long, value_name = "BOOL")]
+    pub force_tika: Option<bool>,
+
+    /// Print the progress of the extractions to stderr: the pages, embedded documents and OCR
+    /// [default: false]
+    #[arg(long, value_name = "BOOL")]
+    pub progress: Option<bool>,
+
+    /// Add the timing and resource stats of the extractions to their metadata, under the
+    /// `X-EXTRACTOUS:` keys [default: false]
+    #[arg(long, value_name = "BOOL")]
+    pub stats: Option<bool>,
+
+    /// Add the SHA-256 of the input, the SHA-256 of the normalized text and the SimHash of the
+    /// text to the metadata, under the `X-EXTRACTOUS:` keys [default: false]
+    #[arg(long, value_name = "BOOL")]
+    pub fingerprints: Option<bool>,
+
+    /// Extract only the main content of html pages, without the navigation, banners and
+    /// footers, and add the article title, byline and date to the metadata [default: false]
+    #[arg(long, value_name = "BOOL")]
+    pub html_main_content: Option<bool>,
+
+    /// Remove the running headers, footers and page numbers of the pdf pages from the text,
+    /// and report them in the metadata under the `X-EXTRACTOUS:removed_*` keys [default: false]
+    #[arg(long, value_name = "BOOL")]
+    pub remove_headers_and_footers: Option<bool>,
+
+    /// Minimum fraction of the pages a line must be repeated on to be a header or a footer
+    /// [default: 0.5]
+    #[arg(long, value_name = "RATIO")]
+    pub header_footer_page_ratio: Option<f64>,
+
+    /// Number of lines at the top and at the bottom of the pages that can be headers, footers
+    /// or page numbers [default: 3]
+    #[arg(long, value_name = "LINES")]
+    pub header_footer_lines: Option<usize>,
+
+    /// Remove the page numbers with the headers and footers [default: true]
+    #[arg(long, value_name = "BOOL")]
+    pub header_footer_page_numbers: Option<bool>,
+
+    /// Directory of the cache of the extractions to text, reused by the next runs for the
+    /// unchanged documents extracted with the same options [default: no cache]
+    #[arg(long, value_name = "DIR")]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Maximum size of the cache, the least recently used extractions are removed once it is
+    /// exceeded [default: 1073741824]
+    #[arg(long, value_name = "BYTES", requires = "cache_dir")]
+    pub cache_max_size: Option<u64>,
+
+    #[command(flatten)]
+    pub pdf: PdfOptions,
+
+    #[command(flatten)]
+    pub office: OfficeOptions,
+
+    #[command(flatten)]
+    pub ocr: OcrOptions,
+
+    #[command(flatten)]
+    pub fetch: FetchOptions,
+
+    #[command(flatten)]
+    pub normalize: NormalizeOptions,
+}
+
+impl ExtractorOptions {
+    /// Creates an extractor configured with the given options
+    pub fn extractor(&self) -> CliResult<Extractor> {
+        let mut extractor = Extractor::new()
+            .set_pdf_config(self.pdf.config())
+            .set_office_config(self.office.config())
+            .set_ocr_config(self.ocr.config())
+            .set_url_fetch_config(self.fetch.config()?)
+            .set_text_normalizer(self.normalize.config())
+            // Tika disables the write limit for negative lengths
+            .set_extract_string_max_length(self.max_length.unwrap_or(-1));
+        apply_options!(extractor,
+            self.encoding => set_encoding,
+            self.max_pages => set_max_pages,
+            self.force_tika => set_force_tika,
+            self.stats => set_collect_stats,
+            self.fingerprints => set_compute_fingerprints,
+            self.remove_headers_and_footers => set_remove_headers_and_footers,
+            self.html_main_content => set_html_main_content,
+        );
+        let mut header_footer_config = HeaderFooterConfig::new();
+        apply_options!(header_footer_config,
+            self.header_footer_page_ratio => set_min_page_ratio,
+            self.header_footer_lines => set_max_lines,
+            self.header_footer_page_numbers => set_remove_page_numbers,
+        );
+        extractor = extractor.set_header_footer_config(header_footer_config);
+        if self.progress == Some(true) {
+            extractor = extractor.set_progress_callback(print_progress);
+        }
+        if let Some(cache_dir) = &self.cache_dir {
+            let mut cache = ExtractionCache::new(cache_dir)?;
+            apply_options!(cache, self.cache_max_size => set_max_size);
+            extractor = extractor.set_cache(cache);
+        }
+        Ok(extractor)
+    }
+}
+
+/// Prints the progress of an extraction to stderr, without the output produced
+fn print_progress(progress: &Progress) {
+    let message = match progress {
+        Progress::DocumentStarted { content_type } => format!("parsing {}", content_type),
+        Progress::PageDone {
+            page,
+            total: Some(total),
+        } => format!("page {}/{} done", page, total),
+        Progress::PageDone { page, total: None } => format!("page {} done", page),
+        Progress::EmbeddedDocumentStarted { resource_name } => format!(
+            "parsing embedded document {}",
+            resource_name.as_deref().unwrap_or("")
+        ),
+        Progress::OcrStarted { page } => format!("OCR of page {} started", page),
+        Progress::OcrFinished { page } => format!("OCR of page {} finished", page),
+        Progress::OutputProduced { .. } => return,
+        Progress::DocumentFinished => "done".to_string(),
+    };
+    eprintln!("extractous: {}", message);
+}
+
+/// Page numbers and ranges separated by commas, e.g. `1-3,7`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageRanges(pub Vec<RangeInclusive<u32>>);
+
+fn parse_page_ranges(value: &str) -> Result<PageRanges, String> {
+    let parse_page = |page: &str| {
+        page.trim()
+            .parse::<u32>()
+            .ok()
+            .filter(|&page| page > 0)
+            .ok_or_else(|| format!("invalid page number {:?}, pages start at 1", page.trim()))
+    };
+    value
+        .split(',')
+        .map(|part| match part.split_once('-') {
+            Some((start, end)) => Ok(parse_page(start)?..=parse_page(end)?),
+            None => parse_page(part).map(|page| page..=page),
+        })
+        .collect::<Result<_, _>>()
+        .map(PageRanges)
+}
+
+/// PDF parser options, see [`PdfParserConfig`]
+#[derive(Args, Debug, Clone, Default)]
+#[command(next_help_heading = "PDF options")]
+pub struct PdfOptions {
+    /// Pages to extract, e.g. 1-3,7. Parsing stops after the last page [default: all]
+    #[arg(long, value_name = "PAGES", value_parser = parse_page_ranges)]
+    pub pdf_pages: Option<PageRanges>,
+
+    /// OCR strategy: NO_OCR, OCR_ONLY, OCR_AND_TEXT_EXTRACTION or AUTO [default: AUTO]
+    #[arg(long, value_name = "STRATEGY")]
+    pub pdf_ocr_strategy: Option<PdfOcrStrategy>,
+
+    /// Extract the inline embedded images [default: false]
+    #[arg(long, value_name = "BOOL")]
+    pub pdf_extract_inline_images: Option<bool>,
+
+    /// Extract an inline image only once even if it is referenced by several pages [default: false]
+    #[arg(long, value_name = "BOOL")]
+    pub pdf_extract_unique_inline_images_only: Option<bool>,
+
+    /// Extract the text and the marked structure of marked content [default: false]
+    #[arg(long, value_name = "BOOL")]
+    pub pdf_extract_marked_content: Option<bool>,
+
+    /// Extract the text of the annotations [default: true]
+    #[arg(long, value_name = "BOOL")]
+    pub pdf_extract_annotation_text: Option<bool>,
+
+    /// Sort the text by its position on the page, e.g. for multi-column layouts [default: false]
+    #[arg(long, value_name = "BOOL")]
+    pub pdf_sort_by_position: Option<bool>,
+
+    /// Whitespace, relative to the character height, beyond which a space is inserted [default: 0.5]
+    #[arg(long, value_name = "RATIO")]
+    pub pdf_spacing_tolerance: Option<f32>,
+
+    /// Gap, relative to the average character width, beyond which a space is inserted [default: 0.3]
+    #[arg(long, value_name = "RATIO")]
+    pub pdf_average_char_tolerance: Option<f32>,
+
+    /// Insert spaces between words rendered without a space character [default: true]
+    #[arg(long, value_name = "BOOL")]
+    pub pdf_enable_auto_space: Option<bool>,
+
+    /// Remove the text rendered several times to simulate bold text [default: false]
+    #[arg(long, value_name = "BOOL")]
+    pub pdf_suppress_duplicate_overlapping_text: Option<bool>,
+
+    /// Extract the content of the interactive form fields [default: true]
+    #[arg(long, value_name = "BOOL")]
+    pub pdf_extract_acro_form_content: Option<bool>,
+
+    /// Extract the text of the bookmarks [default: true]
+    #[arg(long, value_name = "BOOL")]
+    pub pdf_extract_bookmarks_text: Option<bool>,
+
+    /// Extract the document and form actions, e.g. javascript [default: false]
+    #[arg(long, value_name = "BOOL")]
+    pub pdf_extract_actions: Option<bool>,
+
+    /// DPI of the pages rendered for OCR [default: 300]
+    #[arg(long, value_name = "DPI")]
+    pub pdf_ocr_dpi: Option<i32>,
+
+    /// Color type of the pages rendered for OCR: BINARY, GRAY, RGB or ARGB [default: GRAY]
+    #[arg(long, value_name = "TYPE")]
+    pub pdf_ocr_image_type: Option<PdfOcrImageType>,
+
+    /// Image format of the pages rendered for OCR: PNG, TIFF or JPEG [default: PNG]
+    #[arg(long, value_name = "FORMAT")]
+    pub pdf_ocr_image_format: Option<PdfOcrImageFormat>,
+
+    /// Elements rendered for OCR: NO_TEXT, TEXT_ONLY, VECTOR_GRAPHICS_ONLY or ALL [default: ALL]
+    #[arg(long, value_name = "STRATEGY")]
+    pub pdf_ocr_rendering_strategy: Option<PdfOcrRenderingStrategy>,
+
+    /// Main memory the parser may use before falling back to temp files, -1 for no limit
+    /// [default: 536870912]
+    #[arg(long, value_name = "BYTES")]
+    pub pdf_max_main_memory_bytes: Option<i64>,
+
+    /// Keep parsing the remaining pages when a page fails [default: true]
+    #[arg(long, value_name = "BOOL")]
+    pub pdf_catch_intermediate_io_exceptions: Option<bool>,
+}
+
+impl PdfOptions {
+    pub fn config(&self) -> PdfParserConfig {
+        let mut config = PdfParserConfig::new();
+        apply_options!(config,
+            self.pdf_ocr_strategy => set_ocr_strategy,
+            self.pdf_extract_inline_images => set_extract_inline_images,
+            self.pdf_extract_unique_inline_images_only => set_extract_unique_inline_images_only,
+            self.pdf_extract_marked_content => set_extract_marked_content,
+            self.pdf_extract_annotation_text => set_extract_annotation_text,
+            self.pdf_sort_by_position => set_sort_by_position,
+            self.pdf_spacing_tolerance => set_spacing_tolerance,
+            self.pdf_average_char_tolerance => set_average_char_tolerance,
+            self.pdf_enable_auto_space => set_enable_auto_space,
+            self.pdf_suppress_duplicate_overlapping_text => set_suppress_duplicate_overlapping_text,
+            self.pdf_extract_acro_form_content => set_extract_acro_form_content,
+            self.pdf_extract_bookmarks_text => set_extract_bookmarks_text,
+            self.pdf_extract_actions => set_extract_actions,
+            self.pdf_ocr_dpi => set_ocr_dpi,
+            self.pdf_ocr_image_type => set_ocr_image_type,
+            self.pdf_ocr_image_format => set_ocr_image_format,
+            self.pdf_ocr_rendering_strategy => set_ocr_rendering_strategy,
+            self.pdf_max_main_memory_bytes => set_max_main_memory_bytes,
+            self.pdf_catch_intermediate_io_exceptions => set_catch_intermediate_io_exceptions,
+        );
+        if let Some(PageRanges(ranges)) = &self.pdf_pages {
+            config = match ranges.as_slice() {
+                [range] => config.set_page_range(range.clone()),
+                ranges => config.set_pages(&ranges.iter().cloned().flatten().collect::<Vec<_>>()),
+            };
+        }
+        config
+    }
+}
+
+/// Microsoft Office parser options, see [`OfficeParserConfig`]
+#[derive(Args, Debug, Clone, Default)]
+#[command(next_help_heading = "Office options")]
+pub struct OfficeOptions {
+    /// Extract the macros [default: false]
+    #[arg(long, value_name = "BOOL")]
+    pub office_extract_macros: Option<bool>,
+
+    /// Include the deleted content of docx files [default: false]
+    #[arg(long, value_name = "BOOL")]
+    pub office_include_deleted_content: Option<bool>,
+
+    /// Include the original location (moveFrom) of moved docx sections [default: false]
+    #[arg(long, value_name = "BOOL")]
+    pub office_include_move_from_content: Option<bool>,
+
+    /// Include the text stored within drawing shapes [default: true]
+    #[arg(long, value_name = "BOOL")]
+    pub office_include_shape_based_content: Option<bool>,
+
+    /// Include the headers and footers of Word and Excel files [default: false]
+    #[arg(long, value_name = "BOOL")]
+    pub office_include_headers_and_footers: Option<bool>,
+
+    /// Output the missing rows of sparse tables [default: false]
+    #[arg(long, value_name = "BOOL")]
+    pub office_include_missing_rows: Option<bool>,
+
+    /// Include the slide notes of PowerPoint files [default: true]
+    #[arg(long, value_name = "BOOL")]
+    pub office_include_slide_notes: Option<bool>,
+
+    /// Include the content of the slide, notes and handout masters [default: true]
+    #[arg(long, value_name = "BOOL")]
+    pub office_include_slide_master_content: Option<bool>,
+
+    /// Concatenate the phonetic runs of Excel files to the original text [default: true]
+    #[arg(long, value_name = "BOOL")]
+    pub office_concatenate_phonetic_runs: Option<bool>,
+
+    /// Extract all the body alternatives (html, rtf, text) of msg files [default: false]
+    #[arg(long, value_name = "BOOL")]
+    pub office_extract_all_alternatives_from_msg: Option<bool>,
+
+    /// Include the comments of docx, pptx and xlsx files in the text [default: true]
+    #[arg(long, value_name = "BOOL")]
+    pub office_include_comments: Option<bool>,
+}
+
+impl OfficeOptions {
+    pub fn config(&self) -> OfficeParserConfig {
+        let mut config = OfficeParserConfig::new();
+        apply_options!(config,
+            self.office_extract_macros => set_extract_macros,
+            self.office_include_deleted_content => set_include_deleted_content,
+            self.office_include_move_from_content => set_include_move_from_content,
+            self.office_include_shape_based_content => set_include_shape_based_content,
+            self.office_include_headers_and_footers => set_include_headers_and_footers,
+            self.office_include_missing_rows => set_include_missing_rows,
+            self.office_include_slide_notes => set_include_slide_notes,
+            self.office_include_slide_master_content => set_include_slide_master_content,
+            self.office_concatenate_phonetic_runs => set_concatenate_phonetic_runs,
+            self.office_extract_all_alternatives_from_msg => set_extract_all_alternatives_from_msg,
+            self.office_include_comments => set_include_comments,
+        );
+        config
+    }
+}
+
+/// Tesseract OCR options, see [`TesseractOcrConfig`]
+#[derive(Args, Debug, Clone, Default)]
+#[command(next_help_heading = "OCR options")]
+pub struct OcrOptions {
+    /// Tesseract languages separated by plus characters, e.g. "eng+deu" [default: eng]
+    #[arg(long, value_name = "LANGS")]
+    pub ocr_language: Option<String>,
+
+    /// DPI of the images to be processed [default: 300]
+    #[arg(long, value_name = "DPI")]
+    pub ocr_density: Option<i32>,
+
+    /// Color depth of the images to be processed [default: 4]
+    #[arg(long, value_name = "BITS")]
+    pub ocr_depth: Option<i32>,
+
+    /// Maximum time in seconds spent on the OCR of a document [default: 130]
+    #[arg(long, value_name = "SECONDS")]
+    pub ocr_timeout_seconds: Option<i32>,
+
+    /// Preprocess the images before the OCR [default: false]
+    #[arg(long, value_name = "BOOL")]
+    pub ocr_enable_image_preprocessing: Option<bool>,
+
+    /// Rotate the images before the OCR [default: false]
+    #[arg(long, value_name = "BOOL")]
+    pub ocr_apply_rotation: Option<bool>,
+
+    /// Tesseract page segmentation mode, from 0 to 13 [default: 1]
+    #[arg(long, value_name = "MODE")]
+    pub ocr_page_seg_mode: Option<i32>,
+
+    /// Tesseract OCR engine mode: 0 legacy, 1 LSTM, 2 legacy and LSTM, 3 default
+    #[arg(long, value_name = "MODE")]
+    pub ocr_engine_mode: Option<i32>,
+
+    /// Preserve the spacing between words [default: false]
+    #[arg(long, value_name = "BOOL")]
+    pub ocr_preserve_interword_spacing: Option<bool>,
+
+    /// Minimum size in bytes of an image to be OCRed [default: 0]
+    #[arg(long, value_name = "BYTES")]
+    pub ocr_min_file_size: Option<i64>,
+
+    /// Maximum size in bytes of an image to be OCRed [default: 2147483647]
+    #[arg(long, value_name = "BYTES")]
+    pub ocr_max_file_size: Option<i64>,
+
+    /// Directory containing the tesseract executable [default: looked up on the PATH]
+    #[arg(long, value_name = "DIR")]
+    pub ocr_tesseract_path: Option<String>,
+
+    /// Directory containing the tessdata language files
+    #[arg(long, value_name = "DIR")]
+    pub ocr_tessdata_path: Option<String>,
+
+    /// Directory containing the ImageMagick executables [default: looked up on the PATH]
+    #[arg(long, value_name = "DIR")]
+    pub ocr_image_magick_path: Option<String>,
+
+    /// ImageMagick colorspace of the preprocessed images [default: gray]
+    #[arg(long, value_name = "COLORSPACE")]
+    pub ocr_colorspace: Option<String>,
+
+    /// ImageMagick filter used to resize the preprocessed images [default: triangle]
+    #[arg(long, value_name = "FILTER")]
+    pub ocr_filter: Option<String>,
+
+    /// Percentage the preprocessed images are resized to, 100 to 900 [default: 200]
+    #[arg(long, value_name = "PERCENT")]
+    pub ocr_resize: Option<i32>,
+
+    /// Tesseract output: TXT, or HOCR to keep the hOCR word boxes in the xml output
+    /// [default: TXT]
+    #[arg(long, value_name = "TYPE")]
+    pub ocr_output_type: Option<OcrOutputType>,
+
+    /// Tesseract config variable passed with -c, can be repeated
+    #[arg(long = "ocr-config", value_name = "KEY=VALUE", value_parser = parse_key_value)]
+    pub ocr_other_config: Vec<(String, String)>,
+}
+
+fn parse_key_value(value: &str) -> Result<(String, String), String> {
+    value
+        .split_once('=')
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .ok_or_else(|| format!("invalid {:?}, expected KEY=VALUE", value))
+}
+
+impl OcrOptions {
+    pub fn config(&self) -> TesseractOcrConfig {
+        let mut config = TesseractOcrConfig::new();
+        apply_options!(config,
+            self.ocr_language.as_deref() => set_language,
+            self.ocr_density => set_density,
+            self.ocr_depth => set_depth,
+            self.ocr_timeout_seconds => set_timeout_seconds,
+            self.ocr_enable_image_preprocessing => set_enable_image_preprocessing,
+            self.ocr_apply_rotation => set_apply_rotation,
+            self.ocr_page_seg_mode => set_page_seg_mode,
+            self.ocr_engine_mode => set_ocr_engine_mode,
+            self.ocr_preserve_interword_spacing => set_preserve_interword_spacing,
+            self.ocr_min_file_size => set_min_file_size_to_ocr,
+            self.ocr_max_file_size => set_max_file_size_to_ocr,
+            self.ocr_tesseract_path.as_deref() => set_tesseract_path,
+            self.ocr_tessdata_path.as_deref() => set_tessdata_path,
+            self.ocr_image_magick_path.as_deref() => set_image_magick_path,
+            self.ocr_colorspace.as_deref() => set_colorspace,
+            self.ocr_filter.as_deref() => set_filter,
+            self.ocr_resize => set_resize,
+            self.ocr_output_type => set_output_type,
+        );
+        for (key, value) in self.ocr_other_config.iter() {
+            config = config.add_other_tesseract_config(key, value);
+        }
+        config
+    }
+}
+
+/// Options used to download http(s) urls, see [`UrlFetchConfig`]
+#[derive(Args, Debug, Clone, Default)]
+#[command(next_help_heading = "Url options")]
+pub struct FetchOptions {
+    /// Extra request header, can be repeated
+    #[arg(long = "header", value_name = "NAME:VALUE")]
+    pub headers: Vec<String>,
+
+    /// User-Agent request header [default: extractous/{version}]
+    #[arg(long, value_name = "AGENT")]
+    pub user_agent: Option<String>,
+
+    /// Send an `Authorization: Bearer` header
+    #[arg(
+        long,
+        value_name = "TOKEN",
+        env = "EXTRACTOUS_BEARER_TOKEN",
+        hide_env_values = true
+    )]
+    pub bearer_token: Option<String>,
+
+    /// Send an `Authorization: Basic` header
+    #[arg(long, value_name = "USER[:PASSWORD]")]
+    pub basic_auth: Option<String>,
+
+    /// Total time in seconds allowed for a download [default: 120]
+    #[arg(long, value_name = "SECONDS")]
+    pub fetch_timeout_seconds: Option<u64>,
+
+    /// Time in seconds allowed to establish a connection [default: 30]
+    #[arg(long, value_name = "SECONDS")]
+    pub connect_timeout_seconds: Option<u64>,
+
+    /// Proxy url used for all requests
+    #[arg(long, value_name = "URL")]
+    pub proxy: Option<String>,
+
+    /// Maximum number of redirects to follow, 0 disables redirects [default: 10]
+    #[arg(long, value_name = "COUNT")]
+    pub max_redirects: Option<usize>,
+
+    /// Do not verify the TLS certificates
+    #[arg(long)]
+    pub insecure: bool,
+
+    /// PEM encoded root certificate to trust, can be repeated
+    #[arg(long = "cacert", value_name = "FILE")]
+    pub root_certificates: Vec<String>,
+
+    /// Maximum size in bytes of a downloaded document
+    #[arg(long, value_name = "BYTES")]
+    pub max_body_bytes: Option<u64>,
+}
+
+impl FetchOptions {
+    pub fn config(&self) -> CliResult<UrlFetchConfig> {
+        let mut config = UrlFetchConfig::new().set_accept_invalid_certs(self.insecure);
+        for header in self.headers.iter() {
+            let (name, value) = header.split_once(':').ok_or_else(|| {
+                CliError::Usage(format!("Invalid header {:?}, expected NAME:VALUE", header))
+            })?;
+            config = config.set_header(name.trim(), value.trim());
+        }
+        for pem_file in self.root_certificates.iter() {
+            config = config.add_root_certificate(pem_file);
+        }
+        if let Some(basic_auth) = &self.basic_auth {
+            config = match basic_auth.split_once(':') {
+                Some((username, password)) => config.set_basic_auth(username, Some(password)),
+                None => config.set_basic_auth(basic_auth, None),
+            };
+        }
+        apply_options!(config,
+            self.user_agent.as_deref() => set_user_agent,
+            self.bearer_token.as_deref() => set_bearer_auth,
+            self.fetch_timeout_seconds => set_timeout_seconds,
+            self.connect_timeout_seconds => set_connect_timeout_seconds,
+            self.proxy.as_deref() => set_proxy,
+            self.max_redirects => set_max_redirects,
+            self.max_body_bytes => set_max_body_bytes,
+        );
+        Ok(config)
+    }
+}
+
+/// Text normalization options, see [`TextNormalizer`]
+#[derive(Args, Debug, Clone, Default)]
+#[command(next_help_heading = "Normalization options")]
+pub struct NormalizeOptions {
+    /// Remove the control characters, except the tabs and line breaks [default: false]
+    #[arg(long, value_name = "BOOL")]
+    pub normalize_control_chars: Option<bool>,
+
+    /// Expand the typographic ligatures such as "ﬁ" to their letters [default: false]
+    #[arg(long, value_name = "BOOL")]
+    pub normalize_ligatures: Option<bool>,
+
+    /// Unicode normalization form of the text: NFC or NFKC [default: none]
+    #[arg(long, value_name = "FORM")]
+    pub normalize_unicode: Option<UnicodeForm>,
+
+    /// Join the words hyphenated at the end of a line and remove the soft hyphens
+    /// [default: false]
+    #[arg(long, value_name = "BOOL")]
+    pub normalize_dehyphenate: Option<bool>,
+
+    /// Collapse the runs of spaces, trim the lines and keep at most one blank line between two
+    /// lines [default: false]
+    #[arg(long, value_name = "BOOL")]
+    pub normalize_whitespace: Option<bool>,
+}
+
+impl NormalizeOptions {
+    pub fn config(&self) -> TextNormalizer {
+        let mut config = TextNormalizer::new();
+        apply_options!(config,
+            self.normalize_control_chars => set_strip_control_chars,
+            self.normalize_ligatures => set_expand_ligatures,
+            self.normalize_unicode.map(Some) => set_unicode_form,
+            self.normalize_dehyphenate => set_dehyphenate,
+            self.normalize_whitespace => set_collapse_whitespace,
+        );
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{Cli, Command};
+    use clap::Parser;
+
+    fn parse_options(args: &[&str]) -> ExtractorOptions {
+        let cli = Cli::try_parse_from([&["extractous", "extract"], args, &["file.pdf"]].concat())
+            .unwrap();
+        match cli.command {
+            Command::Extract(args) => args.options,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn default_options_test() {
+        let options = parse_options(&[]);
+        assert_eq!(options.pdf.config(), PdfParserConfig::new());
+        assert_eq!(options.office.config(), OfficeParserConfig::new());
+        assert_eq!(options.ocr.config(), TesseractOcrConfig::new());
+        assert_eq!(options.fetch.config().unwrap(), UrlFetchConfig::new());
+        assert_eq!(options.normalize.config(), TextNormalizer::new());
+    }
+
+    #[test]
+    fn config_options_test() {
+        let options = parse_options(&[
+            "--pdf-ocr-strategy",
+            "OCR_ONLY",
+            "--pdf-extract-annotation-text",
+            "false",
+            "--pdf-sort-by-position",
+            "true",
+            "--pdf-ocr-rendering-strategy",
+            "NO_TEXT",
+            "--pdf-pages",
+            "2-4",
+            "--max-pages",
+            "3",
+            "--force-tika",
+            "true",
+            "--progress",
+            "true",
+            "--stats",
+            "true",
+            "--fingerprints",
+            "true",
+            "--remove-headers-and-footers",
+            "true",
+            "--header-footer-page-ratio",
+            "0.8",
+            "--header-footer-lines",
+            "2",
+            "--html-main-content",
+            "true",
+            "--cache-dir",
+            "extractous-cache",
+            "--cache-max-size",
+            "1000",
+            "--office-include-slide-notes",
+            "false",
+            "--office-include-comments",
+            "false",
+            "--ocr-language",
+            "deu",
+            "--ocr-density",
+            "200",
+            "--ocr-page-seg-mode",
+            "6",
+            "--ocr-config",
+            "tessedit_do_invert=0",
+            "--header",
+            "Accept-Language: en",
+            "--basic-auth",
+            "user:secret",
+            "--normalize-ligatures",
+            "true",
+            "--normalize-unicode",
+            "NFKC",
+            "--normalize-dehyphenate",
+            "true",
+        ]);
+        assert_eq!(
+            options.pdf.config(),
+            PdfParserConfig::new()
+                .set_ocr_strategy(PdfOcrStrategy::OCR_ONLY)
+                .set_extract_annotation_text(false)
+                .set_sort_by_position(true)
+                .set_ocr_rendering_strategy(PdfOcrRenderingStrategy::NO_TEXT)
+                .set_page_range(2..=4)
+        );
+        assert_eq!(options.max_pages, Some(3));
+        assert_eq!(options.force_tika, Some(true));
+        assert_eq!(options.progress, Some(true));
+        assert_eq!(options.stats, Some(true));
+        assert_eq!(options.fingerprints, Some(true));
+        assert_eq!(options.remove_headers_and_footers, Some(true));
+        assert_eq!(options.header_footer_page_ratio, Some(0.8));
+        assert_eq!(options.header_footer_lines, Some(2));
+        assert_eq!(options.html_main_content, Some(true));
+        assert_eq!(options.cache_dir, Some(PathBuf::from("extractous-cache")));
+        assert_eq!(options.cache_max_size, Some(1000));
+        assert_eq!(
+            options.office.config(),
+            OfficeParserConfig::new()
+                .set_include_slide_notes(false)
+                .set_include_comments(false)
+        );
+        assert_eq!(
+            options.ocr.config(),
+            TesseractOcrConfig::new()
+                .set_language("deu")
+                .set_density(200)
+                .set_page_seg_mode(6)
+                .add_other_tesseract_config("tessedit_do_invert", "0")
+        );
+        assert_eq!(
+            options.fetch.config().unwrap(),
+            UrlFetchConfig::new()
+                .set_header("Accept-Language", "en")
+                .set_basic_auth("user", Some("secret"))
+        );
+        assert_eq!(
+            options.normalize.config(),
+            TextNormalizer::new()
+                .set_expand_ligatures(true)
+                .set_unicode_form(Some(UnicodeForm::NFKC))
+                .set_dehyphenate(true)
+        );
+    }
+
+    #[test]
+    fn page_ranges_test() {
+        assert_eq!(
+            parse_page_ranges("1-3,7"),
+            Ok(PageRanges(vec![1..=3, 7..=7]))
+        );
+        assert_eq!(
+            parse_options(&["--pdf-pages", "1, 3,5"]).pdf.config(),
+            PdfParserConfig::new().set_pages(&[1, 3, 5])
+        );
+        assert_eq!(
+            parse_options(&["--pdf-pages", "2-3,6-7"]).pdf.config(),
+            PdfParserConfig::new().set_pages(&[2, 3, 6, 7])
+        );
+        for invalid in ["0", "1-", "a-b", ""] {
+            assert!(parse_page_ranges(invalid).is_err(), "{:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn invalid_header_test() {
+        let options = parse_options(&["--header", "no-separator"]);
+        assert!(matches!(options.fetch.config(), Err(CliError::Usage(_))));
+    }
+}
@@ -0,0 +1,122 @@
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+use extractous::{WatchEvent, Watcher};
+use serde_json::{json, Value};
+
+use crate::batch::build_glob_set;
+use crate::cli::{ContentFormat, WatchArgs};
+use crate::error::{CliError, CliResult};
+use crate::markdown::xhtml_to_markdown;
+
+/// The `watch` command. Every change is written as a json line, either
+/// `{"event": "created" | "modified", "path", "content", "metadata"}`, `{"event": "deleted", "path"}`
+/// or `{"event": "failed", "path", "error", "error_kind"}`. Lines are flushed as the changes
+/// are found, and the command runs until a scan or a write fails, unless `--once` is given
+pub fn run(args: &WatchArgs) -> CliResult<()> {
+    let as_xml = matches!(args.format, ContentFormat::Xml | ContentFormat::Markdown);
+    let extractor = args.options.extractor()?.set_xml_output(as_xml);
+    let include = build_glob_set(&args.include)?;
+    let exclude = build_glob_set(&args.exclude)?;
+    let mut watcher = Watcher::new(&args.dir, extractor).set_filter(move |relative| {
+        (include.is_empty() || include.is_match(relative)) && !exclude.is_match(relative)
+    });
+    if let Some(state_file) = &args.state_file {
+        watcher = watcher.set_state_file(state_file);
+    }
+
+    // Restarted watches append to the output, the state file only reports the new changes
+    let mut out: Box<dyn Write> = match &args.output {
+        Some(path) => Box::new(io::BufWriter::new(
+            std::fs::File::options()
+                .create(true)
+                .append(true)
+                .open(path)?,
+        )),
+        None => Box::new(io::BufWriter::new(io::stdout().lock())),
+    };
+
+    loop {
+        let mut write_error = None;
+        watcher.scan(|event| {
+            if write_error.is_none() {
+                let record = event_record(event, args.format);
+                if let Err(e) = writeln!(out, "{}", record).and_then(|()| out.flush()) {
+                    write_error = Some(e);
+                }
+            }
+        })?;
+        if let Some(e) = write_error {
+            return Err(e.into());
+        }
+        if args.once {
+            return Ok(());
+        }
+        thread::sleep(Duration::from_secs(args.interval));
+    }
+}
+
+fn event_record(event: WatchEvent, format: ContentFormat) -> Value {
+    let path = event.path().to_string_lossy().into_owned();
+    let (kind, content, metadata) = match event {
+        WatchEvent::Created {
+            content, metadata, ..
+        } => ("created", content, metadata),
+        WatchEvent::Modified {
+            content, metadata, ..
+        } => ("modified", content, metadata),
+        WatchEvent::Deleted { .. } => return json!({"event": "deleted", "path": path}),
+        WatchEvent::Failed { error, .. } => return failed_record(path, error.into()),
+    };
+
+    let content = match format {
+        ContentFormat::Markdown => match xhtml_to_markdown(&content) {
+            Ok(content) => content,
+            Err(e) => return failed_record(path, e.into()),
+        },
+        _ => content,
+    };
+    json!({
+        "event": kind,
+        "path": path,
+        "content": content,
+        "metadata": metadata,
+    })
+}
+
+fn failed_record(path: String, error: CliError) -> Value {
+    json!({
+        "event": "failed",
+        "path": path,
+        "error": error.to_string(),
+        "error_kind": error.kind(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn event_record_test() {
+        let record = event_record(
+            WatchEvent::Deleted {
+                path: PathBuf::from("docs/a.pdf"),
+            },
+            ContentFormat::Text,
+        );
+        assert_eq!(record, json!({"event": "deleted", "path": "docs/a.pdf"}));
+
+        let record = event_record(
+            WatchEvent::Failed {
+                path: PathBuf::from("docs/b.pdf"),
+                error: extractous::Error::ParseError("corrupt".to_string()),
+            },
+            ContentFormat::Text,
+        );
+        assert_eq!(record["event"], "failed");
+        assert_eq!(record["error_kind"], "parse");
+    }
+}
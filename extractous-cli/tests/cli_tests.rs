@@ -0,0 +1,160 @@
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn extractous(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_extractous"))
+        .args(args)
+        .output()
+        .expect("Failed to run extractous")
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn test_extract_text() {
+    let output = extractous(&["extract", "../test_files/documents/category-level.docx"]);
+    assert!(output.status.success());
+
+    let expected =
+        std::fs::read_to_string("../test_files/expected_result/category-level.docx.txt").unwrap();
+    assert_eq!(stdout(&output).trim(), expected.trim());
+}
+
+#[test]
+fn test_extract_json_with_max_length() {
+    let output = extractous(&[
+        "extract",
+        "--format",
+        "json",
+        "--max-length",
+        "20",
+        "../test_files/documents/2022_Q3_AAPL.pdf",
+    ]);
+    assert!(output.status.success());
+
+    let value: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert!(value["content"].as_str().unwrap().len() <= 20);
+    assert_eq!(value["metadata"]["Content-Type"][0], "application/pdf");
+}
+
+#[test]
+fn test_extract_markdown_from_stdin() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_extractous"))
+        .args(["extract", "--format", "markdown", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"<html><body><h1>Title</h1><p>Some <b>bold</b> text</p></body></html>")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("# Title"));
+    assert!(stdout(&output).contains("Some **bold** text"));
+}
+
+#[test]
+fn test_detect() {
+    let output = extractous(&[
+        "detect",
+        "--json",
+        "../test_files/documents/2022_Q3_AAPL.pdf",
+        "../test_files/documents/vodafone.xlsx",
+    ]);
+    assert!(output.status.success());
+
+    let lines: Vec<serde_json::Value> = stdout(&output)
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(lines[0]["mime_type"], "application/pdf");
+    assert_eq!(
+        lines[1]["mime_type"],
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+    );
+}
+
+#[test]
+fn test_metadata() {
+    let output = extractous(&["metadata", "../test_files/documents/simple.odt"]);
+    assert!(output.status.success());
+
+    let metadata: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(
+        metadata["Content-Type"][0],
+        "application/vnd.oasis.opendocument.text"
+    );
+}
+
+#[test]
+fn test_batch() {
+    let output = extractous(&[
+        "batch",
+        "../test_files/documents",
+        "--include",
+        "*.docx",
+        "--jobs",
+        "2",
+    ]);
+    assert!(output.status.success());
+
+    let mut paths: Vec<String> = stdout(&output)
+        .lines()
+        .map(|l| serde_json::from_str::<serde_json::Value>(l).unwrap())
+        .map(|v| v["path"].as_str().unwrap().to_string())
+        .collect();
+    paths.sort();
+    assert_eq!(
+        paths,
+        vec![
+            "../test_files/documents/bug_16.docx",
+            "../test_files/documents/category-level.docx"
+        ]
+    );
+}
+
+#[test]
+fn test_watch_once() {
+    let state_file =
+        std::env::temp_dir().join(format!("extractous-watch-{}.json", std::process::id()));
+    let state_file = state_file.to_str().unwrap();
+    let args = [
+        "watch",
+        "../test_files/documents",
+        "--include",
+        "*.docx",
+        "--state-file",
+        state_file,
+        "--once",
+    ];
+    let output = extractous(&args);
+    assert!(output.status.success());
+    let events: Vec<serde_json::Value> = stdout(&output)
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(events.len(), 2);
+    assert!(events.iter().all(|e| e["event"] == "created"));
+
+    // The files did not change since the state file was written
+    let output = extractous(&args);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "");
+    std::fs::remove_file(state_file).unwrap();
+}
+
+#[test]
+fn test_exit_codes() {
+    let output = extractous(&["extract", "../test_files/documents/does-not-exist.pdf"]);
+    assert_eq!(output.status.code(), Some(3));
+
+    let output = extractous(&["extract", "--pdf-ocr-strategy", "SOMETIMES", "file.pdf"]);
+    assert_eq!(output.status.code(), Some(2));
+}
@@ -0,0 +1,669 @@
+//! Comments and annotations of the extracted documents, as separate records.
+//!
+//! The `extract_*_annotations` functions of the [`crate::Extractor`] return the reviewer
+//! comments of a document as a list of [`Annotation`], read without extracting its text:
+//! * the annotations of the pdf pages, such as the notes, highlights and free texts, read with
+//!   pdfbox. The links, form fields and popups are not annotations
+//! * the comments of docx documents, with the text of the document they are anchored on
+//! * the comments of pptx slides, the legacy comments and the modern threaded comments
+//! * the cell comments of xlsx sheets
+//!
+//! The other documents have no annotations. To keep the comments out of the extracted text, see
+//! [`crate::OfficeParserConfig::set_include_comments`] and
+//! [`crate::PdfParserConfig::set_extract_annotation_text`].
+
+use crate::errors::{Error, ExtractResult};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::collections::HashMap;
+use std::io::{Read, Seek};
+use zip::ZipArchive;
+
+/// A comment or annotation of a document. Returned by
+/// [`crate::Extractor::extract_file_annotations`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Annotation {
+    /// Kind of the annotation: `comment` for the comments of office documents, and the snake
+    /// case subtype of pdf annotations, e.g. `note` for the sticky notes, `highlight`,
+    /// `free_text`, `underline`, `strike_out` or `ink`
+    pub kind: String,
+    /// Name of the author of the annotation
+    pub author: Option<String>,
+    /// Date of the annotation, in ISO 8601 when the document has a valid date. The legacy
+    /// comments of xlsx sheets have no date
+    pub date: Option<String>,
+    /// Text of the annotation
+    pub text: String,
+    /// What the annotation is anchored on: the commented text of docx documents, the text under
+    /// the text markup annotations of pdfs such as highlights, and the cell reference of xlsx
+    /// comments, e.g. `Sheet1!B2`
+    pub anchor_text: Option<String>,
+    /// Page of pdf annotations, slide of pptx comments and sheet of xlsx comments, starting at 1
+    pub page: Option<u32>,
+}
+
+/// The source of the annotations of a document, sniffed from its first bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AnnotationSource {
+    Pdf,
+    Zip,
+    None,
+}
+
+impl AnnotationSource {
+    pub(crate) fn sniff(head: &[u8]) -> Self {
+        if head[..head.len().min(1024)]
+            .windows(5)
+            .any(|window| window == b"%PDF-")
+        {
+            AnnotationSource::Pdf
+        } else if head.starts_with(b"PK\x03\x04") {
+            AnnotationSource::Zip
+        } else {
+            AnnotationSource::None
+        }
+    }
+}
+
+/// Builds the annotations of the lines written by the java `PdfAnnotations`: the subtype, page,
+/// author, date, anchor text and contents of every annotation separated by tabs, with their
+/// backslashes, tabs and line breaks escaped
+pub(crate) fn parse_pdf_annotations(lines: &str) -> Vec<Annotation> {
+    lines
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<String> = line.split('\t').map(unescape).collect();
+            let [subtype, page, author, date, anchor_text, text] =
+                <[String; 6]>::try_from(fields).ok()?;
+            Some(Annotation {
+                kind: pdf_kind(&subtype),
+                author: non_empty(author),
+                date: non_empty(date),
+                text,
+                anchor_text: non_empty(anchor_text),
+                page: page.parse().ok().filter(|page| *page > 0),
+            })
+        })
+        .collect()
+}
+
+fn unescape(field: &str) -> String {
+    let mut unescaped = String::with_capacity(field.len());
+    let mut chars = field.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => unescaped.push('\t'),
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+/// The kind of a pdf annotation subtype, e.g. `free_text` for `FreeText`. The `Text`
+/// annotations are the sticky notes
+fn pdf_kind(subtype: &str) -> String {
+    if subtype == "Text" {
+        return "note".to_string();
+    }
+    let mut kind = String::new();
+    for (i, c) in subtype.chars().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            kind.push('_');
+        }
+        kind.push(c.to_ascii_lowercase());
+    }
+    kind
+}
+
+pub(crate) fn non_empty(value: String) -> Option<String> {
+    Some(value).filter(|value| !value.is_empty())
+}
+
+/// Reads the comments of a docx, pptx or xlsx archive. Returns no annotations for the other
+/// archives
+pub(crate) fn read_ooxml_annotations<R: Read + Seek>(reader: R) -> ExtractResult<Vec<Annotation>> {
+    let mut archive = ZipArchive::new(reader).map_err(ooxml_error)?;
+    let has = |archive: &ZipArchive<R>, name: &str| archive.index_for_name(name).is_some();
+    if has(&archive, "word/document.xml") {
+        read_docx_comments(&mut archive)
+    } else if has(&archive, "ppt/presentation.xml") {
+        read_pptx_comments(&mut archive)
+    } else if has(&archive, "xl/workbook.xml") {
+        read_xlsx_comments(&mut archive)
+    } else {
+        Ok(Vec::new())
+    }
+}
+
+pub(crate) fn ooxml_error<E: std::fmt::Display>(e: E) -> Error {
+    Error::ParseError(format!("Invalid office document: {}", e))
+}
+
+pub(crate) fn read_archive_file<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    name: &str,
+) -> ExtractResult<Option<String>> {
+    let mut file = match archive.by_name(name) {
+        Ok(file) => file,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(ooxml_error(e)),
+    };
+    let mut content = String::new();
+    file.read_to_string(&mut content)
+        .map_err(|e| Error::ParseError(format!("Invalid office document {}: {}", name, e)))?;
+    Ok(Some(content))
+}
+
+/// The unescaped attributes of an element, by local name
+pub(crate) fn attributes(element: &BytesStart) -> ExtractResult<HashMap<String, String>> {
+    let mut attributes = HashMap::new();
+    for attr in element.attributes().flatten() {
+        let name = String::from_utf8_lossy(attr.key.local_name().as_ref()).into_owned();
+        let value = attr.unescape_value().map_err(ooxml_error)?.into_owned();
+        attributes.insert(name, value);
+    }
+    Ok(attributes)
+}
+
+/// The `r:id` attribute of an element, the id of its relationship
+fn relationship_id(element: &BytesStart) -> ExtractResult<Option<String>> {
+    for attr in element.attributes().flatten() {
+        if attr.key.prefix().is_some() && attr.key.local_name().as_ref() == b"id" {
+            return Ok(Some(
+                attr.unescape_value().map_err(ooxml_error)?.into_owned(),
+            ));
+        }
+    }
+    Ok(None)
+}
+
+/// The targets of the relationships of a part by id, resolved to archive paths. Only the
+/// relationships whose type ends with the suffix are kept
+fn relationships<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    part: &str,
+    type_suffix: &str,
+) -> ExtractResult<HashMap<String, String>> {
+    let (directory, file) = part.rsplit_once('/').unwrap_or(("", part));
+    let rels_path = format!("{}/_rels/{}.rels", directory, file);
+    let Some(rels) = read_archive_file(archive, &rels_path)? else {
+        return Ok(HashMap::new());
+    };
+    let mut targets = HashMap::new();
+    let mut reader = Reader::from_str(&rels);
+    loop {
+        match reader.read_event().map_err(ooxml_error)? {
+            Event::Start(e) | Event::Empty(e) if e.local_name().as_ref() == b"Relationship" => {
+                let attributes = attributes(&e)?;
+                let get = |name: &str| attributes.get(name).map(String::as_str);
+                if get("TargetMode") == Some("External")
+                    || !get("Type").is_some_and(|t| t.ends_with(type_suffix))
+                {
+                    continue;
+                }
+                if let (Some(id), Some(target)) = (get("Id"), get("Target")) {
+                    targets.insert(id.to_string(), resolve_target(directory, target));
+                }
+            }
+            Event::Eof => return Ok(targets),
+            _ => {}
+        }
+    }
+}
+
+/// The archive path of a relationship target, relative to the directory of its part or absolute
+fn resolve_target(directory: &str, target: &str) -> String {
+    let mut segments: Vec<&str> = match target.strip_prefix('/') {
+        Some(_) => Vec::new(),
+        None => directory.split('/').filter(|s| !s.is_empty()).collect(),
+    };
+    for segment in target.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+    segments.join("/")
+}
+
+/// The text of the `t` elements of an xml fragment, with a line break between its paragraphs
+struct TextBuilder {
+    text: String,
+    in_text: bool,
+}
+
+impl TextBuilder {
+    fn new() -> Self {
+        Self {
+            text: String::new(),
+            in_text: false,
+        }
+    }
+
+    /// Adds the text of an event, for the paragraphs named `paragraph`
+    fn add(&mut self, event: &Event, paragraph: &[u8]) -> ExtractResult<()> {
+        match event {
+            Event::Start(e) if e.local_name().as_ref() == b"t" => self.in_text = true,
+            Event::End(e) if e.local_name().as_ref() == b"t" => self.in_text = false,
+            Event::Empty(e) if e.local_name().as_ref() == b"tab" => self.text.push('\t'),
+            Event::End(e) if e.local_name().as_ref() == paragraph => self.text.push('\n'),
+            Event::Text(text) if self.in_text => {
+                self.text.push_str(&text.unescape().map_err(ooxml_error)?)
+            }
+            Event::CData(text) if self.in_text => {
+                self.text.push_str(&String::from_utf8_lossy(text))
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn build(self) -> String {
+        self.text.trim().to_string()
+    }
+}
+
+fn read_docx_comments<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+) -> ExtractResult<Vec<Annotation>> {
+    let mut comments_paths: Vec<String> = relationships(archive, "word/document.xml", "/comments")?
+        .into_values()
+        .collect();
+    comments_paths.sort();
+    let mut annotations = Vec::new();
+    let mut ids = Vec::new();
+    for path in comments_paths {
+        let Some(comments) = read_archive_file(archive, &path)? else {
+            continue;
+        };
+        let mut reader = Reader::from_str(&comments);
+        // The comment being read with its id
+        let mut comment: Option<(String, Annotation, TextBuilder)> = None;
+        loop {
+            let event = reader.read_event().map_err(ooxml_error)?;
+            match &event {
+                Event::Start(e) if e.local_name().as_ref() == b"comment" => {
+                    let mut attributes = attributes(e)?;
+                    let annotation = Annotation {
+                        kind: "comment".to_string(),
+                        author: attributes.remove("author").and_then(non_empty),
+                        date: attributes.remove("date").and_then(non_empty),
+                        text: String::new(),
+                        anchor_text: None,
+                        page: None,
+                    };
+                    let id = attributes.remove("id").unwrap_or_default();
+                    comment = Some((id, annotation, TextBuilder::new()));
+                }
+                Event::End(e) if e.local_name().as_ref() == b"comment" => {
+                    if let Some((id, mut annotation, text)) = comment.take() {
+                        annotation.text = text.build();
+                        ids.push(id);
+                        annotations.push(annotation);
+                    }
+                }
+                Event::Eof => break,
+                event => {
+                    if let Some((_, _, text)) = &mut comment {
+                        text.add(event, b"p")?;
+                    }
+                }
+            }
+        }
+    }
+
+    if !annotations.is_empty() {
+        if let Some(document) = read_archive_file(archive, "word/document.xml")? {
+            let anchors = docx_anchor_texts(&document)?;
+            for (id, annotation) in ids.iter().zip(annotations.iter_mut()) {
+                annotation.anchor_text = anchors.get(id).cloned().and_then(non_empty);
+            }
+        }
+    }
+    Ok(annotations)
+}
+
+/// The text between the `commentRangeStart` and `commentRangeEnd` of the comments of a docx
+/// document, by comment id. The ranges of the comments can overlap
+fn docx_anchor_texts(document: &str) -> ExtractResult<HashMap<String, String>> {
+    let mut anchors: HashMap<String, TextBuilder> = HashMap::new();
+    let mut open: Vec<String> = Vec::new();
+    let mut reader = Reader::from_str(document);
+    loop {
+        let event = reader.read_event().map_err(ooxml_error)?;
+        match &event {
+            Event::Start(e) | Event::Empty(e)
+                if e.local_name().as_ref() == b"commentRangeStart" =>
+            {
+                if let Some(id) = attributes(e)?.remove("id") {
+                    anchors.entry(id.clone()).or_insert_with(TextBuilder::new);
+                    open.push(id);
+                }
+            }
+            Event::Start(e) | Event::Empty(e) if e.local_name().as_ref() == b"commentRangeEnd" => {
+                if let Some(id) = attributes(e)?.remove("id") {
+                    open.retain(|open_id| *open_id != id);
+                }
+            }
+            Event::Eof => break,
+            event => {
+                for id in open.iter() {
+                    if let Some(text) = anchors.get_mut(id) {
+                        text.add(event, b"p")?;
+                    }
+                }
+            }
+        }
+    }
+    Ok(anchors
+        .into_iter()
+        .map(|(id, text)| (id, text.build()))
+        .collect())
+}
+
+fn read_pptx_comments<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+) -> ExtractResult<Vec<Annotation>> {
+    // The legacy and the modern comments have their own authors
+    let mut authors = HashMap::new();
+    for path in ["ppt/commentAuthors.xml", "ppt/authors.xml"] {
+        if let Some(xml) = read_archive_file(archive, path)? {
+            let mut reader = Reader::from_str(&xml);
+            loop {
+                match reader.read_event().map_err(ooxml_error)? {
+                    Event::Start(e) | Event::Empty(e)
+                        if matches!(e.local_name().as_ref(), b"cmAuthor" | b"author") =>
+                    {
+                        let mut attributes = attributes(&e)?;
+                        if let (Some(id), Some(name)) =
+                            (attributes.remove("id"), attributes.remove("name"))
+                        {
+                            authors.insert(id, name);
+                        }
+                    }
+                    Event::Eof => break,
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    let mut annotations = Vec::new();
+    for (index, slide) in pptx_slides(archive)?.iter().enumerate() {
+        let mut comments_paths: Vec<String> = relationships(archive, slide, "/comments")?
+            .into_values()
+            .collect();
+        comments_paths.sort();
+        for path in comments_paths {
+            let Some(comments) = read_archive_file(archive, &path)? else {
+                continue;
+            };
+            let mut reader = Reader::from_str(&comments);
+            let mut comment: Option<(Annotation, TextBuilder)> = None;
+            // The replies of the modern comments are not part of their text
+            let mut in_replies = false;
+            loop {
+                let event = reader.read_event().map_err(ooxml_error)?;
+                match &event {
+                    Event::Start(e) if e.local_name().as_ref() == b"replyLst" => in_replies = true,
+                    Event::End(e) if e.local_name().as_ref() == b"replyLst" => in_replies = false,
+                    Event::Start(e) if e.local_name().as_ref() == b"cm" => {
+                        let mut attributes = attributes(e)?;
+                        let author = attributes
+                            .remove("authorId")
+                            .and_then(|id| authors.get(&id).cloned());
+                        // The legacy comments have a `dt`, the modern ones a `created` date
+                        let date = attributes
+                            .remove("created")
+                            .or_else(|| attributes.remove("dt"));
+                        let annotation = Annotation {
+                            kind: "comment".to_string(),
+                            author,
+                            date,
+                            text: String::new(),
+                            anchor_text: None,
+                            page: u32::try_from(index + 1).ok(),
+                        };
+                        comment = Some((annotation, TextBuilder::new()));
+                    }
+                    Event::End(e) if e.local_name().as_ref() == b"cm" => {
+                        if let Some((mut annotation, text)) = comment.take() {
+                            annotation.text = text.build();
+                            annotations.push(annotation);
+                        }
+                    }
+                    // The text of the legacy comments is a `text` element, not a `t`
+                    Event::Start(e) if e.local_name().as_ref() == b"text" => {
+                        if let Some((_, text)) = &mut comment {
+                            text.in_text = true;
+                        }
+                    }
+                    Event::End(e) if e.local_name().as_ref() == b"text" => {
+                        if let Some((_, text)) = &mut comment {
+                            text.in_text = false;
+                        }
+                    }
+                    Event::Eof => break,
+                    event if !in_replies => {
+                        if let Some((_, text)) = &mut comment {
+                            text.add(event, b"p")?;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    Ok(annotations)
+}
+
+/// The archive paths of the slides of a presentation, in presentation order
+fn pptx_slides<R: Read + Seek>(archive: &mut ZipArchive<R>) -> ExtractResult<Vec<String>> {
+    let targets = relationships(archive, "ppt/presentation.xml", "/slide")?;
+    let Some(presentation) = read_archive_file(archive, "ppt/presentation.xml")? else {
+        return Ok(Vec::new());
+    };
+    let mut slides = Vec::new();
+    let mut reader = Reader::from_str(&presentation);
+    loop {
+        match reader.read_event().map_err(ooxml_error)? {
+            Event::Start(e) | Event::Empty(e) if e.local_name().as_ref() == b"sldId" => {
+                if let Some(slide) = relationship_id(&e)?.and_then(|id| targets.get(&id)) {
+                    slides.push(slide.clone());
+                }
+            }
+            Event::Eof => return Ok(slides),
+            _ => {}
+        }
+    }
+}
+
+fn read_xlsx_comments<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+) -> ExtractResult<Vec<Annotation>> {
+    let mut annotations = Vec::new();
+    for (index, (name, sheet)) in xlsx_sheets(archive)?.iter().enumerate() {
+        let mut comments_paths: Vec<String> = relationships(archive, sheet, "/comments")?
+            .into_values()
+            .collect();
+        comments_paths.sort();
+        for path in comments_paths {
+            let Some(comments) = read_archive_file(archive, &path)? else {
+                continue;
+            };
+            let mut reader = Reader::from_str(&comments);
+            let mut authors = Vec::new();
+            let mut author: Option<TextBuilder> = None;
+            let mut comment: Option<(Annotation, TextBuilder)> = None;
+            loop {
+                let event = reader.read_event().map_err(ooxml_error)?;
+                match &event {
+                    Event::Start(e) if e.local_name().as_ref() == b"author" => {
+                        let mut text = TextBuilder::new();
+                        text.in_text = true;
+                        author = Some(text);
+                    }
+                    Event::End(e) if e.local_name().as_ref() == b"author" => {
+                        authors.extend(author.take().map(TextBuilder::build));
+                    }
+                    Event::Empty(e) if e.local_name().as_ref() == b"author" => {
+                        authors.push(String::new());
+                    }
+                    Event::Start(e) if e.local_name().as_ref() == b"comment" => {
+                        let mut attributes = attributes(e)?;
+                        let author = attributes
+                            .remove("authorId")
+                            .and_then(|id| id.parse::<usize>().ok())
+                            .and_then(|id| authors.get(id).cloned())
+                            .and_then(non_empty);
+                        let annotation = Annotation {
+                            kind: "comment".to_string(),
+                            author,
+                            date: None,
+                            text: String::new(),
+                            anchor_text: attributes
+                                .remove("ref")
+                                .map(|cell| format!("{}!{}", name, cell)),
+                            page: u32::try_from(index + 1).ok(),
+                        };
+                        comment = Some((annotation, TextBuilder::new()));
+                    }
+                    Event::End(e) if e.local_name().as_ref() == b"comment" => {
+                        if let Some((mut annotation, text)) = comment.take() {
+                            annotation.text = xlsx_comment_text(text.build(), &annotation.author);
+                            annotations.push(annotation);
+                        }
+                    }
+                    Event::Eof => break,
+                    event => {
+                        if let Some(text) = &mut author {
+                            text.add(event, b"author")?;
+                        } else if let Some((_, text)) = &mut comment {
+                            text.add(event, b"text")?;
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Ok(annotations)
+}
+
+/// Excel starts the text of the comments with the name of their author
+fn xlsx_comment_text(text: String, author: &Option<String>) -> String {
+    author
+        .as_ref()
+        .and_then(|author| text.strip_prefix(&format!("{}:", author)))
+        .map(|text| text.trim().to_string())
+        .unwrap_or(text)
+}
+
+/// The names and archive paths of the sheets of a workbook, in workbook order
+fn xlsx_sheets<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+) -> ExtractResult<Vec<(String, String)>> {
+    let targets = relationships(archive, "xl/workbook.xml", "/worksheet")?;
+    let Some(workbook) = read_archive_file(archive, "xl/workbook.xml")? else {
+        return Ok(Vec::new());
+    };
+    let mut sheets = Vec::new();
+    let mut reader = Reader::from_str(&workbook);
+    loop {
+        match reader.read_event().map_err(ooxml_error)? {
+            Event::Start(e) | Event::Empty(e) if e.local_name().as_ref() == b"sheet" => {
+                let sheet = relationship_id(&e)?.and_then(|id| targets.get(&id).cloned());
+                if let (Some(name), Some(sheet)) = (attributes(&e)?.remove("name"), sheet) {
+                    sheets.push((name, sheet));
+                }
+            }
+            Event::Eof => return Ok(sheets),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_pdf_annotations_test() {
+        let lines =
+            "Highlight\t2\tJane\t2024-03-01T10:00:00Z\tthe indemnity\tToo broad\\, see \\\\3\n\
+            Text\t0\t\t\t\tfirst line\\nsecond\\tline\n\
+            FreeText\t1\tJohn\tD:garbage\t\t\n\
+            malformed line\n";
+        let annotations = parse_pdf_annotations(lines);
+        assert_eq!(
+            annotations,
+            vec![
+                Annotation {
+                    kind: "highlight".to_string(),
+                    author: Some("Jane".to_string()),
+                    date: Some("2024-03-01T10:00:00Z".to_string()),
+                    text: "Too broad, see \\3".to_string(),
+                    anchor_text: Some("the indemnity".to_string()),
+                    page: Some(2),
+                },
+                Annotation {
+                    kind: "note".to_string(),
+                    author: None,
+                    date: None,
+                    text: "first line\nsecond\tline".to_string(),
+                    anchor_text: None,
+                    page: None,
+                },
+                Annotation {
+                    kind: "free_text".to_string(),
+                    author: Some("John".to_string()),
+                    date: Some("D:garbage".to_string()),
+                    text: String::new(),
+                    anchor_text: None,
+                    page: Some(1),
+                },
+            ]
+        );
+        assert_eq!(pdf_kind("StrikeOut"), "strike_out");
+    }
+
+    #[test]
+    fn resolve_target_test() {
+        assert_eq!(
+            resolve_target("ppt/slides", "../comments/comment1.xml"),
+            "ppt/comments/comment1.xml"
+        );
+        assert_eq!(
+            resolve_target("xl", "worksheets/sheet1.xml"),
+            "xl/worksheets/sheet1.xml"
+        );
+        assert_eq!(
+            resolve_target("xl", "/xl/comments1.xml"),
+            "xl/comments1.xml"
+        );
+    }
+
+    #[test]
+    fn docx_anchor_texts_test() {
+        let document = r#"<w:document xmlns:w="w"><w:body>
+            <w:p><w:r><w:t>The </w:t></w:r><w:commentRangeStart w:id="0"/>
+            <w:r><w:t>supplier</w:t></w:r><w:commentRangeStart w:id="1"/>
+            <w:r><w:t xml:space="preserve"> shall indemnify</w:t></w:r>
+            <w:commentRangeEnd w:id="0"/><w:r><w:t> the buyer</w:t></w:r></w:p>
+            <w:p><w:r><w:t>in full</w:t></w:r><w:commentRangeEnd w:id="1"/></w:p>
+            </w:body></w:document>"#;
+        let anchors = docx_anchor_texts(document).unwrap();
+        assert_eq!(anchors["0"], "supplier shall indemnify");
+        assert_eq!(anchors["1"], "shall indemnify the buyer\nin full");
+    }
+}
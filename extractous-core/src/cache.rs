@@ -0,0 +1,405 @@
+//! On-disk cache of the extractions to string. An [`ExtractionCache`] set with
+//! [`Extractor::set_cache`] is checked before parsing a document, with a key combining the hash
+//! of the document bytes and the hash of the extractor's configuration. Unchanged documents
+//! extracted with the same configuration are read from the cache, without calling into tika.
+//!
+//! [`Extractor::set_cache`]: crate::Extractor::set_cache
+
+use crate::errors::{Error, ExtractResult};
+use crate::{stats, Metadata};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io::{BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::SystemTime;
+
+/// The default maximum size of a cache, 1 GiB
+pub const DEFAULT_MAX_SIZE: u64 = 1024 * 1024 * 1024;
+
+/// The extension of the cache entries
+const ENTRY_EXTENSION: &str = "json";
+
+/// The version of the configuration hashed in the cache keys, to be increased when its fields
+/// change meaning
+pub(crate) const KEY_VERSION: u32 = 1;
+
+/// The counters of an [`ExtractionCache`], shared by its clones. The entries and size are the
+/// ones of the cache directory, the other counters are counted since the cache was created
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CacheStats {
+    /// The extractions read from the cache
+    pub hits: u64,
+    /// The extractions not found in the cache
+    pub misses: u64,
+    /// The entries removed to keep the cache under its maximum size
+    pub evictions: u64,
+    /// The number of entries in the cache
+    pub entries: u64,
+    /// The size of the entries in bytes
+    pub size: u64,
+}
+
+/// A cache of the text and metadata of the extractions to string, backed by a local directory.
+/// Once the entries exceed the maximum size of the cache, the least recently used ones are
+/// removed. Clones share the same counters.
+///
+/// The cache is used by the `extract_*_to_string` functions of an extractor without registered
+/// parsers, whose output can not be part of the key. The directory can be shared by several
+/// processes: the entries are written atomically, a failure to read or write the cache is
+/// logged and the document is extracted as if it was not cached.
+#[derive(Debug, Clone)]
+pub struct ExtractionCache {
+    inner: Arc<CacheInner>,
+}
+
+#[derive(Debug)]
+struct CacheInner {
+    dir: PathBuf,
+    max_size: AtomicU64,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+    /// The number of entries and their size, updated by the cache and rescanned on eviction
+    usage: Mutex<(u64, u64)>,
+}
+
+/// The document whose extraction is cached
+pub(crate) enum CacheInput<'a> {
+    File(&'a str),
+    Bytes(&'a [u8]),
+}
+
+impl CacheInput<'_> {
+    /// The hex sha256 of the document bytes. Returns None if the file can not be read, the
+    /// extraction then reports the error
+    pub(crate) fn sha256(&self) -> Option<String> {
+        match self {
+            CacheInput::File(file_path) => file_hash(Path::new(file_path)).ok(),
+            CacheInput::Bytes(bytes) => Some(format!("{:x}", Sha256::digest(bytes))),
+        }
+    }
+}
+
+impl ExtractionCache {
+    /// Creates a cache backed by the directory, which is created if missing. The existing
+    /// entries of the directory are reused
+    pub fn new<P: AsRef<Path>>(dir: P) -> ExtractResult<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir).map_err(|e| {
+            Error::IoError(format!(
+                "Could not create the cache directory {}: {}",
+                dir.display(),
+                e
+            ))
+        })?;
+
+        let cache = Self {
+            inner: Arc::new(CacheInner {
+                dir,
+                max_size: AtomicU64::new(DEFAULT_MAX_SIZE),
+                hits: AtomicU64::new(0),
+                misses: AtomicU64::new(0),
+                evictions: AtomicU64::new(0),
+                usage: Mutex::new((0, 0)),
+            }),
+        };
+        let entries = cache.entries();
+        *cache.usage() = (
+            entries.len() as u64,
+            entries.iter().map(|entry| entry.1).sum(),
+        );
+        Ok(cache)
+    }
+
+    /// Set the maximum size in bytes of the entries of the cache. The least recently used
+    /// entries are removed once the cache exceeds it
+    /// Default: 1 GiB
+    pub fn set_max_size(self, max_size: u64) -> Self {
+        self.inner.max_size.store(max_size, Ordering::Relaxed);
+        self.evict();
+        self
+    }
+
+    /// The directory of the cache
+    pub fn dir(&self) -> &Path {
+        &self.inner.dir
+    }
+
+    /// The hits, misses and evictions of the cache, and the number and size of its entries
+    pub fn stats(&self) -> CacheStats {
+        let (entries, size) = *self.usage();
+        CacheStats {
+            hits: self.inner.hits.load(Ordering::Relaxed),
+            misses: self.inner.misses.load(Ordering::Relaxed),
+            evictions: self.inner.evictions.load(Ordering::Relaxed),
+            entries,
+            size,
+        }
+    }
+
+    /// Removes all the entries of the cache
+    pub fn clear(&self) -> ExtractResult<()> {
+        let mut usage = self.usage();
+        for (path, _, _) in self.entries() {
+            fs::remove_file(&path).map_err(|e| {
+                Error::IoError(format!("Could not remove {}: {}", path.display(), e))
+            })?;
+        }
+        *usage = (0, 0);
+        Ok(())
+    }
+
+    /// The key of the extraction of the input with the given configuration: the hash of the
+    /// input bytes and the hash of the configuration
+    pub(crate) fn key(&self, input_sha256: &str, config: &str) -> String {
+        // The output of the extraction changes with the version of extractous
+        let config_hash = Sha256::new()
+            .chain_update(env!("CARGO_PKG_VERSION"))
+            .chain_update([0])
+            .chain_update(config)
+            .finalize();
+        format!("{}-{:x}", input_sha256, config_hash)
+    }
+
+    /// Reads the cached extraction of the key, counting a hit or a miss
+    pub(crate) fn get(&self, key: &str) -> Option<(String, Metadata)> {
+        let path = self.entry_path(key);
+        let entry = match fs::read(&path) {
+            Ok(entry) => entry,
+            Err(e) => {
+                if e.kind() != std::io::ErrorKind::NotFound {
+                    log::warn!("Could not read the cache entry {}: {}", path.display(), e);
+                }
+                self.inner.misses.fetch_add(1, Ordering::Relaxed);
+                return None;
+            }
+        };
+
+        match serde_json::from_slice::<(String, Metadata)>(&entry) {
+            Ok(result) => {
+                // The modification time of the entries orders them for the eviction
+                if let Err(e) = File::options()
+                    .write(true)
+                    .open(&path)
+                    .and_then(|file| file.set_modified(SystemTime::now()))
+                {
+                    log::debug!("Could not touch the cache entry {}: {}", path.display(), e);
+                }
+                self.inner.hits.fetch_add(1, Ordering::Relaxed);
+                Some(result)
+            }
+            Err(e) => {
+                log::warn!("Invalid cache entry {}: {}", path.display(), e);
+                self.inner.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    /// Caches the extraction of the key, without its stats, and evicts the least recently used
+    /// entries if the cache exceeds its maximum size
+    pub(crate) fn put(&self, key: &str, content: &str, metadata: &Metadata) {
+        let mut metadata = metadata.clone();
+        stats::remove_stats(&mut metadata);
+        let path = self.entry_path(key);
+        // The entry may exist, e.g. when another process cached the same extraction
+        let previous_size = fs::metadata(&path).ok().map(|metadata| metadata.len());
+        match write_entry(&path, content, &metadata) {
+            Ok(size) => {
+                let mut usage = self.usage();
+                match previous_size {
+                    Some(previous_size) => usage.1 = usage.1.saturating_sub(previous_size),
+                    None => usage.0 += 1,
+                }
+                usage.1 += size;
+                let exceeded = usage.1 > self.inner.max_size.load(Ordering::Relaxed);
+                drop(usage);
+                if exceeded {
+                    self.evict();
+                }
+            }
+            Err(e) => log::warn!("Could not write the cache entry {}: {}", path.display(), e),
+        }
+    }
+
+    /// Removes the least recently used entries until the cache is under its maximum size
+    fn evict(&self) {
+        let mut usage = self.usage();
+        let mut entries = self.entries();
+        let max_size = self.inner.max_size.load(Ordering::Relaxed);
+        let mut size: u64 = entries.iter().map(|entry| entry.1).sum();
+        // The entries that could not be removed are still counted
+        let mut count = entries.len() as u64;
+        if size > max_size {
+            entries.sort_by_key(|entry| entry.2);
+            entries.reverse();
+            while size > max_size {
+                let Some((path, entry_size, _)) = entries.pop() else {
+                    break;
+                };
+                match fs::remove_file(&path) {
+                    Ok(()) => {
+                        size -= entry_size;
+                        count -= 1;
+                        self.inner.evictions.fetch_add(1, Ordering::Relaxed);
+                    }
+                    Err(e) => {
+                        log::warn!("Could not evict the cache entry {}: {}", path.display(), e)
+                    }
+                }
+            }
+        }
+        *usage = (count, size);
+    }
+
+    /// The entries of the cache directory with their size and modification time
+    fn entries(&self) -> Vec<(PathBuf, u64, SystemTime)> {
+        let subdirs = match fs::read_dir(self.dir()) {
+            Ok(subdirs) => subdirs,
+            Err(e) => {
+                log::warn!("Could not list the cache {}: {}", self.dir().display(), e);
+                return Vec::new();
+            }
+        };
+        subdirs
+            .flatten()
+            .filter_map(|subdir| fs::read_dir(subdir.path()).ok())
+            .flatten()
+            .flatten()
+            .filter(|entry| {
+                entry.path().extension().and_then(|e| e.to_str()) == Some(ENTRY_EXTENSION)
+            })
+            .filter_map(|entry| {
+                let metadata = entry.metadata().ok()?;
+                Some((entry.path(), metadata.len(), metadata.modified().ok()?))
+            })
+            .collect()
+    }
+
+    /// The entries are spread in subdirectories named after the first characters of their key
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.inner
+            .dir
+            .join(&key[..2])
+            .join(format!("{}.{}", key, ENTRY_EXTENSION))
+    }
+
+    fn usage(&self) -> std::sync::MutexGuard<'_, (u64, u64)> {
+        self.inner
+            .usage
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Writes the entry of the cache and returns its size
+fn write_entry(path: &Path, content: &str, metadata: &Metadata) -> std::io::Result<u64> {
+    let entry = serde_json::to_vec(&(content, metadata))?;
+    write_atomically(path, &entry)?;
+    Ok(entry.len() as u64)
+}
+
+/// The hex sha256 of the content of the file
+pub(crate) fn file_hash(path: &Path) -> std::io::Result<String> {
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut BufReader::new(File::open(path)?), &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Writes to a temporary file `.{file name}.*.tmp` renamed to the path, so that the other
+/// processes never read a partially written file. The parent directory is created if missing
+pub(crate) fn write_atomically(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let dir = path.parent().unwrap_or(Path::new("."));
+    fs::create_dir_all(dir)?;
+
+    let temp_path = dir.join(format!(
+        ".{}.{}.{}.tmp",
+        path.file_name().unwrap_or_default().to_string_lossy(),
+        std::process::id(),
+        NEXT_TEMP_ID.fetch_add(1, Ordering::Relaxed)
+    ));
+    let written = File::create(&temp_path)
+        .and_then(|mut file| file.write_all(bytes))
+        .and_then(|()| fs::rename(&temp_path, path));
+    if written.is_err() {
+        fs::remove_file(&temp_path).ok();
+    }
+    written
+}
+
+static NEXT_TEMP_ID: AtomicU64 = AtomicU64::new(0);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("extractous-{}-{}", name, std::process::id()));
+        fs::remove_dir_all(&dir).ok();
+        dir
+    }
+
+    #[test]
+    fn get_put_test() {
+        let dir = temp_dir("cache-get-put");
+        let cache = ExtractionCache::new(&dir).unwrap();
+        let input_sha256 = CacheInput::Bytes(b"text").sha256().unwrap();
+        let key = cache.key(&input_sha256, "config");
+        assert_ne!(key, cache.key(&input_sha256, "other config"));
+        assert_eq!(cache.get(&key), None);
+
+        let metadata = Metadata::from([
+            ("Content-Type".to_string(), vec!["text/plain".to_string()]),
+            (stats::TOTAL_TIME_KEY.to_string(), vec!["10".to_string()]),
+        ]);
+        cache.put(&key, "content", &metadata);
+        let (content, cached_metadata) = cache.get(&key).unwrap();
+        assert_eq!(content, "content");
+        assert_eq!(cached_metadata.len(), 1);
+
+        // Overwriting an entry does not count it twice
+        let size = cache.stats().size;
+        cache.put(&key, "content", &metadata);
+        assert_eq!((cache.stats().entries, cache.stats().size), (1, size));
+
+        // A new cache reuses the entries of the directory
+        let cache = ExtractionCache::new(&dir).unwrap();
+        assert!(cache.get(&key).is_some());
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (1, 0, 1));
+
+        cache.clear().unwrap();
+        assert_eq!(cache.get(&key), None);
+        assert_eq!(cache.stats().entries, 0);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn evict_test() {
+        let dir = temp_dir("cache-evict");
+        let cache = ExtractionCache::new(&dir).unwrap();
+        let keys: Vec<String> = (0..3)
+            .map(|i| {
+                let key = cache.key(&CacheInput::Bytes(&[i]).sha256().unwrap(), "config");
+                cache.put(&key, "content", &Metadata::new());
+                // Orders the modification times of the entries
+                std::thread::sleep(std::time::Duration::from_millis(20));
+                key
+            })
+            .collect();
+        // The first entry becomes the most recently used
+        assert!(cache.get(&keys[0]).is_some());
+
+        let entry_size = cache.stats().size / 3;
+        let cache = cache.set_max_size(entry_size * 2);
+        let stats = cache.stats();
+        assert_eq!((stats.evictions, stats.entries), (1, 2));
+        assert!(cache.get(&keys[0]).is_some());
+        assert!(cache.get(&keys[1]).is_none());
+        assert!(cache.get(&keys[2]).is_some());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
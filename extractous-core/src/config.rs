@@ -1,7 +1,11 @@
+use crate::errors::{Error, ExtractResult};
+use serde_json::{json, Value};
+use std::ops::RangeInclusive;
 use strum_macros::{Display, EnumString};
 
 /// OCR Strategy for PDF parsing
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(non_camel_case_types)]
 pub enum PdfOcrStrategy {
     NO_OCR,
@@ -11,16 +15,85 @@ pub enum PdfOcrStrategy {
     AUTO,
 }
 
+/// Color type of the images pages are rendered to before OCR
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(non_camel_case_types)]
+pub enum PdfOcrImageType {
+    BINARY,
+    #[default]
+    GRAY,
+    RGB,
+    ARGB,
+}
+
+/// File format of the images pages are rendered to before OCR
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(non_camel_case_types)]
+pub enum PdfOcrImageFormat {
+    #[default]
+    PNG,
+    TIFF,
+    JPEG,
+}
+
+/// Which elements of a page are rendered before OCR
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(non_camel_case_types)]
+pub enum PdfOcrRenderingStrategy {
+    /// Render the page without its text, only the images and vector graphics
+    NO_TEXT,
+    /// Render only the text of the page
+    TEXT_ONLY,
+    /// Render only the vector graphics of the page
+    VECTOR_GRAPHICS_ONLY,
+    /// Render the whole page
+    #[default]
+    ALL,
+}
+
+/// Output of the Tesseract OCR
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(non_camel_case_types)]
+pub enum OcrOutputType {
+    /// Plain text
+    #[default]
+    TXT,
+    /// hOCR markup with the bounding box and confidence of every word, see
+    /// [`crate::Extractor::extract_file_to_ocr_words`]
+    HOCR,
+}
+
 /// PDF parsing configuration settings
 ///
 /// These settings are used to configure the behavior of the PDF parsing.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
 pub struct PdfParserConfig {
     pub(crate) ocr_strategy: PdfOcrStrategy,
     pub(crate) extract_inline_images: bool,
     pub(crate) extract_unique_inline_images_only: bool,
     pub(crate) extract_marked_content: bool,
     pub(crate) extract_annotation_text: bool,
+    pub(crate) sort_by_position: bool,
+    pub(crate) spacing_tolerance: f32,
+    pub(crate) average_char_tolerance: f32,
+    pub(crate) enable_auto_space: bool,
+    pub(crate) suppress_duplicate_overlapping_text: bool,
+    pub(crate) extract_acro_form_content: bool,
+    pub(crate) extract_bookmarks_text: bool,
+    pub(crate) extract_actions: bool,
+    pub(crate) ocr_dpi: i32,
+    pub(crate) ocr_image_type: PdfOcrImageType,
+    pub(crate) ocr_image_format: PdfOcrImageFormat,
+    pub(crate) ocr_rendering_strategy: PdfOcrRenderingStrategy,
+    pub(crate) max_main_memory_bytes: i64,
+    pub(crate) catch_intermediate_io_exceptions: bool,
+    pub(crate) page_ranges: Vec<RangeInclusive<u32>>,
 }
 
 impl Default for PdfParserConfig {
@@ -31,6 +104,21 @@ impl Default for PdfParserConfig {
             extract_unique_inline_images_only: false,
             extract_marked_content: false,
             extract_annotation_text: true,
+            sort_by_position: false,
+            spacing_tolerance: 0.5,
+            average_char_tolerance: 0.3,
+            enable_auto_space: true,
+            suppress_duplicate_overlapping_text: false,
+            extract_acro_form_content: true,
+            extract_bookmarks_text: true,
+            extract_actions: false,
+            ocr_dpi: 300,
+            ocr_image_type: PdfOcrImageType::GRAY,
+            ocr_image_format: PdfOcrImageFormat::PNG,
+            ocr_rendering_strategy: PdfOcrRenderingStrategy::ALL,
+            max_main_memory_bytes: 512 * 1024 * 1024,
+            catch_intermediate_io_exceptions: true,
+            page_ranges: Vec::new(),
         }
     }
 }
@@ -90,12 +178,185 @@ impl PdfParserConfig {
         self.extract_annotation_text = val;
         self
     }
+
+    /// If true, sort the text tokens by their x/y position before extracting the text. This
+    /// may be needed for PDFs whose content stream is not in reading order, e.g. with columns.
+    /// Default: false.
+    pub fn set_sort_by_position(mut self, val: bool) -> Self {
+        self.sort_by_position = val;
+        self
+    }
+
+    /// Sets the minimum whitespace, as a multiple of the max height of the current characters,
+    /// beyond which a space is inserted between two characters.
+    /// Default: 0.5.
+    pub fn set_spacing_tolerance(mut self, val: f32) -> Self {
+        self.spacing_tolerance = val;
+        self
+    }
+
+    /// Sets the average character width tolerance, as a multiple of the current character
+    /// width, beyond which a space is inserted between two characters.
+    /// Default: 0.3.
+    pub fn set_average_char_tolerance(mut self, val: f32) -> Self {
+        self.average_char_tolerance = val;
+        self
+    }
+
+    /// If true, insert spaces between words that are rendered without a space character.
+    /// Default: true.
+    pub fn set_enable_auto_space(mut self, val: bool) -> Self {
+        self.enable_auto_space = val;
+        self
+    }
+
+    /// If true, remove the duplicate text that some PDF writers render several times at
+    /// slightly different positions to simulate bold text. Slows down the extraction.
+    /// Default: false.
+    pub fn set_suppress_duplicate_overlapping_text(mut self, val: bool) -> Self {
+        self.suppress_duplicate_overlapping_text = val;
+        self
+    }
+
+    /// If true, extract the content of the AcroForm fields, i.e. the interactive form fields.
+    /// Default: true.
+    pub fn set_extract_acro_form_content(mut self, val: bool) -> Self {
+        self.extract_acro_form_content = val;
+        self
+    }
+
+    /// If true, extract the text of the bookmarks, i.e. the document outline.
+    /// Default: true.
+    pub fn set_extract_bookmarks_text(mut self, val: bool) -> Self {
+        self.extract_bookmarks_text = val;
+        self
+    }
+
+    /// If true, extract the actions of the document, such as javascript or links triggered
+    /// when a page is opened or a form field is changed.
+    /// Default: false.
+    pub fn set_extract_actions(mut self, val: bool) -> Self {
+        self.extract_actions = val;
+        self
+    }
+
+    /// Sets the DPI at which the pages are rendered before OCR.
+    /// Default: 300.
+    pub fn set_ocr_dpi(mut self, val: i32) -> Self {
+        self.ocr_dpi = val;
+        self
+    }
+
+    /// Sets the color type of the images the pages are rendered to before OCR.
+    /// Default: GRAY.
+    pub fn set_ocr_image_type(mut self, val: PdfOcrImageType) -> Self {
+        self.ocr_image_type = val;
+        self
+    }
+
+    /// Sets the file format of the images the pages are rendered to before OCR.
+    /// Default: PNG.
+    pub fn set_ocr_image_format(mut self, val: PdfOcrImageFormat) -> Self {
+        self.ocr_image_format = val;
+        self
+    }
+
+    /// Sets which elements of the pages are rendered before OCR. NO_TEXT avoids running OCR
+    /// on text that is extracted anyway when using the OCR_AND_TEXT_EXTRACTION strategy.
+    /// Default: ALL.
+    pub fn set_ocr_rendering_strategy(mut self, val: PdfOcrRenderingStrategy) -> Self {
+        self.ocr_rendering_strategy = val;
+        self
+    }
+
+    /// Sets the maximum main memory in bytes the PDF parser may use before it falls back to
+    /// temporary files. Set to -1 for no limit.
+    /// Default: 512 MB.
+    pub fn set_max_main_memory_bytes(mut self, val: i64) -> Self {
+        self.max_main_memory_bytes = val;
+        self
+    }
+
+    /// If true, exceptions thrown while parsing a page are recorded in the metadata and the
+    /// remaining pages are parsed. If false, the first exception stops the parsing.
+    /// Default: true.
+    pub fn set_catch_intermediate_io_exceptions(mut self, val: bool) -> Self {
+        self.catch_intermediate_io_exceptions = val;
+        self
+    }
+
+    /// Extracts only the pages of the given range. Pages are numbered from 1, e.g. `1..=3`
+    /// extracts the first three pages. Parsing stops once the last page of the range is
+    /// extracted, so the following pages are neither parsed nor OCRed. The pages before the
+    /// range are still parsed, but their content is dropped. Page 0 and inverted ranges, e.g.
+    /// `5..=2`, are rejected by [`PdfParserConfig::validate`].
+    /// Default: all pages.
+    pub fn set_page_range(mut self, range: RangeInclusive<u32>) -> Self {
+        self.page_ranges = vec![range];
+        self
+    }
+
+    /// Extracts only the given pages, numbered from 1. Parsing stops once the last given page
+    /// is extracted, see [`PdfParserConfig::set_page_range`].
+    /// Default: all pages.
+    pub fn set_pages(mut self, pages: &[u32]) -> Self {
+        self.page_ranges = pages.iter().map(|&page| page..=page).collect();
+        self
+    }
+
+    /// Checks that the page ranges start at page 1 and are not inverted. Called before every
+    /// extraction, returns a [`Error::ConfigError`] for invalid ranges.
+    pub fn validate(&self) -> ExtractResult<()> {
+        for range in self.page_ranges.iter() {
+            if *range.start() == 0 || range.start() > range.end() {
+                return Err(Error::ConfigError(format!(
+                    "Invalid pdf page range {}..={}, expected pages numbered from 1 and a start \
+                     not after the end",
+                    range.start(),
+                    range.end()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// The settings by name, hashed in the keys of the extraction cache
+    pub(crate) fn cache_key(&self) -> Value {
+        json!({
+            "ocr_strategy": self.ocr_strategy.to_string(),
+            "extract_inline_images": self.extract_inline_images,
+            "extract_unique_inline_images_only": self.extract_unique_inline_images_only,
+            "extract_marked_content": self.extract_marked_content,
+            "extract_annotation_text": self.extract_annotation_text,
+            "sort_by_position": self.sort_by_position,
+            "spacing_tolerance": self.spacing_tolerance,
+            "average_char_tolerance": self.average_char_tolerance,
+            "enable_auto_space": self.enable_auto_space,
+            "suppress_duplicate_overlapping_text": self.suppress_duplicate_overlapping_text,
+            "extract_acro_form_content": self.extract_acro_form_content,
+            "extract_bookmarks_text": self.extract_bookmarks_text,
+            "extract_actions": self.extract_actions,
+            "ocr_dpi": self.ocr_dpi,
+            "ocr_image_type": self.ocr_image_type.to_string(),
+            "ocr_image_format": self.ocr_image_format.to_string(),
+            "ocr_rendering_strategy": self.ocr_rendering_strategy.to_string(),
+            "max_main_memory_bytes": self.max_main_memory_bytes,
+            "catch_intermediate_io_exceptions": self.catch_intermediate_io_exceptions,
+            "page_ranges": self
+                .page_ranges
+                .iter()
+                .map(|range| [range.start(), range.end()])
+                .collect::<Vec<_>>(),
+        })
+    }
 }
 
 /// Microsoft Office parser configuration settings
 ///
 /// These settings are used to configure the behavior of the MSOffice parsing.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
 pub struct OfficeParserConfig {
     pub(crate) extract_macros: bool,
     pub(crate) include_deleted_content: bool,
@@ -107,6 +368,7 @@ pub struct OfficeParserConfig {
     pub(crate) include_slide_master_content: bool,
     pub(crate) concatenate_phonetic_runs: bool,
     pub(crate) extract_all_alternatives_from_msg: bool,
+    pub(crate) include_comments: bool,
 }
 
 impl Default for OfficeParserConfig {
@@ -122,6 +384,7 @@ impl Default for OfficeParserConfig {
             include_slide_master_content: true,
             concatenate_phonetic_runs: true,
             extract_all_alternatives_from_msg: false,
+            include_comments: true,
         }
     }
 }
@@ -215,12 +478,42 @@ impl OfficeParserConfig {
         self.extract_all_alternatives_from_msg = val;
         self
     }
+
+    /// Whether to include the comments of docx documents, the comments of pptx slides and the
+    /// cell comments of xlsx sheets in the extracted text. Set to false to keep the comments
+    /// out of the text and read them with the `extract_*_annotations` functions of the
+    /// [`crate::Extractor`] instead. The annotations of pdfs are configured with
+    /// [`PdfParserConfig::set_extract_annotation_text`].
+    /// Default: true
+    pub fn set_include_comments(mut self, val: bool) -> Self {
+        self.include_comments = val;
+        self
+    }
+
+    /// The settings by name, hashed in the keys of the extraction cache
+    pub(crate) fn cache_key(&self) -> Value {
+        json!({
+            "extract_macros": self.extract_macros,
+            "include_deleted_content": self.include_deleted_content,
+            "include_move_from_content": self.include_move_from_content,
+            "include_shape_based_content": self.include_shape_based_content,
+            "include_headers_and_footers": self.include_headers_and_footers,
+            "include_missing_rows": self.include_missing_rows,
+            "include_slide_notes": self.include_slide_notes,
+            "include_slide_master_content": self.include_slide_master_content,
+            "concatenate_phonetic_runs": self.concatenate_phonetic_runs,
+            "extract_all_alternatives_from_msg": self.extract_all_alternatives_from_msg,
+            "include_comments": self.include_comments,
+        })
+    }
 }
 
 /// Tesseract OCR configuration settings
 ///
 /// These settings are used to configure the behavior of the optical image recognition.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
 pub struct TesseractOcrConfig {
     pub(crate) density: i32,
     pub(crate) depth: i32,
@@ -228,6 +521,19 @@ pub struct TesseractOcrConfig {
     pub(crate) enable_image_preprocessing: bool,
     pub(crate) apply_rotation: bool,
     pub(crate) language: String,
+    pub(crate) page_seg_mode: i32,
+    pub(crate) ocr_engine_mode: Option<i32>,
+    pub(crate) preserve_interword_spacing: bool,
+    pub(crate) min_file_size_to_ocr: i64,
+    pub(crate) max_file_size_to_ocr: i64,
+    pub(crate) tesseract_path: Option<String>,
+    pub(crate) tessdata_path: Option<String>,
+    pub(crate) image_magick_path: Option<String>,
+    pub(crate) colorspace: String,
+    pub(crate) filter: String,
+    pub(crate) resize: i32,
+    pub(crate) other_tesseract_config: Vec<(String, String)>,
+    pub(crate) output_type: OcrOutputType,
 }
 
 impl Default for TesseractOcrConfig {
@@ -239,10 +545,51 @@ impl Default for TesseractOcrConfig {
             enable_image_preprocessing: false,
             apply_rotation: false,
             language: "eng".to_string(),
+            page_seg_mode: 1,
+            ocr_engine_mode: None,
+            preserve_interword_spacing: false,
+            min_file_size_to_ocr: 0,
+            max_file_size_to_ocr: i32::MAX as i64,
+            tesseract_path: None,
+            tessdata_path: None,
+            image_magick_path: None,
+            colorspace: "gray".to_string(),
+            filter: "triangle".to_string(),
+            resize: 200,
+            other_tesseract_config: Vec::new(),
+            output_type: OcrOutputType::TXT,
         }
     }
 }
 
+/// Valid color depths of the ImageMagick preprocessing
+const OCR_DEPTHS: [i32; 8] = [2, 4, 8, 16, 32, 64, 256, 4096];
+/// Valid resize filters of the ImageMagick preprocessing
+const OCR_FILTERS: [&str; 9] = [
+    "point",
+    "hermite",
+    "cubic",
+    "box",
+    "gaussian",
+    "catrom",
+    "triangle",
+    "quadratic",
+    "mitchell",
+];
+
+/// The characters tika accepts in the keys and values of the other tesseract config, besides
+/// the ascii alphanumeric ones. Tika's pattern is `(?i)^[-_/\.A-Z0-9]+$`
+const OCR_CONFIG_CHARS: &str = "-_/.";
+
+/// Returns true if the value is not empty and contains only ascii alphanumeric characters
+/// and the given extra characters
+fn is_safe_value(value: &str, extra_chars: &str) -> bool {
+    !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || extra_chars.contains(c))
+}
+
 impl TesseractOcrConfig {
     /// Creates a new instance of TesseractOcrConfig with default settings.
     pub fn new() -> Self {
@@ -293,4 +640,348 @@ impl TesseractOcrConfig {
         self.timeout_seconds = val;
         self
     }
+
+    /// Sets the tesseract page segmentation mode, from 0 to 13, e.g. 6 assumes a single
+    /// uniform block of text. See `tesseract --help-psm` for the list of modes.
+    /// Default: 1, automatic page segmentation with orientation and script detection.
+    pub fn set_page_seg_mode(mut self, val: i32) -> Self {
+        self.page_seg_mode = val;
+        self
+    }
+
+    /// Sets the tesseract OCR engine mode, from 0 to 3: 0 legacy engine only, 1 LSTM engine
+    /// only, 2 legacy and LSTM engines, 3 default, based on what is available.
+    /// Default: None, tesseract's default.
+    pub fn set_ocr_engine_mode(mut self, val: i32) -> Self {
+        self.ocr_engine_mode = Some(val);
+        self
+    }
+
+    /// If true, tesseract preserves the spacing between words instead of collapsing it
+    /// to a single space.
+    /// Default: false.
+    pub fn set_preserve_interword_spacing(mut self, val: bool) -> Self {
+        self.preserve_interword_spacing = val;
+        self
+    }
+
+    /// Sets the minimum size in bytes of an image to be OCRed. Smaller images are skipped.
+    /// Default: 0.
+    pub fn set_min_file_size_to_ocr(mut self, val: i64) -> Self {
+        self.min_file_size_to_ocr = val;
+        self
+    }
+
+    /// Sets the maximum size in bytes of an image to be OCRed. Larger images are skipped.
+    /// Default: 2147483647.
+    pub fn set_max_file_size_to_ocr(mut self, val: i64) -> Self {
+        self.max_file_size_to_ocr = val;
+        self
+    }
+
+    /// Sets the directory containing the tesseract executable.
+    /// Default: None, tesseract is looked up on the `PATH`.
+    pub fn set_tesseract_path(mut self, val: &str) -> Self {
+        self.tesseract_path = Some(val.to_string());
+        self
+    }
+
+    /// Sets the directory containing the tessdata language files.
+    /// Default: None, tesseract's default tessdata directory.
+    pub fn set_tessdata_path(mut self, val: &str) -> Self {
+        self.tessdata_path = Some(val.to_string());
+        self
+    }
+
+    /// Sets the directory containing the ImageMagick executables used to preprocess the
+    /// images, see [`TesseractOcrConfig::set_enable_image_preprocessing`].
+    /// Default: None, ImageMagick is looked up on the `PATH`.
+    pub fn set_image_magick_path(mut self, val: &str) -> Self {
+        self.image_magick_path = Some(val.to_string());
+        self
+    }
+
+    /// Sets the ImageMagick colorspace the images are converted to during preprocessing,
+    /// e.g. "gray" or "rgb".
+    /// Default: "gray".
+    pub fn set_colorspace(mut self, val: &str) -> Self {
+        self.colorspace = val.to_string();
+        self
+    }
+
+    /// Sets the ImageMagick filter used to resize the images during preprocessing, one of
+    /// point, hermite, cubic, box, gaussian, catrom, triangle, quadratic or mitchell.
+    /// Default: "triangle".
+    pub fn set_filter(mut self, val: &str) -> Self {
+        self.filter = val.to_string();
+        self
+    }
+
+    /// Sets the percentage, from 100 to 900 in steps of 100, the images are resized to
+    /// during preprocessing.
+    /// Default: 200.
+    pub fn set_resize(mut self, val: i32) -> Self {
+        self.resize = val;
+        self
+    }
+
+    /// Adds a tesseract config variable, passed as `-c key=value` to tesseract, e.g.
+    /// `("textord_initialx_ile", "0.75")`. Can be called multiple times. As in tika, keys and
+    /// values may only contain ascii letters, digits, `-`, `_`, `/` and `.`.
+    /// Default: no extra variables.
+    pub fn add_other_tesseract_config(mut self, key: &str, value: &str) -> Self {
+        self.other_tesseract_config
+            .push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Sets the output of tesseract. With [`OcrOutputType::HOCR`], the xml output contains the
+    /// hOCR `ocr_page`, `ocr_line` and `ocrx_word` elements of the OCRed images.
+    /// Default: OcrOutputType::TXT.
+    pub fn set_output_type(mut self, val: OcrOutputType) -> Self {
+        self.output_type = val;
+        self
+    }
+
+    /// Checks that all the settings have values that tesseract and ImageMagick accept.
+    /// Called before every extraction, returns a [`Error::ConfigError`] for invalid values.
+    pub fn validate(&self) -> ExtractResult<()> {
+        let invalid = |name: &str, value: &dyn std::fmt::Display, expected: &str| {
+            Err(Error::ConfigError(format!(
+                "Invalid tesseract {} {}, expected {}",
+                name, value, expected
+            )))
+        };
+
+        if !(150..=1200).contains(&self.density) {
+            return invalid("density", &self.density, "a value from 150 to 1200");
+        }
+        if !OCR_DEPTHS.contains(&self.depth) {
+            return invalid(
+                "depth",
+                &self.depth,
+                "one of 2, 4, 8, 16, 32, 64, 256 or 4096",
+            );
+        }
+        if self.timeout_seconds < 0 {
+            return invalid("timeout", &self.timeout_seconds, "0 or more seconds");
+        }
+        let is_valid_language = self
+            .language
+            .split('+')
+            .all(|language| is_safe_value(language, "-_/."));
+        if !is_valid_language {
+            return invalid(
+                "language",
+                &self.language,
+                "language names separated by plus characters",
+            );
+        }
+        if !(0..=13).contains(&self.page_seg_mode) {
+            return invalid("page segmentation mode", &self.page_seg_mode, "0 to 13");
+        }
+        if let Some(mode) = self.ocr_engine_mode.filter(|mode| !(0..=3).contains(mode)) {
+            return invalid("ocr engine mode", &mode, "0 to 3");
+        }
+        if self.min_file_size_to_ocr < 0 || self.max_file_size_to_ocr < self.min_file_size_to_ocr {
+            return invalid(
+                "file size range",
+                &format!(
+                    "{}..{}",
+                    self.min_file_size_to_ocr, self.max_file_size_to_ocr
+                ),
+                "a positive minimum not larger than the maximum",
+            );
+        }
+        let paths = [
+            ("tesseract path", &self.tesseract_path),
+            ("tessdata path", &self.tessdata_path),
+            ("ImageMagick path", &self.image_magick_path),
+        ];
+        for (name, path) in paths {
+            if let Some(path) = path.as_ref().filter(|p| p.trim().is_empty()) {
+                return invalid(name, &format!("{:?}", path), "a directory");
+            }
+        }
+        if !is_safe_value(&self.colorspace, "-_") {
+            return invalid("colorspace", &self.colorspace, "an ImageMagick colorspace");
+        }
+        if !OCR_FILTERS.contains(&self.filter.to_ascii_lowercase().as_str()) {
+            return invalid("filter", &self.filter, &OCR_FILTERS.join(", "));
+        }
+        if !(100..=900).contains(&self.resize) || self.resize % 100 != 0 {
+            return invalid("resize", &self.resize, "100 to 900 in steps of 100");
+        }
+        for (key, value) in self.other_tesseract_config.iter() {
+            if !is_safe_value(key, OCR_CONFIG_CHARS) || !is_safe_value(value, OCR_CONFIG_CHARS) {
+                return invalid(
+                    "config variable",
+                    &format!("{}={}", key, value),
+                    "keys and values of ascii letters, digits, -, _, / or .",
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// The settings by name, hashed in the keys of the extraction cache
+    pub(crate) fn cache_key(&self) -> Value {
+        json!({
+            "density": self.density,
+            "depth": self.depth,
+            "timeout_seconds": self.timeout_seconds,
+            "enable_image_preprocessing": self.enable_image_preprocessing,
+            "apply_rotation": self.apply_rotation,
+            "language": self.language,
+            "page_seg_mode": self.page_seg_mode,
+            "ocr_engine_mode": self.ocr_engine_mode,
+            "preserve_interword_spacing": self.preserve_interword_spacing,
+            "min_file_size_to_ocr": self.min_file_size_to_ocr,
+            "max_file_size_to_ocr": self.max_file_size_to_ocr,
+            "tesseract_path": self.tesseract_path,
+            "tessdata_path": self.tessdata_path,
+            "image_magick_path": self.image_magick_path,
+            "colorspace": self.colorspace,
+            "filter": self.filter,
+            "resize": self.resize,
+            "other_tesseract_config": self.other_tesseract_config,
+            "output_type": self.output_type.to_string(),
+        })
+    }
+}
+
+/// Url fetching configuration settings
+///
+/// These settings are used to configure how `extract_url` functions download http(s) urls.
+/// The download is performed on the Rust side and the body is then handed to the parser
+/// together with the response `Content-Type` and file name as detection hints.
+///
+/// With the `serde` feature, the list settings are serialized as `headers`, a list of
+/// `[name, value]` pairs, `root_certificates`, a list of pem file paths, and `bearer_token`
+/// and `basic_auth`, a `[username, password]` pair. The other fields are named after their setters.
+/// The `bearer_token` and `basic_auth` credentials are read from config files but never
+/// serialized, so that they are not written to disk in plain text with the rest of the config.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+pub struct UrlFetchConfig {
+    pub(crate) headers: Vec<(String, String)>,
+    pub(crate) user_agent: String,
+    #[cfg_attr(feature = "serde", serde(skip_serializing))]
+    pub(crate) bearer_token: Option<String>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing))]
+    pub(crate) basic_auth: Option<(String, Option<String>)>,
+    pub(crate) timeout_seconds: u64,
+    pub(crate) connect_timeout_seconds: u64,
+    pub(crate) proxy: Option<String>,
+    pub(crate) max_redirects: usize,
+    pub(crate) accept_invalid_certs: bool,
+    pub(crate) root_certificates: Vec<String>,
+    pub(crate) max_body_bytes: Option<u64>,
+}
+
+impl Default for UrlFetchConfig {
+    fn default() -> Self {
+        Self {
+            headers: Vec::new(),
+            user_agent: format!("extractous/{}", env!("CARGO_PKG_VERSION")),
+            bearer_token: None,
+            basic_auth: None,
+            timeout_seconds: 120,
+            connect_timeout_seconds: 30,
+            proxy: None,
+            max_redirects: 10,
+            accept_invalid_certs: false,
+            root_certificates: Vec::new(),
+            max_body_bytes: None,
+        }
+    }
+}
+
+impl UrlFetchConfig {
+    /// Creates a new instance of UrlFetchConfig with default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a header that is sent with every request. Can be called multiple times.
+    /// Default: no extra headers.
+    pub fn set_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Sets the `User-Agent` header.
+    /// Default: "extractous/{crate version}".
+    pub fn set_user_agent(mut self, val: &str) -> Self {
+        self.user_agent = val.to_string();
+        self
+    }
+
+    /// Sends an `Authorization: Bearer {token}` header with every request. The token is not
+    /// serialized with the `serde` feature.
+    /// Default: None.
+    pub fn set_bearer_auth(mut self, token: &str) -> Self {
+        self.bearer_token = Some(token.to_string());
+        self
+    }
+
+    /// Sends an `Authorization: Basic` header built from the username and optional password.
+    /// The credentials are not serialized with the `serde` feature.
+    /// Default: None.
+    pub fn set_basic_auth(mut self, username: &str, password: Option<&str>) -> Self {
+        self.basic_auth = Some((username.to_string(), password.map(|p| p.to_string())));
+        self
+    }
+
+    /// Sets the total time in seconds allowed for a request, including reading the body.
+    /// Default: 120.
+    pub fn set_timeout_seconds(mut self, val: u64) -> Self {
+        self.timeout_seconds = val;
+        self
+    }
+
+    /// Sets the time in seconds allowed to establish a connection.
+    /// Default: 30.
+    pub fn set_connect_timeout_seconds(mut self, val: u64) -> Self {
+        self.connect_timeout_seconds = val;
+        self
+    }
+
+    /// Routes all requests through the given proxy url, e.g. "http://proxy.local:3128".
+    /// Default: None, the `HTTP_PROXY`/`HTTPS_PROXY` environment variables are honored.
+    pub fn set_proxy(mut self, val: &str) -> Self {
+        self.proxy = Some(val.to_string());
+        self
+    }
+
+    /// Sets the maximum number of redirects to follow. Set to 0 to disable redirects.
+    /// Default: 10.
+    pub fn set_max_redirects(mut self, val: usize) -> Self {
+        self.max_redirects = val;
+        self
+    }
+
+    /// If true, TLS certificates are not verified. Only use this against trusted hosts.
+    /// Default: false.
+    pub fn set_accept_invalid_certs(mut self, val: bool) -> Self {
+        self.accept_invalid_certs = val;
+        self
+    }
+
+    /// Adds a PEM encoded root certificate file that is trusted in addition to the
+    /// built-in roots. Can be called multiple times.
+    /// Default: no extra certificates.
+    pub fn add_root_certificate(mut self, pem_file_path: &str) -> Self {
+        self.root_certificates.push(pem_file_path.to_string());
+        self
+    }
+
+    /// Sets the maximum size in bytes of a response body. Larger responses fail with a
+    /// `FetchError` instead of being loaded into memory.
+    /// Default: None (no limit).
+    pub fn set_max_body_bytes(mut self, val: u64) -> Self {
+        self.max_body_bytes = Some(val);
+        self
+    }
 }
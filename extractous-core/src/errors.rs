@@ -21,6 +21,15 @@ pub enum Error {
 
     #[error("{0}")]
     JniEnvCall(&'static str),
+
+    #[error("{0}")]
+    FetchError(String),
+
+    #[error("HTTP status {0} returned by {1}")]
+    HttpStatusError(u16, String),
+
+    #[error("{0}")]
+    ConfigError(String),
 }
 
 // Implement the conversion from our Error type to io::Error
@@ -41,6 +50,16 @@ impl From<Error> for io::Error {
             Error::JniEnvCall(msg) => {
                 io::Error::new(io::ErrorKind::Other, format!("JNI env call error: {}", msg))
             }
+            Error::FetchError(msg) => {
+                io::Error::new(io::ErrorKind::Other, format!("Fetch error: {}", msg))
+            }
+            Error::HttpStatusError(status, url) => io::Error::new(
+                io::ErrorKind::Other,
+                format!("HTTP status {} returned by {}", status, url),
+            ),
+            Error::ConfigError(msg) => {
+                io::Error::new(io::ErrorKind::Other, format!("Config error: {}", msg))
+            }
             _ => io::Error::new(io::ErrorKind::Other, "Unknown error"),
         }
     }
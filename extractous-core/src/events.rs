@@ -0,0 +1,141 @@
+//! SAX-style events of the extracted documents.
+//!
+//! The `extract_*_events` functions of the [`crate::Extractor`] call a [`ContentHandler`] with
+//! the xhtml elements and text of a document as tika's parsers produce them, without writing the
+//! document to text or xml. Embedded documents, such as the files of an archive or the
+//! attachments of an email, are delimited by [`ContentHandler::embedded_start`] and
+//! [`ContentHandler::embedded_end`]. Every document ends with its [`ContentHandler::metadata`].
+
+use crate::errors::ExtractResult;
+use crate::Metadata;
+
+/// A handler of the SAX-style events of a document. Every method does nothing by default.
+/// Returning an error stops the extraction, which then fails with this error
+pub trait ContentHandler {
+    /// Starts an xhtml element, e.g. `p`, `table` or `a`. The attributes are (name, value) pairs
+    fn start_element(
+        &mut self,
+        _name: &str,
+        _attributes: &[(String, String)],
+    ) -> ExtractResult<()> {
+        Ok(())
+    }
+
+    /// Ends the xhtml element started by [`ContentHandler::start_element`]
+    fn end_element(&mut self, _name: &str) -> ExtractResult<()> {
+        Ok(())
+    }
+
+    /// Text of the current element. The text of an element can be split into several events
+    fn characters(&mut self, _text: &str) -> ExtractResult<()> {
+        Ok(())
+    }
+
+    /// Starts an embedded document. The metadata is the metadata known before parsing it,
+    /// such as its `resourceName`
+    fn embedded_start(&mut self, _metadata: &Metadata) -> ExtractResult<()> {
+        Ok(())
+    }
+
+    /// Ends the embedded document started by [`ContentHandler::embedded_start`]
+    fn embedded_end(&mut self) -> ExtractResult<()> {
+        Ok(())
+    }
+
+    /// The metadata of the current document, sent once it is parsed
+    fn metadata(&mut self, _metadata: &Metadata) -> ExtractResult<()> {
+        Ok(())
+    }
+}
+
+/// A SAX-style event of a document, see [`ContentHandler`]. A `Vec<Event>` is a
+/// [`ContentHandler`] collecting the events
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Event {
+    StartElement {
+        name: String,
+        attributes: Vec<(String, String)>,
+    },
+    EndElement {
+        name: String,
+    },
+    Characters(String),
+    EmbeddedStart(Metadata),
+    EmbeddedEnd,
+    Metadata(Metadata),
+}
+
+impl Event {
+    /// Calls the handler method of this event
+    pub fn dispatch(&self, handler: &mut dyn ContentHandler) -> ExtractResult<()> {
+        match self {
+            Event::StartElement { name, attributes } => handler.start_element(name, attributes),
+            Event::EndElement { name } => handler.end_element(name),
+            Event::Characters(text) => handler.characters(text),
+            Event::EmbeddedStart(metadata) => handler.embedded_start(metadata),
+            Event::EmbeddedEnd => handler.embedded_end(),
+            Event::Metadata(metadata) => handler.metadata(metadata),
+        }
+    }
+}
+
+impl ContentHandler for Vec<Event> {
+    fn start_element(&mut self, name: &str, attributes: &[(String, String)]) -> ExtractResult<()> {
+        self.push(Event::StartElement {
+            name: name.to_string(),
+            attributes: attributes.to_vec(),
+        });
+        Ok(())
+    }
+
+    fn end_element(&mut self, name: &str) -> ExtractResult<()> {
+        self.push(Event::EndElement {
+            name: name.to_string(),
+        });
+        Ok(())
+    }
+
+    fn characters(&mut self, text: &str) -> ExtractResult<()> {
+        self.push(Event::Characters(text.to_string()));
+        Ok(())
+    }
+
+    fn embedded_start(&mut self, metadata: &Metadata) -> ExtractResult<()> {
+        self.push(Event::EmbeddedStart(metadata.clone()));
+        Ok(())
+    }
+
+    fn embedded_end(&mut self) -> ExtractResult<()> {
+        self.push(Event::EmbeddedEnd);
+        Ok(())
+    }
+
+    fn metadata(&mut self, metadata: &Metadata) -> ExtractResult<()> {
+        self.push(Event::Metadata(metadata.clone()));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collect_and_dispatch_test() {
+        let mut events: Vec<Event> = Vec::new();
+        events
+            .start_element("a", &[("href".to_string(), "x".to_string())])
+            .unwrap();
+        events.characters("link").unwrap();
+        events.end_element("a").unwrap();
+        events.metadata(&Metadata::new()).unwrap();
+
+        let mut replayed: Vec<Event> = Vec::new();
+        for event in events.iter() {
+            event.dispatch(&mut replayed).unwrap();
+        }
+        assert_eq!(events, replayed);
+        assert_eq!(events[1], Event::Characters("link".to_string()));
+    }
+}
@@ -1,8 +1,26 @@
-use crate::errors::ExtractResult;
+use crate::annotations::{self, Annotation, AnnotationSource};
+use crate::cache::{self, CacheInput, ExtractionCache};
+use crate::errors::{Error, ExtractResult};
+use crate::events::{ContentHandler, Event};
+use crate::fetch;
+use crate::fingerprint::Fingerprints;
+use crate::headers::HeaderFooterConfig;
+use crate::links::{self, Link, LinkCollector};
+use crate::native::{self, ContentSink, NativeType};
+use crate::normalize::{StreamNormalizer, TextNormalizer};
+use crate::ocr;
+use crate::outline::{self, HeadingCollector, OutlineEntry, OutlineSource};
+use crate::parser::{self, Parser, ParserRegistry};
+use crate::progress::{Progress, ProgressReporter};
+use crate::revisions::{self, DocumentRevisions};
+use crate::stats::{ExtractionStats, StatsRecorder};
 use crate::tika;
 use crate::tika::JReaderInputStream;
-use crate::{OfficeParserConfig, PdfParserConfig, TesseractOcrConfig};
-use std::collections::HashMap;
+use crate::{
+    OcrOutputType, OcrWord, OfficeParserConfig, PdfParserConfig, TesseractOcrConfig, UrlFetchConfig,
+};
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap};
 use strum_macros::{Display, EnumString};
 
 /// Metadata type alias
@@ -10,6 +28,7 @@ pub type Metadata = HashMap<String, Vec<String>>;
 
 /// CharSet enum of all supported encodings
 #[derive(Debug, Clone, Default, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(non_camel_case_types)]
 pub enum CharSet {
     #[default]
@@ -36,15 +55,130 @@ pub enum CharSet {
 /// ```
 ///
 pub struct StreamReader {
-    pub(crate) inner: JReaderInputStream,
+    pub(crate) inner: StreamSource,
+    /// Keeps the registered parsers alive while tika parses the stream
+    _parsers: ParserRegistry,
+    /// Keeps the progress callback alive while tika parses the stream
+    _progress: ProgressReporter,
+    /// Records the stats of a tika parse until the stream is read
+    stats_recorder: Option<StatsRecorder>,
+    stats: Option<ExtractionStats>,
+    /// Normalizes the text of a tika parse while the stream is read
+    normalizer: Option<StreamNormalizer>,
+}
+
+/// The source of the bytes of a [`StreamReader`]
+pub(crate) enum StreamSource {
+    /// The content decoded by tika in the JVM
+    Tika(JReaderInputStream),
+    /// The content extracted by a native parser, already encoded
+    Native(std::io::Cursor<Vec<u8>>),
+}
+
+impl StreamReader {
+    pub(crate) fn tika(
+        reader: JReaderInputStream,
+        parsers: &ParserRegistry,
+        progress: &ProgressReporter,
+    ) -> Self {
+        Self {
+            inner: StreamSource::Tika(reader),
+            _parsers: parsers.clone(),
+            _progress: progress.clone(),
+            stats_recorder: None,
+            stats: None,
+            normalizer: None,
+        }
+    }
+
+    pub(crate) fn native(content: String, encoding: &CharSet) -> Self {
+        Self {
+            inner: StreamSource::Native(std::io::Cursor::new(native::encode(content, encoding))),
+            _parsers: ParserRegistry::default(),
+            _progress: ProgressReporter::default(),
+            stats_recorder: None,
+            stats: None,
+            normalizer: None,
+        }
+    }
+
+    /// The stats of the extraction, when collected with [`Extractor::set_collect_stats`]. The
+    /// stats of the documents parsed by tika are known once the stream is read to its end
+    pub fn stats(&self) -> Option<&ExtractionStats> {
+        self.stats.as_ref()
+    }
+
+    /// Finishes the stats of the tika parse once the stream is read
+    fn finish_stats_when_read(mut self, stats: Option<StatsRecorder>) -> Self {
+        self.stats_recorder = stats;
+        self
+    }
+
+    /// Normalizes the text of the tika parse while the stream is read
+    fn normalize_when_read(mut self, normalizer: &TextNormalizer, encoding: &CharSet) -> Self {
+        if normalizer.is_enabled() {
+            self.normalizer = Some(StreamNormalizer::new(normalizer.clone(), *encoding));
+        }
+        self
+    }
 }
 
 impl std::io::Read for StreamReader {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
-        self.inner.read(buf)
+        let Self {
+            inner,
+            stats_recorder,
+            stats,
+            normalizer,
+            ..
+        } = self;
+        match normalizer {
+            Some(normalizer) => normalizer.read(buf, |chunk| {
+                read_source(inner, stats_recorder, stats, chunk)
+            }),
+            None => read_source(inner, stats_recorder, stats, buf),
+        }
     }
 }
 
+/// Reads the bytes of the source of a [`StreamReader`], finishing the stats once a tika parse
+/// is read
+fn read_source(
+    inner: &mut StreamSource,
+    stats_recorder: &mut Option<StatsRecorder>,
+    stats: &mut Option<ExtractionStats>,
+    buf: &mut [u8],
+) -> std::io::Result<usize> {
+    use std::io::Read;
+    match inner {
+        StreamSource::Tika(reader) => {
+            let read = reader.read(buf)?;
+            if read == 0 && !buf.is_empty() {
+                if let Some(recorder) = stats_recorder.take() {
+                    // The parse finished and added its stats to the java metadata
+                    let mut metadata = reader.read_metadata()?;
+                    *stats = recorder.finish(None, &mut metadata);
+                }
+            }
+            Ok(read)
+        }
+        StreamSource::Native(cursor) => cursor.read(buf),
+    }
+}
+
+/// A document embedded in another one, such as an email attachment, an image of an office
+/// document or a file of an archive. Returned by [`Extractor::unpack_bytes`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EmbeddedDocument {
+    /// Unique file name of the document inside its container
+    pub name: String,
+    /// Metadata of the document
+    pub metadata: Metadata,
+    /// Raw bytes of the document
+    pub data: Vec<u8>,
+}
+
 /// Extractor for extracting text from different file formats
 ///
 /// The Extractor uses the builder pattern to set configurations. This allows configuring and
@@ -58,25 +192,58 @@ impl std::io::Read for StreamReader {
 /// println!("{}", text);
 /// ```
 ///
+/// With the `serde` feature, an extractor can be loaded from a TOML or JSON config file with
+/// [`Extractor::from_config_file`]. Missing fields keep their default values.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
 pub struct Extractor {
     extract_string_max_length: i32,
+    max_pages: i32,
     encoding: CharSet,
     pdf_config: PdfParserConfig,
     office_config: OfficeParserConfig,
     ocr_config: TesseractOcrConfig,
+    url_fetch_config: UrlFetchConfig,
     xml_output: bool,
+    force_tika: bool,
+    collect_stats: bool,
+    compute_fingerprints: bool,
+    text_normalizer: TextNormalizer,
+    remove_headers_and_footers: bool,
+    header_footer_config: HeaderFooterConfig,
+    html_main_content: bool,
+    link_base_url: Option<String>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    parsers: ParserRegistry,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    progress: ProgressReporter,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    cache: Option<ExtractionCache>,
 }
 
 impl Default for Extractor {
     fn default() -> Self {
         Self {
             extract_string_max_length: 500_000, // 500KB
+            max_pages: -1,
             encoding: CharSet::UTF_8,
             pdf_config: PdfParserConfig::default(),
             office_config: OfficeParserConfig::default(),
             ocr_config: TesseractOcrConfig::default(),
+            url_fetch_config: UrlFetchConfig::default(),
             xml_output: false,
+            force_tika: false,
+            collect_stats: false,
+            compute_fingerprints: false,
+            text_normalizer: TextNormalizer::default(),
+            remove_headers_and_footers: false,
+            header_footer_config: HeaderFooterConfig::default(),
+            html_main_content: false,
+            link_base_url: None,
+            parsers: ParserRegistry::default(),
+            progress: ProgressReporter::default(),
+            cache: None,
         }
     }
 }
@@ -86,6 +253,39 @@ impl Extractor {
         Self::default()
     }
 
+    /// Loads an extractor from a TOML (`.toml`) or JSON (`.json`) config file. The fields
+    /// have the names of the setters without the `set_` prefix, e.g.
+    /// ```toml
+    /// extract_string_max_length = 1000
+    /// xml_output = true
+    ///
+    /// [pdf_config]
+    /// ocr_strategy = "OCR_ONLY"
+    ///
+    /// [ocr_config]
+    /// language = "deu"
+    /// ```
+    /// Missing fields keep their default values, unknown fields are rejected.
+    #[cfg(feature = "serde")]
+    pub fn from_config_file<P: AsRef<std::path::Path>>(path: P) -> ExtractResult<Self> {
+        let path = path.as_ref();
+        let config_error =
+            |e: &dyn std::fmt::Display| Error::ConfigError(format!("{}: {}", path.display(), e));
+        let config = std::fs::read_to_string(path).map_err(|e| config_error(&e))?;
+
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default();
+        match extension.to_ascii_lowercase().as_str() {
+            "toml" => toml::from_str(&config).map_err(|e| config_error(&e)),
+            "json" => serde_json::from_str(&config).map_err(|e| config_error(&e)),
+            _ => Err(config_error(
+                &"unsupported config file extension, expected .toml or .json",
+            )),
+        }
+    }
+
     /// Set the maximum length of the extracted text. Used only for extract_to_string functions
     /// Default: 500_000
     pub fn set_extract_string_max_length(mut self, max_length: i32) -> Self {
@@ -93,6 +293,16 @@ impl Extractor {
         self
     }
 
+    /// Set the maximum number of pages of pdf documents or slides of presentations to extract.
+    /// Parsing stops once the last page is extracted. Set to -1 for no limit. Used by the
+    /// extract functions, not by the recursive and unpack functions. For pdf documents, see
+    /// also [`PdfParserConfig::set_page_range`]
+    /// Default: -1
+    pub fn set_max_pages(mut self, max_pages: i32) -> Self {
+        self.max_pages = max_pages;
+        self
+    }
+
     /// Set the encoding to use for when extracting text to a stream.
     /// Not used for extract_to_string functions.
     /// Default: CharSet::UTF_8
@@ -119,96 +329,1047 @@ impl Extractor {
         self
     }
 
+    /// Set the [`TextNormalizer`] cleaning up the extracted text of the string and stream
+    /// outputs, e.g. to join the hyphenated words and expand the ligatures of pdf documents
+    /// Default: no normalization
+    pub fn set_text_normalizer(mut self, normalizer: TextNormalizer) -> Self {
+        self.text_normalizer = normalizer;
+        self
+    }
+
+    /// Set to true to remove the running headers, footers and page numbers of the pdf pages
+    /// from the text extracted to string: the lines repeated at the top or at the bottom of
+    /// most pages, and the page numbers, detected according to the
+    /// [`Extractor::set_header_footer_config`]. The removed lines are reported in the metadata,
+    /// see the [`headers`](crate::headers) module. The streams and the xml output are not
+    /// changed.
+    /// Default: false
+    pub fn set_remove_headers_and_footers(mut self, remove_headers_and_footers: bool) -> Self {
+        self.remove_headers_and_footers = remove_headers_and_footers;
+        self
+    }
+
+    /// Set the configuration of the detection of the headers and footers removed with
+    /// [`Extractor::set_remove_headers_and_footers`]
+    pub fn set_header_footer_config(mut self, config: HeaderFooterConfig) -> Self {
+        self.header_footer_config = config;
+        self
+    }
+
+    /// Set to true to extract only the main content of html documents, without the navigation,
+    /// banners, sidebars, comments and footers, with a readability-style scoring of the text
+    /// blocks. The title, byline and publication date of the article are added to the metadata
+    /// as `dc:title`, `dc:creator` and `dcterms:created` when found. Html documents are then
+    /// parsed natively even with [`Extractor::set_force_tika`], and the bytes without a
+    /// `Content-Type` hint are detected as html from their first tag.
+    /// Default: false
+    pub fn set_html_main_content(mut self, html_main_content: bool) -> Self {
+        self.html_main_content = html_main_content;
+        self
+    }
+
+    /// Set the url the relative links returned by the `extract_*_links` functions are resolved
+    /// against, e.g. `https://intranet.example.com/docs/`. An invalid url fails the extraction
+    /// of the links with a [`Error::ConfigError`].
+    /// Default: None, the urls of [`Extractor::extract_url_links`] are their own base url
+    pub fn set_link_base_url(mut self, base_url: &str) -> Self {
+        self.link_base_url = Some(base_url.to_string());
+        self
+    }
+
+    /// Set the configuration used to download http(s) urls in the `extract_url` functions
+    pub fn set_url_fetch_config(mut self, config: UrlFetchConfig) -> Self {
+        self.url_fetch_config = config;
+        self
+    }
+
     /// Set the configuration for the parse as xml
     pub fn set_xml_output(mut self, xml_output: bool) -> Self {
         self.xml_output = xml_output;
         self
     }
 
+    /// Set to true to parse all the documents with tika, e.g. to compare the results of the
+    /// native parsers with tika. Otherwise plain text, markdown, csv, tsv, json, xml and html
+    /// documents are parsed natively in Rust, without going through the JVM. Their type is
+    /// detected from the file extension, or from the `resourceName` or `Content-Type` metadata
+    /// hints and the http response headers of urls.
+    /// Default: false
+    pub fn set_force_tika(mut self, force_tika: bool) -> Self {
+        self.force_tika = force_tika;
+        self
+    }
+
+    /// Registers a custom parser, which wins over tika and the native parsers for its
+    /// [`Parser::supported_types`], including for the documents embedded in the documents parsed
+    /// by tika. The last registered parser wins for the types supported by several parsers.
+    ///
+    /// The type of files is detected by tika, or from the file extension for the natively
+    /// parsed types. Give the `Content-Type` hint of bytes of a custom type with
+    /// [`Extractor::extract_bytes_to_string_with_hints`], otherwise tika detects their type.
+    pub fn register_parser<P: Parser + 'static>(mut self, parser: P) -> Self {
+        self.parsers = self.parsers.register(std::sync::Arc::new(parser));
+        self
+    }
+
+    /// Sets a callback called with the [`Progress`] of the extractions: the start and end of
+    /// the documents, the pages and slides parsed, the embedded documents entered, the OCR of
+    /// every page and the bytes of output produced. The progress is reported from inside the
+    /// tika parse, on the thread parsing the document, which is a background thread for the
+    /// stream outputs. Documents parsed natively are reported once parsed. The callback is not
+    /// called by the recursive and unpack functions
+    pub fn set_progress_callback<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&Progress) + Send + Sync + 'static,
+    {
+        self.progress = ProgressReporter::new(callback);
+        self
+    }
+
+    /// Set to true to add the [`ExtractionStats`] of every extraction to its metadata: the time
+    /// spent detecting, parsing, OCRing and copying the results from the JVM, the bytes in and
+    /// characters out, the embedded documents, the JVM heap used and whether an OCR ran. Read
+    /// them with [`ExtractionStats::from_metadata`], or with [`StreamReader::stats`] for the
+    /// streams. The recursive and unpack functions do not collect stats.
+    /// Default: false
+    pub fn set_collect_stats(mut self, collect_stats: bool) -> Self {
+        self.collect_stats = collect_stats;
+        self
+    }
+
+    /// Set to true to add the [`Fingerprints`] of every extraction to string to its metadata:
+    /// the SHA-256 of the document bytes, the SHA-256 of the normalized extracted text and the
+    /// SimHash of the text. Read them with [`Fingerprints::from_metadata`] and compare them with
+    /// [`Fingerprints::is_duplicate`] and [`Fingerprints::similarity`]. The fingerprints of the
+    /// text are computed on the returned string, truncated to `extract_string_max_length`.
+    /// Default: false
+    pub fn set_compute_fingerprints(mut self, compute_fingerprints: bool) -> Self {
+        self.compute_fingerprints = compute_fingerprints;
+        self
+    }
+
+    /// Set the [`ExtractionCache`] checked by the `extract_*_to_string` functions before parsing
+    /// a document. The cached extractions are keyed by the hash of the document bytes and of
+    /// the configuration of the extractor, so that a document is parsed again once it or the
+    /// configuration changes. The cache is not used by an extractor with registered parsers,
+    /// and the extractions read from the cache have no [`ExtractionStats`].
+    /// Default: None
+    pub fn set_cache(mut self, cache: ExtractionCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Checks that the extractor can OCR documents: the PDF and OCR configs are valid, tesseract
+    /// can be run and the languages of [`TesseractOcrConfig::set_language`] are installed.
+    /// Returns a [`Error::ConfigError`] otherwise, see [`ocr::diagnose_config`] for the details
+    /// of the installation. Unlike the config validation done before every extraction, this check
+    /// runs tesseract and is meant to be called once, e.g. when an application starts.
+    pub fn validate(&self) -> ExtractResult<()> {
+        self.pdf_config.validate()?;
+        self.ocr_config.validate()?;
+
+        let diagnostics = ocr::diagnose_config(&self.ocr_config);
+        if !diagnostics.tesseract_found || diagnostics.languages.is_empty() {
+            return Err(Error::ConfigError(diagnostics.error.unwrap_or_else(|| {
+                "Tesseract has no installed languages".to_string()
+            })));
+        }
+        let missing = diagnostics.missing_languages(&self.ocr_config.language);
+        if !missing.is_empty() {
+            return Err(Error::ConfigError(format!(
+                "Tesseract language {} is not installed, installed languages: {}",
+                missing.join("+"),
+                diagnostics.languages.join(", ")
+            )));
+        }
+        Ok(())
+    }
+
     /// Extracts text from a file path. Returns a tuple with stream of the extracted text and metadata.
     /// the stream is decoded using the extractor's `encoding`
     pub fn extract_file(&self, file_path: &str) -> ExtractResult<(StreamReader, Metadata)> {
-        tika::parse_file(
-            file_path,
-            &self.encoding,
-            &self.pdf_config,
-            &self.office_config,
-            &self.ocr_config,
-            self.xml_output,
-        )
+        let stats = self.start_stats(file_size(file_path));
+        if let Some((content, metadata)) = self.parse_file_natively(file_path)? {
+            return Ok(self.native_stream(stats, content, metadata));
+        }
+        let (reader, metadata) =
+            tika::parse_file(file_path, &self.encoding, &self.parse_options())?;
+        Ok((
+            reader
+                .finish_stats_when_read(stats)
+                .normalize_when_read(&self.text_normalizer, &self.encoding),
+            metadata,
+        ))
     }
 
     /// Extracts text from a byte buffer. Returns a tuple with stream of the extracted text and metadata.
     /// the stream is decoded using the extractor's `encoding`
     pub fn extract_bytes(&self, buffer: &[u8]) -> ExtractResult<(StreamReader, Metadata)> {
-        tika::parse_bytes(
+        let stats = self.start_stats(Some(buffer.len() as u64));
+        if let Some((content, metadata)) = self.parse_bytes_natively(buffer, &Metadata::new())? {
+            return Ok(self.native_stream(stats, content, metadata));
+        }
+        let (reader, metadata) = tika::parse_bytes(
             buffer,
+            &Metadata::new(),
             &self.encoding,
-            &self.pdf_config,
-            &self.office_config,
-            &self.ocr_config,
-            self.xml_output,
-        )
+            &self.parse_options(),
+        )?;
+        Ok((
+            reader
+                .finish_stats_when_read(stats)
+                .normalize_when_read(&self.text_normalizer, &self.encoding),
+            metadata,
+        ))
     }
 
     /// Extracts text from an url. Returns a tuple with stream of the extracted text and metadata.
     /// the stream is decoded using the extractor's `encoding`
+    ///
+    /// http(s) urls are downloaded according to the extractor's [`UrlFetchConfig`]. The
+    /// response status, final url and `Content-Type`/`Content-Disposition` headers are added
+    /// to the metadata as `http-header:status-code`, `http-connection:target-url`,
+    /// `http-header:content-type` and `http-header:content-disposition`.
     pub fn extract_url(&self, url: &str) -> ExtractResult<(StreamReader, Metadata)> {
-        tika::parse_url(
-            url,
-            &self.encoding,
-            &self.pdf_config,
-            &self.office_config,
-            &self.ocr_config,
-            self.xml_output,
-        )
+        let mut stats = self.start_stats(None);
+        if fetch::is_http_url(url) {
+            let response = fetch::fetch_url(url, &self.url_fetch_config)?;
+            set_bytes_in(&mut stats, response.body.len());
+            if let Some((content, metadata)) =
+                self.parse_bytes_natively(&response.body, &response.metadata)?
+            {
+                return Ok(self.native_stream(stats, content, metadata));
+            }
+            let (reader, metadata) = tika::parse_owned_bytes(
+                response.body,
+                &response.metadata,
+                &self.encoding,
+                &self.parse_options(),
+            )?;
+            return Ok((
+                reader
+                    .finish_stats_when_read(stats)
+                    .normalize_when_read(&self.text_normalizer, &self.encoding),
+                metadata,
+            ));
+        }
+
+        let (reader, metadata) = tika::parse_url(url, &self.encoding, &self.parse_options())?;
+        Ok((
+            reader
+                .finish_stats_when_read(stats)
+                .normalize_when_read(&self.text_normalizer, &self.encoding),
+            metadata,
+        ))
     }
 
     /// Extracts text from a file path. Returns a tuple with string that is of maximum length
     /// of the extractor's `extract_string_max_length` and metadata.
     pub fn extract_file_to_string(&self, file_path: &str) -> ExtractResult<(String, Metadata)> {
-        tika::parse_file_to_string(
-            file_path,
-            self.extract_string_max_length,
-            &self.pdf_config,
-            &self.office_config,
-            &self.ocr_config,
-            self.xml_output,
-        )
+        self.cached(CacheInput::File(file_path), &Metadata::new(), || {
+            let stats = self.start_stats(file_size(file_path));
+            if let Some((content, metadata)) = self.parse_file_natively(file_path)? {
+                return Ok(self.finish_string(stats, (self.truncate(content), metadata)));
+            }
+            let result = tika::parse_file_to_string(
+                file_path,
+                self.extract_string_max_length,
+                &self.string_parse_options(),
+            )?;
+            Ok(self.finish_string(stats, self.remove_headers_and_footers(result)))
+        })
     }
 
     /// Extracts text from a byte buffer. Returns a tuple with string that is of maximum length
     /// of the extractor's `extract_string_max_length` and metadata.
     pub fn extract_bytes_to_string(&self, buffer: &[u8]) -> ExtractResult<(String, Metadata)> {
-        tika::parse_bytes_to_string(
+        self.cached(CacheInput::Bytes(buffer), &Metadata::new(), || {
+            let stats = self.start_stats(Some(buffer.len() as u64));
+            if let Some((content, metadata)) =
+                self.parse_bytes_natively(buffer, &Metadata::new())?
+            {
+                return Ok(self.finish_string(stats, (self.truncate(content), metadata)));
+            }
+            let result = tika::parse_bytes_to_string(
+                buffer,
+                &Metadata::new(),
+                self.extract_string_max_length,
+                &self.string_parse_options(),
+            )?;
+            Ok(self.finish_string(stats, self.remove_headers_and_footers(result)))
+        })
+    }
+
+    /// Same as [`Extractor::extract_bytes_to_string`], but with metadata hints such as the
+    /// `resourceName` or `Content-Type` of the bytes, which are used to detect the document type
+    pub fn extract_bytes_to_string_with_hints(
+        &self,
+        buffer: &[u8],
+        metadata_hints: &Metadata,
+    ) -> ExtractResult<(String, Metadata)> {
+        self.cached(CacheInput::Bytes(buffer), metadata_hints, || {
+            let stats = self.start_stats(Some(buffer.len() as u64));
+            if let Some((content, metadata)) = self.parse_bytes_natively(buffer, metadata_hints)? {
+                return Ok(self.finish_string(stats, (self.truncate(content), metadata)));
+            }
+            let result = tika::parse_bytes_to_string(
+                buffer,
+                metadata_hints,
+                self.extract_string_max_length,
+                &self.string_parse_options(),
+            )?;
+            Ok(self.finish_string(stats, self.remove_headers_and_footers(result)))
+        })
+    }
+
+    /// Extracts text from a file path with the words recognized by the OCR. Returns a tuple with
+    /// the text, the [`OcrWord`]s with their bounding box and confidence, and metadata.
+    ///
+    /// The OCR runs with [`OcrOutputType::HOCR`] whatever the extractor's ocr config output
+    /// type, on images and on pdf pages OCRed according to the pdf config `ocr_strategy`.
+    /// The text of the OCRed images is rebuilt from the words, one line per OCRed line.
+    /// The xml output the words are read from is of maximum length of the extractor's
+    /// `extract_string_max_length`.
+    pub fn extract_file_to_ocr_words(
+        &self,
+        file_path: &str,
+    ) -> ExtractResult<(String, Vec<OcrWord>, Metadata)> {
+        let stats = self.start_stats(file_size(file_path));
+        let hocr_config = self.hocr_config();
+        let (xhtml, metadata) = tika::parse_file_to_string(
+            file_path,
+            self.extract_string_max_length,
+            &tika::ParseOptions {
+                ocr_conf: &hocr_config,
+                as_xml: true,
+                ..self.parse_options()
+            },
+        )?;
+        self.read_ocr_words(stats, xhtml, metadata)
+    }
+
+    /// Same as [`Extractor::extract_file_to_ocr_words`] for a byte buffer
+    pub fn extract_bytes_to_ocr_words(
+        &self,
+        buffer: &[u8],
+    ) -> ExtractResult<(String, Vec<OcrWord>, Metadata)> {
+        let stats = self.start_stats(Some(buffer.len() as u64));
+        let hocr_config = self.hocr_config();
+        let (xhtml, metadata) = tika::parse_bytes_to_string(
             buffer,
+            &Metadata::new(),
             self.extract_string_max_length,
-            &self.pdf_config,
-            &self.office_config,
-            &self.ocr_config,
-            self.xml_output,
+            &tika::ParseOptions {
+                ocr_conf: &hocr_config,
+                as_xml: true,
+                ..self.parse_options()
+            },
+        )?;
+        self.read_ocr_words(stats, xhtml, metadata)
+    }
+
+    /// Parses the file with a registered parser or the native parsers if its type, detected
+    /// from the extension, is parsed in Rust. Returns None to parse it with tika
+    fn parse_file_natively(&self, file_path: &str) -> ExtractResult<Option<(String, Metadata)>> {
+        let sink = ContentSink::new(self.xml_output);
+        let parsed = self.parse_file_to_sink(file_path, sink)?;
+        Ok(parsed.map(|(sink, metadata)| self.finish_natively(sink, metadata)))
+    }
+
+    /// Parses the bytes with a registered parser or the native parsers if the metadata hints
+    /// tell a type parsed in Rust. Returns None to parse them with tika
+    fn parse_bytes_natively(
+        &self,
+        buffer: &[u8],
+        metadata_hints: &Metadata,
+    ) -> ExtractResult<Option<(String, Metadata)>> {
+        let sink = ContentSink::new(self.xml_output);
+        let parsed = self.parse_bytes_to_sink(buffer, metadata_hints, sink)?;
+        Ok(parsed.map(|(sink, metadata)| self.finish_natively(sink, metadata)))
+    }
+
+    /// Returns the content of a document parsed in Rust and reports its progress
+    fn finish_natively(&self, sink: ContentSink, metadata: Metadata) -> (String, Metadata) {
+        let content = sink.finish(&metadata);
+        self.progress
+            .report_parsed(content_type(&metadata), content.len() as u64);
+        (content, metadata)
+    }
+
+    /// Sends the events recorded by a sink and the metadata of the document to the handler and
+    /// reports its progress
+    fn send_events(
+        &self,
+        stats: Option<StatsRecorder>,
+        sink: ContentSink,
+        mut metadata: Metadata,
+        handler: &mut dyn ContentHandler,
+    ) -> ExtractResult<Metadata> {
+        let mut output_bytes = 0;
+        let mut output_chars = 0;
+        for event in sink.finish_events(&metadata) {
+            if let Event::Characters(text) = &event {
+                output_bytes += text.len() as u64;
+                output_chars += text.chars().count() as u64;
+            }
+            event.dispatch(handler)?;
+        }
+        finish_stats(stats, Some(output_chars), &mut metadata);
+        handler.metadata(&metadata)?;
+        self.progress
+            .report_parsed(content_type(&metadata), output_bytes);
+        Ok(metadata)
+    }
+
+    /// Same as [`Extractor::parse_file_natively`], into the given sink
+    fn parse_file_to_sink(
+        &self,
+        file_path: &str,
+        sink: ContentSink,
+    ) -> ExtractResult<Option<(ContentSink, Metadata)>> {
+        let native_type = match NativeType::detect_file(file_path) {
+            Some(native_type) => native_type,
+            None => return Ok(None),
+        };
+        let registered = self.parsers.find(native_type.mime_type());
+        if registered.is_none() && self.force_tika && !self.main_content_of(native_type) {
+            return Ok(None);
+        }
+        let buffer = std::fs::read(file_path)
+            .map_err(|e| Error::IoError(format!("Could not read {}: {}", file_path, e)))?;
+
+        // The metadata tika adds for files
+        let file_name = std::path::Path::new(file_path)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let metadata_hints = Metadata::from([
+            (native::RESOURCE_NAME_KEY.to_string(), vec![file_name]),
+            (
+                native::CONTENT_LENGTH_KEY.to_string(),
+                vec![buffer.len().to_string()],
+            ),
+        ]);
+        match registered {
+            Some(parser) => parser::parse(
+                parser.as_ref(),
+                &buffer,
+                native_type.mime_type(),
+                &metadata_hints,
+                sink,
+            )
+            .map(Some),
+            None => native::parse(
+                &buffer,
+                native_type,
+                &metadata_hints,
+                self.html_main_content,
+                sink,
+            ),
+        }
+    }
+
+    /// Same as [`Extractor::parse_bytes_natively`], into the given sink
+    fn parse_bytes_to_sink(
+        &self,
+        buffer: &[u8],
+        metadata_hints: &Metadata,
+        sink: ContentSink,
+    ) -> ExtractResult<Option<(ContentSink, Metadata)>> {
+        // In main content mode, the bytes of an unknown type are sniffed for html
+        let sniff =
+            self.html_main_content && !metadata_hints.contains_key(native::CONTENT_TYPE_KEY);
+        let native_type = NativeType::detect_hints(metadata_hints)
+            .or_else(|| (sniff && NativeType::sniff_html(buffer)).then_some(NativeType::Html));
+        let content_type = metadata_hints
+            .get(native::CONTENT_TYPE_KEY)
+            .and_then(|values| values.first())
+            .map(|content_type| content_type.as_str());
+        let registered = content_type
+            .into_iter()
+            .chain(native_type.map(|native_type| native_type.mime_type()))
+            .find_map(|mime_type| Some((self.parsers.find(mime_type)?, mime_type)));
+        if let Some((parser, mime_type)) = registered {
+            return parser::parse(parser.as_ref(), buffer, mime_type, metadata_hints, sink)
+                .map(Some);
+        }
+
+        match native_type {
+            Some(native_type) if !self.force_tika || self.main_content_of(native_type) => {
+                native::parse(
+                    buffer,
+                    native_type,
+                    metadata_hints,
+                    self.html_main_content,
+                    sink,
+                )
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Whether only the main content of the documents of this type is extracted, natively
+    fn main_content_of(&self, native_type: NativeType) -> bool {
+        self.html_main_content && native_type == NativeType::Html
+    }
+
+    /// Truncates natively extracted content to the `extract_string_max_length`, like tika
+    fn truncate(&self, content: String) -> String {
+        match usize::try_from(self.extract_string_max_length) {
+            Ok(max_length) if content.chars().count() > max_length => {
+                content.chars().take(max_length).collect()
+            }
+            _ => content,
+        }
+    }
+
+    /// Whether the stats of the extractions are collected, to be returned or recorded as metrics
+    fn collects_stats(&self) -> bool {
+        self.collect_stats || cfg!(feature = "metrics")
+    }
+
+    /// The progress reporter of an extraction, which tells tika whether to collect the stats
+    fn reporter(&self) -> ProgressReporter {
+        self.progress.with_stats(self.collects_stats())
+    }
+
+    /// The options of the tika parses to a stream or to events, which do not mark the page breaks
+    fn parse_options(&self) -> tika::ParseOptions<'_> {
+        tika::ParseOptions {
+            pdf_conf: &self.pdf_config,
+            office_conf: &self.office_config,
+            ocr_conf: &self.ocr_config,
+            max_pages: self.max_pages,
+            page_breaks: false,
+            as_xml: self.xml_output,
+            parsers: &self.parsers,
+            progress: self.reporter(),
+        }
+    }
+
+    /// The options of the tika parses to string, which mark the page breaks for the removal of
+    /// the headers and footers
+    fn string_parse_options(&self) -> tika::ParseOptions<'_> {
+        tika::ParseOptions {
+            page_breaks: self.marks_page_breaks(),
+            ..self.parse_options()
+        }
+    }
+
+    /// Starts recording the stats of an extraction of a document of the given size, if known
+    fn start_stats(&self, bytes_in: Option<u64>) -> Option<StatsRecorder> {
+        self.collects_stats()
+            .then(|| StatsRecorder::start(bytes_in, self.collect_stats))
+    }
+
+    /// Returns the stream of a document parsed in Rust, with its stats
+    fn native_stream(
+        &self,
+        stats: Option<StatsRecorder>,
+        content: String,
+        mut metadata: Metadata,
+    ) -> (StreamReader, Metadata) {
+        let content = self.normalize(content);
+        let chars_out = content.chars().count() as u64;
+        let mut reader = StreamReader::native(content, &self.encoding);
+        reader.stats = stats.and_then(|stats| stats.finish(Some(chars_out), &mut metadata));
+        (reader, metadata)
+    }
+
+    fn normalize(&self, content: String) -> String {
+        match self.text_normalizer.is_enabled() {
+            true => self.text_normalizer.normalize(&content),
+            false => content,
+        }
+    }
+
+    /// Whether tika marks the end of the pdf pages, for the removal of the headers and footers
+    fn marks_page_breaks(&self) -> bool {
+        self.remove_headers_and_footers && !self.xml_output
+    }
+
+    /// Removes the headers and footers of the pages marked by tika
+    fn remove_headers_and_footers(
+        &self,
+        (content, mut metadata): (String, Metadata),
+    ) -> (String, Metadata) {
+        match self.marks_page_breaks() {
+            true => {
+                let content = self.header_footer_config.remove(&content, &mut metadata);
+                (content, metadata)
+            }
+            false => (content, metadata),
+        }
+    }
+
+    /// Normalizes the extracted text and finishes the stats of the extraction to string
+    fn finish_string(
+        &self,
+        stats: Option<StatsRecorder>,
+        (content, metadata): (String, Metadata),
+    ) -> (String, Metadata) {
+        finish_string_stats(stats, (self.normalize(content), metadata))
+    }
+
+    /// Reads the extraction of the input from the cache, or extracts and caches it, then adds
+    /// the fingerprints of the input. The input is extracted without the cache when it is not
+    /// set or when parsers are registered
+    fn cached<F>(
+        &self,
+        input: CacheInput,
+        metadata_hints: &Metadata,
+        extract: F,
+    ) -> ExtractResult<(String, Metadata)>
+    where
+        F: FnOnce() -> ExtractResult<(String, Metadata)>,
+    {
+        let cache = self
+            .cache
+            .as_ref()
+            .filter(|_| self.parsers.parsers().is_empty());
+        if cache.is_none() && !self.compute_fingerprints {
+            return extract();
+        }
+        let input_sha256 = input.sha256();
+
+        let result = match cache.zip(input_sha256.as_deref()) {
+            Some((cache, input_sha256)) => {
+                let key = cache.key(input_sha256, &self.cache_config(metadata_hints));
+                match cache.get(&key) {
+                    Some((content, mut metadata)) => {
+                        // The volatile metadata of the url responses are not part of the key
+                        for name in fetch::VOLATILE_METADATA {
+                            if let Some(values) = metadata_hints.get(name) {
+                                metadata.insert(name.to_string(), values.clone());
+                            }
+                        }
+                        (content, metadata)
+                    }
+                    None => {
+                        let (content, metadata) = extract()?;
+                        cache.put(&key, &content, &metadata);
+                        (content, metadata)
+                    }
+                }
+            }
+            None => extract()?,
+        };
+        Ok(self.fingerprint(input_sha256, result))
+    }
+
+    /// Adds the fingerprints of the extracted text to the metadata, when they are computed
+    fn fingerprint(
+        &self,
+        input_sha256: Option<String>,
+        (content, mut metadata): (String, Metadata),
+    ) -> (String, Metadata) {
+        if self.compute_fingerprints {
+            Fingerprints::new(input_sha256, &content).write(&mut metadata);
+        }
+        (content, metadata)
+    }
+
+    /// The configuration of the extractions to string, hashed in the cache keys. The settings
+    /// are listed by name in json, whose objects have sorted keys. The volatile metadata of the
+    /// url responses are left out, so that a downloaded body is read from the cache
+    fn cache_config(&self, metadata_hints: &Metadata) -> String {
+        let metadata_hints: BTreeMap<_, _> = metadata_hints
+            .iter()
+            .filter(|(key, _)| !fetch::VOLATILE_METADATA.contains(&key.as_str()))
+            .collect();
+        json!({
+            "version": cache::KEY_VERSION,
+            "pdf_config": self.pdf_config.cache_key(),
+            "office_config": self.office_config.cache_key(),
+            "ocr_config": self.ocr_config.cache_key(),
+            "xml_output": self.xml_output,
+            "extract_string_max_length": self.extract_string_max_length,
+            "max_pages": self.max_pages,
+            "force_tika": self.force_tika,
+            "text_normalizer": self.text_normalizer.cache_key(),
+            "remove_headers_and_footers": self.marks_page_breaks(),
+            "header_footer_config": self.header_footer_config.cache_key(),
+            "html_main_content": self.html_main_content,
+            "metadata_hints": metadata_hints,
+        })
+        .to_string()
+    }
+
+    fn hocr_config(&self) -> TesseractOcrConfig {
+        self.ocr_config.clone().set_output_type(OcrOutputType::HOCR)
+    }
+
+    fn read_ocr_words(
+        &self,
+        stats: Option<StatsRecorder>,
+        xhtml: String,
+        metadata: Metadata,
+    ) -> ExtractResult<(String, Vec<OcrWord>, Metadata)> {
+        let truncated = xhtml.chars().count() >= self.extract_string_max_length as usize;
+        let (text, words) = ocr::read_hocr_xhtml(&xhtml, truncated)?;
+        let (text, metadata) = finish_string_stats(stats, (text, metadata));
+        Ok((text, words, metadata))
+    }
+
+    /// Extracts text from a byte buffer and from all the documents embedded in it, such as
+    /// email attachments or the files of an archive. Returns the metadata of every document,
+    /// the container first, with its content under the `X-TIKA:content` key. The content of
+    /// each document is of maximum length of the extractor's `extract_string_max_length`.
+    pub fn extract_bytes_recursive(
+        &self,
+        buffer: &[u8],
+        metadata_hints: &Metadata,
+    ) -> ExtractResult<Vec<Metadata>> {
+        tika::parse_bytes_recursive(
+            buffer,
+            metadata_hints,
+            self.extract_string_max_length,
+            &self.parse_options(),
+        )
+    }
+
+    /// Extracts text from a byte buffer and the raw bytes of the documents embedded in it.
+    /// Returns a tuple with the text of the container, of maximum length of the extractor's
+    /// `extract_string_max_length`, its metadata and the embedded documents.
+    pub fn unpack_bytes(
+        &self,
+        buffer: &[u8],
+        metadata_hints: &Metadata,
+    ) -> ExtractResult<(String, Metadata, Vec<EmbeddedDocument>)> {
+        tika::unpack_bytes(
+            buffer,
+            metadata_hints,
+            self.extract_string_max_length,
+            &self.parse_options(),
         )
     }
 
     /// Extracts text from a URL. Returns a tuple with string that is of maximum length
     /// of the extractor's `extract_string_max_length` and metadata.
+    ///
+    /// http(s) urls are downloaded according to the extractor's [`UrlFetchConfig`], see
+    /// [`Extractor::extract_url`] for the added metadata.
     pub fn extract_url_to_string(&self, url: &str) -> ExtractResult<(String, Metadata)> {
-        tika::parse_url_to_string(
+        let mut stats = self.start_stats(None);
+        if fetch::is_http_url(url) {
+            let response = fetch::fetch_url(url, &self.url_fetch_config)?;
+            set_bytes_in(&mut stats, response.body.len());
+            // The downloaded body is cached, the url is parsed again once its body changes
+            return self.cached(
+                CacheInput::Bytes(&response.body),
+                &response.metadata,
+                || {
+                    if let Some((content, metadata)) =
+                        self.parse_bytes_natively(&response.body, &response.metadata)?
+                    {
+                        return Ok(self.finish_string(stats, (self.truncate(content), metadata)));
+                    }
+                    let result = tika::parse_bytes_to_string(
+                        &response.body,
+                        &response.metadata,
+                        self.extract_string_max_length,
+                        &self.string_parse_options(),
+                    )?;
+                    Ok(self.finish_string(stats, self.remove_headers_and_footers(result)))
+                },
+            );
+        }
+
+        let result = tika::parse_url_to_string(
             url,
             self.extract_string_max_length,
-            &self.pdf_config,
-            &self.office_config,
-            &self.ocr_config,
-            self.xml_output,
-        )
+            &self.string_parse_options(),
+        )?;
+        // The urls read by tika have no input hash
+        Ok(self.fingerprint(
+            None,
+            self.finish_string(stats, self.remove_headers_and_footers(result)),
+        ))
+    }
+
+    /// Extracts a file as SAX-style events sent to the handler: the elements and text of the
+    /// xhtml document of the xml output, without writing it, with the embedded documents
+    /// delimited by [`ContentHandler::embedded_start`] and [`ContentHandler::embedded_end`].
+    /// Returns the metadata of the file, which is also the last event.
+    pub fn extract_file_events(
+        &self,
+        file_path: &str,
+        handler: &mut dyn ContentHandler,
+    ) -> ExtractResult<Metadata> {
+        let stats = self.start_stats(file_size(file_path));
+        if let Some((sink, metadata)) = self.parse_file_to_sink(file_path, ContentSink::events())? {
+            return self.send_events(stats, sink, metadata, handler);
+        }
+        let mut metadata = tika::parse_file_to_events(file_path, &self.parse_options(), handler)?;
+        finish_stats(stats, None, &mut metadata);
+        handler.metadata(&metadata)?;
+        Ok(metadata)
+    }
+
+    /// Same as [`Extractor::extract_file_events`] for a byte buffer, with metadata hints such
+    /// as the `resourceName` or `Content-Type` of the bytes
+    pub fn extract_bytes_events(
+        &self,
+        buffer: &[u8],
+        metadata_hints: &Metadata,
+        handler: &mut dyn ContentHandler,
+    ) -> ExtractResult<Metadata> {
+        let stats = self.start_stats(Some(buffer.len() as u64));
+        self.bytes_events(stats, buffer, metadata_hints, handler)
+    }
+
+    /// Same as [`Extractor::extract_bytes_events`], recording the stats
+    fn bytes_events(
+        &self,
+        stats: Option<StatsRecorder>,
+        buffer: &[u8],
+        metadata_hints: &Metadata,
+        handler: &mut dyn ContentHandler,
+    ) -> ExtractResult<Metadata> {
+        let sink = ContentSink::events();
+        if let Some((sink, metadata)) = self.parse_bytes_to_sink(buffer, metadata_hints, sink)? {
+            return self.send_events(stats, sink, metadata, handler);
+        }
+        let mut metadata =
+            tika::parse_bytes_to_events(buffer, metadata_hints, &self.parse_options(), handler)?;
+        finish_stats(stats, None, &mut metadata);
+        handler.metadata(&metadata)?;
+        Ok(metadata)
+    }
+
+    /// Same as [`Extractor::extract_file_events`] for an url. http(s) urls are downloaded
+    /// according to the extractor's [`UrlFetchConfig`]
+    pub fn extract_url_events(
+        &self,
+        url: &str,
+        handler: &mut dyn ContentHandler,
+    ) -> ExtractResult<Metadata> {
+        let mut stats = self.start_stats(None);
+        if fetch::is_http_url(url) {
+            let response = fetch::fetch_url(url, &self.url_fetch_config)?;
+            set_bytes_in(&mut stats, response.body.len());
+            return self.bytes_events(stats, &response.body, &response.metadata, handler);
+        }
+
+        let mut metadata = tika::parse_url_to_events(url, &self.parse_options(), handler)?;
+        finish_stats(stats, None, &mut metadata);
+        handler.metadata(&metadata)?;
+        Ok(metadata)
+    }
+
+    /// Extracts the hyperlinks of a file: the `<a href>` elements of its xhtml document, such as
+    /// the links of html pages and emails, the link annotations of pdfs and the hyperlinks of
+    /// office documents, including the ones of its embedded documents. Returns a tuple with the
+    /// [`Link`]s in document order and the metadata. The relative links are resolved against
+    /// the base url of [`Extractor::set_link_base_url`], if any
+    pub fn extract_file_links(&self, file_path: &str) -> ExtractResult<(Vec<Link>, Metadata)> {
+        let mut collector =
+            LinkCollector::new(links::base_url(self.link_base_url.as_deref(), None)?);
+        let metadata = self.extract_file_events(file_path, &mut collector)?;
+        Ok((collector.into_links(), metadata))
     }
 
+    /// Same as [`Extractor::extract_file_links`] for a byte buffer
+    pub fn extract_bytes_links(&self, buffer: &[u8]) -> ExtractResult<(Vec<Link>, Metadata)> {
+        let mut collector =
+            LinkCollector::new(links::base_url(self.link_base_url.as_deref(), None)?);
+        let metadata = self.extract_bytes_events(buffer, &Metadata::new(), &mut collector)?;
+        Ok((collector.into_links(), metadata))
+    }
+
+    /// Same as [`Extractor::extract_file_links`] for an url. Without
+    /// [`Extractor::set_link_base_url`], the relative links are resolved against the url
+    pub fn extract_url_links(&self, url: &str) -> ExtractResult<(Vec<Link>, Metadata)> {
+        let mut collector =
+            LinkCollector::new(links::base_url(self.link_base_url.as_deref(), Some(url))?);
+        let metadata = self.extract_url_events(url, &mut collector)?;
+        Ok((collector.into_links(), metadata))
+    }
+
+    /// Extracts the outline of a file as a tree of [`OutlineEntry`]: the bookmarks of pdfs with
+    /// the page they point to, the navigation document of epubs, or else the headings of the
+    /// document, such as the heading styles of docx and odt documents or the `h1` to `h6` of
+    /// html pages. The pdf bookmarks and epub navigation documents are read without extracting
+    /// the text of the document
+    pub fn extract_file_outline(&self, file_path: &str) -> ExtractResult<Vec<OutlineEntry>> {
+        use std::io::Read;
+        let read_error =
+            |e: std::io::Error| Error::IoError(format!("Could not read {}: {}", file_path, e));
+        let mut head = Vec::new();
+        std::fs::File::open(file_path)
+            .and_then(|file| file.take(1024).read_to_end(&mut head))
+            .map_err(read_error)?;
+        match OutlineSource::sniff(&head) {
+            OutlineSource::Pdf => {
+                let lines = tika::parse_file_to_pdf_outline(file_path)?;
+                return Ok(outline::parse_pdf_outline(&lines));
+            }
+            OutlineSource::Epub => {
+                let file = std::fs::File::open(file_path).map_err(read_error)?;
+                if let Some(outline) = outline::read_epub_outline(std::io::BufReader::new(file))? {
+                    return Ok(outline);
+                }
+            }
+            OutlineSource::Headings => {}
+        }
+        let mut collector = HeadingCollector::default();
+        self.extract_file_events(file_path, &mut collector)?;
+        Ok(collector.into_outline())
+    }
+
+    /// Same as [`Extractor::extract_file_outline`] for a byte buffer
+    pub fn extract_bytes_outline(&self, buffer: &[u8]) -> ExtractResult<Vec<OutlineEntry>> {
+        match OutlineSource::sniff(buffer) {
+            OutlineSource::Pdf => {
+                let lines = tika::parse_bytes_to_pdf_outline(buffer)?;
+                return Ok(outline::parse_pdf_outline(&lines));
+            }
+            OutlineSource::Epub => {
+                if let Some(outline) = outline::read_epub_outline(std::io::Cursor::new(buffer))? {
+                    return Ok(outline);
+                }
+            }
+            OutlineSource::Headings => {}
+        }
+        let mut collector = HeadingCollector::default();
+        self.extract_bytes_events(buffer, &Metadata::new(), &mut collector)?;
+        Ok(collector.into_outline())
+    }
+
+    /// Extracts the comments and annotations of a file as a list of [`Annotation`], without
+    /// extracting its text: the annotations of the pdf pages, such as the notes, highlights and
+    /// free texts, the comments of docx documents and pptx slides and the cell comments of xlsx
+    /// sheets. The other documents have no annotations
+    pub fn extract_file_annotations(&self, file_path: &str) -> ExtractResult<Vec<Annotation>> {
+        use std::io::Read;
+        let read_error =
+            |e: std::io::Error| Error::IoError(format!("Could not read {}: {}", file_path, e));
+        let mut head = Vec::new();
+        std::fs::File::open(file_path)
+            .and_then(|file| file.take(1024).read_to_end(&mut head))
+            .map_err(read_error)?;
+        match AnnotationSource::sniff(&head) {
+            AnnotationSource::Pdf => {
+                let lines = tika::parse_file_to_pdf_annotations(file_path)?;
+                Ok(annotations::parse_pdf_annotations(&lines))
+            }
+            AnnotationSource::Zip => {
+                let file = std::fs::File::open(file_path).map_err(read_error)?;
+                annotations::read_ooxml_annotations(std::io::BufReader::new(file))
+            }
+            AnnotationSource::None => Ok(Vec::new()),
+        }
+    }
+
+    /// Same as [`Extractor::extract_file_annotations`] for a byte buffer
+    pub fn extract_bytes_annotations(&self, buffer: &[u8]) -> ExtractResult<Vec<Annotation>> {
+        match AnnotationSource::sniff(buffer) {
+            AnnotationSource::Pdf => {
+                let lines = tika::parse_bytes_to_pdf_annotations(buffer)?;
+                Ok(annotations::parse_pdf_annotations(&lines))
+            }
+            AnnotationSource::Zip => {
+                annotations::read_ooxml_annotations(std::io::Cursor::new(buffer))
+            }
+            AnnotationSource::None => Ok(Vec::new()),
+        }
+    }
+
+    /// Extracts the tracked changes of the body of a docx file: its insertions, deletions, moves
+    /// and formatting changes, with their author and date, and the text of the document with the
+    /// text of all its revisions, from which [`DocumentRevisions::original_text`] and
+    /// [`DocumentRevisions::final_text`] are rebuilt. The document is read without tika. Returns
+    /// a [`Error::ParseError`] for the files that are not docx documents
+    pub fn extract_file_revisions(&self, file_path: &str) -> ExtractResult<DocumentRevisions> {
+        let file = std::fs::File::open(file_path)
+            .map_err(|e| Error::IoError(format!("Could not read {}: {}", file_path, e)))?;
+        revisions::read_docx_revisions(std::io::BufReader::new(file))
+    }
+
+    /// Same as [`Extractor::extract_file_revisions`] for a byte buffer
+    pub fn extract_bytes_revisions(&self, buffer: &[u8]) -> ExtractResult<DocumentRevisions> {
+        revisions::read_docx_revisions(std::io::Cursor::new(buffer))
+    }
+
+    /// Detects the mime type of a file without extracting its content. Returns a tuple with
+    /// the detected mime type, e.g. "application/pdf", and the metadata gathered during detection.
+    pub fn detect_file(&self, file_path: &str) -> ExtractResult<(String, Metadata)> {
+        tika::detect_file(file_path)
+    }
+
+    /// Detects the mime type of a byte buffer without extracting its content. Returns a tuple
+    /// with the detected mime type and the metadata gathered during detection.
+    pub fn detect_bytes(&self, buffer: &[u8]) -> ExtractResult<(String, Metadata)> {
+        tika::detect_bytes(buffer, &Metadata::new())
+    }
+
+    /// Same as [`Extractor::detect_bytes`], but with metadata hints such as the `resourceName`
+    /// or `Content-Type` of the bytes
+    pub fn detect_bytes_with_hints(
+        &self,
+        buffer: &[u8],
+        metadata_hints: &Metadata,
+    ) -> ExtractResult<(String, Metadata)> {
+        tika::detect_bytes(buffer, metadata_hints)
+    }
+
+    /// Detects the mime type of an http(s) url. The url is downloaded according to the
+    /// extractor's [`UrlFetchConfig`] and the response `Content-Type` and file name are used
+    /// as detection hints.
+    pub fn detect_url(&self, url: &str) -> ExtractResult<(String, Metadata)> {
+        if !fetch::is_http_url(url) {
+            return Err(Error::FetchError(format!(
+                "Only http(s) urls can be detected: {}",
+                url
+            )));
+        }
+        let response = fetch::fetch_url(url, &self.url_fetch_config)?;
+        tika::detect_bytes(&response.body, &response.metadata)
+    }
+}
+
+/// Finishes the stats of an extraction and adds them to the metadata if they are returned
+fn finish_stats(stats: Option<StatsRecorder>, chars_out: Option<u64>, metadata: &mut Metadata) {
+    if let Some(stats) = stats.and_then(|stats| stats.finish(chars_out, metadata)) {
+        stats.write(metadata);
+    }
+}
+
+/// Finishes the stats of an extraction to a string
+fn finish_string_stats(
+    stats: Option<StatsRecorder>,
+    (content, mut metadata): (String, Metadata),
+) -> (String, Metadata) {
+    let chars_out = stats.as_ref().map(|_| content.chars().count() as u64);
+    finish_stats(stats, chars_out, &mut metadata);
+    (content, metadata)
+}
+
+/// Sets the size of a downloaded document
+fn set_bytes_in(stats: &mut Option<StatsRecorder>, bytes_in: usize) {
+    if let Some(stats) = stats {
+        stats.set_bytes_in(bytes_in as u64);
+    }
+}
+
+fn file_size(file_path: &str) -> Option<u64> {
+    std::fs::metadata(file_path)
+        .ok()
+        .map(|metadata| metadata.len())
+}
+
+fn content_type(metadata: &Metadata) -> &str {
+    metadata
+        .get(native::CONTENT_TYPE_KEY)
+        .and_then(|values| values.first())
+        .map(|content_type| content_type.as_str())
+        .unwrap_or_default()
 }
 
 #[cfg(test)]
 mod tests {
     use super::StreamReader;
-    use crate::Extractor;
+    use crate::{fetch, Extractor, Metadata, PdfParserConfig};
     use std::fs::File;
     use std::io::BufReader;
     use std::io::{self, Read};
@@ -309,6 +1470,73 @@ mod tests {
         );
     }
 
+    #[test]
+    fn cache_config_test() {
+        let extractor = Extractor::new();
+        let response_hints = |target_url: &str| {
+            Metadata::from([
+                ("resourceName".to_string(), vec!["page.html".to_string()]),
+                (fetch::HTTP_STATUS_CODE.to_string(), vec!["200".to_string()]),
+                (
+                    fetch::HTTP_TARGET_URL.to_string(),
+                    vec![target_url.to_string()],
+                ),
+                ("Content-Length".to_string(), vec!["10".to_string()]),
+            ])
+        };
+        let config = extractor.cache_config(&response_hints("https://a.example.com/page.html"));
+
+        // The settings are named and versioned
+        let value: serde_json::Value = serde_json::from_str(&config).unwrap();
+        assert_eq!(value["version"], 1);
+        assert_eq!(value["pdf_config"]["ocr_strategy"], "AUTO");
+        assert_eq!(value["metadata_hints"]["resourceName"][0], "page.html");
+
+        // The volatile metadata of the responses are not part of the key, the hints are
+        assert_eq!(
+            config,
+            extractor.cache_config(&response_hints("https://b.example.com/page.html"))
+        );
+        assert_ne!(config, extractor.cache_config(&Metadata::new()));
+        let pdf_config = PdfParserConfig::new().set_page_range(1..=2);
+        assert_ne!(
+            config,
+            extractor
+                .set_pdf_config(pdf_config)
+                .cache_config(&response_hints("https://a.example.com/page.html"))
+        );
+    }
+
+    #[test]
+    fn detect_file_test() {
+        let extractor = Extractor::new();
+        let (mime_type, _metadata) = extractor.detect_file(TEST_FILE).unwrap();
+        assert_eq!(mime_type, "text/x-web-markdown");
+
+        let file_bytes = read_file_as_bytes(TEST_FILE).unwrap();
+        let (mime_type, _metadata) = extractor.detect_bytes(&file_bytes).unwrap();
+        assert!(mime_type.starts_with("text/"));
+    }
+
+    #[test]
+    fn extract_bytes_recursive_test() {
+        let file_bytes = read_file_as_bytes(TEST_FILE).unwrap();
+        let extractor = Extractor::new();
+        let metadata_list = extractor
+            .extract_bytes_recursive(&file_bytes, &Default::default())
+            .unwrap();
+
+        assert_eq!(metadata_list.len(), 1);
+        let content = &metadata_list[0]["X-TIKA:content"][0];
+        assert!(content.contains("Extractous"));
+
+        let (content, _metadata, embedded) = extractor
+            .unpack_bytes(&file_bytes, &Default::default())
+            .unwrap();
+        assert_eq!(content.trim(), expected_content().trim());
+        assert!(embedded.is_empty());
+    }
+
     #[test]
     fn extract_file_to_xml_test() {
         // Parse the files using extractous
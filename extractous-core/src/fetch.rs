@@ -0,0 +1,361 @@
+use std::io::Read;
+use std::time::Duration;
+
+use crate::errors::{Error, ExtractResult};
+use crate::{Metadata, UrlFetchConfig};
+use reqwest::blocking::Client;
+use reqwest::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+use reqwest::redirect::Policy;
+
+/// Metadata keys filled from the http response. They follow the naming used by Tika's
+/// own `HttpFetcher`, so the results look the same as when fetching through tika-server.
+pub(crate) const HTTP_STATUS_CODE: &str = "http-header:status-code";
+pub(crate) const HTTP_TARGET_URL: &str = "http-connection:target-url";
+pub(crate) const HTTP_CONTENT_TYPE: &str = "http-header:content-type";
+pub(crate) const HTTP_CONTENT_DISPOSITION: &str = "http-header:content-disposition";
+
+// Tika metadata keys used as detection hints
+const RESOURCE_NAME_KEY: &str = "resourceName";
+const CONTENT_TYPE_KEY: &str = "Content-Type";
+const CONTENT_LENGTH_KEY: &str = "Content-Length";
+
+/// Metadata of the response that may change between two downloads of the same body, e.g.
+/// after a redirect. They are not detection hints and are left out of the cache keys
+pub(crate) const VOLATILE_METADATA: [&str; 3] =
+    [HTTP_STATUS_CODE, HTTP_TARGET_URL, CONTENT_LENGTH_KEY];
+
+/// The downloaded body of an url and the metadata that should be passed to the parser
+pub(crate) struct FetchResponse {
+    pub body: Vec<u8>,
+    pub metadata: Metadata,
+}
+
+/// Returns true if the url should be downloaded by [`fetch_url`]. Other schemes such as
+/// `file:` or `ftp:` are left to the Tika url handling
+pub(crate) fn is_http_url(url: &str) -> bool {
+    let lower = url.trim_start().to_ascii_lowercase();
+    lower.starts_with("http://") || lower.starts_with("https://")
+}
+
+fn build_client(config: &UrlFetchConfig) -> ExtractResult<Client> {
+    let redirect_policy = if config.max_redirects == 0 {
+        Policy::none()
+    } else {
+        Policy::limited(config.max_redirects)
+    };
+
+    let mut builder = Client::builder()
+        .user_agent(config.user_agent.as_str())
+        .timeout(Duration::from_secs(config.timeout_seconds))
+        .connect_timeout(Duration::from_secs(config.connect_timeout_seconds))
+        .redirect(redirect_policy)
+        .danger_accept_invalid_certs(config.accept_invalid_certs);
+
+    if let Some(proxy) = &config.proxy {
+        let proxy = reqwest::Proxy::all(proxy)
+            .map_err(|e| Error::FetchError(format!("Invalid proxy {}: {}", proxy, e)))?;
+        builder = builder.proxy(proxy);
+    }
+
+    for pem_file in config.root_certificates.iter() {
+        let pem = std::fs::read(pem_file).map_err(|e| {
+            Error::IoError(format!("Could not read certificate {}: {}", pem_file, e))
+        })?;
+        let cert = reqwest::Certificate::from_pem(&pem)
+            .map_err(|e| Error::FetchError(format!("Invalid certificate {}: {}", pem_file, e)))?;
+        builder = builder.add_root_certificate(cert);
+    }
+
+    builder
+        .build()
+        .map_err(|e| Error::FetchError(format!("Could not create http client: {}", e)))
+}
+
+/// Downloads the given url according to the config. Non success http statuses are
+/// returned as [`Error::HttpStatusError`]
+pub(crate) fn fetch_url(url: &str, config: &UrlFetchConfig) -> ExtractResult<FetchResponse> {
+    let client = build_client(config)?;
+
+    let mut request = client.get(url);
+    for (name, value) in config.headers.iter() {
+        request = request.header(name.as_str(), value.as_str());
+    }
+    if let Some(token) = &config.bearer_token {
+        request = request.bearer_auth(token);
+    }
+    if let Some((username, password)) = &config.basic_auth {
+        request = request.basic_auth(username, password.as_ref());
+    }
+
+    let response = request
+        .send()
+        .map_err(|e| Error::FetchError(format!("Could not fetch {}: {}", url, e)))?;
+
+    let status = response.status();
+    let final_url = response.url().to_string();
+    if !status.is_success() {
+        return Err(Error::HttpStatusError(status.as_u16(), final_url));
+    }
+
+    let header_value = |name| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_string())
+    };
+    let content_type = header_value(CONTENT_TYPE);
+    let content_disposition = header_value(CONTENT_DISPOSITION);
+
+    // Prefer the name advertised by the server, fallback to the last url path segment
+    let resource_name = content_disposition
+        .as_deref()
+        .and_then(parse_content_disposition_filename)
+        .or_else(|| {
+            response
+                .url()
+                .path_segments()
+                .and_then(|mut segments| segments.next_back())
+                .filter(|s| !s.is_empty())
+                .map(percent_decode)
+        });
+
+    // Read the body, but never more than max_body_bytes
+    let mut body = Vec::new();
+    match config.max_body_bytes {
+        Some(max) => {
+            response
+                .take(max + 1)
+                .read_to_end(&mut body)
+                .map_err(|e| Error::FetchError(format!("Could not read {}: {}", url, e)))?;
+            if body.len() as u64 > max {
+                return Err(Error::FetchError(format!(
+                    "Response body of {} exceeds the maximum of {} bytes",
+                    final_url, max
+                )));
+            }
+        }
+        None => {
+            let mut response = response;
+            response
+                .read_to_end(&mut body)
+                .map_err(|e| Error::FetchError(format!("Could not read {}: {}", url, e)))?;
+        }
+    }
+
+    let mut metadata = Metadata::new();
+    metadata.insert(
+        HTTP_STATUS_CODE.to_string(),
+        vec![status.as_u16().to_string()],
+    );
+    metadata.insert(HTTP_TARGET_URL.to_string(), vec![final_url]);
+    metadata.insert(CONTENT_LENGTH_KEY.to_string(), vec![body.len().to_string()]);
+    if let Some(content_type) = content_type {
+        metadata.insert(HTTP_CONTENT_TYPE.to_string(), vec![content_type.clone()]);
+        metadata.insert(CONTENT_TYPE_KEY.to_string(), vec![content_type]);
+    }
+    if let Some(content_disposition) = content_disposition {
+        metadata.insert(
+            HTTP_CONTENT_DISPOSITION.to_string(),
+            vec![content_disposition],
+        );
+    }
+    if let Some(resource_name) = resource_name {
+        metadata.insert(RESOURCE_NAME_KEY.to_string(), vec![resource_name]);
+    }
+
+    Ok(FetchResponse { body, metadata })
+}
+
+/// Extracts the file name from a `Content-Disposition` header value. The RFC 6266
+/// `filename*` parameter takes precedence over `filename`
+fn parse_content_disposition_filename(value: &str) -> Option<String> {
+    let mut filename = None;
+    for param in value.split(';').map(|p| p.trim()) {
+        let Some((key, val)) = param.split_once('=') else {
+            continue;
+        };
+        match key.trim().to_ascii_lowercase().as_str() {
+            "filename*" => {
+                // Format is charset'language'percent-encoded-value
+                let encoded = val.trim().splitn(3, '\'').nth(2)?;
+                return Some(percent_decode(encoded)).filter(|s| !s.is_empty());
+            }
+            "filename" => {
+                filename = Some(val.trim().trim_matches('"').to_string());
+            }
+            _ => {}
+        }
+    }
+    filename.filter(|s| !s.is_empty())
+}
+
+fn percent_decode(val: &str) -> String {
+    let hex = |b: u8| (b as char).to_digit(16).map(|d| d as u8);
+    let bytes = val.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            if let (Some(high), Some(low)) = (hex(bytes[i + 1]), hex(bytes[i + 2])) {
+                decoded.push(high << 4 | low);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+
+    /// Serves the given http responses, one per connection, on a local port. Returns the base
+    /// url of the server and the handle of the thread returning the heads of the requests
+    fn serve(responses: Vec<String>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                // The head of the request ends with an empty line
+                while reader.read_line(&mut request).unwrap() > 2 {}
+                requests.push(request.to_ascii_lowercase());
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+            requests
+        });
+        (base_url, handle)
+    }
+
+    fn response(status: &str, headers: &[&str], body: &str) -> String {
+        let mut response = format!("HTTP/1.1 {}\r\nConnection: close\r\n", status);
+        for header in headers {
+            response.push_str(&format!("{}\r\n", header));
+        }
+        response.push_str(&format!("Content-Length: {}\r\n\r\n{}", body.len(), body));
+        response
+    }
+
+    #[test]
+    fn fetch_url_headers_and_auth_test() {
+        let ok = || response("200 OK", &["Content-Type: text/plain"], "hello");
+        let (base_url, server) = serve(vec![ok(), ok()]);
+
+        let config = UrlFetchConfig::new()
+            .set_user_agent("extractous-test")
+            .set_header("Accept-Language", "de")
+            .set_bearer_auth("token");
+        let fetched = fetch_url(&format!("{}/docs/notes.txt", base_url), &config).unwrap();
+        let config = UrlFetchConfig::new().set_basic_auth("user", Some("pass"));
+        fetch_url(&base_url, &config).unwrap();
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("get /docs/notes.txt http/1.1"));
+        assert!(requests[0].contains("user-agent: extractous-test\r\n"));
+        assert!(requests[0].contains("accept-language: de\r\n"));
+        assert!(requests[0].contains("authorization: bearer token\r\n"));
+        // base64 of user:pass
+        assert!(requests[1].contains("authorization: basic dxnlcjpwyxnz\r\n"));
+
+        assert_eq!(fetched.body, b"hello");
+        assert_eq!(fetched.metadata[HTTP_STATUS_CODE], vec!["200"]);
+        assert_eq!(
+            fetched.metadata[HTTP_TARGET_URL],
+            vec![format!("{}/docs/notes.txt", base_url)]
+        );
+        assert_eq!(fetched.metadata[CONTENT_TYPE_KEY], vec!["text/plain"]);
+        assert_eq!(fetched.metadata[CONTENT_LENGTH_KEY], vec!["5"]);
+        assert_eq!(fetched.metadata[RESOURCE_NAME_KEY], vec!["notes.txt"]);
+    }
+
+    #[test]
+    fn fetch_url_http_status_error_test() {
+        let (base_url, server) = serve(vec![response("404 Not Found", &[], "missing")]);
+        let url = format!("{}/missing.pdf", base_url);
+
+        let result = fetch_url(&url, &UrlFetchConfig::new());
+        server.join().unwrap();
+        match result {
+            Err(Error::HttpStatusError(404, final_url)) => assert_eq!(final_url, url),
+            _ => panic!("expected a 404 HttpStatusError"),
+        }
+    }
+
+    #[test]
+    fn fetch_url_redirect_test() {
+        let redirect = || response("302 Found", &["Location: /final/report.csv"], "");
+        let (base_url, server) = serve(vec![
+            redirect(),
+            response(
+                "200 OK",
+                &["Content-Disposition: attachment; filename=\"data.csv\""],
+                "a,b",
+            ),
+            redirect(),
+        ]);
+
+        let fetched = fetch_url(&format!("{}/start", base_url), &UrlFetchConfig::new()).unwrap();
+        assert_eq!(fetched.body, b"a,b");
+        assert_eq!(
+            fetched.metadata[HTTP_TARGET_URL],
+            vec![format!("{}/final/report.csv", base_url)]
+        );
+        // The name advertised by the server wins over the url path
+        assert_eq!(fetched.metadata[RESOURCE_NAME_KEY], vec!["data.csv"]);
+
+        // Without redirects, the redirect status is an error
+        let config = UrlFetchConfig::new().set_max_redirects(0);
+        let result = fetch_url(&format!("{}/start", base_url), &config);
+        assert!(matches!(result, Err(Error::HttpStatusError(302, _))));
+
+        let requests = server.join().unwrap();
+        assert!(requests[1].starts_with("get /final/report.csv http/1.1"));
+    }
+
+    #[test]
+    fn fetch_url_max_body_bytes_test() {
+        let body = || response("200 OK", &[], "0123456789");
+        let (base_url, server) = serve(vec![body(), body()]);
+
+        let config = UrlFetchConfig::new().set_max_body_bytes(10);
+        let fetched = fetch_url(&base_url, &config).unwrap();
+        assert_eq!(fetched.body, b"0123456789");
+
+        let config = UrlFetchConfig::new().set_max_body_bytes(5);
+        let result = fetch_url(&base_url, &config);
+        assert!(matches!(result, Err(Error::FetchError(_))));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn content_disposition_filename_test() {
+        assert_eq!(
+            parse_content_disposition_filename("attachment; filename=\"report.pdf\""),
+            Some("report.pdf".to_string())
+        );
+        assert_eq!(
+            parse_content_disposition_filename(
+                "attachment; filename=\"fallback.pdf\"; filename*=UTF-8''r%C3%A9sum%C3%A9.pdf"
+            ),
+            Some("résumé.pdf".to_string())
+        );
+        assert_eq!(parse_content_disposition_filename("inline"), None);
+    }
+
+    #[test]
+    fn is_http_url_test() {
+        assert!(is_http_url("https://www.google.com/"));
+        assert!(is_http_url("HTTP://example.com"));
+        assert!(!is_http_url("file:///tmp/a.pdf"));
+    }
+}
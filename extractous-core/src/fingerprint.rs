@@ -0,0 +1,188 @@
+//! Fingerprints of the extracted documents, to find the exact and near duplicates of a corpus.
+//! With [`Extractor::set_compute_fingerprints`], the [`Fingerprints`] of every extraction to
+//! string are added to its metadata, read them with [`Fingerprints::from_metadata`].
+//!
+//! The text hash and the SimHash are computed on the normalized text: the lowercased words,
+//! without the punctuation and whitespace. A document saved as pdf, docx and doc has three
+//! different input hashes, but usually the same text hash, or a SimHash at a small Hamming
+//! distance when the formats extract slightly different text.
+//!
+//! ```no_run
+//! use extractous::{Extractor, Fingerprints};
+//!
+//! let extractor = Extractor::new().set_compute_fingerprints(true);
+//! let (_content, pdf) = extractor.extract_file_to_string("contract.pdf").unwrap();
+//! let (_content, docx) = extractor.extract_file_to_string("contract.docx").unwrap();
+//! let pdf = Fingerprints::from_metadata(&pdf).unwrap();
+//! let docx = Fingerprints::from_metadata(&docx).unwrap();
+//! if pdf.similarity(&docx) > 0.9 {
+//!     println!("near duplicates");
+//! }
+//! ```
+//!
+//! [`Extractor::set_compute_fingerprints`]: crate::Extractor::set_compute_fingerprints
+
+use crate::Metadata;
+use sha2::{Digest, Sha256};
+
+// The metadata keys of the fingerprints
+pub(crate) const INPUT_SHA256_KEY: &str = "X-EXTRACTOUS:input_sha256";
+pub(crate) const TEXT_SHA256_KEY: &str = "X-EXTRACTOUS:text_sha256";
+pub(crate) const SIMHASH_KEY: &str = "X-EXTRACTOUS:simhash";
+
+/// The number of words of the shingles hashed by the SimHash
+const SHINGLE_WORDS: usize = 3;
+
+/// The fingerprints of an extracted document
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fingerprints {
+    /// The hex SHA-256 of the document bytes, `None` for the urls that are not downloaded by
+    /// extractous, such as `file://` urls
+    pub input_sha256: Option<String>,
+    /// The hex SHA-256 of the normalized extracted text
+    pub text_sha256: String,
+    /// The 64 bits SimHash of the shingles of 3 words of the normalized extracted text
+    pub simhash: u64,
+}
+
+impl Fingerprints {
+    /// Computes the fingerprints of the extracted text, with the hash of the document bytes
+    pub fn new(input_sha256: Option<String>, text: &str) -> Self {
+        let words = normalized_words(text);
+        Self {
+            input_sha256,
+            text_sha256: format!("{:x}", Sha256::digest(words.join(" "))),
+            simhash: simhash(&words),
+        }
+    }
+
+    /// Reads the fingerprints added to the metadata by an extraction. Returns None if the
+    /// fingerprints were not computed
+    pub fn from_metadata(metadata: &Metadata) -> Option<Self> {
+        let value = |key: &str| metadata.get(key)?.first().cloned();
+        Some(Self {
+            input_sha256: value(INPUT_SHA256_KEY),
+            text_sha256: value(TEXT_SHA256_KEY)?,
+            simhash: u64::from_str_radix(&value(SIMHASH_KEY)?, 16).ok()?,
+        })
+    }
+
+    /// Adds the fingerprints to the metadata, the SimHash as 16 hex digits
+    pub(crate) fn write(&self, metadata: &mut Metadata) {
+        if let Some(input_sha256) = &self.input_sha256 {
+            metadata.insert(INPUT_SHA256_KEY.to_string(), vec![input_sha256.clone()]);
+        }
+        metadata.insert(TEXT_SHA256_KEY.to_string(), vec![self.text_sha256.clone()]);
+        metadata.insert(
+            SIMHASH_KEY.to_string(),
+            vec![format!("{:016x}", self.simhash)],
+        );
+    }
+
+    /// Whether the documents have the same bytes or the same normalized text
+    pub fn is_duplicate(&self, other: &Fingerprints) -> bool {
+        let same_input = self.input_sha256.is_some() && self.input_sha256 == other.input_sha256;
+        same_input || self.text_sha256 == other.text_sha256
+    }
+
+    /// The similarity of the texts of the documents, from 0.0 to 1.0, estimated from the
+    /// Hamming distance of their SimHashes. See [`simhash_similarity`]
+    pub fn similarity(&self, other: &Fingerprints) -> f64 {
+        simhash_similarity(self.simhash, other.simhash)
+    }
+}
+
+/// The similarity of two SimHashes, 1.0 minus their Hamming distance divided by 64. Identical
+/// texts have a similarity of 1.0, near duplicates usually above 0.9 and unrelated texts
+/// around 0.5
+pub fn simhash_similarity(a: u64, b: u64) -> f64 {
+    1.0 - f64::from((a ^ b).count_ones()) / 64.0
+}
+
+/// The lowercased alphanumeric words of the text
+fn normalized_words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// The SimHash of the shingles of the words, each bit is the majority of the bits of the
+/// hashes of the shingles. The hash is FNV-1a, stable across the versions and platforms
+fn simhash(words: &[String]) -> u64 {
+    let mut counts = [0i64; 64];
+    for shingle in words.windows(SHINGLE_WORDS.min(words.len()).max(1)) {
+        let hash = shingle
+            .iter()
+            .enumerate()
+            .fold(FNV_OFFSET, |hash, (i, word)| {
+                let hash = if i > 0 { fnv1a(hash, b" ") } else { hash };
+                fnv1a(hash, word.as_bytes())
+            });
+        for (bit, count) in counts.iter_mut().enumerate() {
+            *count += if hash >> bit & 1 == 1 { 1 } else { -1 };
+        }
+    }
+    counts
+        .iter()
+        .enumerate()
+        .filter(|(_, count)| **count > 0)
+        .fold(0, |simhash, (bit, _)| simhash | 1 << bit)
+}
+
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fingerprints_test() {
+        let text = "This Agreement is made between the Seller and the Buyer. The Seller agrees to \
+                    deliver the goods to the Buyer before the end of the month, and the Buyer \
+                    agrees to pay the price of the goods on delivery.";
+        let fingerprints = Fingerprints::new(None, text);
+
+        // The case, punctuation and whitespace are normalized
+        let reformatted = Fingerprints::new(
+            Some("00".to_string()),
+            &text.to_uppercase().replace(' ', "\n  ").replace('.', " ;"),
+        );
+        assert_eq!(reformatted.text_sha256, fingerprints.text_sha256);
+        assert_eq!(reformatted.simhash, fingerprints.simhash);
+        assert!(reformatted.is_duplicate(&fingerprints));
+
+        let edited = Fingerprints::new(None, &text.replace("end of the month", "end of the week"));
+        assert!(!edited.is_duplicate(&fingerprints));
+        assert!(edited.similarity(&fingerprints) > 0.8);
+
+        let unrelated = Fingerprints::new(None, "Quarterly results of the company were strong");
+        assert!(unrelated.similarity(&fingerprints) < edited.similarity(&fingerprints));
+
+        let mut metadata = Metadata::new();
+        reformatted.write(&mut metadata);
+        assert_eq!(Fingerprints::from_metadata(&metadata), Some(reformatted));
+        assert_eq!(Fingerprints::from_metadata(&Metadata::new()), None);
+    }
+
+    #[test]
+    fn simhash_test() {
+        assert_eq!(simhash(&[]), 0);
+        assert_eq!(simhash_similarity(0, u64::MAX), 0.0);
+        assert_eq!(simhash_similarity(42, 42), 1.0);
+        // Texts of less than 3 words are a single shingle
+        let words = normalized_words("two words");
+        assert_eq!(
+            simhash(&words),
+            fnv1a(fnv1a(fnv1a(FNV_OFFSET, b"two"), b" "), b"words")
+        );
+    }
+}
@@ -0,0 +1,325 @@
+//! Removal of the running headers, footers and page numbers of pdf documents. With
+//! [`Extractor::set_remove_headers_and_footers`], the lines found at the top or at the bottom of
+//! most pages of a document, and the page numbers, are removed from the text extracted to string.
+//!
+//! The lines are compared with their digits masked, so that `Annual Report 2023 - page 3` and
+//! `Annual Report 2023 - page 4` are the same running header. What was removed is reported in the
+//! metadata:
+//! * `X-EXTRACTOUS:removed_headers`: the distinct header lines, as found on their first page
+//! * `X-EXTRACTOUS:removed_footers`: the distinct footer lines, as found on their first page
+//! * `X-EXTRACTOUS:removed_page_numbers`: the number of page number lines removed
+//!
+//! [`Extractor::set_remove_headers_and_footers`]: crate::Extractor::set_remove_headers_and_footers
+
+use crate::Metadata;
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+
+// The metadata keys of the removed lines
+pub(crate) const REMOVED_HEADERS_KEY: &str = "X-EXTRACTOUS:removed_headers";
+pub(crate) const REMOVED_FOOTERS_KEY: &str = "X-EXTRACTOUS:removed_footers";
+pub(crate) const REMOVED_PAGE_NUMBERS_KEY: &str = "X-EXTRACTOUS:removed_page_numbers";
+
+/// The character tika writes after every pdf page when the pages are marked
+pub(crate) const PAGE_BREAK: char = '\u{C}';
+
+/// The indexes of the lines of the top and of the bottom of a page
+type Zones = (Vec<usize>, Vec<usize>);
+
+/// Header and footer detection settings
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+pub struct HeaderFooterConfig {
+    pub(crate) min_page_ratio: f64,
+    pub(crate) max_lines: usize,
+    pub(crate) remove_page_numbers: bool,
+}
+
+impl Default for HeaderFooterConfig {
+    fn default() -> Self {
+        Self {
+            min_page_ratio: 0.5,
+            max_lines: 3,
+            remove_page_numbers: true,
+        }
+    }
+}
+
+impl HeaderFooterConfig {
+    /// Creates a new instance of HeaderFooterConfig with the default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The minimum fraction of the pages, from 0.0 to 1.0, a line must be repeated on to be a
+    /// header or a footer. A line must be repeated on at least two pages.
+    /// Default: 0.5
+    pub fn set_min_page_ratio(mut self, val: f64) -> Self {
+        self.min_page_ratio = val;
+        self
+    }
+
+    /// The number of non blank lines at the top and at the bottom of every page that can be
+    /// headers, footers or page numbers.
+    /// Default: 3
+    pub fn set_max_lines(mut self, val: usize) -> Self {
+        self.max_lines = val;
+        self
+    }
+
+    /// Removes the page numbers found at the top or at the bottom of the pages, such as `12`,
+    /// `- 12 -`, `Page 12`, `Page 12 of 40`, `12/40` or `xii`.
+    /// Default: true
+    pub fn set_remove_page_numbers(mut self, val: bool) -> Self {
+        self.remove_page_numbers = val;
+        self
+    }
+
+    /// The settings by name, hashed in the keys of the extraction cache
+    pub(crate) fn cache_key(&self) -> Value {
+        json!({
+            "min_page_ratio": self.min_page_ratio,
+            "max_lines": self.max_lines,
+            "remove_page_numbers": self.remove_page_numbers,
+        })
+    }
+
+    /// Removes the headers, footers and page numbers of the pages of the text, which are
+    /// followed by a [`PAGE_BREAK`], and the page breaks. The removed lines are reported in the
+    /// metadata. The text after the last page break, if any, is not a page and is kept as is
+    pub(crate) fn remove(&self, text: &str, metadata: &mut Metadata) -> String {
+        let mut pages: Vec<Vec<&str>> = text
+            .split(PAGE_BREAK)
+            .map(|page| page.split('\n').collect())
+            .collect();
+        let trailer = pages.pop().unwrap_or_default();
+        if pages.is_empty() {
+            return text.to_string();
+        }
+
+        let zones: Vec<Zones> = pages.iter().map(|lines| self.zones(lines)).collect();
+        let min_pages = ((self.min_page_ratio * pages.len() as f64).ceil() as usize).max(2);
+        let repeated = |zone_of: fn(&Zones) -> &Vec<usize>| {
+            let mut counts: HashMap<String, usize> = HashMap::new();
+            for (lines, zone) in pages.iter().zip(&zones) {
+                let keys: HashSet<String> = zone_of(zone).iter().map(|i| key(lines[*i])).collect();
+                for key in keys {
+                    *counts.entry(key).or_default() += 1;
+                }
+            }
+            counts
+                .into_iter()
+                .filter(|(_, count)| *count >= min_pages)
+                .map(|(key, _)| key)
+                .collect::<HashSet<String>>()
+        };
+        let repeated_headers = repeated(|zone| &zone.0);
+        let repeated_footers = repeated(|zone| &zone.1);
+
+        let mut headers = Removed::default();
+        let mut footers = Removed::default();
+        let mut page_numbers = 0;
+        let mut cleaned = String::with_capacity(text.len());
+        for (lines, (top, bottom)) in pages.iter().zip(&zones) {
+            let mut removed = HashSet::new();
+            for (zone, repeated, found) in [
+                (top, &repeated_headers, &mut headers),
+                (bottom, &repeated_footers, &mut footers),
+            ] {
+                for i in zone {
+                    let line = lines[*i].trim();
+                    if is_page_number(line) {
+                        if self.remove_page_numbers && removed.insert(*i) {
+                            page_numbers += 1;
+                        }
+                    } else if repeated.contains(&key(line)) && removed.insert(*i) {
+                        found.add(line);
+                    }
+                }
+            }
+            let kept: Vec<&str> = (0..lines.len())
+                .filter(|i| !removed.contains(i))
+                .map(|i| lines[i])
+                .collect();
+            cleaned.push_str(&kept.join("\n"));
+        }
+        cleaned.push_str(&trailer.join("\n"));
+
+        if !headers.lines.is_empty() {
+            metadata.insert(REMOVED_HEADERS_KEY.to_string(), headers.lines);
+        }
+        if !footers.lines.is_empty() {
+            metadata.insert(REMOVED_FOOTERS_KEY.to_string(), footers.lines);
+        }
+        metadata.insert(
+            REMOVED_PAGE_NUMBERS_KEY.to_string(),
+            vec![page_numbers.to_string()],
+        );
+        cleaned
+    }
+
+    /// The indexes of the first and of the last `max_lines` non blank lines of a page. The top
+    /// zone takes the first half of the lines of the short pages, the bottom zone the rest
+    fn zones(&self, lines: &[&str]) -> Zones {
+        let non_blank: Vec<usize> = (0..lines.len())
+            .filter(|i| !lines[*i].trim().is_empty())
+            .collect();
+        let top = self.max_lines.min(non_blank.len().div_ceil(2));
+        let bottom = self.max_lines.min(non_blank.len() - top);
+        (
+            non_blank[..top].to_vec(),
+            non_blank[non_blank.len() - bottom..].to_vec(),
+        )
+    }
+}
+
+/// The distinct removed lines, as found the first time
+#[derive(Default)]
+struct Removed {
+    keys: HashSet<String>,
+    lines: Vec<String>,
+}
+
+impl Removed {
+    fn add(&mut self, line: &str) {
+        if self.keys.insert(key(line)) {
+            self.lines.push(line.to_string());
+        }
+    }
+}
+
+/// The line compared across the pages: lowercased, with the runs of digits replaced by `#` and
+/// the runs of whitespace by a single space
+fn key(line: &str) -> String {
+    let mut key = String::with_capacity(line.len());
+    for word in line.split_whitespace() {
+        if !key.is_empty() {
+            key.push(' ');
+        }
+        for c in word.chars().flat_map(char::to_lowercase) {
+            match c.is_ascii_digit() {
+                true if key.ends_with('#') => (),
+                true => key.push('#'),
+                false => key.push(c),
+            }
+        }
+    }
+    key
+}
+
+/// Whether the trimmed line is a page number, optionally surrounded by dashes or brackets and
+/// prefixed by `page`, `p.` or `pg`, followed by an optional `of` or `/` page count
+fn is_page_number(line: &str) -> bool {
+    let line = line.to_lowercase();
+    let line = line.trim_matches(|c: char| {
+        c.is_whitespace() || matches!(c, '-' | '\u{2013}' | '\u{2014}' | '[' | ']' | '(' | ')')
+    });
+    let line = ["page", "pg.", "pg", "p."]
+        .iter()
+        .find_map(|prefix| line.strip_prefix(prefix))
+        .unwrap_or(line)
+        .trim_start();
+    let number = match line.split_once(" of ").or_else(|| line.split_once('/')) {
+        Some((number, count)) if is_number(count.trim()) => number.trim(),
+        Some(_) => return false,
+        None => line,
+    };
+    is_number(number) || is_roman_number(number)
+}
+
+fn is_number(text: &str) -> bool {
+    !text.is_empty() && text.len() <= 5 && text.chars().all(|c| c.is_ascii_digit())
+}
+
+/// The roman numbers of the front matter, made of `i`, `v` and `x` only so that words are not
+/// taken for numbers
+fn is_roman_number(text: &str) -> bool {
+    !text.is_empty() && text.len() <= 6 && text.chars().all(|c| matches!(c, 'i' | 'v' | 'x'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(lines: &[&str]) -> String {
+        format!("\n{}\n{}", lines.join("\n"), PAGE_BREAK)
+    }
+
+    #[test]
+    fn remove_test() {
+        let text = [
+            page(&["ACME Corp - Annual Report 2023", "", "Intro text.", "1"]),
+            page(&[
+                "ACME Corp - Annual Report 2023",
+                "Body text.",
+                "Confidential",
+                "Page 2 of 3",
+            ]),
+            page(&[
+                "ACME Corp - Annual Report 2023",
+                "Last text.",
+                "Confidential",
+                "- 3 -",
+            ]),
+        ]
+        .concat()
+            + "\nembedded\n";
+        let mut metadata = Metadata::new();
+        let cleaned = HeaderFooterConfig::new().remove(&text, &mut metadata);
+        assert_eq!(
+            cleaned,
+            "\n\nIntro text.\n\nBody text.\n\nLast text.\n\nembedded\n"
+        );
+        assert_eq!(
+            metadata[REMOVED_HEADERS_KEY],
+            vec!["ACME Corp - Annual Report 2023"]
+        );
+        assert_eq!(metadata[REMOVED_FOOTERS_KEY], vec!["Confidential"]);
+        assert_eq!(metadata[REMOVED_PAGE_NUMBERS_KEY], vec!["3"]);
+
+        // Page numbers are kept, and a line on a single page is not a header
+        let mut metadata = Metadata::new();
+        let cleaned = HeaderFooterConfig::new()
+            .set_remove_page_numbers(false)
+            .set_min_page_ratio(1.0)
+            .remove(&text, &mut metadata);
+        assert!(cleaned.contains("Page 2 of 3") && cleaned.contains("Intro text."));
+        assert!(!cleaned.contains("ACME") && cleaned.contains("Confidential"));
+
+        // Text without pages is unchanged
+        let mut metadata = Metadata::new();
+        assert_eq!(
+            HeaderFooterConfig::new().remove("1\ntext", &mut metadata),
+            "1\ntext"
+        );
+        assert!(metadata.is_empty());
+    }
+
+    #[test]
+    fn page_number_test() {
+        for number in [
+            "7",
+            "- 7 -",
+            "[7]",
+            "Page 7",
+            "p. 7",
+            "page 7 of 12",
+            "7 / 12",
+            "xiv",
+        ] {
+            assert!(is_page_number(number), "{:?}", number);
+        }
+        for text in [
+            "",
+            "Chapter 7",
+            "7 apples",
+            "page 7 of the book",
+            "mix",
+            "2023-2024",
+        ] {
+            assert!(!is_page_number(text), "{:?}", text);
+        }
+        assert_eq!(key("Report  2023 - Page 12"), "report # - page #");
+    }
+}
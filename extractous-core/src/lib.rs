@@ -10,6 +10,7 @@
 //! - [create and configure new the extractor](#create-and-config-an-extractor)
 //! - [use the extractor to extract text](#extract-text)
 //! - [enable OCR for the extractor](#extract-text-with-ocr)
+//! - [load the extractor from a config file](#load-an-extractor-from-a-config-file)
 //!
 //! ## Create and config an extractor
 //!
@@ -48,7 +49,9 @@
 //!
 //! ## Extract text with OCR
 //! * Make sure Tesseract is installed with the corresponding language packs. For example on debian `sudo apt install tesseract-ocr tesseract-ocr-deu` to install tesseract with German language pack.
-//! * If you get `Parse error occurred : Unable to extract PDF content`, it is most likely that the OCR language pack is not installed
+//! * If you get `Parse error occurred : Unable to extract PDF content`, it is most likely that the OCR language pack is not installed.
+//!   [`Extractor::validate`] checks the installed languages before extracting, and [`ocr::diagnose`] reports the
+//!   tesseract version and languages
 //!
 //! ```no_run
 //! use extractous::{Extractor, TesseractOcrConfig, PdfParserConfig, PdfOcrStrategy};
@@ -60,11 +63,34 @@
 //!  .set_ocr_config(TesseractOcrConfig::new().set_language("deu"))
 //!  .set_pdf_config(PdfParserConfig::new().set_ocr_strategy(PdfOcrStrategy::OCR_ONLY));
 //!
+//! // fail fast if tesseract or the deu language pack is not installed
+//! extractor.validate().unwrap();
+//!
 //! // extract file with extractor
 //! let (content, metadata) = extractor.extract_file_to_string(file_path).unwrap();
 //! println!("{}", content);
 //!
 //! ```
+//!
+//! ## Load an extractor from a config file
+//! With the `serde` feature enabled, all the configs, the [`Extractor`] and the
+//! [`EmbeddedDocument`] results implement `Serialize` and `Deserialize`. Field names are the
+//! names of the config setters without the `set_` prefix (see [`UrlFetchConfig`] for the
+//! exceptions), enum values are their variant names, e.g. `"OCR_ONLY"`, and missing fields keep
+//! their default values. The url credentials, `bearer_token` and `basic_auth` of the
+//! [`UrlFetchConfig`], are read from config files but never serialized, so a serialized
+//! extractor can be saved as a profile without writing them to disk in plain text.
+//!
+//! ```ignore
+//! use extractous::Extractor;
+//!
+//! // extraction-profile.toml:
+//! // extract_string_max_length = 1000
+//! // [pdf_config]
+//! // ocr_strategy = "OCR_ONLY"
+//! let extractor = Extractor::from_config_file("extraction-profile.toml").unwrap();
+//! let (content, metadata) = extractor.extract_file_to_string("README.md").unwrap();
+//! ```
 
 /// Default buffer size
 pub const DEFAULT_BUF_SIZE: usize = 32768;
@@ -80,8 +106,72 @@ pub use config::*;
 mod extractor;
 pub use extractor::*;
 
+// ocr module contains the OCR results and diagnostics
+pub mod ocr;
+pub use ocr::{OcrBoundingBox, OcrWord};
+
+// native module parses the text formats in Rust, only its content sink is exposed
+mod native;
+pub use native::ContentSink;
+
+// parser module contains the trait of the custom Rust parsers
+mod parser;
+pub use parser::Parser;
+
+// events module contains the SAX-style events of the extracted documents
+pub mod events;
+pub use events::{ContentHandler, Event};
+
+// progress module contains the progress reported to the progress callbacks
+pub mod progress;
+pub use progress::Progress;
+
+// stats module contains the timing and resource stats of the extractions
+pub mod stats;
+pub use stats::ExtractionStats;
+
+// normalize module contains the post-processing of the extracted text
+pub mod normalize;
+pub use normalize::{TextNormalizer, UnicodeForm};
+
+// headers module contains the removal of the headers, footers and page numbers of pdf pages
+pub mod headers;
+pub use headers::HeaderFooterConfig;
+
+// links module contains the hyperlinks of the extracted documents
+pub mod links;
+pub use links::Link;
+
+// outline module contains the bookmarks and tables of contents of the extracted documents
+pub mod outline;
+pub use outline::OutlineEntry;
+
+// annotations module contains the comments and annotations of the extracted documents
+pub mod annotations;
+pub use annotations::Annotation;
+
+// revisions module contains the tracked changes of docx documents
+pub mod revisions;
+pub use revisions::{DocumentRevisions, Revision, RevisionKind};
+
+// fingerprint module contains the fingerprints used to find duplicate documents
+pub mod fingerprint;
+pub use fingerprint::Fingerprints;
+
+// cache module contains the on-disk cache of the extractions to string
+pub mod cache;
+pub use cache::{CacheStats, ExtractionCache};
+
+// watch module extracts the changed files of a directory tree
+pub mod watch;
+pub use watch::{WatchEvent, Watcher};
+
+// fetch module downloads urls, not exposed outside this crate
+mod fetch;
+
 // tika module, not exposed outside this crate
 mod tika {
+    mod callbacks;
     mod jni_utils;
     mod parse;
     mod wrappers;
@@ -0,0 +1,257 @@
+//! Hyperlinks of the extracted documents.
+//!
+//! The `extract_*_links` functions of the [`crate::Extractor`] collect the `<a href>` elements of
+//! the xhtml produced by the parsers, like tika's `LinkContentHandler`: the links of html pages
+//! and of the html bodies of emails, the uri link annotations of pdf pages, and the hyperlinks of
+//! word processing documents and presentations. The links of the embedded documents, such as
+//! the attachments of an email, are included.
+//!
+//! The relative hrefs are resolved against the base url of
+//! [`crate::Extractor::set_link_base_url`], or against the url of
+//! [`crate::Extractor::extract_url_links`].
+
+use crate::errors::{Error, ExtractResult};
+use crate::events::ContentHandler;
+use crate::Metadata;
+use reqwest::Url;
+
+/// A hyperlink of a document. Returned by [`crate::Extractor::extract_file_links`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Link {
+    /// Target of the link, resolved against the base url if it is relative
+    pub href: String,
+    /// Text of the link, with its runs of whitespace collapsed to a single space
+    pub anchor_text: String,
+    /// The `rel` attribute of the link, e.g. `nofollow`
+    pub rel: Option<String>,
+    /// Page or slide of the link, starting at 1, for the pdfs and presentations. None for the
+    /// documents without pages
+    pub source_page: Option<u32>,
+}
+
+/// The url the relative links are resolved against: the configured base url, which must be
+/// valid, or else the default url if it is one
+pub(crate) fn base_url(
+    configured: Option<&str>,
+    default: Option<&str>,
+) -> ExtractResult<Option<Url>> {
+    match configured {
+        Some(base_url) => Url::parse(base_url)
+            .map(Some)
+            .map_err(|e| Error::ConfigError(format!("Invalid link base url {}: {}", base_url, e))),
+        None => Ok(default.and_then(|url| Url::parse(url).ok())),
+    }
+}
+
+/// A [`ContentHandler`] collecting the links of the events of a document
+#[derive(Debug, Default)]
+pub(crate) struct LinkCollector {
+    base_url: Option<Url>,
+    links: Vec<Link>,
+    /// The link whose text is being read
+    link: Option<Link>,
+    /// Number of open `a` elements, links cannot be nested
+    open_anchors: usize,
+    /// The last page seen in the current document and in its containers, 0 before the first
+    /// page
+    pages: Vec<u32>,
+}
+
+impl LinkCollector {
+    pub(crate) fn new(base_url: Option<Url>) -> Self {
+        Self {
+            base_url,
+            pages: vec![0],
+            ..Default::default()
+        }
+    }
+
+    /// The links of the document, in document order
+    pub(crate) fn into_links(self) -> Vec<Link> {
+        self.links
+    }
+
+    fn resolve(&self, href: &str) -> String {
+        match &self.base_url {
+            Some(base_url) if Url::parse(href).is_err() => base_url
+                .join(href)
+                .map(String::from)
+                .unwrap_or_else(|_| href.to_string()),
+            _ => href.to_string(),
+        }
+    }
+
+    fn page(&self) -> Option<u32> {
+        self.pages.last().copied().filter(|page| *page > 0)
+    }
+}
+
+/// Whether the element starts a pdf page or a presentation slide
+fn is_page(name: &str, attributes: &[(String, String)]) -> bool {
+    name == "div"
+        && attribute(attributes, "class").is_some_and(|class| {
+            class
+                .split_whitespace()
+                .any(|c| c == "page" || c == "slide-content")
+        })
+}
+
+fn attribute<'a>(attributes: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    attributes
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
+impl ContentHandler for LinkCollector {
+    fn start_element(&mut self, name: &str, attributes: &[(String, String)]) -> ExtractResult<()> {
+        if is_page(name, attributes) {
+            if let Some(page) = self.pages.last_mut() {
+                *page += 1;
+            }
+        } else if name == "a" {
+            self.open_anchors += 1;
+            let href = attribute(attributes, "href").map(str::trim);
+            if let (1, Some(href)) = (self.open_anchors, href.filter(|h| !h.is_empty())) {
+                self.link = Some(Link {
+                    href: self.resolve(href),
+                    anchor_text: String::new(),
+                    rel: attribute(attributes, "rel").map(str::to_string),
+                    source_page: self.page(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn end_element(&mut self, name: &str) -> ExtractResult<()> {
+        if name == "a" && self.open_anchors > 0 {
+            self.open_anchors -= 1;
+            if self.open_anchors == 0 {
+                if let Some(mut link) = self.link.take() {
+                    link.anchor_text = link
+                        .anchor_text
+                        .split_whitespace()
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    self.links.push(link);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn characters(&mut self, text: &str) -> ExtractResult<()> {
+        if let Some(link) = &mut self.link {
+            link.anchor_text.push_str(text);
+        }
+        Ok(())
+    }
+
+    fn embedded_start(&mut self, _metadata: &Metadata) -> ExtractResult<()> {
+        self.pages.push(0);
+        Ok(())
+    }
+
+    fn embedded_end(&mut self) -> ExtractResult<()> {
+        if self.pages.len() > 1 {
+            self.pages.pop();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::Event;
+
+    fn start(name: &str, attributes: &[(&str, &str)]) -> Event {
+        Event::StartElement {
+            name: name.to_string(),
+            attributes: attributes
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        }
+    }
+
+    fn end(name: &str) -> Event {
+        Event::EndElement {
+            name: name.to_string(),
+        }
+    }
+
+    fn text(text: &str) -> Event {
+        Event::Characters(text.to_string())
+    }
+
+    #[test]
+    fn collect_links_test() {
+        let events = [
+            start("a", &[("href", "intro.html"), ("rel", "nofollow")]),
+            text("The\n  intro"),
+            end("a"),
+            start("a", &[("name", "bookmark")]),
+            end("a"),
+            start("div", &[("class", "page")]),
+            start("div", &[("class", "page")]),
+            start("a", &[("href", "https://example.org/")]),
+            text("example"),
+            end("a"),
+            end("div"),
+            Event::EmbeddedStart(Metadata::new()),
+            start("a", &[("href", "mailto:jane@example.com")]),
+            text("Jane"),
+            end("a"),
+            Event::EmbeddedEnd,
+            start("a", &[("href", "#top")]),
+            end("a"),
+        ];
+        let intranet = base_url(Some("https://intranet.example.com/docs/index.html"), None);
+        let mut collector = LinkCollector::new(intranet.unwrap());
+        for event in events.iter() {
+            event.dispatch(&mut collector).unwrap();
+        }
+        let links = collector.into_links();
+        let summary: Vec<(&str, &str, Option<&str>, Option<u32>)> = links
+            .iter()
+            .map(|l| {
+                let rel = l.rel.as_deref();
+                (l.href.as_str(), l.anchor_text.as_str(), rel, l.source_page)
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "https://intranet.example.com/docs/intro.html",
+                    "The intro",
+                    Some("nofollow"),
+                    None
+                ),
+                ("https://example.org/", "example", None, Some(2)),
+                ("mailto:jane@example.com", "Jane", None, None),
+                (
+                    "https://intranet.example.com/docs/index.html#top",
+                    "",
+                    None,
+                    Some(2)
+                ),
+            ]
+        );
+
+        // Without base url the hrefs are kept as is
+        let mut collector = LinkCollector::new(base_url(None, Some("intro")).unwrap());
+        for event in events.iter() {
+            event.dispatch(&mut collector).unwrap();
+        }
+        assert_eq!(collector.into_links()[0].href, "intro.html");
+
+        assert!(matches!(
+            base_url(Some("not an url"), None),
+            Err(Error::ConfigError(_))
+        ));
+    }
+}
@@ -0,0 +1,294 @@
+//! Readability-style detection of the main content of html pages. The text blocks are scored
+//! by their length and commas, their scores are given to their parent and grandparent, and the
+//! element with the best score, weighted by its class, id and link density, is the main content.
+//! Navigation, banners, sidebars, comments and footers are skipped.
+
+use scraper::node::Element;
+use scraper::{ElementRef, Html, Selector};
+use std::collections::HashMap;
+
+/// Elements that are never part of the main content
+const BOILERPLATE_ELEMENTS: [&str; 12] = [
+    "nav", "header", "footer", "aside", "form", "noscript", "iframe", "button", "select", "svg",
+    "dialog", "menu",
+];
+
+/// Roles of the elements that are never part of the main content
+const BOILERPLATE_ROLES: [&str; 7] = [
+    "navigation",
+    "banner",
+    "contentinfo",
+    "complementary",
+    "dialog",
+    "alertdialog",
+    "search",
+];
+
+/// Words of the class and id of the elements that are unlikely to be the main content
+const NEGATIVE_WORDS: [&str; 24] = [
+    "ad-",
+    "advert",
+    "banner",
+    "breadcrumb",
+    "combx",
+    "comment",
+    "consent",
+    "cookie",
+    "disqus",
+    "footer",
+    "header",
+    "masthead",
+    "menu",
+    "modal",
+    "nav",
+    "newsletter",
+    "popup",
+    "promo",
+    "related",
+    "share",
+    "sidebar",
+    "social",
+    "sponsor",
+    "subscribe",
+];
+
+/// Words of the class and id of the elements that are likely to be the main content
+const POSITIVE_WORDS: [&str; 10] = [
+    "article", "blog", "body", "content", "entry", "main", "page", "post", "story", "text",
+];
+
+/// The elements whose text is scored
+const TEXT_BLOCKS: &str = "p, pre, td, blockquote";
+
+/// The minimum number of characters of a scored text block
+const MIN_BLOCK_LENGTH: usize = 25;
+
+/// The main content of an html page, with the metadata of the article
+pub(crate) struct Article<'a> {
+    pub(crate) content: ElementRef<'a>,
+    pub(crate) title: Option<String>,
+    pub(crate) byline: Option<String>,
+    pub(crate) published: Option<String>,
+}
+
+impl<'a> Article<'a> {
+    /// Finds the main content of the page, or its body when no text block is found. Returns None
+    /// for a page without body
+    pub(crate) fn find(document: &'a Html) -> Option<Self> {
+        let body = document.select(&selector("body")).next()?;
+        let content = best_candidate(document).unwrap_or(body);
+        Some(Self {
+            content,
+            title: find_title(document, content),
+            byline: find_byline(document),
+            published: find_published(document, content),
+        })
+    }
+}
+
+/// Whether the element is boilerplate, such as the navigation, a banner or a sidebar
+pub(crate) fn is_boilerplate(element: &Element) -> bool {
+    if BOILERPLATE_ELEMENTS.contains(&element.name())
+        || element.attr("hidden").is_some()
+        || element.attr("aria-hidden") == Some("true")
+    {
+        return true;
+    }
+    if let Some(role) = element.attr("role") {
+        if BOILERPLATE_ROLES.contains(&role) {
+            return true;
+        }
+    }
+    match element.name() {
+        "html" | "body" | "article" | "main" => false,
+        _ => class_weight(element) < 0,
+    }
+}
+
+/// -25 for the elements whose class or id has negative words only, 25 for the ones with
+/// positive words, 0 otherwise
+fn class_weight(element: &Element) -> i32 {
+    let names = [element.attr("class"), element.attr("id")]
+        .iter()
+        .flatten()
+        .map(|name| name.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let has = |words: &[&str]| words.iter().any(|word| names.contains(word));
+    match (has(&POSITIVE_WORDS), has(&NEGATIVE_WORDS)) {
+        (true, _) => 25,
+        (false, true) => -25,
+        (false, false) => 0,
+    }
+}
+
+/// The initial score of a candidate, from its tag and class
+fn initial_score(element: &Element) -> f64 {
+    let tag_weight = match element.name() {
+        "article" | "main" => 10,
+        "div" => 5,
+        "pre" | "td" | "blockquote" => 3,
+        "ol" | "ul" | "dl" | "dd" | "dt" | "li" | "form" => -3,
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "th" => -5,
+        _ => 0,
+    };
+    f64::from(tag_weight + class_weight(element))
+}
+
+/// The candidate with the best score weighted by its link density
+fn best_candidate(document: &Html) -> Option<ElementRef<'_>> {
+    // The candidates and their scores by node id
+    let mut scores = HashMap::new();
+    for block in document.select(&selector(TEXT_BLOCKS)) {
+        if in_boilerplate(block) {
+            continue;
+        }
+        let text: String = block.text().collect();
+        let length = text.trim().chars().count();
+        if length < MIN_BLOCK_LENGTH {
+            continue;
+        }
+        let score = 1.0 + text.matches(',').count() as f64 + (length as f64 / 100.0).min(3.0);
+
+        let parent = block.parent().and_then(ElementRef::wrap);
+        let grandparent = parent.and_then(|p| p.parent()).and_then(ElementRef::wrap);
+        for (candidate, share) in [(parent, 1.0), (grandparent, 0.5)] {
+            if let Some(candidate) = candidate {
+                scores
+                    .entry(candidate.id())
+                    .or_insert_with(|| (candidate, initial_score(candidate.value())))
+                    .1 += score * share;
+            }
+        }
+    }
+    scores
+        .into_values()
+        .map(|(candidate, score)| (candidate, score * (1.0 - link_density(candidate))))
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(candidate, _)| candidate)
+}
+
+/// Whether the element or one of its ancestors is boilerplate
+fn in_boilerplate(element: ElementRef) -> bool {
+    std::iter::once(*element)
+        .chain(element.ancestors())
+        .filter_map(|node| node.value().as_element())
+        .any(is_boilerplate)
+}
+
+/// The fraction of the text of the element that is in links
+fn link_density(element: ElementRef) -> f64 {
+    let length = text_length(element);
+    if length == 0 {
+        return 0.0;
+    }
+    let link_length: usize = element.select(&selector("a")).map(text_length).sum();
+    link_length as f64 / length as f64
+}
+
+fn text_length(element: ElementRef) -> usize {
+    element.text().map(|text| text.trim().chars().count()).sum()
+}
+
+/// The `og:title`, the first heading of the content or the first heading of the page
+fn find_title(document: &Html, content: ElementRef) -> Option<String> {
+    meta_content(
+        document,
+        "meta[property='og:title'], meta[name='twitter:title']",
+    )
+    .or_else(|| first_text(content.select(&selector("h1"))))
+    .or_else(|| first_text(document.select(&selector("h1"))))
+}
+
+/// The author meta tag or the first author element of the page
+fn find_byline(document: &Html) -> Option<String> {
+    meta_content(document, "meta[name='author'], meta[name='byl']").or_else(|| {
+        first_text(document.select(&selector(
+            "[rel='author'], [itemprop='author'], .byline, .author",
+        )))
+    })
+}
+
+/// The publication date meta tag or the first datetime of the content or of the page
+fn find_published(document: &Html, content: ElementRef) -> Option<String> {
+    meta_content(
+        document,
+        "meta[property='article:published_time'], meta[itemprop='datePublished'], \
+         meta[name='date'], meta[name='pubdate'], meta[name='publishdate'], \
+         meta[name='publish-date'], meta[name='dc.date'], meta[name='DC.date.issued']",
+    )
+    .or_else(|| {
+        let datetime = selector("[itemprop='datePublished'][datetime], time[datetime]");
+        content
+            .select(&datetime)
+            .chain(document.select(&datetime))
+            .find_map(|time| time.value().attr("datetime"))
+            .map(|datetime| datetime.trim().to_string())
+    })
+}
+
+fn meta_content(document: &Html, meta_selector: &str) -> Option<String> {
+    document
+        .select(&selector(meta_selector))
+        .filter_map(|meta| meta.value().attr("content"))
+        .map(str::trim)
+        .find(|content| !content.is_empty())
+        .map(str::to_string)
+}
+
+/// The first non empty text of the elements, with its whitespace collapsed
+fn first_text<'a>(mut elements: impl Iterator<Item = ElementRef<'a>>) -> Option<String> {
+    elements.find_map(|element| {
+        let text = element.text().collect::<Vec<_>>().join(" ");
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        (!text.is_empty()).then_some(text)
+    })
+}
+
+fn selector(s: &str) -> Selector {
+    Selector::parse(s).expect("valid selector")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_article_test() {
+        let html = "<html><head><title>Big news | The Daily</title>\
+            <meta property=\"article:published_time\" content=\"2024-05-01T08:00:00Z\"></head>\
+            <body><nav><a href=\"/\">Home</a> <a href=\"/world\">World</a></nav>\
+            <div id=\"cookie-banner\"><p>We use cookies to improve your experience, accept them.</p></div>\
+            <div class=\"article-body\"><h1>Big news today</h1>\
+            <span class=\"byline\">By  Jane Doe</span>\
+            <p>The first paragraph of the article, with enough text to be scored.</p>\
+            <p>The second paragraph, which also has commas, clauses, and length.</p></div>\
+            <footer><p>Copyright The Daily, all rights reserved, since 1900.</p></footer>\
+            </body></html>";
+        let document = Html::parse_document(html);
+        let article = Article::find(&document).unwrap();
+        assert_eq!(article.content.value().attr("class"), Some("article-body"));
+        assert_eq!(article.title.as_deref(), Some("Big news today"));
+        assert_eq!(article.byline.as_deref(), Some("By Jane Doe"));
+        assert_eq!(article.published.as_deref(), Some("2024-05-01T08:00:00Z"));
+
+        // A page without text blocks falls back to its body
+        let document = Html::parse_document("<html><body><span>Short</span></body></html>");
+        let article = Article::find(&document).unwrap();
+        assert_eq!(article.content.value().name(), "body");
+        assert_eq!(article.title, None);
+    }
+
+    #[test]
+    fn is_boilerplate_test() {
+        let document = Html::parse_document(
+            "<div class=\"cookie-consent\"></div><div class=\"post-content share\"></div>\
+             <div role=\"navigation\"></div><nav></nav><div></div>",
+        );
+        let boilerplate: Vec<bool> = document
+            .select(&selector("div, nav"))
+            .map(|element| is_boilerplate(element.value()))
+            .collect();
+        assert_eq!(boilerplate, vec![true, false, true, true, false]);
+    }
+}
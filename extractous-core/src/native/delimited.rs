@@ -0,0 +1,57 @@
+use super::{text, ContentSink};
+use crate::Metadata;
+use csv::ReaderBuilder;
+
+// Tika csv metadata keys
+const NUM_ROWS_KEY: &str = "csv:num_rows";
+const NUM_COLUMNS_KEY: &str = "csv:num_columns";
+const DELIMITER_KEY: &str = "csv:delimiter";
+
+/// Parses comma or tab separated values into a table, one row per record, and adds the number
+/// of rows and columns to the metadata. Text that can not be read as delimited values is parsed
+/// as plain text
+pub(crate) fn parse(
+    text: &str,
+    delimiter: u8,
+    sink: &mut ContentSink,
+    metadata: &mut Metadata,
+) -> &'static str {
+    let records: Result<Vec<_>, _> = ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(text.as_bytes())
+        .into_records()
+        .collect();
+    let records = match records {
+        Ok(records) => records,
+        Err(_) => return text::parse(text, sink),
+    };
+
+    sink.start_element("table", &[]);
+    for record in records.iter() {
+        sink.start_element("tr", &[]);
+        for field in record.iter() {
+            sink.element("td", field);
+        }
+        sink.end_element("tr");
+    }
+    sink.end_element("table");
+
+    let num_columns = records.iter().map(|r| r.len()).max().unwrap_or_default();
+    let mut set = |key: &str, value: String| {
+        metadata.insert(key.to_string(), vec![value]);
+    };
+    set(NUM_ROWS_KEY, records.len().to_string());
+    set(NUM_COLUMNS_KEY, num_columns.to_string());
+    set(DELIMITER_KEY, delimiter_name(delimiter).to_string());
+    text::PARSER_NAME
+}
+
+/// The name tika gives to a delimiter, also used in the `Content-Type` parameters
+pub(crate) fn delimiter_name(delimiter: u8) -> &'static str {
+    match delimiter {
+        b'\t' => "tab",
+        _ => "comma",
+    }
+}
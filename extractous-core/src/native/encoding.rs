@@ -0,0 +1,40 @@
+use chardetng::EncodingDetector;
+use encoding_rs::{Encoding, UTF_8};
+
+/// Number of bytes checked for NUL bytes to tell text from binary content
+const BINARY_SNIFF_LENGTH: usize = 8192;
+
+/// Returns true if the bytes look like binary content rather than text. Text in UTF-16 is
+/// recognized only with a byte order mark
+pub(crate) fn is_binary(bytes: &[u8]) -> bool {
+    Encoding::for_bom(bytes).is_none() && bytes.iter().take(BINARY_SNIFF_LENGTH).any(|b| *b == 0)
+}
+
+/// Name of the encoding reported for ASCII text, which tika detects as ISO-8859-1
+const ASCII_ENCODING_NAME: &str = "ISO-8859-1";
+
+/// Decodes text of unknown encoding. The encoding is taken from the byte order mark if any,
+/// otherwise valid UTF-8 is decoded as UTF-8 and other bytes as the encoding guessed by
+/// chardetng. Returns the text and the name of the encoding, e.g. "UTF-8" or "windows-1252".
+/// Like tika, ASCII text is reported as ISO-8859-1
+pub(crate) fn decode(bytes: &[u8]) -> (String, &'static str) {
+    if let Some((encoding, bom_length)) = Encoding::for_bom(bytes) {
+        let (text, _) = encoding.decode_without_bom_handling(&bytes[bom_length..]);
+        return (text.into_owned(), encoding.name());
+    }
+    if bytes.is_ascii() {
+        return (
+            String::from_utf8_lossy(bytes).into_owned(),
+            ASCII_ENCODING_NAME,
+        );
+    }
+    if let Ok(text) = std::str::from_utf8(bytes) {
+        return (text.to_string(), UTF_8.name());
+    }
+
+    let mut detector = EncodingDetector::new();
+    detector.feed(bytes, true);
+    let encoding = detector.guess(None, true);
+    let (text, _) = encoding.decode_without_bom_handling(bytes);
+    (text.into_owned(), encoding.name())
+}
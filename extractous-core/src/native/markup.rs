@@ -0,0 +1,141 @@
+use super::article::{self, Article};
+use super::{ContentSink, CREATED_KEY, CREATOR_KEY, TITLE_KEY};
+use crate::Metadata;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use scraper::node::Node;
+use scraper::{ElementRef, Html, Selector};
+
+/// Names of the tika parsers of these documents, reported in the `X-TIKA:Parsed-By` metadata
+const XML_PARSER_NAME: &str = "org.apache.tika.parser.xml.DcXMLParser";
+const HTML_PARSER_NAME: &str = "org.apache.tika.parser.html.HtmlParser";
+
+/// Html elements kept in the xhtml output with their allowed attributes, the other elements
+/// are replaced by their content
+const HTML_SAFE_ELEMENTS: [(&str, &[&str]); 28] = [
+    ("p", &[]),
+    ("h1", &[]),
+    ("h2", &[]),
+    ("h3", &[]),
+    ("h4", &[]),
+    ("h5", &[]),
+    ("h6", &[]),
+    ("pre", &[]),
+    ("blockquote", &[]),
+    ("div", &[]),
+    ("ul", &[]),
+    ("ol", &[]),
+    ("li", &[]),
+    ("dl", &[]),
+    ("dt", &[]),
+    ("dd", &[]),
+    ("table", &[]),
+    ("thead", &[]),
+    ("tbody", &[]),
+    ("tfoot", &[]),
+    ("tr", &[]),
+    ("th", &["colspan", "rowspan"]),
+    ("td", &["colspan", "rowspan"]),
+    ("caption", &[]),
+    ("a", &["href", "name", "rel"]),
+    ("img", &["src", "alt"]),
+    ("br", &[]),
+    ("hr", &[]),
+];
+
+/// Html elements whose content is not text
+const HTML_SKIPPED_ELEMENTS: [&str; 3] = ["script", "style", "template"];
+
+/// Parses xml into a paragraph with the text of all the elements, like tika's `XMLParser`.
+/// Returns None for malformed xml
+pub(crate) fn parse_xml(text: &str, sink: &mut ContentSink) -> Option<&'static str> {
+    let mut reader = Reader::from_str(text);
+    let mut content = String::new();
+    loop {
+        match reader.read_event().ok()? {
+            Event::Text(t) => content.push_str(&t.unescape().ok()?),
+            Event::CData(t) => content.push_str(&String::from_utf8_lossy(&t)),
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    sink.element("p", &content);
+    Some(XML_PARSER_NAME)
+}
+
+/// Parses html into xhtml with the structural elements of the body. The title and the named
+/// meta tags of the head are added to the metadata. With `main_content`, only the main content
+/// of the body is kept, and the title, byline and publication date of the article are added to
+/// the metadata as `dc:title`, `dc:creator` and `dcterms:created`
+pub(crate) fn parse_html(
+    text: &str,
+    sink: &mut ContentSink,
+    metadata: &mut Metadata,
+    main_content: bool,
+) -> &'static str {
+    let document = Html::parse_document(text);
+
+    let selector = |s: &str| Selector::parse(s).expect("valid selector");
+    if let Some(title) = document.select(&selector("head title")).next() {
+        let title = title.text().collect::<String>().trim().to_string();
+        metadata.insert(TITLE_KEY.to_string(), vec![title]);
+    }
+    for meta in document.select(&selector("meta[name][content]")) {
+        let element = meta.value();
+        if let (Some(name), Some(content)) = (element.attr("name"), element.attr("content")) {
+            metadata
+                .entry(name.to_string())
+                .or_default()
+                .push(content.to_string());
+        }
+    }
+
+    if main_content {
+        if let Some(article) = Article::find(&document) {
+            let mut set = |key: &str, value: Option<String>| {
+                if let Some(value) = value {
+                    metadata.insert(key.to_string(), vec![value]);
+                }
+            };
+            set(TITLE_KEY, article.title);
+            set(CREATOR_KEY, article.byline);
+            set(CREATED_KEY, article.published);
+            add_html_children(article.content, sink, main_content);
+        }
+    } else if let Some(body) = document.select(&selector("body")).next() {
+        add_html_children(body, sink, main_content);
+    }
+    HTML_PARSER_NAME
+}
+
+/// Adds the children of the element to the sink, without the boilerplate with `main_content`
+fn add_html_children(element: ElementRef, sink: &mut ContentSink, main_content: bool) {
+    for child in element.children() {
+        match child.value() {
+            Node::Text(text) => sink.characters(text),
+            Node::Element(child_element) => {
+                let name = child_element.name();
+                if main_content && article::is_boilerplate(child_element) {
+                    continue;
+                }
+                let child = match ElementRef::wrap(child) {
+                    Some(child) if !HTML_SKIPPED_ELEMENTS.contains(&name) => child,
+                    _ => continue,
+                };
+                match HTML_SAFE_ELEMENTS.iter().find(|(safe, _)| *safe == name) {
+                    Some((_, allowed)) => {
+                        let attributes: Vec<(&str, &str)> = allowed
+                            .iter()
+                            .filter_map(|a| child_element.attr(a).map(|value| (*a, value)))
+                            .collect();
+                        sink.start_element(name, &attributes);
+                        add_html_children(child, sink, main_content);
+                        sink.end_element(name);
+                    }
+                    None => add_html_children(child, sink, main_content),
+                }
+            }
+            _ => {}
+        }
+    }
+}
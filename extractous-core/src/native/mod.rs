@@ -0,0 +1,422 @@
+//! Native Rust parsers of the text formats. Documents of these formats are parsed without
+//! going through the JVM, with the same text and xhtml output and metadata keys as tika.
+
+use crate::errors::ExtractResult;
+use crate::{CharSet, Metadata};
+use std::path::Path;
+
+mod article;
+mod delimited;
+mod encoding;
+mod markup;
+mod sink;
+mod text;
+
+pub use sink::ContentSink;
+
+// Tika metadata keys
+pub(crate) const RESOURCE_NAME_KEY: &str = "resourceName";
+pub(crate) const CONTENT_TYPE_KEY: &str = "Content-Type";
+pub(crate) const CONTENT_LENGTH_KEY: &str = "Content-Length";
+pub(crate) const CONTENT_ENCODING_KEY: &str = "Content-Encoding";
+pub(crate) const PARSED_BY_KEY: &str = "X-TIKA:Parsed-By";
+pub(crate) const PARSED_BY_FULL_SET_KEY: &str = "X-TIKA:Parsed-By-Full-Set";
+pub(crate) const TITLE_KEY: &str = "dc:title";
+pub(crate) const CREATOR_KEY: &str = "dc:creator";
+pub(crate) const CREATED_KEY: &str = "dcterms:created";
+
+/// Name of the tika parser delegating to the parser of the detected type, reported first in
+/// the `X-TIKA:Parsed-By` metadata
+const DEFAULT_PARSER_NAME: &str = "org.apache.tika.parser.DefaultParser";
+
+/// The document types parsed natively
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NativeType {
+    Text,
+    Markdown,
+    Csv,
+    Tsv,
+    Json,
+    Xml,
+    Html,
+}
+
+impl NativeType {
+    /// The mime type tika detects for documents of this type
+    pub(crate) fn mime_type(&self) -> &'static str {
+        match self {
+            NativeType::Text => "text/plain",
+            NativeType::Markdown => "text/x-web-markdown",
+            NativeType::Csv => "text/csv",
+            NativeType::Tsv => "text/tab-separated-values",
+            NativeType::Json => "application/json",
+            NativeType::Xml => "application/xml",
+            NativeType::Html => "text/html",
+        }
+    }
+
+    fn from_extension(extension: &str) -> Option<Self> {
+        Some(match extension.to_ascii_lowercase().as_str() {
+            "txt" | "text" | "log" => NativeType::Text,
+            "md" | "markdown" => NativeType::Markdown,
+            "csv" => NativeType::Csv,
+            "tsv" | "tab" => NativeType::Tsv,
+            "json" => NativeType::Json,
+            "xml" => NativeType::Xml,
+            "html" | "htm" | "xhtml" => NativeType::Html,
+            _ => return None,
+        })
+    }
+
+    fn from_mime_type(mime_type: &str) -> Option<Self> {
+        let essence = mime_type.split(';').next().unwrap_or_default().trim();
+        Some(match essence.to_ascii_lowercase().as_str() {
+            "text/plain" => NativeType::Text,
+            "text/markdown" | "text/x-web-markdown" => NativeType::Markdown,
+            "text/csv" => NativeType::Csv,
+            "text/tab-separated-values" => NativeType::Tsv,
+            "application/json" => NativeType::Json,
+            "application/xml" | "text/xml" => NativeType::Xml,
+            "text/html" | "application/xhtml+xml" => NativeType::Html,
+            _ => return None,
+        })
+    }
+
+    /// Detects the type of a file from its extension
+    pub(crate) fn detect_file(file_path: &str) -> Option<Self> {
+        Path::new(file_path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .and_then(Self::from_extension)
+    }
+
+    /// Detects html documents from their first tag, for the bytes without metadata hints
+    pub(crate) fn sniff_html(buffer: &[u8]) -> bool {
+        let start = String::from_utf8_lossy(&buffer[..buffer.len().min(1024)]).to_lowercase();
+        let start = start.trim_start_matches(|c: char| c == '\u{FEFF}' || c.is_whitespace());
+        start.starts_with("<!doctype html")
+            || start.starts_with("<html")
+            || (start.starts_with("<!--") && start.contains("<html"))
+    }
+
+    /// Detects the type of a document from the `resourceName` extension or the `Content-Type`
+    /// of the metadata hints
+    pub(crate) fn detect_hints(metadata_hints: &Metadata) -> Option<Self> {
+        let first = |key: &str| metadata_hints.get(key).and_then(|v| v.first());
+        first(RESOURCE_NAME_KEY)
+            .and_then(|name| Self::detect_file(name))
+            .or_else(|| first(CONTENT_TYPE_KEY).and_then(|m| Self::from_mime_type(m)))
+    }
+}
+
+/// Parses a document natively into the sink. Returns the sink and the metadata hints completed
+/// with the parser metadata. Returns None when the content does not match the detected type,
+/// e.g. binary content or malformed xml, to fall back to tika. With `html_main_content`, only the
+/// main content of html documents is extracted.
+pub(crate) fn parse(
+    buffer: &[u8],
+    native_type: NativeType,
+    metadata_hints: &Metadata,
+    html_main_content: bool,
+    mut sink: ContentSink,
+) -> ExtractResult<Option<(ContentSink, Metadata)>> {
+    if encoding::is_binary(buffer) {
+        return Ok(None);
+    }
+    let (text, encoding_name) = encoding::decode(buffer);
+
+    let mut metadata = metadata_hints.clone();
+    let parser_name = match native_type {
+        NativeType::Text | NativeType::Markdown | NativeType::Json => text::parse(&text, &mut sink),
+        NativeType::Csv => delimited::parse(&text, b',', &mut sink, &mut metadata),
+        NativeType::Tsv => delimited::parse(&text, b'\t', &mut sink, &mut metadata),
+        NativeType::Xml => match markup::parse_xml(&text, &mut sink) {
+            Some(parser_name) => parser_name,
+            None => return Ok(None),
+        },
+        NativeType::Html => markup::parse_html(&text, &mut sink, &mut metadata, html_main_content),
+    };
+
+    // Like tika, the charset and the delimiter are parameters of the content type, except for
+    // xml whose encoding is not reported
+    let content_type = match native_type {
+        NativeType::Xml => native_type.mime_type().to_string(),
+        NativeType::Csv => format!(
+            "{}; charset={}; delimiter={}",
+            native_type.mime_type(),
+            encoding_name,
+            delimited::delimiter_name(b',')
+        ),
+        NativeType::Tsv => format!(
+            "{}; charset={}; delimiter={}",
+            native_type.mime_type(),
+            encoding_name,
+            delimited::delimiter_name(b'\t')
+        ),
+        _ => format!("{}; charset={}", native_type.mime_type(), encoding_name),
+    };
+    let content_length = metadata
+        .get(CONTENT_LENGTH_KEY)
+        .cloned()
+        .unwrap_or_else(|| vec![buffer.len().to_string()]);
+    let parsers = vec![DEFAULT_PARSER_NAME.to_string(), parser_name.to_string()];
+    metadata.insert(CONTENT_TYPE_KEY.to_string(), vec![content_type]);
+    if native_type != NativeType::Xml {
+        metadata.insert(
+            CONTENT_ENCODING_KEY.to_string(),
+            vec![encoding_name.to_string()],
+        );
+    }
+    metadata.insert(PARSED_BY_KEY.to_string(), parsers.clone());
+    metadata.insert(PARSED_BY_FULL_SET_KEY.to_string(), parsers);
+    metadata.insert(CONTENT_LENGTH_KEY.to_string(), content_length);
+
+    Ok(Some((sink, metadata)))
+}
+
+/// Encodes natively extracted content with the charset of the extractor streams. Characters
+/// that can not be encoded in US-ASCII are replaced by `?`
+pub(crate) fn encode(content: String, charset: &CharSet) -> Vec<u8> {
+    match charset {
+        CharSet::UTF_8 => content.into_bytes(),
+        CharSet::US_ASCII => content
+            .chars()
+            .map(|c| if c.is_ascii() { c as u8 } else { b'?' })
+            .collect(),
+        CharSet::UTF_16BE => content
+            .encode_utf16()
+            .flat_map(|unit| unit.to_be_bytes())
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::Event;
+
+    fn parse_buffer(
+        buffer: &[u8],
+        native_type: NativeType,
+        as_xml: bool,
+    ) -> Option<(String, Metadata)> {
+        let sink = ContentSink::new(as_xml);
+        let (sink, metadata) =
+            parse(buffer, native_type, &Metadata::new(), false, sink).unwrap()?;
+        Some((sink.finish(&metadata), metadata))
+    }
+
+    fn parse_text(content: &str, native_type: NativeType, as_xml: bool) -> (String, Metadata) {
+        parse_buffer(content.as_bytes(), native_type, as_xml).unwrap()
+    }
+
+    #[test]
+    fn detect_test() {
+        assert_eq!(
+            NativeType::detect_file("docs/README.md"),
+            Some(NativeType::Markdown)
+        );
+        assert_eq!(NativeType::detect_file("data.CSV"), Some(NativeType::Csv));
+        assert_eq!(NativeType::detect_file("report.pdf"), None);
+        assert_eq!(NativeType::detect_file("Makefile"), None);
+
+        let hints = Metadata::from([(
+            CONTENT_TYPE_KEY.to_string(),
+            vec!["text/html; charset=utf-8".to_string()],
+        )]);
+        assert_eq!(NativeType::detect_hints(&hints), Some(NativeType::Html));
+        assert_eq!(NativeType::detect_hints(&Metadata::new()), None);
+    }
+
+    #[test]
+    fn parse_text_test() {
+        let (content, metadata) = parse_text("Hello\nWorld & co", NativeType::Text, false);
+        assert_eq!(content, "Hello\nWorld & co\n");
+        assert_eq!(
+            metadata[CONTENT_TYPE_KEY],
+            vec!["text/plain; charset=ISO-8859-1"]
+        );
+        assert_eq!(metadata[CONTENT_ENCODING_KEY], vec!["ISO-8859-1"]);
+        assert_eq!(metadata[CONTENT_LENGTH_KEY], vec!["16"]);
+
+        let (_content, metadata) = parse_text("Grüße", NativeType::Text, false);
+        assert_eq!(metadata[CONTENT_ENCODING_KEY], vec!["UTF-8"]);
+
+        let (content, _metadata) = parse_text("Hello & co", NativeType::Text, true);
+        assert!(content.starts_with("<?xml"));
+        assert!(content.contains("<body><p>Hello &amp; co</p>\n</body></html>"));
+    }
+
+    #[test]
+    fn parse_encoded_text_test() {
+        // "Grüße" in windows-1252 and in UTF-16LE with a byte order mark
+        let latin = b"Gr\xfc\xdfe aus M\xfcnchen, sch\xf6ne Gr\xfc\xdfe";
+        let (content, metadata) = parse_buffer(latin, NativeType::Text, false).unwrap();
+        assert_eq!(content, "Grüße aus München, schöne Grüße\n");
+        assert_eq!(metadata[CONTENT_ENCODING_KEY], vec!["windows-1252"]);
+
+        let utf16: Vec<u8> = [0xff, 0xfe]
+            .into_iter()
+            .chain("Grüße".encode_utf16().flat_map(|c| c.to_le_bytes()))
+            .collect();
+        let (content, metadata) = parse_buffer(&utf16, NativeType::Text, false).unwrap();
+        assert_eq!(content, "Grüße\n");
+        assert_eq!(metadata[CONTENT_ENCODING_KEY], vec!["UTF-16LE"]);
+    }
+
+    #[test]
+    fn parse_binary_falls_back_test() {
+        let binary = b"%PDF-1.4\x00\x01\x02";
+        assert!(parse_buffer(binary, NativeType::Text, false).is_none());
+        assert!(parse_buffer(b"<a><b></a>", NativeType::Xml, false).is_none());
+    }
+
+    #[test]
+    fn encode_test() {
+        assert_eq!(
+            encode("Grüße".to_string(), &CharSet::UTF_8),
+            "Grüße".as_bytes()
+        );
+        assert_eq!(encode("Grüße".to_string(), &CharSet::US_ASCII), b"Gr??e");
+        assert_eq!(
+            encode("Gr".to_string(), &CharSet::UTF_16BE),
+            [0, b'G', 0, b'r']
+        );
+    }
+
+    #[test]
+    fn parse_csv_test() {
+        let (content, metadata) =
+            parse_text("name,age\n\"Doe, John\",42\n", NativeType::Csv, false);
+        assert_eq!(content, "\tname\tage\n\tDoe, John\t42\n\n");
+        assert_eq!(
+            metadata[CONTENT_TYPE_KEY],
+            vec!["text/csv; charset=ISO-8859-1; delimiter=comma"]
+        );
+        assert_eq!(metadata["csv:num_rows"], vec!["2"]);
+        assert_eq!(metadata["csv:num_columns"], vec!["2"]);
+        assert_eq!(metadata["csv:delimiter"], vec!["comma"]);
+
+        let (content, _metadata) = parse_text("a\tb\n", NativeType::Tsv, true);
+        assert!(content.contains("<table><tr>\t<td>a</td>\t<td>b</td></tr>\n</table>\n"));
+    }
+
+    #[test]
+    fn parse_json_test() {
+        // Like tika, json is extracted as plain text
+        let json = r#"{"title": "Report", "tags": ["a", "b"]}"#;
+        let (content, metadata) = parse_text(json, NativeType::Json, false);
+        assert_eq!(content, format!("{}\n", json));
+        assert_eq!(
+            metadata[CONTENT_TYPE_KEY],
+            vec!["application/json; charset=ISO-8859-1"]
+        );
+        assert_eq!(
+            metadata[PARSED_BY_KEY],
+            vec![DEFAULT_PARSER_NAME, text::PARSER_NAME]
+        );
+    }
+
+    #[test]
+    fn parse_xml_test() {
+        let xml = "<?xml version=\"1.0\"?><note><to>Tove</to> <body>Don't &amp; <![CDATA[forget]]></body></note>";
+        let (content, metadata) = parse_text(xml, NativeType::Xml, false);
+        assert_eq!(content, "Tove Don't & forget\n");
+        assert_eq!(metadata[CONTENT_TYPE_KEY], vec!["application/xml"]);
+        assert!(!metadata.contains_key(CONTENT_ENCODING_KEY));
+        assert_eq!(
+            metadata[PARSED_BY_FULL_SET_KEY],
+            vec![
+                DEFAULT_PARSER_NAME,
+                "org.apache.tika.parser.xml.DcXMLParser"
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_html_test() {
+        let html = "<html><head><title>Page</title><meta name=\"author\" content=\"Jane\">\
+                    <style>p {}</style></head><body><h1>Title</h1><p>Some <b>bold</b> \
+                    <a href=\"https://example.com\">link</a><br>next</p>\
+                    <script>var x;</script><ul><li>one</li></ul></body></html>";
+        let (content, metadata) = parse_text(html, NativeType::Html, false);
+        assert_eq!(content, "Title\nSome bold link\nnext\n\tone\n\n");
+        assert_eq!(metadata[TITLE_KEY], vec!["Page"]);
+        assert_eq!(metadata["author"], vec!["Jane"]);
+
+        let (content, _metadata) = parse_text(html, NativeType::Html, true);
+        assert!(content.contains("<title>Page</title>"));
+        assert!(content.contains("<a href=\"https://example.com\">link</a><br></br>\nnext</p>"));
+    }
+
+    #[test]
+    fn parse_html_main_content_test() {
+        let html = "<!DOCTYPE html><html><head><title>News | Site</title>\
+                    <meta name=\"author\" content=\"Jane Doe\"></head><body>\
+                    <nav><a href=\"/\">Home</a></nav><div class=\"consent\">Accept cookies</div>\
+                    <article><h1>Headline</h1><time datetime=\"2024-05-01\">May 1</time>\
+                    <p>The article text, long enough to be the main content of the page.</p>\
+                    <aside>Read also</aside></article><footer>Contact</footer></body></html>";
+        let sink = ContentSink::new(false);
+        let (sink, metadata) = parse(
+            html.as_bytes(),
+            NativeType::Html,
+            &Metadata::new(),
+            true,
+            sink,
+        )
+        .unwrap()
+        .unwrap();
+        let content = sink.finish(&metadata);
+        assert_eq!(
+            content,
+            "Headline\nMay 1The article text, long enough to be the main content of the page.\n"
+        );
+        assert_eq!(metadata[TITLE_KEY], vec!["Headline"]);
+        assert_eq!(metadata[CREATOR_KEY], vec!["Jane Doe"]);
+        assert_eq!(metadata[CREATED_KEY], vec!["2024-05-01"]);
+
+        assert!(NativeType::sniff_html(html.as_bytes()));
+        assert!(NativeType::sniff_html(
+            b"\xEF\xBB\xBF  <html><body></body></html>"
+        ));
+        assert!(!NativeType::sniff_html(b"%PDF-1.7 <html>"));
+    }
+
+    #[test]
+    fn parse_events_test() {
+        let sink = ContentSink::events();
+        let (sink, metadata) = parse(b"Hello", NativeType::Text, &Metadata::new(), false, sink)
+            .unwrap()
+            .unwrap();
+        let events = sink.finish_events(&metadata);
+
+        let start = |name: &str| Event::StartElement {
+            name: name.to_string(),
+            attributes: Vec::new(),
+        };
+        let end = |name: &str| Event::EndElement {
+            name: name.to_string(),
+        };
+        assert_eq!(events.first(), Some(&start("html")));
+        assert!(events.contains(&Event::StartElement {
+            name: "meta".to_string(),
+            attributes: vec![
+                ("name".to_string(), CONTENT_ENCODING_KEY.to_string()),
+                ("content".to_string(), "ISO-8859-1".to_string()),
+            ],
+        }));
+        let body = events.iter().position(|e| *e == start("body")).unwrap();
+        assert_eq!(
+            events[body + 1..],
+            [
+                start("p"),
+                Event::Characters("Hello".to_string()),
+                end("p"),
+                Event::Characters("\n".to_string()),
+                end("body"),
+                end("html"),
+            ]
+        );
+    }
+}
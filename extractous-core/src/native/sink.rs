@@ -0,0 +1,213 @@
+use crate::events::Event;
+use crate::Metadata;
+
+/// Elements followed by a new line in the text output, like tika's `XHTMLContentHandler`
+const ENDLINE_ELEMENTS: &str = "p h1 h2 h3 h4 h5 h6 div ul ol dl pre hr blockquote address \
+                                fieldset table form noscript li dt dd br tr";
+
+/// Elements preceded by a tab in the text output, like tika's `XHTMLContentHandler`
+const INDENT_ELEMENTS: &str = "li dd dt td th";
+
+fn is_one_of(name: &str, elements: &str) -> bool {
+    elements.split_whitespace().any(|element| element == name)
+}
+
+/// What a [`ContentSink`] writes the body of the document to
+enum Output {
+    Text(String),
+    Xml(String),
+    Events(Vec<Event>),
+}
+
+/// Receives the xhtml body of a document from a parser and writes it as text or as xhtml, with
+/// the same whitespace as the tika output. Elements are xhtml elements such as `p`, `h1`, `table`,
+/// `tr`, `td`, `ul`, `li` or `a`, the `html`, `head` and `body` elements are added by the sink.
+pub struct ContentSink {
+    output: Output,
+}
+
+impl ContentSink {
+    pub(crate) fn new(as_xml: bool) -> Self {
+        let output = if as_xml {
+            Output::Xml(String::new())
+        } else {
+            Output::Text(String::new())
+        };
+        Self { output }
+    }
+
+    /// Creates a sink recording the body as [`Event`]s, taken with [`ContentSink::finish_events`]
+    pub(crate) fn events() -> Self {
+        Self {
+            output: Output::Events(Vec::new()),
+        }
+    }
+
+    /// Starts an xhtml element of the body, the attributes are (name, value) pairs
+    pub fn start_element(&mut self, name: &str, attributes: &[(&str, &str)]) {
+        if is_one_of(name, INDENT_ELEMENTS) {
+            self.whitespace("\t");
+        }
+        match &mut self.output {
+            Output::Text(_) => {}
+            Output::Xml(body) => {
+                body.push('<');
+                body.push_str(name);
+                for (key, value) in attributes {
+                    body.push(' ');
+                    body.push_str(key);
+                    body.push_str("=\"");
+                    escape_into(body, value);
+                    body.push('"');
+                }
+                body.push('>');
+            }
+            Output::Events(events) => events.push(Event::StartElement {
+                name: name.to_string(),
+                attributes: attributes
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), value.to_string()))
+                    .collect(),
+            }),
+        }
+    }
+
+    /// Ends the xhtml element started by [`ContentSink::start_element`]
+    pub fn end_element(&mut self, name: &str) {
+        match &mut self.output {
+            Output::Text(_) => {}
+            Output::Xml(body) => {
+                body.push_str("</");
+                body.push_str(name);
+                body.push('>');
+            }
+            Output::Events(events) => events.push(Event::EndElement {
+                name: name.to_string(),
+            }),
+        }
+        if is_one_of(name, ENDLINE_ELEMENTS) {
+            self.whitespace("\n");
+        }
+    }
+
+    /// Adds text to the current element
+    pub fn characters(&mut self, text: &str) {
+        match &mut self.output {
+            Output::Text(body) => body.push_str(text),
+            Output::Xml(body) => escape_into(body, text),
+            Output::Events(events) => events.push(Event::Characters(text.to_string())),
+        }
+    }
+
+    /// Adds an element containing only text
+    pub fn element(&mut self, name: &str, text: &str) {
+        self.start_element(name, &[]);
+        self.characters(text);
+        self.end_element(name);
+    }
+
+    /// Adds the whitespace tika's `XHTMLContentHandler` adds around some elements
+    fn whitespace(&mut self, whitespace: &str) {
+        match &mut self.output {
+            Output::Text(body) | Output::Xml(body) => body.push_str(whitespace),
+            Output::Events(events) => events.push(Event::Characters(whitespace.to_string())),
+        }
+    }
+
+    /// Returns the text, or the xhtml document with the metadata in its head. Returns an empty
+    /// string for a sink recording events
+    pub(crate) fn finish(self, metadata: &Metadata) -> String {
+        let body = match self.output {
+            Output::Text(body) => return body,
+            Output::Xml(body) => body,
+            Output::Events(_) => return String::new(),
+        };
+
+        let mut xhtml = String::with_capacity(body.len() + 1024);
+        xhtml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>");
+        xhtml.push_str("<html xmlns=\"http://www.w3.org/1999/xhtml\">\n<head>\n");
+        for (key, value) in sorted_metadata(metadata) {
+            xhtml.push_str("<meta name=\"");
+            escape_into(&mut xhtml, key);
+            xhtml.push_str("\" content=\"");
+            escape_into(&mut xhtml, value);
+            xhtml.push_str("\"/>\n");
+        }
+        xhtml.push_str("<title>");
+        escape_into(&mut xhtml, title(metadata));
+        xhtml.push_str("</title>\n</head>\n<body>");
+        xhtml.push_str(&body);
+        xhtml.push_str("</body></html>");
+        xhtml
+    }
+
+    /// Returns the events of the xhtml document with the metadata in its head, like the events
+    /// of tika's parsers. Returns no events for a sink writing text or xhtml
+    pub(crate) fn finish_events(self, metadata: &Metadata) -> Vec<Event> {
+        let body = match self.output {
+            Output::Events(body) => body,
+            Output::Text(_) | Output::Xml(_) => return Vec::new(),
+        };
+
+        let start = |name: &str, attributes: Vec<(String, String)>| Event::StartElement {
+            name: name.to_string(),
+            attributes,
+        };
+        let end = |name: &str| Event::EndElement {
+            name: name.to_string(),
+        };
+        let mut events = Vec::with_capacity(body.len() + 2 * metadata.len() + 10);
+        events.push(start("html", Vec::new()));
+        events.push(start("head", Vec::new()));
+        for (key, value) in sorted_metadata(metadata) {
+            let attributes = vec![
+                ("name".to_string(), key.to_string()),
+                ("content".to_string(), value.to_string()),
+            ];
+            events.push(start("meta", attributes));
+            events.push(end("meta"));
+        }
+        events.push(start("title", Vec::new()));
+        events.push(Event::Characters(title(metadata).to_string()));
+        events.push(end("title"));
+        events.push(end("head"));
+        events.push(start("body", Vec::new()));
+        events.extend(body);
+        events.push(end("body"));
+        events.push(end("html"));
+        events
+    }
+}
+
+/// The metadata (key, value) pairs sorted by key
+fn sorted_metadata(metadata: &Metadata) -> Vec<(&str, &str)> {
+    let mut keys: Vec<&String> = metadata.keys().collect();
+    keys.sort();
+    keys.into_iter()
+        .flat_map(|key| {
+            metadata[key]
+                .iter()
+                .map(|value| (key.as_str(), value.as_str()))
+        })
+        .collect()
+}
+
+fn title(metadata: &Metadata) -> &str {
+    metadata
+        .get("dc:title")
+        .and_then(|t| t.first())
+        .map(|t| t.as_str())
+        .unwrap_or_default()
+}
+
+fn escape_into(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+}
@@ -0,0 +1,12 @@
+use super::ContentSink;
+
+/// Name of the tika parser of plain text and delimited values, reported in the
+/// `X-TIKA:Parsed-By` metadata
+pub(crate) const PARSER_NAME: &str = "org.apache.tika.parser.csv.TextAndCSVParser";
+
+/// Parses plain text into a single paragraph. Like tika, markdown and json are extracted as
+/// plain text
+pub(crate) fn parse(text: &str, sink: &mut ContentSink) -> &'static str {
+    sink.element("p", text);
+    PARSER_NAME
+}
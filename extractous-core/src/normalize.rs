@@ -0,0 +1,358 @@
+//! Post-processing of the extracted text. A [`TextNormalizer`] set with
+//! [`Extractor::set_text_normalizer`] cleans up the text of the string and stream outputs with
+//! the opt-in steps it enables, applied in this order:
+//! * control characters stripping
+//! * ligatures expansion, e.g. `ﬁ` to `fi`
+//! * Unicode normalization to NFC or NFKC
+//! * dehyphenation of the words split at the end of a line, and removal of the soft hyphens
+//! * whitespace collapsing
+//!
+//! The streams are normalized chunk by chunk, split between two letters so that the steps give
+//! the same text as on the whole string.
+//!
+//! [`Extractor::set_text_normalizer`]: crate::Extractor::set_text_normalizer
+
+use crate::native;
+use crate::CharSet;
+use serde_json::{json, Value};
+use strum_macros::{Display, EnumString};
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// Unicode normalization form of [`TextNormalizer::set_unicode_form`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(non_camel_case_types)]
+pub enum UnicodeForm {
+    /// Canonical composition, e.g. `e` followed by a combining acute accent becomes `é`
+    NFC,
+    /// Compatibility composition, which also replaces the compatibility characters such as the
+    /// ligatures, the full width forms or the superscripts, e.g. `²` becomes `2`
+    NFKC,
+}
+
+/// Text normalization settings. All the steps are disabled by default
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+pub struct TextNormalizer {
+    pub(crate) strip_control_chars: bool,
+    pub(crate) expand_ligatures: bool,
+    pub(crate) unicode_form: Option<UnicodeForm>,
+    pub(crate) dehyphenate: bool,
+    pub(crate) collapse_whitespace: bool,
+}
+
+impl TextNormalizer {
+    /// Creates a new instance of TextNormalizer with all the steps disabled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Removes the control characters, except the tabs and line breaks.
+    /// Default: false
+    pub fn set_strip_control_chars(mut self, val: bool) -> Self {
+        self.strip_control_chars = val;
+        self
+    }
+
+    /// Expands the Latin typographic ligatures, such as `ﬁ`, `ﬂ` or `ﬀ`, to their letters.
+    /// Default: false
+    pub fn set_expand_ligatures(mut self, val: bool) -> Self {
+        self.expand_ligatures = val;
+        self
+    }
+
+    /// Normalizes the text to the Unicode normalization form, see [`UnicodeForm`].
+    /// Default: None
+    pub fn set_unicode_form(mut self, val: Option<UnicodeForm>) -> Self {
+        self.unicode_form = val;
+        self
+    }
+
+    /// Joins the words hyphenated at the end of a line when the next line starts with a
+    /// lowercase letter, e.g. `extrac-` and `tion` become `extraction`, and removes the soft
+    /// hyphens.
+    /// Default: false
+    pub fn set_dehyphenate(mut self, val: bool) -> Self {
+        self.dehyphenate = val;
+        self
+    }
+
+    /// Replaces the runs of spaces and tabs with a single space, trims the lines and keeps at
+    /// most one blank line between two lines.
+    /// Default: false
+    pub fn set_collapse_whitespace(mut self, val: bool) -> Self {
+        self.collapse_whitespace = val;
+        self
+    }
+
+    /// The steps by name, hashed in the keys of the extraction cache
+    pub(crate) fn cache_key(&self) -> Value {
+        json!({
+            "strip_control_chars": self.strip_control_chars,
+            "expand_ligatures": self.expand_ligatures,
+            "unicode_form": self.unicode_form.map(|form| form.to_string()),
+            "dehyphenate": self.dehyphenate,
+            "collapse_whitespace": self.collapse_whitespace,
+        })
+    }
+
+    /// Whether at least one step is enabled
+    pub fn is_enabled(&self) -> bool {
+        *self != Self::default()
+    }
+
+    /// Normalizes the text with the enabled steps
+    pub fn normalize(&self, text: &str) -> String {
+        let mut text = text.to_string();
+        if self.strip_control_chars {
+            text.retain(|c| !c.is_control() || matches!(c, '\t' | '\n' | '\r'));
+        }
+        if self.expand_ligatures {
+            text = expand_ligatures(&text);
+        }
+        match self.unicode_form {
+            Some(UnicodeForm::NFC) => text = text.nfc().collect(),
+            Some(UnicodeForm::NFKC) => text = text.nfkc().collect(),
+            None => (),
+        }
+        if self.dehyphenate {
+            text = dehyphenate(&text);
+        }
+        if self.collapse_whitespace {
+            text = collapse_whitespace(&text);
+        }
+        text
+    }
+}
+
+fn expand_ligatures(text: &str) -> String {
+    let mut expanded = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\u{FB00}' => expanded.push_str("ff"),
+            '\u{FB01}' => expanded.push_str("fi"),
+            '\u{FB02}' => expanded.push_str("fl"),
+            '\u{FB03}' => expanded.push_str("ffi"),
+            '\u{FB04}' => expanded.push_str("ffl"),
+            '\u{FB05}' | '\u{FB06}' => expanded.push_str("st"),
+            _ => expanded.push(c),
+        }
+    }
+    expanded
+}
+
+const SOFT_HYPHEN: char = '\u{AD}';
+
+/// Removes the hyphen and the line break between a letter and a lowercase letter, and the soft
+/// hyphens with the line break that follows them
+fn dehyphenate(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut dehyphenated = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if matches!(c, '-' | '\u{2010}' | SOFT_HYPHEN) {
+            let follows_letter = i > 0 && chars[i - 1].is_alphabetic();
+            if let Some(next) = next_line_start(&chars, i + 1) {
+                if follows_letter && chars[next].is_lowercase() {
+                    i = next;
+                    continue;
+                }
+            }
+        }
+        if c != SOFT_HYPHEN {
+            dehyphenated.push(c);
+        }
+        i += 1;
+    }
+    dehyphenated
+}
+
+/// The index of the first character of the next line, when only whitespace with a single line
+/// break separates it from the index
+fn next_line_start(chars: &[char], start: usize) -> Option<usize> {
+    let mut line_breaks = 0;
+    let position = chars[start..].iter().position(|c| {
+        if *c == '\n' {
+            line_breaks += 1;
+        }
+        !c.is_whitespace()
+    })?;
+    (line_breaks == 1).then_some(start + position)
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    let mut collapsed = String::with_capacity(text.len());
+    let mut blank_lines = 0;
+    for (i, line) in text.split('\n').enumerate() {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        if words.is_empty() {
+            blank_lines += 1;
+            if i > 0 && blank_lines == 1 {
+                collapsed.push('\n');
+            }
+            continue;
+        }
+        if i > 0 {
+            collapsed.push('\n');
+        }
+        blank_lines = 0;
+        collapsed.push_str(&words.join(" "));
+    }
+    collapsed
+}
+
+/// Normalizes a stream chunk by chunk. The decoded text is normalized up to the last split
+/// point between two letters, the rest is kept until the next chunk or the end of the stream
+pub(crate) struct StreamNormalizer {
+    normalizer: TextNormalizer,
+    encoding: CharSet,
+    decoder: encoding_rs::Decoder,
+    pending: String,
+    output: Vec<u8>,
+    position: usize,
+    finished: bool,
+}
+
+/// The size of the chunks read from the stream
+const CHUNK_SIZE: usize = 8192;
+
+impl StreamNormalizer {
+    pub(crate) fn new(normalizer: TextNormalizer, encoding: CharSet) -> Self {
+        // The JVM replaces the characters that are not ASCII by `?`, decoded as UTF-8
+        let decoder = match encoding {
+            CharSet::UTF_16BE => encoding_rs::UTF_16BE.new_decoder_without_bom_handling(),
+            CharSet::UTF_8 | CharSet::US_ASCII => {
+                encoding_rs::UTF_8.new_decoder_without_bom_handling()
+            }
+        };
+        Self {
+            normalizer,
+            encoding,
+            decoder,
+            pending: String::new(),
+            output: Vec::new(),
+            position: 0,
+            finished: false,
+        }
+    }
+
+    /// Reads the normalized stream, reading the chunks of the source with `read_source`
+    pub(crate) fn read<F>(&mut self, buf: &mut [u8], mut read_source: F) -> std::io::Result<usize>
+    where
+        F: FnMut(&mut [u8]) -> std::io::Result<usize>,
+    {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let mut chunk = [0u8; CHUNK_SIZE];
+        while self.position == self.output.len() {
+            if self.finished {
+                return Ok(0);
+            }
+            let read = read_source(&mut chunk)?;
+            self.finished = read == 0;
+            let max_length = self
+                .decoder
+                .max_utf8_buffer_length(read)
+                .unwrap_or(CHUNK_SIZE * 3);
+            self.pending.reserve(max_length);
+            let _ = self
+                .decoder
+                .decode_to_string(&chunk[..read], &mut self.pending, self.finished);
+
+            let split = match self.finished {
+                true => self.pending.len(),
+                false => split_point(&self.pending),
+            };
+            let rest = self.pending.split_off(split);
+            let text = std::mem::replace(&mut self.pending, rest);
+            self.output = native::encode(self.normalizer.normalize(&text), &self.encoding);
+            self.position = 0;
+        }
+
+        let read = buf.len().min(self.output.len() - self.position);
+        buf[..read].copy_from_slice(&self.output[self.position..self.position + read]);
+        self.position += read;
+        Ok(read)
+    }
+}
+
+/// The byte index of the last split point of the text, between two letters or digits that are
+/// not combining marks or Hangul vowels and trailing consonants, which could combine with the
+/// previous character. None of the steps changes the text around such a point
+fn split_point(text: &str) -> usize {
+    let is_stable = |c: char| {
+        c.is_alphanumeric() && !is_combining_mark(c) && !('\u{1160}'..='\u{11FF}').contains(&c)
+    };
+    let mut chars = text.char_indices().rev().peekable();
+    while let Some((i, c)) = chars.next() {
+        if let Some((_, previous)) = chars.peek() {
+            if is_stable(c) && is_stable(*previous) {
+                return i;
+            }
+        }
+    }
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    fn all_steps() -> TextNormalizer {
+        TextNormalizer::new()
+            .set_strip_control_chars(true)
+            .set_expand_ligatures(true)
+            .set_unicode_form(Some(UnicodeForm::NFC))
+            .set_dehyphenate(true)
+            .set_collapse_whitespace(true)
+    }
+
+    #[test]
+    fn normalize_test() {
+        let text =
+            "The e\u{301}tude of the \u{FB01}nal extrac-\n  tion\u{7}  was  \n\n\n\nwell-\nKnown \
+                    and hy\u{AD}phen\u{AD}\nated\t.";
+        assert_eq!(
+            all_steps().normalize(text),
+            "The \u{E9}tude of the final extraction was\n\nwell-\nKnown and hyphenated ."
+        );
+        assert_eq!(TextNormalizer::new().normalize(text), text);
+        assert!(!TextNormalizer::new().is_enabled());
+        assert_eq!(
+            TextNormalizer::new()
+                .set_unicode_form(Some(UnicodeForm::NFKC))
+                .normalize("\u{FB01}x\u{B2}"),
+            "fix2"
+        );
+    }
+
+    #[test]
+    fn stream_normalizer_test() {
+        let text = "Extrac-\ntion of the \u{FB02}ow,  line\u{AD}\nbreaks   \n\n\n and e\u{301}. "
+            .repeat(2000);
+        for encoding in [CharSet::UTF_8, CharSet::UTF_16BE] {
+            let mut source = std::io::Cursor::new(native::encode(text.clone(), &encoding));
+            let mut normalizer = StreamNormalizer::new(all_steps(), encoding);
+            let mut normalized = Vec::new();
+            let mut buf = [0u8; 1000];
+            loop {
+                let read = normalizer
+                    .read(&mut buf, |chunk| source.read(chunk))
+                    .unwrap();
+                if read == 0 {
+                    break;
+                }
+                normalized.extend_from_slice(&buf[..read]);
+            }
+            assert_eq!(
+                normalized,
+                native::encode(all_steps().normalize(&text), &encoding)
+            );
+        }
+    }
+}
@@ -0,0 +1,446 @@
+//! OCR results and diagnostics.
+//!
+//! The words recognized by tesseract, with their position on the page, are read from the hOCR
+//! markup that tika adds to the xml output with [`crate::OcrOutputType::HOCR`].
+//!
+//! [`diagnose`] checks the tesseract installation used for the OCR. Tika silently skips the
+//! OCR when tesseract is not found and fails with an unhelpful parse error when a language is
+//! not installed, see also [`crate::Extractor::validate`].
+
+use crate::errors::{Error, ExtractResult};
+use crate::TesseractOcrConfig;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Bounding box of an OCRed word in pixels of the OCRed image, `(x0, y0)` is the top left
+/// corner and `(x1, y1)` the bottom right corner
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OcrBoundingBox {
+    pub x0: u32,
+    pub y0: u32,
+    pub x1: u32,
+    pub y1: u32,
+}
+
+/// A word recognized by tesseract. Returned by [`crate::Extractor::extract_file_to_ocr_words`]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OcrWord {
+    /// Text of the word
+    pub text: String,
+    /// Bounding box of the word
+    pub bbox: OcrBoundingBox,
+    /// Confidence of tesseract in the recognized text, from 0 to 100
+    pub confidence: f32,
+    /// Page of the word, starting at 1. The page number for pdf pages, otherwise the number
+    /// of the OCRed image in the document
+    pub page: u32,
+    /// Line of the word in its page, starting at 1
+    pub line: u32,
+}
+
+/// hOCR classes of the elements containing a line of text
+const HOCR_LINE_CLASSES: [&str; 4] = ["ocr_line", "ocr_header", "ocr_caption", "ocr_textfloat"];
+
+/// The elements of the xhtml output relevant to the OCR results
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Element {
+    Body,
+    PdfPage,
+    OcrPage,
+    Paragraph,
+    Line,
+    Word,
+    Other,
+}
+
+/// Builds the text and the words of an xhtml document containing hOCR markup
+#[derive(Default)]
+struct HocrReader {
+    elements: Vec<Element>,
+    text: String,
+    words: Vec<OcrWord>,
+    word: Option<OcrWord>,
+    pdf_pages: u32,
+    ocr_pages: u32,
+    line: u32,
+    line_has_words: bool,
+}
+
+impl HocrReader {
+    fn is_inside(&self, element: Element) -> bool {
+        self.elements.contains(&element)
+    }
+
+    fn page(&self) -> u32 {
+        if self.is_inside(Element::PdfPage) {
+            self.pdf_pages
+        } else {
+            self.ocr_pages
+        }
+    }
+
+    fn start(&mut self, e: &BytesStart) -> ExtractResult<()> {
+        let class = match e.try_get_attribute("class").map_err(xml_error)? {
+            Some(class) => class.unescape_value().map_err(xml_error)?.into_owned(),
+            None => String::new(),
+        };
+        let has_class = |name: &str| class.split_whitespace().any(|c| c == name);
+
+        let element = if e.local_name().as_ref() == b"body" {
+            Element::Body
+        } else if e.local_name().as_ref() == b"div" && has_class("page") {
+            self.pdf_pages += 1;
+            self.line = 0;
+            Element::PdfPage
+        } else if has_class("ocr_page") {
+            self.ocr_pages += 1;
+            if !self.is_inside(Element::PdfPage) {
+                self.line = 0;
+            }
+            Element::OcrPage
+        } else if has_class("ocr_par") {
+            Element::Paragraph
+        } else if HOCR_LINE_CLASSES.iter().any(|c| has_class(c)) {
+            self.line += 1;
+            self.line_has_words = false;
+            Element::Line
+        } else if has_class("ocrx_word") {
+            let title = match e.try_get_attribute("title").map_err(xml_error)? {
+                Some(title) => title.unescape_value().map_err(xml_error)?.into_owned(),
+                None => String::new(),
+            };
+            let (bbox, confidence) = parse_title(&title);
+            self.word = Some(OcrWord {
+                text: String::new(),
+                bbox,
+                confidence,
+                page: self.page(),
+                line: self.line,
+            });
+            Element::Word
+        } else {
+            Element::Other
+        };
+        self.elements.push(element);
+        Ok(())
+    }
+
+    fn end(&mut self) {
+        match self.elements.pop() {
+            Some(Element::Word) => {
+                let word = self.word.take().filter(|w| !w.text.trim().is_empty());
+                if let Some(mut word) = word {
+                    word.text = word.text.trim().to_string();
+                    if self.line_has_words {
+                        self.text.push(' ');
+                    }
+                    self.text.push_str(&word.text);
+                    self.line_has_words = true;
+                    self.words.push(word);
+                }
+            }
+            Some(Element::Line) if self.line_has_words => self.text.push('\n'),
+            Some(Element::Paragraph) => self.text.push('\n'),
+            _ => {}
+        }
+    }
+
+    fn characters(&mut self, text: &str) {
+        if let Some(word) = self.word.as_mut() {
+            word.text.push_str(text);
+        } else if self.is_inside(Element::Body) && !self.is_inside(Element::OcrPage) {
+            // The formatting whitespace of the hOCR markup is not part of the text
+            self.text.push_str(text);
+        }
+    }
+}
+
+/// Parses the `bbox x0 y0 x1 y1; x_wconf 95` title of an hOCR word
+fn parse_title(title: &str) -> (OcrBoundingBox, f32) {
+    let mut bbox = OcrBoundingBox::default();
+    let mut confidence = 0.0;
+    for property in title.split(';') {
+        let mut values = property.split_whitespace();
+        match values.next() {
+            Some("bbox") => {
+                let coords: Vec<u32> = values.filter_map(|v| v.parse().ok()).collect();
+                if let [x0, y0, x1, y1] = coords[..] {
+                    bbox = OcrBoundingBox { x0, y0, x1, y1 };
+                }
+            }
+            Some("x_wconf") => {
+                confidence = values.next().and_then(|v| v.parse().ok()).unwrap_or(0.0);
+            }
+            _ => {}
+        }
+    }
+    (bbox, confidence)
+}
+
+fn xml_error(e: impl std::fmt::Display) -> Error {
+    Error::ParseError(format!("Invalid hOCR xhtml: {}", e))
+}
+
+/// Reads the text and the OCRed words of the xhtml output of an extraction with
+/// [`crate::OcrOutputType::HOCR`]. The text of the hOCR elements is rebuilt from their words,
+/// one line per hOCR line. `truncated` tells that the xhtml may end in the middle of an
+/// element because it reached the maximum length, the content read so far is then returned.
+pub(crate) fn read_hocr_xhtml(
+    xhtml: &str,
+    truncated: bool,
+) -> ExtractResult<(String, Vec<OcrWord>)> {
+    let mut reader = Reader::from_str(xhtml);
+    let mut hocr = HocrReader::default();
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => hocr.start(&e)?,
+            Ok(Event::Empty(e)) => {
+                hocr.start(&e)?;
+                hocr.end();
+            }
+            Ok(Event::End(_)) => hocr.end(),
+            Ok(Event::Text(t)) => hocr.characters(&t.unescape().map_err(xml_error)?),
+            Ok(Event::CData(t)) => hocr.characters(&String::from_utf8_lossy(&t)),
+            Ok(Event::Eof) => break,
+            Ok(_) => {}
+            Err(_) if truncated => break,
+            Err(e) => return Err(xml_error(e)),
+        }
+    }
+    Ok((hocr.text, hocr.words))
+}
+
+/// The tesseract installation found by [`diagnose`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OcrDiagnostics {
+    /// The tesseract executable that was run, inside the configured tesseract path if any
+    pub tesseract: PathBuf,
+    /// True if the tesseract executable could be run
+    pub tesseract_found: bool,
+    /// Version of tesseract, e.g. "5.3.0"
+    pub version: Option<String>,
+    /// The installed tessdata languages, e.g. `["eng", "osd"]`
+    pub languages: Vec<String>,
+    /// The reason tesseract or its languages could not be found
+    pub error: Option<String>,
+}
+
+impl OcrDiagnostics {
+    /// Returns the languages of a `+` separated tesseract language setting, e.g. "eng+deu",
+    /// that are not installed
+    pub fn missing_languages(&self, language: &str) -> Vec<String> {
+        language
+            .split('+')
+            .filter(|l| !self.languages.iter().any(|installed| installed == l))
+            .map(|l| l.to_string())
+            .collect()
+    }
+}
+
+/// Checks the tesseract installation on the `PATH`, see [`diagnose_config`]
+pub fn diagnose() -> OcrDiagnostics {
+    diagnose_config(&TesseractOcrConfig::default())
+}
+
+/// Checks the tesseract installation used with the config: runs tesseract, from the
+/// configured tesseract path or the `PATH`, to get its version and the languages installed in
+/// the configured tessdata path or tesseract's default tessdata directory.
+pub fn diagnose_config(config: &TesseractOcrConfig) -> OcrDiagnostics {
+    let executable = if cfg!(windows) {
+        "tesseract.exe"
+    } else {
+        "tesseract"
+    };
+    let tesseract = match config.tesseract_path.as_ref() {
+        Some(path) => PathBuf::from(path).join(executable),
+        None => PathBuf::from(executable),
+    };
+    let mut diagnostics = OcrDiagnostics {
+        tesseract,
+        tesseract_found: false,
+        version: None,
+        languages: Vec::new(),
+        error: None,
+    };
+
+    let output = match Command::new(&diagnostics.tesseract)
+        .arg("--version")
+        .output()
+    {
+        Ok(output) => output,
+        Err(e) => {
+            diagnostics.error = Some(format!(
+                "Tesseract could not be run from {}: {}",
+                diagnostics.tesseract.display(),
+                e
+            ));
+            return diagnostics;
+        }
+    };
+    diagnostics.tesseract_found = true;
+    // Older versions of tesseract print the version on stderr
+    diagnostics.version = parse_version(&String::from_utf8_lossy(&output.stdout))
+        .or_else(|| parse_version(&String::from_utf8_lossy(&output.stderr)));
+
+    let mut command = Command::new(&diagnostics.tesseract);
+    if let Some(tessdata_path) = config.tessdata_path.as_ref() {
+        command.arg("--tessdata-dir").arg(tessdata_path);
+    }
+    match command.arg("--list-langs").output() {
+        Ok(output) if output.status.success() => {
+            diagnostics.languages = parse_languages(&String::from_utf8_lossy(&output.stdout));
+        }
+        Ok(output) => {
+            diagnostics.error = Some(format!(
+                "Tesseract could not list the installed languages: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Err(e) => {
+            diagnostics.error = Some(format!(
+                "Tesseract could not list the installed languages: {}",
+                e
+            ));
+        }
+    }
+    diagnostics
+}
+
+/// Parses the `tesseract 5.3.0` first line of `tesseract --version`
+fn parse_version(output: &str) -> Option<String> {
+    output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("tesseract "))
+        .map(|version| version.trim().trim_start_matches('v').to_string())
+}
+
+/// Parses the output of `tesseract --list-langs`, one language per line after a
+/// `List of available languages in "/usr/share/tessdata/" (2):` header
+fn parse_languages(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with("List of available languages"))
+        .map(|line| line.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOCR_XHTML: &str = r#"<html xmlns="http://www.w3.org/1999/xhtml">
+<head><title>scan</title></head>
+<body><p>Before</p>
+<div class="ocr"><div class='ocr_page' id='page_1' title='image "scan.png"; bbox 0 0 800 600; ppageno 0'>
+   <div class='ocr_carea' id='block_1_1' title="bbox 36 92 618 184">
+    <p class='ocr_par' id='par_1_1' lang='eng' title="bbox 36 92 618 184">
+     <span class='ocr_line' id='line_1_1' title="bbox 36 92 580 122; baseline 0 -6">
+      <span class='ocrx_word' id='word_1_1' title='bbox 36 92 96 116; x_wconf 93'>Hello</span>
+      <span class='ocrx_word' id='word_1_2' title='bbox 109 92 170 116; x_wconf 88.5'>&amp;world</span>
+     </span>
+     <span class='ocr_line' id='line_1_2' title="bbox 36 150 618 184">
+      <span class='ocrx_word' id='word_1_3' title='bbox 36 150 118 184; x_wconf 71'><strong>again</strong></span>
+     </span>
+    </p>
+   </div>
+  </div></div>
+</body></html>"#;
+
+    #[test]
+    fn read_hocr_xhtml_test() {
+        let (text, words) = read_hocr_xhtml(HOCR_XHTML, false).unwrap();
+
+        assert_eq!(text.trim(), "Before\nHello &world\nagain");
+        assert_eq!(words.len(), 3);
+        assert_eq!(
+            words[0],
+            OcrWord {
+                text: "Hello".to_string(),
+                bbox: OcrBoundingBox {
+                    x0: 36,
+                    y0: 92,
+                    x1: 96,
+                    y1: 116
+                },
+                confidence: 93.0,
+                page: 1,
+                line: 1,
+            }
+        );
+        assert_eq!(words[1].text, "&world");
+        assert_eq!(words[1].confidence, 88.5);
+        assert_eq!((words[2].text.as_str(), words[2].line), ("again", 2));
+    }
+
+    #[test]
+    fn read_hocr_xhtml_pdf_pages_test() {
+        let page = |n: u32| {
+            format!(
+                "<div class=\"page\"><div class=\"ocr\"><div class=\"ocr_page\">\
+                 <span class=\"ocr_line\"><span class=\"ocrx_word\" title=\"bbox 1 2 3 4; \
+                 x_wconf 90\">page{}</span></span></div></div></div>",
+                n
+            )
+        };
+        let xhtml = format!("<html><body>{}{}</body></html>", page(1), page(2));
+        let (text, words) = read_hocr_xhtml(&xhtml, false).unwrap();
+
+        assert_eq!(text, "page1\npage2\n");
+        let pages: Vec<(u32, u32)> = words.iter().map(|w| (w.page, w.line)).collect();
+        assert_eq!(pages, vec![(1, 1), (2, 1)]);
+    }
+
+    #[test]
+    fn read_truncated_hocr_xhtml_test() {
+        let truncated = &HOCR_XHTML[..HOCR_XHTML.find("word_1_3").unwrap()];
+        assert!(read_hocr_xhtml(truncated, false).is_err());
+
+        let (_text, words) = read_hocr_xhtml(truncated, true).unwrap();
+        assert_eq!(words.len(), 2);
+    }
+
+    #[test]
+    fn parse_tesseract_output_test() {
+        let version = "tesseract 5.3.0\n leptonica-1.82.0\n  libgif 5.2.1 : libjpeg 6b\n";
+        assert_eq!(parse_version(version), Some("5.3.0".to_string()));
+        assert_eq!(
+            parse_version("tesseract v4.0.0.20181030\n"),
+            Some("4.0.0.20181030".to_string())
+        );
+        assert_eq!(parse_version("unknown"), None);
+
+        let languages =
+            "List of available languages in \"/usr/share/tesseract-ocr/5/tessdata/\" (3):\nara\neng\nosd\n";
+        assert_eq!(parse_languages(languages), vec!["ara", "eng", "osd"]);
+    }
+
+    #[test]
+    fn missing_languages_test() {
+        let diagnostics = OcrDiagnostics {
+            tesseract: PathBuf::from("tesseract"),
+            tesseract_found: true,
+            version: Some("5.3.0".to_string()),
+            languages: vec!["eng".to_string(), "script/Arabic".to_string()],
+            error: None,
+        };
+        assert!(diagnostics
+            .missing_languages("eng+script/Arabic")
+            .is_empty());
+        assert_eq!(diagnostics.missing_languages("eng+deu"), vec!["deu"]);
+    }
+
+    #[test]
+    fn diagnose_missing_tesseract_test() {
+        let diagnostics =
+            diagnose_config(&TesseractOcrConfig::new().set_tesseract_path("/extractous/missing"));
+        assert!(!diagnostics.tesseract_found);
+        assert!(diagnostics.version.is_none());
+        assert!(diagnostics.error.is_some());
+    }
+}
@@ -0,0 +1,487 @@
+//! Outlines of the extracted documents, i.e. their bookmarks or tables of contents.
+//!
+//! The `extract_*_outline` functions of the [`crate::Extractor`] return the outline of a
+//! document as a tree of [`OutlineEntry`], read from the best source available for its type:
+//! * the bookmarks of pdfs, read with pdfbox without extracting the text of the pages, with the
+//!   page every bookmark points to
+//! * the navigation document of epubs, the EPUB 3 `nav` document or else the EPUB 2 `toc.ncx`,
+//!   read from the archive without parsing the book
+//! * the headings of the other documents and of the epubs without navigation document: the
+//!   `h1` to `h6` elements of their xhtml, such as the heading styles of docx and odt documents,
+//!   nested by level. The headings of the embedded documents are skipped
+
+use crate::errors::{Error, ExtractResult};
+use crate::events::ContentHandler;
+use crate::Metadata;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use scraper::{ElementRef, Html, Selector};
+use std::io::{Read, Seek};
+use zip::ZipArchive;
+
+/// An entry of the outline of a document. Returned by [`crate::Extractor::extract_file_outline`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OutlineEntry {
+    /// Title of the entry, with its runs of whitespace collapsed to a single space
+    pub title: String,
+    /// Level of the entry, starting at 1. For headings the level of the heading, e.g. 2 for
+    /// `h2`, so that the children of an entry can skip levels
+    pub level: u32,
+    /// Page the entry points to, starting at 1, for the bookmarks of pdfs
+    pub page: Option<u32>,
+    /// The entries nested under this entry
+    pub children: Vec<OutlineEntry>,
+}
+
+impl OutlineEntry {
+    fn new(title: &str, level: u32, page: Option<u32>) -> Self {
+        Self {
+            title: title.split_whitespace().collect::<Vec<_>>().join(" "),
+            level,
+            page,
+            children: Vec::new(),
+        }
+    }
+}
+
+/// Nests the entries listed in document order under the previous entry of lower level
+fn into_tree(entries: Vec<OutlineEntry>) -> Vec<OutlineEntry> {
+    let mut roots = Vec::new();
+    // The entry being filled and its ancestors
+    let mut path: Vec<OutlineEntry> = Vec::new();
+    let close = |path: &mut Vec<OutlineEntry>, roots: &mut Vec<OutlineEntry>| {
+        if let Some(entry) = path.pop() {
+            match path.last_mut() {
+                Some(parent) => parent.children.push(entry),
+                None => roots.push(entry),
+            }
+        }
+    };
+    for entry in entries {
+        while path.last().is_some_and(|last| last.level >= entry.level) {
+            close(&mut path, &mut roots);
+        }
+        path.push(entry);
+    }
+    while !path.is_empty() {
+        close(&mut path, &mut roots);
+    }
+    roots
+}
+
+/// The source of the outline of a document, sniffed from its first bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OutlineSource {
+    Pdf,
+    Epub,
+    Headings,
+}
+
+impl OutlineSource {
+    pub(crate) fn sniff(head: &[u8]) -> Self {
+        let contains = |text: &[u8], limit: usize| {
+            head[..head.len().min(limit)]
+                .windows(text.len())
+                .any(|window| window == text)
+        };
+        if contains(b"%PDF-", 1024) {
+            OutlineSource::Pdf
+        } else if head.starts_with(b"PK\x03\x04") && contains(b"application/epub+zip", 128) {
+            // The stored `mimetype` file of the epubs is the first file of the archive
+            OutlineSource::Epub
+        } else {
+            OutlineSource::Headings
+        }
+    }
+}
+
+/// Builds the outline of the lines written by the java `PdfOutline`: the level, page and title
+/// of every bookmark separated by tabs, the page is 0 when unknown
+pub(crate) fn parse_pdf_outline(lines: &str) -> Vec<OutlineEntry> {
+    let entries = lines
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            let level = fields.next()?.parse().ok()?;
+            let page = fields.next()?.parse().ok().filter(|page| *page > 0);
+            Some(OutlineEntry::new(fields.next()?, level, page))
+        })
+        .collect();
+    into_tree(entries)
+}
+
+/// Reads the outline of the navigation document of an epub. Returns None for the epubs without
+/// navigation document
+pub(crate) fn read_epub_outline<R: Read + Seek>(
+    reader: R,
+) -> ExtractResult<Option<Vec<OutlineEntry>>> {
+    let mut archive = ZipArchive::new(reader).map_err(epub_error)?;
+    let container = read_archive_file(&mut archive, "META-INF/container.xml")?;
+    let Some(package_path) = xml_attribute(&container, b"rootfile", b"full-path")? else {
+        return Ok(None);
+    };
+    let package = read_archive_file(&mut archive, &package_path)?;
+    let (nav, ncx) = navigation_documents(&package)?;
+
+    // The hrefs of the package are relative to its directory
+    let directory = package_path
+        .rsplit_once('/')
+        .map(|(directory, _)| format!("{}/", directory))
+        .unwrap_or_default();
+    if let Some(nav) = nav {
+        let nav = read_archive_file(&mut archive, &format!("{}{}", directory, nav))?;
+        return Ok(Some(parse_nav(&nav)));
+    }
+    if let Some(ncx) = ncx {
+        let ncx = read_archive_file(&mut archive, &format!("{}{}", directory, ncx))?;
+        return parse_ncx(&ncx).map(Some);
+    }
+    Ok(None)
+}
+
+fn read_archive_file<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    name: &str,
+) -> ExtractResult<String> {
+    let mut file = archive.by_name(name).map_err(epub_error)?;
+    let mut content = String::new();
+    file.read_to_string(&mut content)
+        .map_err(|e| Error::ParseError(format!("Invalid epub file {}: {}", name, e)))?;
+    Ok(content)
+}
+
+/// The value of the attribute of the first element with this local name
+fn xml_attribute(xml: &str, element: &[u8], attribute: &[u8]) -> ExtractResult<Option<String>> {
+    let mut reader = Reader::from_str(xml);
+    loop {
+        match reader.read_event().map_err(epub_error)? {
+            Event::Start(e) | Event::Empty(e) if e.local_name().as_ref() == element => {
+                for attr in e.attributes().flatten() {
+                    if attr.key.local_name().as_ref() == attribute {
+                        let value = attr.unescape_value().map_err(epub_error)?;
+                        return Ok(Some(value.into_owned()));
+                    }
+                }
+            }
+            Event::Eof => return Ok(None),
+            _ => {}
+        }
+    }
+}
+
+/// The hrefs of the EPUB 3 navigation document and of the EPUB 2 ncx of the package document
+fn navigation_documents(package: &str) -> ExtractResult<(Option<String>, Option<String>)> {
+    let mut reader = Reader::from_str(package);
+    let mut nav = None;
+    let mut ncx = None;
+    let mut spine_toc = None;
+    loop {
+        match reader.read_event().map_err(epub_error)? {
+            Event::Start(e) | Event::Empty(e) => {
+                let mut attributes = std::collections::HashMap::new();
+                for attr in e.attributes().flatten() {
+                    let name = String::from_utf8_lossy(attr.key.local_name().as_ref()).into_owned();
+                    let value = attr.unescape_value().map_err(epub_error)?.into_owned();
+                    attributes.insert(name, value);
+                }
+                let get = |name: &str| attributes.get(name).map(String::as_str);
+                match e.local_name().as_ref() {
+                    b"item" if get("properties").is_some_and(|p| has_word(p, "nav")) => {
+                        nav = get("href").map(str::to_string);
+                    }
+                    b"item" if get("media-type") == Some("application/x-dtbncx+xml") => {
+                        ncx = Some((
+                            get("id").map(str::to_string),
+                            get("href").map(str::to_string),
+                        ));
+                    }
+                    b"spine" => spine_toc = get("toc").map(str::to_string),
+                    _ => {}
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    // The ncx is the one of the spine, or the only one of the manifest
+    let ncx = ncx
+        .filter(|(id, _)| spine_toc.is_none() || *id == spine_toc)
+        .and_then(|(_, href)| href);
+    Ok((nav, ncx))
+}
+
+fn has_word(text: &str, word: &str) -> bool {
+    text.split_whitespace().any(|w| w == word)
+}
+
+/// The outline of the `toc` nav element of an EPUB 3 navigation document, or of its first nav
+/// element
+fn parse_nav(html: &str) -> Vec<OutlineEntry> {
+    let document = Html::parse_document(html);
+    let navs = selector("nav");
+    let toc = document
+        .select(&navs)
+        .find(|nav| {
+            nav.value()
+                .attr("epub:type")
+                .is_some_and(|t| has_word(t, "toc"))
+        })
+        .or_else(|| document.select(&navs).next());
+    let mut entries = Vec::new();
+    if let Some(list) = toc.and_then(|toc| toc.select(&selector("ol")).next()) {
+        add_nav_items(list, 1, &mut entries);
+    }
+    into_tree(entries)
+}
+
+/// Adds the `li` of an `ol` of the navigation document and their nested `ol`
+fn add_nav_items(list: ElementRef, level: u32, entries: &mut Vec<OutlineEntry>) {
+    let items = list
+        .children()
+        .filter_map(ElementRef::wrap)
+        .filter(|item| item.value().name() == "li");
+    for item in items {
+        let mut title = None;
+        let mut sublist = None;
+        for child in item.children().filter_map(ElementRef::wrap) {
+            match child.value().name() {
+                "a" | "span" if title.is_none() => title = Some(child.text().collect::<String>()),
+                "ol" => sublist = Some(child),
+                _ => {}
+            }
+        }
+        entries.push(OutlineEntry::new(&title.unwrap_or_default(), level, None));
+        if let Some(sublist) = sublist {
+            add_nav_items(sublist, level + 1, entries);
+        }
+    }
+}
+
+/// The outline of the `navMap` of an EPUB 2 ncx
+fn parse_ncx(xml: &str) -> ExtractResult<Vec<OutlineEntry>> {
+    let mut reader = Reader::from_str(xml);
+    let mut entries = Vec::new();
+    let mut depth: u32 = 0;
+    // The label of the current nav point, while it is read
+    let mut label: Option<String> = None;
+    loop {
+        match reader.read_event().map_err(epub_error)? {
+            Event::Start(e) => match e.local_name().as_ref() {
+                b"navPoint" => depth += 1,
+                b"navLabel" if depth > 0 => label = Some(String::new()),
+                _ => {}
+            },
+            Event::End(e) => match e.local_name().as_ref() {
+                b"navPoint" => depth = depth.saturating_sub(1),
+                b"navLabel" => {
+                    if let Some(label) = label.take() {
+                        entries.push(OutlineEntry::new(&label, depth, None));
+                    }
+                }
+                _ => {}
+            },
+            Event::Text(text) => {
+                if let Some(label) = &mut label {
+                    label.push_str(&text.unescape().map_err(epub_error)?);
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(into_tree(entries))
+}
+
+fn epub_error(e: impl std::fmt::Display) -> Error {
+    Error::ParseError(format!("Invalid epub: {}", e))
+}
+
+fn selector(s: &str) -> Selector {
+    Selector::parse(s).expect("valid selector")
+}
+
+/// A [`ContentHandler`] collecting the headings of a document
+#[derive(Debug, Default)]
+pub(crate) struct HeadingCollector {
+    entries: Vec<OutlineEntry>,
+    /// The heading whose text is being read
+    heading: Option<OutlineEntry>,
+    /// Depth of the current embedded document, 0 for the document itself
+    embedded_depth: usize,
+}
+
+impl HeadingCollector {
+    /// The outline of the headings of the document
+    pub(crate) fn into_outline(self) -> Vec<OutlineEntry> {
+        into_tree(self.entries)
+    }
+}
+
+/// The level of the `h1` to `h6` elements
+fn heading_level(name: &str) -> Option<u32> {
+    match name.as_bytes() {
+        [b'h', level @ b'1'..=b'6'] => Some(u32::from(level - b'0')),
+        _ => None,
+    }
+}
+
+impl ContentHandler for HeadingCollector {
+    fn start_element(&mut self, name: &str, _attributes: &[(String, String)]) -> ExtractResult<()> {
+        if let (0, None, Some(level)) = (self.embedded_depth, &self.heading, heading_level(name)) {
+            self.heading = Some(OutlineEntry::new("", level, None));
+        }
+        Ok(())
+    }
+
+    fn end_element(&mut self, name: &str) -> ExtractResult<()> {
+        let level = self.heading.as_ref().map(|heading| heading.level);
+        if level.is_some() && heading_level(name) == level {
+            if let Some(heading) = self.heading.take() {
+                let heading = OutlineEntry::new(&heading.title, heading.level, None);
+                if !heading.title.is_empty() {
+                    self.entries.push(heading);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn characters(&mut self, text: &str) -> ExtractResult<()> {
+        if let Some(heading) = &mut self.heading {
+            heading.title.push_str(text);
+        }
+        Ok(())
+    }
+
+    fn embedded_start(&mut self, _metadata: &Metadata) -> ExtractResult<()> {
+        self.embedded_depth += 1;
+        Ok(())
+    }
+
+    fn embedded_end(&mut self) -> ExtractResult<()> {
+        self.embedded_depth = self.embedded_depth.saturating_sub(1);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The (level, title, page, number of children) of the entries, depth first
+    fn flatten(entries: &[OutlineEntry]) -> Vec<(u32, &str, Option<u32>, usize)> {
+        entries
+            .iter()
+            .flat_map(|entry| {
+                let summary = (
+                    entry.level,
+                    entry.title.as_str(),
+                    entry.page,
+                    entry.children.len(),
+                );
+                std::iter::once(summary).chain(flatten(&entry.children))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parse_pdf_outline_test() {
+        let lines = "1\t1\tIntroduction\n1\t3\tResults\n2\t3\tRevenue\n3\t0\tBy  region\n\
+                     2\t5\tCosts\n1\t9\tAppendix\ninvalid line\n";
+        let outline = parse_pdf_outline(lines);
+        assert_eq!(outline.len(), 3);
+        assert_eq!(
+            flatten(&outline),
+            vec![
+                (1, "Introduction", Some(1), 0),
+                (1, "Results", Some(3), 2),
+                (2, "Revenue", Some(3), 1),
+                (3, "By region", None, 0),
+                (2, "Costs", Some(5), 0),
+                (1, "Appendix", Some(9), 0),
+            ]
+        );
+        assert!(parse_pdf_outline("").is_empty());
+    }
+
+    #[test]
+    fn collect_headings_test() {
+        let mut collector = HeadingCollector::default();
+        collector.start_element("h2", &[]).unwrap();
+        collector.characters("Preface").unwrap();
+        collector.end_element("h2").unwrap();
+        collector.start_element("h1", &[]).unwrap();
+        collector.characters("Chapter\n ").unwrap();
+        collector.characters("one").unwrap();
+        collector.end_element("h1").unwrap();
+        collector.start_element("h3", &[]).unwrap();
+        collector.characters("Section").unwrap();
+        collector.end_element("h3").unwrap();
+        collector.embedded_start(&Metadata::new()).unwrap();
+        collector.start_element("h1", &[]).unwrap();
+        collector.characters("Attachment").unwrap();
+        collector.end_element("h1").unwrap();
+        collector.embedded_end().unwrap();
+        collector.start_element("h2", &[]).unwrap();
+        collector.end_element("h2").unwrap();
+        assert_eq!(
+            flatten(&collector.into_outline()),
+            vec![
+                (2, "Preface", None, 0),
+                (1, "Chapter one", None, 1),
+                (3, "Section", None, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_navigation_documents_test() {
+        let nav = "<html xmlns:epub=\"http://www.idpf.org/2007/ops\"><body>\
+            <nav epub:type=\"landmarks\"><ol><li><a href=\"cover.xhtml\">Cover</a></li></ol></nav>\
+            <nav epub:type=\"toc\"><h1>Contents</h1><ol>\
+            <li><a href=\"ch1.xhtml\">Chapter 1</a><ol><li><a href=\"ch1.xhtml#s1\">Skiing</a></li></ol></li>\
+            <li><span>Part II</span></li></ol></nav></body></html>";
+        assert_eq!(
+            flatten(&parse_nav(nav)),
+            vec![
+                (1, "Chapter 1", None, 1),
+                (2, "Skiing", None, 0),
+                (1, "Part II", None, 0),
+            ]
+        );
+
+        let ncx = "<ncx><docTitle><text>Book</text></docTitle><navMap>\
+            <navPoint><navLabel><text>Chapter 1</text></navLabel><content src=\"ch1.html\"/>\
+            <navPoint><navLabel><text>Skiing &amp; skating</text></navLabel></navPoint>\
+            </navPoint><navPoint><navLabel><text>Chapter 2</text></navLabel></navPoint>\
+            </navMap></ncx>";
+        assert_eq!(
+            flatten(&parse_ncx(ncx).unwrap()),
+            vec![
+                (1, "Chapter 1", None, 1),
+                (2, "Skiing & skating", None, 0),
+                (1, "Chapter 2", None, 0),
+            ]
+        );
+
+        let package = "<package><manifest>\
+            <item id=\"ncx\" href=\"toc.ncx\" media-type=\"application/x-dtbncx+xml\"/>\
+            <item id=\"nav\" href=\"nav.xhtml\" properties=\"nav scripted\"/></manifest>\
+            <spine toc=\"ncx\"/></package>";
+        assert_eq!(
+            navigation_documents(package).unwrap(),
+            (Some("nav.xhtml".to_string()), Some("toc.ncx".to_string()))
+        );
+    }
+
+    #[test]
+    fn sniff_test() {
+        assert_eq!(OutlineSource::sniff(b"%PDF-1.7\n"), OutlineSource::Pdf);
+        let epub = b"PK\x03\x04\x0a\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\x08\0\0\0\
+                     mimetypeapplication/epub+zip";
+        assert_eq!(OutlineSource::sniff(epub), OutlineSource::Epub);
+        assert_eq!(OutlineSource::sniff(b"PK\x03\x04"), OutlineSource::Headings);
+        assert_eq!(OutlineSource::sniff(b"<html>"), OutlineSource::Headings);
+    }
+}
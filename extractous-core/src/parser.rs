@@ -0,0 +1,268 @@
+//! Custom parsers written in Rust. Parsers registered with [`Extractor::register_parser`] win
+//! over tika and the native parsers for their mime types.
+//!
+//! [`Extractor::register_parser`]: crate::Extractor::register_parser
+
+use crate::errors::ExtractResult;
+use crate::native::{self, ContentSink};
+use crate::Metadata;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::Read;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError, Weak};
+
+/// A parser of documents of some mime types, e.g. an in-house format. The parser writes the
+/// xhtml body of the document to a [`ContentSink`], which produces the same text, xml and
+/// stream outputs as tika.
+///
+/// ```no_run
+/// use extractous::{ContentSink, Error, ExtractResult, Extractor, Metadata, Parser};
+/// use std::io::Read;
+///
+/// struct NotesParser;
+///
+/// impl Parser for NotesParser {
+///     fn supported_types(&self) -> Vec<String> {
+///         vec!["application/x-notes".to_string()]
+///     }
+///
+///     fn parse(
+///         &self,
+///         input: &mut dyn Read,
+///         sink: &mut ContentSink,
+///         metadata: &mut Metadata,
+///     ) -> ExtractResult<()> {
+///         let mut notes = String::new();
+///         input
+///             .read_to_string(&mut notes)
+///             .map_err(|e| Error::IoError(e.to_string()))?;
+///         for note in notes.split("---") {
+///             sink.element("p", note.trim());
+///         }
+///         metadata.insert("notes:count".to_string(), vec![notes.split("---").count().to_string()]);
+///         Ok(())
+///     }
+/// }
+///
+/// let extractor = Extractor::new().register_parser(NotesParser);
+/// let hints = Metadata::from([(
+///     "Content-Type".to_string(),
+///     vec!["application/x-notes".to_string()],
+/// )]);
+/// let (text, metadata) = extractor
+///     .extract_bytes_to_string_with_hints(b"first --- second", &hints)
+///     .unwrap();
+/// ```
+pub trait Parser: Send + Sync {
+    /// The mime types parsed by this parser, e.g. `application/x-notes`
+    fn supported_types(&self) -> Vec<String>;
+
+    /// Parses the document read from `input`. The metadata holds the metadata hints of the
+    /// document, or the metadata found by tika for embedded documents, and receives the
+    /// metadata of the document
+    fn parse(
+        &self,
+        input: &mut dyn Read,
+        sink: &mut ContentSink,
+        metadata: &mut Metadata,
+    ) -> ExtractResult<()>;
+
+    /// The name of the parser reported in the `X-TIKA:Parsed-By` metadata
+    fn name(&self) -> String {
+        std::any::type_name::<Self>().to_string()
+    }
+}
+
+/// The registries of the extractors, by id. The tika side finds the registered parsers by the
+/// id of their registry
+static REGISTRIES: Mutex<BTreeMap<i64, Weak<Registry>>> = Mutex::new(BTreeMap::new());
+static NEXT_REGISTRY_ID: AtomicI64 = AtomicI64::new(1);
+
+struct Registry {
+    id: i64,
+    parsers: Vec<Arc<dyn Parser>>,
+}
+
+impl Drop for Registry {
+    fn drop(&mut self) {
+        REGISTRIES
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&self.id);
+    }
+}
+
+/// The parsers registered on an extractor. Registering a parser creates a new registry, so that
+/// the clones of an extractor keep their parsers
+#[derive(Clone, Default)]
+pub(crate) struct ParserRegistry {
+    registry: Option<Arc<Registry>>,
+}
+
+impl ParserRegistry {
+    /// Returns a new registry with the parsers of this one and the given parser
+    pub(crate) fn register(&self, parser: Arc<dyn Parser>) -> Self {
+        let mut parsers = self.parsers().to_vec();
+        parsers.push(parser);
+        let registry = Arc::new(Registry {
+            id: NEXT_REGISTRY_ID.fetch_add(1, Ordering::Relaxed),
+            parsers,
+        });
+        REGISTRIES
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(registry.id, Arc::downgrade(&registry));
+        Self {
+            registry: Some(registry),
+        }
+    }
+
+    /// Finds the parser at the index of the registry with the given id. Returns None if the
+    /// registry was dropped
+    pub(crate) fn find_registered(id: i64, index: usize) -> Option<Arc<dyn Parser>> {
+        let registry = REGISTRIES
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&id)
+            .and_then(Weak::upgrade)?;
+        // The lock is released before the registry, dropping the last reference removes it from
+        // the registries
+        registry.parsers.get(index).cloned()
+    }
+
+    pub(crate) fn id(&self) -> i64 {
+        self.registry.as_ref().map(|r| r.id).unwrap_or_default()
+    }
+
+    pub(crate) fn parsers(&self) -> &[Arc<dyn Parser>] {
+        self.registry
+            .as_ref()
+            .map(|r| r.parsers.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Returns the last registered parser supporting the mime type. Parameters of the mime type
+    /// such as the charset are ignored
+    pub(crate) fn find(&self, mime_type: &str) -> Option<&Arc<dyn Parser>> {
+        let essence = mime_type_essence(mime_type);
+        self.parsers().iter().rev().find(|parser| {
+            parser
+                .supported_types()
+                .iter()
+                .any(|t| mime_type_essence(t) == essence)
+        })
+    }
+}
+
+impl fmt::Debug for ParserRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.parsers().iter().map(|parser| parser.name()))
+            .finish()
+    }
+}
+
+fn mime_type_essence(mime_type: &str) -> String {
+    mime_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+/// Parses a document with a registered parser into the sink. Returns the sink and the metadata
+/// hints completed with the parser metadata
+pub(crate) fn parse(
+    parser: &dyn Parser,
+    mut buffer: &[u8],
+    mime_type: &str,
+    metadata_hints: &Metadata,
+    mut sink: ContentSink,
+) -> ExtractResult<(ContentSink, Metadata)> {
+    let mut metadata = metadata_hints.clone();
+    metadata
+        .entry(native::CONTENT_TYPE_KEY.to_string())
+        .or_insert_with(|| vec![mime_type.to_string()]);
+
+    parser.parse(&mut buffer, &mut sink, &mut metadata)?;
+
+    // Like tika, the parsers are added to the parsers of the container documents
+    let name = parser.name();
+    for key in [native::PARSED_BY_KEY, native::PARSED_BY_FULL_SET_KEY] {
+        let parsers = metadata.entry(key.to_string()).or_default();
+        if !parsers.contains(&name) {
+            parsers.push(name.clone());
+        }
+    }
+
+    Ok((sink, metadata))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct UpperCaseParser;
+
+    impl Parser for UpperCaseParser {
+        fn supported_types(&self) -> Vec<String> {
+            vec!["application/x-upper".to_string()]
+        }
+
+        fn parse(
+            &self,
+            input: &mut dyn Read,
+            sink: &mut ContentSink,
+            metadata: &mut Metadata,
+        ) -> ExtractResult<()> {
+            let mut text = String::new();
+            input
+                .read_to_string(&mut text)
+                .map_err(|e| crate::Error::IoError(e.to_string()))?;
+            sink.element("p", &text.to_uppercase());
+            metadata.insert("upper:length".to_string(), vec![text.len().to_string()]);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn registry_test() {
+        let empty = ParserRegistry::default();
+        assert!(empty.parsers().is_empty());
+        assert_eq!(empty.id(), 0);
+
+        let registry = empty.register(Arc::new(UpperCaseParser));
+        assert!(registry
+            .find("application/x-upper; charset=UTF-8")
+            .is_some());
+        assert!(registry.find("text/plain").is_none());
+        assert!(ParserRegistry::find_registered(registry.id(), 0).is_some());
+        assert!(ParserRegistry::find_registered(registry.id(), 1).is_none());
+
+        let id = registry.id();
+        drop(registry);
+        assert!(ParserRegistry::find_registered(id, 0).is_none());
+    }
+
+    #[test]
+    fn parse_test() {
+        let (sink, metadata) = parse(
+            &UpperCaseParser,
+            b"hello",
+            "application/x-upper",
+            &Metadata::new(),
+            ContentSink::new(false),
+        )
+        .unwrap();
+        let content = sink.finish(&metadata);
+        assert_eq!(content, "HELLO\n");
+        assert_eq!(metadata["upper:length"], vec!["5"]);
+        assert_eq!(metadata["Content-Type"], vec!["application/x-upper"]);
+        assert_eq!(
+            metadata["X-TIKA:Parsed-By"],
+            vec!["extractous::parser::tests::UpperCaseParser"]
+        );
+    }
+}
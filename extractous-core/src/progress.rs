@@ -0,0 +1,193 @@
+//! Progress of the extractions. The callback set with [`Extractor::set_progress_callback`] is
+//! called from inside the parse with the [`Progress`] of the extracted documents.
+//!
+//! [`Extractor::set_progress_callback`]: crate::Extractor::set_progress_callback
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError, Weak};
+
+/// The output produced is reported every `OUTPUT_STEP` bytes, like on the tika side
+pub(crate) const OUTPUT_STEP: u64 = 64 * 1024;
+
+/// The progress of an extraction, reported to the progress callback of the extractor
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Progress {
+    /// The parsing of the document of the given content type started
+    DocumentStarted { content_type: String },
+    /// A page of a pdf document or a slide of a presentation is parsed. The total is the
+    /// number of pages found in the document metadata, if any
+    PageDone { page: u32, total: Option<u32> },
+    /// An embedded document, such as the file of an archive or the attachment of an email, is
+    /// entered
+    EmbeddedDocumentStarted { resource_name: Option<String> },
+    /// The OCR of a page started. The page is the pdf page being OCRed, or the number of the
+    /// OCRed image for the other documents
+    OcrStarted { page: u32 },
+    /// The OCR of a page finished
+    OcrFinished { page: u32 },
+    /// The total bytes of UTF-8 output produced so far, reported every 64 KiB and at the end of
+    /// the document
+    OutputProduced { bytes: u64 },
+    /// The parsing of the document finished
+    DocumentFinished,
+}
+
+type Callback = dyn Fn(&Progress) + Send + Sync;
+
+/// The progress callbacks of the extractors, by id. The tika side reports the progress to the
+/// id of the callback
+static CALLBACKS: Mutex<BTreeMap<i64, Weak<Reporter>>> = Mutex::new(BTreeMap::new());
+static NEXT_CALLBACK_ID: AtomicI64 = AtomicI64::new(1);
+
+struct Reporter {
+    id: i64,
+    callback: Box<Callback>,
+}
+
+impl Drop for Reporter {
+    fn drop(&mut self) {
+        CALLBACKS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&self.id);
+    }
+}
+
+/// The progress callback of an extractor, shared by its clones. The reporter of an extraction
+/// also tells whether tika collects the stats of the parse
+#[derive(Clone, Default)]
+pub(crate) struct ProgressReporter {
+    reporter: Option<Arc<Reporter>>,
+    collect_stats: bool,
+}
+
+impl ProgressReporter {
+    pub(crate) fn new<F>(callback: F) -> Self
+    where
+        F: Fn(&Progress) + Send + Sync + 'static,
+    {
+        let reporter = Arc::new(Reporter {
+            id: NEXT_CALLBACK_ID.fetch_add(1, Ordering::Relaxed),
+            callback: Box::new(callback),
+        });
+        CALLBACKS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(reporter.id, Arc::downgrade(&reporter));
+        Self {
+            reporter: Some(reporter),
+            collect_stats: false,
+        }
+    }
+
+    /// The reporter of an extraction, collecting the stats of the parse or not
+    pub(crate) fn with_stats(&self, collect_stats: bool) -> Self {
+        Self {
+            reporter: self.reporter.clone(),
+            collect_stats,
+        }
+    }
+
+    pub(crate) fn collects_stats(&self) -> bool {
+        self.collect_stats
+    }
+
+    /// The id of the callback, 0 without callback
+    pub(crate) fn id(&self) -> i64 {
+        self.reporter.as_ref().map(|r| r.id).unwrap_or_default()
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.reporter.is_some()
+    }
+
+    pub(crate) fn report(&self, progress: &Progress) {
+        if let Some(reporter) = &self.reporter {
+            (reporter.callback)(progress);
+        }
+    }
+
+    /// Reports the progress to the callback with the given id. Does nothing if the callback
+    /// was dropped
+    pub(crate) fn report_registered(id: i64, progress: &Progress) {
+        let reporter = CALLBACKS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&id)
+            .and_then(Weak::upgrade);
+        // The lock is released before calling back, the callback may extract other documents
+        if let Some(reporter) = reporter {
+            (reporter.callback)(progress);
+        }
+    }
+
+    /// Reports the progress of a document parsed at once, such as the documents parsed natively
+    pub(crate) fn report_parsed(&self, content_type: &str, output_bytes: u64) {
+        if !self.is_enabled() {
+            return;
+        }
+        self.report(&Progress::DocumentStarted {
+            content_type: content_type.to_string(),
+        });
+        let mut bytes = OUTPUT_STEP;
+        while bytes < output_bytes {
+            self.report(&Progress::OutputProduced { bytes });
+            bytes += OUTPUT_STEP;
+        }
+        self.report(&Progress::OutputProduced {
+            bytes: output_bytes,
+        });
+        self.report(&Progress::DocumentFinished);
+    }
+}
+
+impl fmt::Debug for ProgressReporter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProgressReporter")
+            .field("enabled", &self.is_enabled())
+            .field("collect_stats", &self.collect_stats)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reporter_test() {
+        let disabled = ProgressReporter::default();
+        assert_eq!(disabled.id(), 0);
+        disabled.report(&Progress::DocumentFinished);
+
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let sink = reported.clone();
+        let reporter =
+            ProgressReporter::new(move |p: &Progress| sink.lock().unwrap().push(p.clone()));
+        ProgressReporter::report_registered(reporter.id(), &Progress::OcrStarted { page: 2 });
+        reporter.report_parsed("text/plain", OUTPUT_STEP + 10);
+
+        assert_eq!(
+            *reported.lock().unwrap(),
+            vec![
+                Progress::OcrStarted { page: 2 },
+                Progress::DocumentStarted {
+                    content_type: "text/plain".to_string()
+                },
+                Progress::OutputProduced { bytes: OUTPUT_STEP },
+                Progress::OutputProduced {
+                    bytes: OUTPUT_STEP + 10
+                },
+                Progress::DocumentFinished,
+            ]
+        );
+
+        let id = reporter.id();
+        drop(reporter);
+        ProgressReporter::report_registered(id, &Progress::DocumentFinished);
+        assert_eq!(reported.lock().unwrap().len(), 5);
+    }
+}
@@ -0,0 +1,369 @@
+//! Tracked changes of docx documents, as separate records.
+//!
+//! The `extract_*_revisions` functions of the [`crate::Extractor`] read the tracked changes of
+//! the body of a docx document, without extracting its text with tika: the insertions,
+//! deletions, moves and formatting changes, with their author and date. The text of the document
+//! is returned with the text of all its revisions, so that both the original and the final text
+//! of the document can be rebuilt, see [`DocumentRevisions::original_text`] and
+//! [`DocumentRevisions::final_text`]. The headers, footers, footnotes and comments of the
+//! document are not read.
+//!
+//! To choose the revisions merged in the text extracted by tika, see
+//! [`crate::OfficeParserConfig::set_include_deleted_content`] and
+//! [`crate::OfficeParserConfig::set_include_move_from_content`].
+
+use crate::annotations::{attributes, non_empty, ooxml_error, read_archive_file};
+use crate::errors::{Error, ExtractResult};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::io::{Read, Seek};
+use strum_macros::{Display, EnumString};
+use zip::ZipArchive;
+
+/// Kind of a [`Revision`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RevisionKind {
+    /// Inserted text, part of the final text only
+    Insert,
+    /// Deleted text, part of the original text only
+    Delete,
+    /// Moved text at its original location, part of the original text only
+    MoveFrom,
+    /// Moved text at its new location, part of the final text only
+    MoveTo,
+    /// Text whose formatting changed, part of both texts
+    Format,
+}
+
+/// A tracked change of a docx document. Returned by [`crate::Extractor::extract_file_revisions`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Revision {
+    pub kind: RevisionKind,
+    /// Name of the author of the revision
+    pub author: Option<String>,
+    /// Date of the revision, in ISO 8601
+    pub date: Option<String>,
+    /// Text of the revision. The insertion or deletion of a paragraph break is a `"\n"`
+    pub text: String,
+    /// Offset of the text of the revision in [`DocumentRevisions::text`], in characters, i.e.
+    /// unicode scalar values
+    pub offset: usize,
+}
+
+/// The tracked changes of a docx document
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DocumentRevisions {
+    /// Text of the body of the document with the text of all its revisions, both the inserted
+    /// and the deleted text, in document order. The paragraphs end with a line break
+    pub text: String,
+    /// The revisions with text, in document order. The revisions can overlap, e.g. the text
+    /// inserted by an author and deleted by another one is part of an insertion and of a
+    /// deletion. The revisions without text, such as inserted images, are not listed
+    pub revisions: Vec<Revision>,
+}
+
+impl DocumentRevisions {
+    /// The text of the document before its revisions: without the insertions and the moved text
+    /// at its new location
+    pub fn original_text(&self) -> String {
+        self.text_without(&[RevisionKind::Insert, RevisionKind::MoveTo])
+    }
+
+    /// The text of the document with all its revisions accepted: without the deletions and the
+    /// moved text at its original location
+    pub fn final_text(&self) -> String {
+        self.text_without(&[RevisionKind::Delete, RevisionKind::MoveFrom])
+    }
+
+    fn text_without(&self, kinds: &[RevisionKind]) -> String {
+        let mut removed = vec![false; self.text.chars().count()];
+        for revision in self.revisions.iter().filter(|r| kinds.contains(&r.kind)) {
+            let end = (revision.offset + revision.text.chars().count()).min(removed.len());
+            let start = revision.offset.min(end);
+            removed[start..end]
+                .iter_mut()
+                .for_each(|removed| *removed = true);
+        }
+        self.text
+            .chars()
+            .zip(removed)
+            .filter_map(|(c, removed)| (!removed).then_some(c))
+            .collect()
+    }
+}
+
+/// Reads the tracked changes of the body of a docx archive
+pub(crate) fn read_docx_revisions<R: Read + Seek>(reader: R) -> ExtractResult<DocumentRevisions> {
+    let mut archive = ZipArchive::new(reader).map_err(ooxml_error)?;
+    match read_archive_file(&mut archive, "word/document.xml")? {
+        Some(document) => parse_document(&document),
+        None => Err(Error::ParseError(
+            "Invalid docx document: no word/document.xml".to_string(),
+        )),
+    }
+}
+
+/// A revision being read, closed at the end of the element at `close_depth`
+struct OpenRevision {
+    index: usize,
+    close_depth: usize,
+}
+
+#[derive(Default)]
+struct RevisionReader {
+    revisions: DocumentRevisions,
+    /// Number of characters of the text
+    chars: usize,
+    open: Vec<OpenRevision>,
+    /// The local names of the open elements
+    elements: Vec<Vec<u8>>,
+    /// Depth of the element whose content is skipped, such as the fallback of an alternate
+    /// content or the previous properties of a formatting change
+    skip_depth: Option<usize>,
+    in_text: bool,
+    /// The revisions of the paragraph breaks of the open paragraphs
+    paragraph_marks: Vec<Vec<Revision>>,
+}
+
+impl RevisionReader {
+    fn push_text(&mut self, text: &str) {
+        self.revisions.text.push_str(text);
+        for open in self.open.iter() {
+            self.revisions.revisions[open.index].text.push_str(text);
+        }
+        self.chars += text.chars().count();
+    }
+
+    fn new_revision(&self, kind: RevisionKind, element: &BytesStart) -> ExtractResult<Revision> {
+        let mut attributes = attributes(element)?;
+        Ok(Revision {
+            kind,
+            author: attributes.remove("author").and_then(non_empty),
+            date: attributes.remove("date").and_then(non_empty),
+            text: String::new(),
+            offset: self.chars,
+        })
+    }
+
+    fn open_revision(
+        &mut self,
+        kind: RevisionKind,
+        element: &BytesStart,
+        close_depth: usize,
+    ) -> ExtractResult<()> {
+        let revision = self.new_revision(kind, element)?;
+        self.revisions.revisions.push(revision);
+        self.open.push(OpenRevision {
+            index: self.revisions.revisions.len() - 1,
+            close_depth,
+        });
+        Ok(())
+    }
+
+    /// The depth of the innermost open element with this name inside the innermost paragraph
+    fn depth_of(&self, name: &[u8]) -> Option<usize> {
+        self.elements
+            .iter()
+            .rposition(|element| element == name)
+            .filter(|index| {
+                let paragraph = self.elements.iter().rposition(|e| e == b"p");
+                name == b"p" || paragraph.is_some_and(|paragraph| paragraph < *index)
+            })
+            .map(|index| index + 1)
+    }
+
+    fn parent(&self, generation: usize) -> Option<&[u8]> {
+        let len = self.elements.len();
+        (len > generation).then(|| self.elements[len - 1 - generation].as_slice())
+    }
+
+    /// Handles the start of an element, already pushed to the open elements
+    fn start(&mut self, element: &BytesStart, empty: bool) -> ExtractResult<()> {
+        let depth = self.elements.len();
+        let name = element.local_name();
+        let kind = match name.as_ref() {
+            b"ins" => Some(RevisionKind::Insert),
+            b"del" => Some(RevisionKind::Delete),
+            b"moveFrom" => Some(RevisionKind::MoveFrom),
+            b"moveTo" => Some(RevisionKind::MoveTo),
+            _ => None,
+        };
+        let parent = self.parent(1).unwrap_or_default().to_vec();
+        match (name.as_ref(), kind) {
+            // The properties of a paragraph break record its insertion or deletion
+            (_, Some(kind)) if parent == b"rPr" && self.parent(2) == Some(b"pPr".as_slice()) => {
+                let revision = self.new_revision(kind, element)?;
+                if let Some(marks) = self.paragraph_marks.last_mut() {
+                    marks.push(revision);
+                }
+            }
+            // The other revisions of properties, such as inserted table rows, have no text
+            (_, Some(_)) if parent.ends_with(b"Pr") => {}
+            (_, Some(kind)) if !empty => self.open_revision(kind, element, depth)?,
+            (b"rPrChange", _) => {
+                if let Some(run) = self.depth_of(b"r") {
+                    self.open_revision(RevisionKind::Format, element, run)?;
+                }
+                self.skip_depth = Some(depth);
+            }
+            (b"pPrChange", _) => {
+                if let Some(paragraph) = self.depth_of(b"p") {
+                    self.open_revision(RevisionKind::Format, element, paragraph)?;
+                }
+                self.skip_depth = Some(depth);
+            }
+            (b"Fallback", _) => self.skip_depth = Some(depth),
+            (b"t" | b"delText", _) => self.in_text = !empty,
+            (b"tab", _) if parent == b"r" => self.push_text("\t"),
+            (b"br" | b"cr", _) if parent == b"r" => self.push_text("\n"),
+            (b"p", _) => {
+                self.paragraph_marks.push(Vec::new());
+                if empty {
+                    self.end_paragraph(depth);
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Handles the end of an element, still in the open elements
+    fn end(&mut self, name: &[u8]) {
+        let depth = self.elements.len();
+        match name {
+            b"t" | b"delText" => self.in_text = false,
+            b"p" => self.end_paragraph(depth),
+            _ => self.open.retain(|open| open.close_depth != depth),
+        }
+    }
+
+    /// Closes the formatting change of the paragraph and adds its break
+    fn end_paragraph(&mut self, depth: usize) {
+        self.open.retain(|open| open.close_depth != depth);
+        let first_mark = self.revisions.revisions.len();
+        for mut mark in self.paragraph_marks.pop().unwrap_or_default() {
+            mark.offset = self.chars;
+            self.revisions.revisions.push(mark);
+        }
+        self.push_text("\n");
+        // The break is the text of the revisions of the paragraph break
+        for mark in self.revisions.revisions[first_mark..].iter_mut() {
+            mark.text.push('\n');
+        }
+    }
+}
+
+/// Reads the tracked changes of the `word/document.xml` part of a docx document
+fn parse_document(document: &str) -> ExtractResult<DocumentRevisions> {
+    let mut reader = Reader::from_str(document);
+    let mut state = RevisionReader::default();
+    loop {
+        let event = reader.read_event().map_err(ooxml_error)?;
+        if let Some(skip_depth) = state.skip_depth {
+            match event {
+                Event::Start(e) => state.elements.push(e.local_name().as_ref().to_vec()),
+                Event::End(_) => {
+                    if state.elements.len() == skip_depth {
+                        state.skip_depth = None;
+                    }
+                    state.elements.pop();
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+            continue;
+        }
+        match event {
+            Event::Start(e) => {
+                state.elements.push(e.local_name().as_ref().to_vec());
+                state.start(&e, false)?;
+            }
+            Event::Empty(e) => {
+                state.elements.push(e.local_name().as_ref().to_vec());
+                state.start(&e, true)?;
+                state.elements.pop();
+            }
+            Event::End(e) => {
+                state.end(e.local_name().as_ref());
+                state.elements.pop();
+            }
+            Event::Text(text) if state.in_text => {
+                state.push_text(&text.unescape().map_err(ooxml_error)?);
+            }
+            Event::CData(text) if state.in_text => {
+                state.push_text(&String::from_utf8_lossy(&text));
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    let mut revisions = state.revisions;
+    revisions
+        .revisions
+        .retain(|revision| !revision.text.is_empty());
+    Ok(revisions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_document_test() {
+        let document = r#"<w:document xmlns:w="w" xmlns:mc="mc"><w:body>
+            <w:p><w:pPr><w:rPr><w:del w:id="9" w:author="Jane" w:date="2024-03-01T10:00:00Z"/></w:rPr></w:pPr>
+            <w:r><w:t xml:space="preserve">The supplier </w:t></w:r>
+            <w:del w:id="1" w:author="Jane" w:date="2024-03-01T10:00:00Z">
+            <w:r><w:delText>may</w:delText></w:r></w:del>
+            <w:ins w:id="2" w:author="Jane" w:date="2024-03-01T10:00:00Z">
+            <w:r><w:t>shall</w:t></w:r>
+            <w:del w:id="3" w:author="John"><w:r><w:delText> not</w:delText></w:r></w:del></w:ins>
+            <w:r><w:rPr><w:b/><w:rPrChange w:id="4" w:author="John"><w:rPr><w:ins w:id="8"/></w:rPr></w:rPrChange></w:rPr>
+            <w:tab/><w:t>indemnify</w:t></w:r></w:p>
+            <w:p><w:pPr><w:pPrChange w:id="5" w:author="John"><w:pPr/></w:pPrChange></w:pPr>
+            <w:moveFrom w:id="6" w:author="Jane"><w:r><w:t>In full.</w:t></w:r></w:moveFrom>
+            <mc:AlternateContent><mc:Choice><w:r><w:t> Choice</w:t></w:r></mc:Choice>
+            <mc:Fallback><w:r><w:t> Fallback</w:t></w:r></mc:Fallback></mc:AlternateContent></w:p>
+            <w:p/>
+            <w:p><w:moveTo w:id="7" w:author="Jane"><w:r><w:t>In full.</w:t></w:r></w:moveTo></w:p>
+            </w:body></w:document>"#;
+        let revisions = parse_document(document).unwrap();
+        assert_eq!(
+            revisions.text,
+            "The supplier mayshall not\tindemnify\nIn full. Choice\n\nIn full.\n"
+        );
+        let summary: Vec<(RevisionKind, Option<&str>, &str, usize)> = revisions
+            .revisions
+            .iter()
+            .map(|r| (r.kind, r.author.as_deref(), r.text.as_str(), r.offset))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (RevisionKind::Delete, Some("Jane"), "may", 13),
+                (RevisionKind::Insert, Some("Jane"), "shall not", 16),
+                (RevisionKind::Delete, Some("John"), " not", 21),
+                (RevisionKind::Format, Some("John"), "\tindemnify", 25),
+                (RevisionKind::Delete, Some("Jane"), "\n", 35),
+                (RevisionKind::Format, Some("John"), "In full. Choice", 36),
+                (RevisionKind::MoveFrom, Some("Jane"), "In full.", 36),
+                (RevisionKind::MoveTo, Some("Jane"), "In full.", 53),
+            ]
+        );
+        assert_eq!(
+            revisions.revisions[0].date.as_deref(),
+            Some("2024-03-01T10:00:00Z")
+        );
+        assert_eq!(
+            revisions.original_text(),
+            "The supplier may\tindemnify\nIn full. Choice\n\n\n"
+        );
+        assert_eq!(
+            revisions.final_text(),
+            "The supplier shall\tindemnify Choice\n\nIn full.\n"
+        );
+    }
+}
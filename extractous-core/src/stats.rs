@@ -0,0 +1,285 @@
+//! Timing and resource stats of the extractions. With [`Extractor::set_collect_stats`], the
+//! [`ExtractionStats`] of every extraction are added to its metadata, read them with
+//! [`ExtractionStats::from_metadata`]. The stats of the stream extractions are completed once the
+//! stream is read, see [`StreamReader::stats`].
+//!
+//! With the `metrics` feature, the stats of every extraction are also recorded as histograms
+//! through the [metrics](https://docs.rs/metrics) facade, labeled with the `format` of the
+//! document, e.g. `application/pdf`:
+//! * `extractous_extraction_seconds`, `extractous_detect_seconds`, `extractous_parse_seconds`,
+//!   `extractous_ocr_seconds` and `extractous_jni_copy_seconds`
+//! * `extractous_bytes_in` and `extractous_chars_out`
+//! * `extractous_throughput_bytes_per_second`, the bytes in by second of extraction
+//!
+//! [`Extractor::set_collect_stats`]: crate::Extractor::set_collect_stats
+//! [`StreamReader::stats`]: crate::StreamReader::stats
+
+use crate::Metadata;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+// The metadata keys of the stats. The keys of the stats collected inside the tika parse are
+// also declared in ai.yobix.ProgressReporter
+pub(crate) const TOTAL_TIME_KEY: &str = "X-EXTRACTOUS:total_time_micros";
+pub(crate) const DETECT_TIME_KEY: &str = "X-EXTRACTOUS:detect_time_micros";
+pub(crate) const PARSE_TIME_KEY: &str = "X-EXTRACTOUS:parse_time_micros";
+pub(crate) const OCR_TIME_KEY: &str = "X-EXTRACTOUS:ocr_time_micros";
+pub(crate) const JNI_COPY_TIME_KEY: &str = "X-EXTRACTOUS:jni_copy_time_micros";
+pub(crate) const BYTES_IN_KEY: &str = "X-EXTRACTOUS:bytes_in";
+pub(crate) const CHARS_OUT_KEY: &str = "X-EXTRACTOUS:chars_out";
+pub(crate) const EMBEDDED_DOCUMENTS_KEY: &str = "X-EXTRACTOUS:embedded_documents";
+pub(crate) const HEAP_USED_BEFORE_KEY: &str = "X-EXTRACTOUS:heap_used_before";
+pub(crate) const HEAP_USED_AFTER_KEY: &str = "X-EXTRACTOUS:heap_used_after";
+pub(crate) const OCR_RAN_KEY: &str = "X-EXTRACTOUS:ocr_ran";
+
+const STATS_KEYS: [&str; 11] = [
+    TOTAL_TIME_KEY,
+    DETECT_TIME_KEY,
+    PARSE_TIME_KEY,
+    OCR_TIME_KEY,
+    JNI_COPY_TIME_KEY,
+    BYTES_IN_KEY,
+    CHARS_OUT_KEY,
+    EMBEDDED_DOCUMENTS_KEY,
+    HEAP_USED_BEFORE_KEY,
+    HEAP_USED_AFTER_KEY,
+    OCR_RAN_KEY,
+];
+
+/// The stats of an extraction. The detection, parse, OCR and JNI copy times do not overlap,
+/// their sum is at most the total time
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExtractionStats {
+    /// The wall time of the extraction, from the call until the stream is read for the stream
+    /// outputs. Includes the download of http(s) urls
+    pub total_time: Duration,
+    /// The time spent detecting the type of the document and of its embedded documents
+    pub detect_time: Duration,
+    /// The time spent parsing the document, without the detection and OCR times
+    pub parse_time: Duration,
+    /// The time spent OCRing images and pdf pages
+    pub ocr_time: Duration,
+    /// The time spent copying the output and the metadata from the JVM to Rust
+    pub jni_copy_time: Duration,
+    /// The size of the document, when known
+    pub bytes_in: u64,
+    /// The number of characters extracted. For the string outputs, these are the characters of
+    /// the returned string, otherwise the characters of the text before any truncation
+    pub chars_out: u64,
+    /// The number of embedded documents parsed, such as the files of an archive
+    pub embedded_documents: u32,
+    /// The bytes of the JVM heap used before the parse, `None` for the documents parsed in Rust
+    pub heap_used_before: Option<u64>,
+    /// The bytes of the JVM heap used after the parse, `None` for the documents parsed in Rust
+    pub heap_used_after: Option<u64>,
+    /// Whether an OCR ran
+    pub ocr_ran: bool,
+}
+
+impl ExtractionStats {
+    /// Reads the stats added to the metadata of a document by an extractor collecting them.
+    /// Returns `None` if the metadata has no stats
+    pub fn from_metadata(metadata: &Metadata) -> Option<Self> {
+        metadata.get(TOTAL_TIME_KEY)?;
+        Some(Self::read(metadata))
+    }
+
+    /// Reads the stats present in the metadata, the missing stats are 0
+    fn read(metadata: &Metadata) -> Self {
+        Self {
+            total_time: read_micros(metadata, TOTAL_TIME_KEY),
+            detect_time: read_micros(metadata, DETECT_TIME_KEY),
+            parse_time: read_micros(metadata, PARSE_TIME_KEY),
+            ocr_time: read_micros(metadata, OCR_TIME_KEY),
+            jni_copy_time: read_micros(metadata, JNI_COPY_TIME_KEY),
+            bytes_in: read(metadata, BYTES_IN_KEY).unwrap_or_default(),
+            chars_out: read(metadata, CHARS_OUT_KEY).unwrap_or_default(),
+            embedded_documents: read(metadata, EMBEDDED_DOCUMENTS_KEY).unwrap_or_default(),
+            heap_used_before: read(metadata, HEAP_USED_BEFORE_KEY),
+            heap_used_after: read(metadata, HEAP_USED_AFTER_KEY),
+            ocr_ran: read(metadata, OCR_RAN_KEY).unwrap_or_default(),
+        }
+    }
+
+    /// Adds the stats to the metadata
+    pub(crate) fn write(&self, metadata: &mut Metadata) {
+        let mut insert = |key: &str, value: String| {
+            metadata.insert(key.to_string(), vec![value]);
+        };
+        insert(TOTAL_TIME_KEY, self.total_time.as_micros().to_string());
+        insert(DETECT_TIME_KEY, self.detect_time.as_micros().to_string());
+        insert(PARSE_TIME_KEY, self.parse_time.as_micros().to_string());
+        insert(OCR_TIME_KEY, self.ocr_time.as_micros().to_string());
+        insert(
+            JNI_COPY_TIME_KEY,
+            self.jni_copy_time.as_micros().to_string(),
+        );
+        insert(BYTES_IN_KEY, self.bytes_in.to_string());
+        insert(CHARS_OUT_KEY, self.chars_out.to_string());
+        insert(EMBEDDED_DOCUMENTS_KEY, self.embedded_documents.to_string());
+        if let Some(heap_used) = self.heap_used_before {
+            insert(HEAP_USED_BEFORE_KEY, heap_used.to_string());
+        }
+        if let Some(heap_used) = self.heap_used_after {
+            insert(HEAP_USED_AFTER_KEY, heap_used.to_string());
+        }
+        insert(OCR_RAN_KEY, self.ocr_ran.to_string());
+    }
+
+    /// Records the stats as histograms labeled with the format of the document
+    #[cfg(feature = "metrics")]
+    fn record_metrics(&self, content_type: &str) {
+        // Without the parameters such as the charset, to keep the number of labels small
+        let format = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+        let seconds = [
+            ("extractous_extraction_seconds", self.total_time),
+            ("extractous_detect_seconds", self.detect_time),
+            ("extractous_parse_seconds", self.parse_time),
+            ("extractous_ocr_seconds", self.ocr_time),
+            ("extractous_jni_copy_seconds", self.jni_copy_time),
+        ];
+        for (name, time) in seconds {
+            metrics::histogram!(name, "format" => format.clone()).record(time.as_secs_f64());
+        }
+        metrics::histogram!("extractous_bytes_in", "format" => format.clone())
+            .record(self.bytes_in as f64);
+        metrics::histogram!("extractous_chars_out", "format" => format.clone())
+            .record(self.chars_out as f64);
+        if !self.total_time.is_zero() {
+            metrics::histogram!("extractous_throughput_bytes_per_second", "format" => format)
+                .record(self.bytes_in as f64 / self.total_time.as_secs_f64());
+        }
+    }
+}
+
+fn read<T: FromStr>(metadata: &Metadata, key: &str) -> Option<T> {
+    metadata.get(key)?.first()?.trim().parse().ok()
+}
+
+fn read_micros(metadata: &Metadata, key: &str) -> Duration {
+    Duration::from_micros(read(metadata, key).unwrap_or_default())
+}
+
+/// Adds the time spent copying the results from the JVM to the metadata, before the stats are
+/// finished
+pub(crate) fn add_jni_copy_time(metadata: &mut Metadata, time: Duration) {
+    let time = read_micros(metadata, JNI_COPY_TIME_KEY) + time;
+    metadata.insert(
+        JNI_COPY_TIME_KEY.to_string(),
+        vec![time.as_micros().to_string()],
+    );
+}
+
+/// Removes the stats from the metadata
+pub(crate) fn remove_stats(metadata: &mut Metadata) {
+    for key in STATS_KEYS {
+        metadata.remove(key);
+    }
+}
+
+/// Records the stats of an extraction, started when the extraction is called
+#[derive(Debug)]
+pub(crate) struct StatsRecorder {
+    start: Instant,
+    bytes_in: Option<u64>,
+    /// Whether the stats are returned, otherwise they are only recorded as metrics
+    publish: bool,
+}
+
+impl StatsRecorder {
+    pub(crate) fn start(bytes_in: Option<u64>, publish: bool) -> Self {
+        Self {
+            start: Instant::now(),
+            bytes_in,
+            publish,
+        }
+    }
+
+    /// Sets the size of the document, once downloaded
+    pub(crate) fn set_bytes_in(&mut self, bytes_in: u64) {
+        self.bytes_in = Some(bytes_in);
+    }
+
+    /// Finishes the stats from the stats added to the metadata by the tika parse, which are
+    /// removed from it. The parse time of the documents parsed in Rust is the time not spent
+    /// on the other steps. Returns the stats if they are published. The characters out default
+    /// to the characters counted by the tika parse
+    pub(crate) fn finish(
+        self,
+        chars_out: Option<u64>,
+        metadata: &mut Metadata,
+    ) -> Option<ExtractionStats> {
+        let parsed_by_tika = metadata.contains_key(PARSE_TIME_KEY);
+        let mut stats = ExtractionStats::read(metadata);
+        remove_stats(metadata);
+
+        // In microseconds, like in the metadata
+        stats.total_time = Duration::from_micros(self.start.elapsed().as_micros() as u64);
+        if !parsed_by_tika {
+            stats.parse_time = stats.total_time.saturating_sub(stats.jni_copy_time);
+        }
+        stats.bytes_in = self
+            .bytes_in
+            .or_else(|| read(metadata, "Content-Length"))
+            .unwrap_or_default();
+        if let Some(chars_out) = chars_out {
+            stats.chars_out = chars_out;
+        }
+
+        #[cfg(feature = "metrics")]
+        stats.record_metrics(
+            metadata
+                .get("Content-Type")
+                .and_then(|values| values.first())
+                .map(|value| value.as_str())
+                .unwrap_or_default(),
+        );
+
+        self.publish.then_some(stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finish_test() {
+        let mut metadata = Metadata::from([
+            (PARSE_TIME_KEY.to_string(), vec!["1500".to_string()]),
+            (OCR_TIME_KEY.to_string(), vec!["2000".to_string()]),
+            (CHARS_OUT_KEY.to_string(), vec!["42".to_string()]),
+            (OCR_RAN_KEY.to_string(), vec!["true".to_string()]),
+            (HEAP_USED_AFTER_KEY.to_string(), vec!["1024".to_string()]),
+            ("Content-Length".to_string(), vec!["100".to_string()]),
+        ]);
+        add_jni_copy_time(&mut metadata, Duration::from_micros(5));
+        add_jni_copy_time(&mut metadata, Duration::from_micros(7));
+
+        let stats = StatsRecorder::start(None, true)
+            .finish(None, &mut metadata)
+            .unwrap();
+        assert_eq!(ExtractionStats::from_metadata(&metadata), None);
+        assert_eq!(stats.parse_time, Duration::from_micros(1500));
+        assert_eq!(stats.ocr_time, Duration::from_micros(2000));
+        assert_eq!(stats.jni_copy_time, Duration::from_micros(12));
+        assert_eq!(stats.bytes_in, 100);
+        assert_eq!(stats.chars_out, 42);
+        assert_eq!(stats.heap_used_before, None);
+        assert_eq!(stats.heap_used_after, Some(1024));
+        assert!(stats.ocr_ran);
+        stats.write(&mut metadata);
+        assert_eq!(ExtractionStats::from_metadata(&metadata), Some(stats));
+
+        // The unpublished stats are only removed from the metadata
+        let stats = StatsRecorder::start(Some(3), false).finish(Some(3), &mut metadata);
+        assert_eq!(stats, None);
+        assert_eq!(metadata.len(), 1);
+    }
+}
@@ -0,0 +1,460 @@
+use std::os::raw::c_void;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use crate::errors::{Error, ExtractResult};
+use crate::events::ContentHandler;
+use crate::parser::{self, ParserRegistry};
+use crate::progress::{Progress, ProgressReporter};
+use crate::tika::jni_utils::{
+    jni_call_static_method, jni_jobject_array_to_vec, jni_jobject_to_string,
+};
+use crate::{ContentSink, Metadata};
+use jni::objects::{JByteArray, JClass, JObject, JObjectArray, JString, JValue};
+use jni::sys::{jboolean, jint, jlong, jobjectArray, jsize, JNI_FALSE, JNI_TRUE};
+use jni::{JNIEnv, NativeMethod};
+
+/// Set once the native methods of `ai.yobix.RustParser` are registered
+static RUST_PARSER_REGISTERED: AtomicBool = AtomicBool::new(false);
+
+/// Set once the native methods of `ai.yobix.RustContentHandler` are registered
+static RUST_CONTENT_HANDLER_REGISTERED: AtomicBool = AtomicBool::new(false);
+
+/// Set once the native methods of `ai.yobix.ProgressReporter` are registered
+static PROGRESS_REPORTER_REGISTERED: AtomicBool = AtomicBool::new(false);
+
+/// Set once the native methods of `ai.yobix.RustLogger` are registered
+static RUST_LOGGER_REGISTERED: AtomicBool = AtomicBool::new(false);
+
+/// The id of the next extraction, carried by the records logged by tika
+static NEXT_EXTRACTION_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Registers the native method of a java class once
+fn register_native_method(
+    env: &mut JNIEnv,
+    registered: &AtomicBool,
+    class: &str,
+    method: NativeMethod,
+) -> ExtractResult<()> {
+    register_native_methods(env, registered, class, vec![method])
+}
+
+/// Registers the native methods of a java class once
+fn register_native_methods(
+    env: &mut JNIEnv,
+    registered: &AtomicBool,
+    class: &str,
+    methods: Vec<NativeMethod>,
+) -> ExtractResult<()> {
+    if registered.load(Ordering::Acquire) {
+        return Ok(());
+    }
+    let class = env.find_class(class)?;
+    env.register_native_methods(&class, &methods)?;
+    registered.store(true, Ordering::Release);
+    Ok(())
+}
+
+/// Registers the native methods that the `ai.yobix.RustParser` java parsers call back to parse
+/// documents with the parsers registered on the extractors
+pub(crate) fn register_rust_parser_natives(env: &mut JNIEnv) -> ExtractResult<()> {
+    register_native_method(
+        env,
+        &RUST_PARSER_REGISTERED,
+        "ai/yobix/RustParser",
+        NativeMethod {
+            name: "parseNative".into(),
+            sig: "(JI[B[Ljava/lang/String;)[Ljava/lang/String;".into(),
+            fn_ptr: rust_parser_parse_native as *mut c_void,
+        },
+    )
+}
+
+/// Registers the native methods that the `ai.yobix.RustContentHandler` calls back with the
+/// SAX events of tika
+pub(crate) fn register_content_handler_natives(env: &mut JNIEnv) -> ExtractResult<()> {
+    register_native_method(
+        env,
+        &RUST_CONTENT_HANDLER_REGISTERED,
+        "ai/yobix/RustContentHandler",
+        NativeMethod {
+            name: "handleEvent".into(),
+            sig: "(JILjava/lang/String;[Ljava/lang/String;)Z".into(),
+            fn_ptr: content_handler_handle_event as *mut c_void,
+        },
+    )
+}
+
+/// Registers the native methods that the `ai.yobix.ProgressReporter` calls back with the
+/// progress of the parse
+pub(crate) fn register_progress_natives(env: &mut JNIEnv) -> ExtractResult<()> {
+    register_native_method(
+        env,
+        &PROGRESS_REPORTER_REGISTERED,
+        "ai/yobix/ProgressReporter",
+        NativeMethod {
+            name: "report".into(),
+            sig: "(JIJJLjava/lang/String;)V".into(),
+            fn_ptr: progress_reporter_report as *mut c_void,
+        },
+    )
+}
+
+/// Implements `ai.yobix.RustParser.parseNative`. Parses the data with a registered parser and
+/// returns a String[] with the error message or null, the xhtml and the metadata as name, value
+/// pairs. Returns null if the result can not be created
+extern "system" fn rust_parser_parse_native<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    registry_id: jlong,
+    index: jint,
+    data: JByteArray<'local>,
+    metadata: JObjectArray<'local>,
+) -> jobjectArray {
+    // A panic must not unwind into the java side
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        parse_with_registered_parser(&mut env, registry_id, index, &data, metadata)
+    }))
+    .unwrap_or_else(|_| Err(Error::ParseError("Rust parser panicked".to_string())));
+
+    let (error, xhtml, metadata) = match result {
+        Ok((xhtml, metadata)) => (None, xhtml, metadata),
+        Err(e) => (Some(e.to_string()), String::new(), Metadata::new()),
+    };
+    match new_result_array(&mut env, error.as_deref(), &xhtml, &metadata) {
+        Ok(array) => array.into_raw(),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+fn parse_with_registered_parser<'local>(
+    env: &mut JNIEnv<'local>,
+    registry_id: jlong,
+    index: jint,
+    data: &JByteArray<'local>,
+    metadata: JObjectArray<'local>,
+) -> ExtractResult<(String, Metadata)> {
+    let parser = usize::try_from(index)
+        .ok()
+        .and_then(|index| ParserRegistry::find_registered(registry_id, index))
+        .ok_or_else(|| {
+            Error::ParseError(format!("Rust parser {} is no longer registered", index))
+        })?;
+
+    let buffer = env.convert_byte_array(data)?;
+    let pairs = jni_jobject_array_to_vec(env, JObject::from(metadata))?;
+    let metadata_hints = pairs_to_metadata(pairs);
+    let mime_type = metadata_hints
+        .get("Content-Type")
+        .and_then(|values| values.first())
+        .cloned()
+        .unwrap_or_default();
+
+    // The xhtml is replayed to the tika content handler on the java side
+    let sink = ContentSink::new(true);
+    let (sink, metadata) =
+        parser::parse(parser.as_ref(), &buffer, &mime_type, &metadata_hints, sink)?;
+    Ok((sink.finish(&metadata), metadata))
+}
+
+fn new_result_array<'local>(
+    env: &mut JNIEnv<'local>,
+    error: Option<&str>,
+    xhtml: &str,
+    metadata: &Metadata,
+) -> ExtractResult<JObjectArray<'local>> {
+    let mut values = vec![error, Some(xhtml)];
+    for (name, name_values) in metadata.iter() {
+        for value in name_values.iter() {
+            values.push(Some(name));
+            values.push(Some(value));
+        }
+    }
+
+    let array = env.new_object_array(values.len() as jsize, "java/lang/String", JObject::null())?;
+    for (i, value) in values.into_iter().enumerate() {
+        if let Some(value) = value {
+            let j_value = env.new_string(value)?;
+            env.set_object_array_element(&array, i as jsize, &j_value)?;
+            env.delete_local_ref(j_value)?;
+        }
+    }
+    Ok(array)
+}
+
+/// Converts flat name, value pairs to metadata
+fn pairs_to_metadata(pairs: Vec<String>) -> Metadata {
+    let mut metadata = Metadata::new();
+    for pair in pairs.chunks_exact(2) {
+        metadata
+            .entry(pair[0].clone())
+            .or_default()
+            .push(pair[1].clone());
+    }
+    metadata
+}
+
+/// The kinds of events of `ai.yobix.RustContentHandler`
+const START_ELEMENT: jint = 0;
+const END_ELEMENT: jint = 1;
+const CHARACTERS: jint = 2;
+const EMBEDDED_START: jint = 3;
+const EMBEDDED_END: jint = 4;
+const METADATA: jint = 5;
+
+/// The content handler called back by `ai.yobix.RustContentHandler` while tika parses a
+/// document. The java handler holds the address of the target, which is only valid during the
+/// call to tika on the thread of the extractor
+pub(crate) struct EventTarget<'h> {
+    handler: &'h mut dyn ContentHandler,
+    /// The error of the handler that stopped the parsing
+    error: Option<Error>,
+}
+
+impl<'h> EventTarget<'h> {
+    pub(crate) fn new(handler: &'h mut dyn ContentHandler) -> Self {
+        Self {
+            handler,
+            error: None,
+        }
+    }
+
+    /// The address passed to the java content handler
+    pub(crate) fn address(&mut self) -> jlong {
+        self as *mut Self as jlong
+    }
+
+    /// Returns the error of the handler if it stopped the parsing, otherwise the tika result
+    pub(crate) fn finish<T>(self, result: ExtractResult<T>) -> ExtractResult<T> {
+        match self.error {
+            Some(error) => Err(error),
+            None => result,
+        }
+    }
+}
+
+/// Implements `ai.yobix.RustContentHandler.handleEvent`. Returns false to stop the parsing
+extern "system" fn content_handler_handle_event<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    address: jlong,
+    kind: jint,
+    name: JString<'local>,
+    values: JObjectArray<'local>,
+) -> jboolean {
+    // Safety: the address is the one of the EventTarget borrowed for the duration of the tika
+    // call that sends the events, see EventTarget
+    let target = unsafe { &mut *(address as *mut EventTarget) };
+    if target.error.is_some() {
+        return JNI_FALSE;
+    }
+
+    // A panic must not unwind into the java side
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        handle_event(&mut env, target.handler, kind, name, values)
+    }))
+    .unwrap_or_else(|_| Err(Error::ParseError("Content handler panicked".to_string())));
+    match result {
+        Ok(()) => JNI_TRUE,
+        Err(error) => {
+            target.error = Some(error);
+            JNI_FALSE
+        }
+    }
+}
+
+fn handle_event<'local>(
+    env: &mut JNIEnv<'local>,
+    handler: &mut dyn ContentHandler,
+    kind: jint,
+    name: JString<'local>,
+    values: JObjectArray<'local>,
+) -> ExtractResult<()> {
+    let name = if name.is_null() {
+        String::new()
+    } else {
+        jni_jobject_to_string(env, JObject::from(name))?
+    };
+    let values = if values.is_null() {
+        Vec::new()
+    } else {
+        jni_jobject_array_to_vec(env, JObject::from(values))?
+    };
+
+    match kind {
+        START_ELEMENT => {
+            let attributes: Vec<(String, String)> = values
+                .chunks_exact(2)
+                .map(|pair| (pair[0].clone(), pair[1].clone()))
+                .collect();
+            handler.start_element(&name, &attributes)
+        }
+        END_ELEMENT => handler.end_element(&name),
+        CHARACTERS => handler.characters(&name),
+        EMBEDDED_START => handler.embedded_start(&pairs_to_metadata(values)),
+        EMBEDDED_END => handler.embedded_end(),
+        METADATA => handler.metadata(&pairs_to_metadata(values)),
+        _ => Err(Error::Unknown(format!(
+            "Unknown content handler event {}",
+            kind
+        ))),
+    }
+}
+
+/// The kinds of progress of `ai.yobix.ProgressReporter`
+const DOCUMENT_STARTED: jint = 0;
+const PAGE_DONE: jint = 1;
+const EMBEDDED_DOCUMENT_STARTED: jint = 2;
+const OCR_STARTED: jint = 3;
+const OCR_FINISHED: jint = 4;
+const OUTPUT_PRODUCED: jint = 5;
+const DOCUMENT_FINISHED: jint = 6;
+
+/// Implements `ai.yobix.ProgressReporter.report`. Reports the progress to the callback with the
+/// given id, errors and panics of the callback are ignored
+extern "system" fn progress_reporter_report<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    id: jlong,
+    kind: jint,
+    value: jlong,
+    total: jlong,
+    name: JString<'local>,
+) {
+    // A panic must not unwind into the java side
+    let _ = catch_unwind(AssertUnwindSafe(|| {
+        let name = if name.is_null() {
+            None
+        } else {
+            jni_jobject_to_string(&mut env, JObject::from(name)).ok()
+        };
+        if let Some(progress) = to_progress(kind, value, total, name) {
+            ProgressReporter::report_registered(id, &progress);
+        }
+    }));
+}
+
+fn to_progress(kind: jint, value: jlong, total: jlong, name: Option<String>) -> Option<Progress> {
+    let page = u32::try_from(value).unwrap_or_default();
+    let progress = match kind {
+        DOCUMENT_STARTED => Progress::DocumentStarted {
+            content_type: name.unwrap_or_default(),
+        },
+        PAGE_DONE => Progress::PageDone {
+            page,
+            total: u32::try_from(total).ok().filter(|total| *total > 0),
+        },
+        EMBEDDED_DOCUMENT_STARTED => Progress::EmbeddedDocumentStarted {
+            resource_name: name,
+        },
+        OCR_STARTED => Progress::OcrStarted { page },
+        OCR_FINISHED => Progress::OcrFinished { page },
+        OUTPUT_PRODUCED => Progress::OutputProduced {
+            bytes: u64::try_from(value).unwrap_or_default(),
+        },
+        DOCUMENT_FINISHED => Progress::DocumentFinished,
+        _ => return None,
+    };
+    Some(progress)
+}
+
+/// Starts the logging of an extraction of the source: registers the native methods of the
+/// `ai.yobix.RustLogger` and gives it a new extraction id, carried by the records logged by
+/// tika on this thread and the threads it starts. Does nothing when logging is disabled
+pub(crate) fn start_extraction_log(env: &mut JNIEnv, source: &str) -> ExtractResult<()> {
+    if log::max_level() == log::LevelFilter::Off {
+        return Ok(());
+    }
+    register_native_methods(
+        env,
+        &RUST_LOGGER_REGISTERED,
+        "ai/yobix/RustLogger",
+        vec![
+            NativeMethod {
+                name: "isEnabled".into(),
+                sig: "(ILjava/lang/String;)Z".into(),
+                fn_ptr: rust_logger_is_enabled as *mut c_void,
+            },
+            NativeMethod {
+                name: "log".into(),
+                sig: "(JILjava/lang/String;Ljava/lang/String;)V".into(),
+                fn_ptr: rust_logger_log as *mut c_void,
+            },
+        ],
+    )?;
+
+    let id = NEXT_EXTRACTION_ID.fetch_add(1, Ordering::Relaxed);
+    jni_call_static_method(
+        env,
+        "ai/yobix/RustLogger",
+        "setExtraction",
+        "(J)V",
+        &[JValue::Long(id as jlong)],
+    )?;
+    log::debug!(target: "extractous", extraction = id; "Extracting {}", source);
+    Ok(())
+}
+
+fn to_log_level(level: jint) -> log::Level {
+    match level {
+        1 => log::Level::Error,
+        2 => log::Level::Warn,
+        3 => log::Level::Info,
+        4 => log::Level::Debug,
+        _ => log::Level::Trace,
+    }
+}
+
+/// Implements `ai.yobix.RustLogger.isEnabled`. The target is the name of the java logger
+extern "system" fn rust_logger_is_enabled<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    level: jint,
+    target: JString<'local>,
+) -> jboolean {
+    let level = to_log_level(level);
+    if level > log::max_level() {
+        return JNI_FALSE;
+    }
+    // A panic must not unwind into the java side
+    let enabled = catch_unwind(AssertUnwindSafe(|| {
+        let target = jni_jobject_to_string(&mut env, JObject::from(target)).unwrap_or_default();
+        let metadata = log::Metadata::builder()
+            .level(level)
+            .target(&target)
+            .build();
+        log::logger().enabled(&metadata)
+    }))
+    .unwrap_or(false);
+    if enabled {
+        JNI_TRUE
+    } else {
+        JNI_FALSE
+    }
+}
+
+/// Implements `ai.yobix.RustLogger.log`. Logs the message with the name of the java logger as
+/// target and the id of the extraction as `extraction` key value
+extern "system" fn rust_logger_log<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    extraction: jlong,
+    level: jint,
+    target: JString<'local>,
+    message: JString<'local>,
+) {
+    // A panic must not unwind into the java side
+    let _ = catch_unwind(AssertUnwindSafe(|| {
+        let target = jni_jobject_to_string(&mut env, JObject::from(target)).unwrap_or_default();
+        let message = jni_jobject_to_string(&mut env, JObject::from(message)).unwrap_or_default();
+        let extraction = [("extraction", extraction as u64)];
+        log::logger().log(
+            &log::Record::builder()
+                .level(to_log_level(level))
+                .target(&target)
+                .key_values(&extraction)
+                .args(format_args!("{}", message))
+                .build(),
+        );
+    }));
+}
@@ -3,7 +3,7 @@ use std::os::raw::{c_char, c_void};
 use crate::errors::{Error, ExtractResult};
 use crate::Metadata;
 use jni::errors::jni_error_code_to_result;
-use jni::objects::{JByteBuffer, JObject, JObjectArray, JString, JValue, JValueOwned};
+use jni::objects::{JByteArray, JByteBuffer, JObject, JObjectArray, JString, JValue, JValueOwned};
 use jni::{sys, JNIEnv, JavaVM};
 use std::collections::HashMap;
 
@@ -143,6 +143,84 @@ pub fn jni_tika_metadata_to_rust_metadata<'local>(
     Ok(metadata)
 }
 
+/// Converts a Java Metadata[] to a Rust Vec<Metadata>
+pub fn jni_tika_metadata_array_to_vec<'local>(
+    env: &mut JNIEnv<'local>,
+    array: JObject<'local>,
+) -> ExtractResult<Vec<Metadata>> {
+    let j_array = JObjectArray::from(array);
+    let j_array_length = env.get_array_length(&j_array)?;
+
+    let mut vec = Vec::with_capacity(j_array_length as usize);
+    for i in 0..j_array_length {
+        let elem_obj = env.get_object_array_element(&j_array, i)?;
+        vec.push(jni_tika_metadata_to_rust_metadata(env, elem_obj)?);
+    }
+    Ok(vec)
+}
+
+/// Converts a Java byte[][] to a Rust Vec<Vec<u8>>
+pub fn jni_byte_array_array_to_vec<'local>(
+    env: &mut JNIEnv<'local>,
+    array: JObject<'local>,
+) -> ExtractResult<Vec<Vec<u8>>> {
+    let j_array = JObjectArray::from(array);
+    let j_array_length = env.get_array_length(&j_array)?;
+
+    let mut vec = Vec::with_capacity(j_array_length as usize);
+    for i in 0..j_array_length {
+        let elem_obj = env.get_object_array_element(&j_array, i)?;
+        let bytes = env.convert_byte_array(JByteArray::from(elem_obj))?;
+        vec.push(bytes);
+    }
+    Ok(vec)
+}
+
+/// Converts a Rust Metadata to a new Tika Metadata object
+pub fn jni_rust_metadata_to_tika_metadata<'local>(
+    env: &mut JNIEnv<'local>,
+    metadata: &Metadata,
+) -> ExtractResult<JObject<'local>> {
+    let class = env.find_class("org/apache/tika/metadata/Metadata")?;
+    let j_tika_metadata_object = env.new_object(&class, "()V", &[])?;
+
+    for (key_name, values) in metadata.iter() {
+        let j_key_name = jni_new_string_as_jvalue(env, key_name)?;
+        for value in values.iter() {
+            let j_value = jni_new_string_as_jvalue(env, value)?;
+            jni_call_method(
+                env,
+                &j_tika_metadata_object,
+                "add",
+                "(Ljava/lang/String;Ljava/lang/String;)V",
+                &[(&j_key_name).into(), (&j_value).into()],
+            )?;
+        }
+    }
+    Ok(j_tika_metadata_object)
+}
+
+/// Copies the data into a new java byte[] and wraps it in a heap `java.nio.ByteBuffer`.
+/// Unlike [`jni_new_direct_buffer`], the returned buffer is owned by the java side, and it
+/// stays valid after the rust data is dropped
+pub fn jni_new_heap_buffer<'local>(
+    env: &mut JNIEnv<'local>,
+    data: &[u8],
+) -> ExtractResult<JObject<'local>> {
+    let byte_array = env
+        .byte_array_from_slice(data)
+        .map_err(|_e| Error::JniEnvCall("Failed to create byte array"))?;
+    let byte_buffer = jni_call_static_method(
+        env,
+        "java/nio/ByteBuffer",
+        "wrap",
+        "([B)Ljava/nio/ByteBuffer;",
+        &[(&byte_array).into()],
+    )?
+    .l()?;
+    Ok(byte_buffer)
+}
+
 /// Checks if there is an exception in the jni environment, describes it to
 /// the stderr and finally clears it
 pub fn jni_check_exception(env: &mut JNIEnv) -> ExtractResult<bool> {
@@ -1,13 +1,20 @@
 use std::sync::OnceLock;
+use std::time::Instant;
 
 use crate::errors::ExtractResult;
+use crate::events::ContentHandler;
+use crate::parser::ParserRegistry;
+use crate::progress::ProgressReporter;
+use crate::stats;
+use crate::tika::callbacks::{register_content_handler_natives, start_extraction_log, EventTarget};
 use crate::tika::jni_utils::*;
 use crate::tika::wrappers::*;
 use crate::{
-    CharSet, Metadata, OfficeParserConfig, PdfParserConfig, StreamReader, TesseractOcrConfig,
+    CharSet, EmbeddedDocument, Metadata, OfficeParserConfig, PdfParserConfig, StreamReader,
+    TesseractOcrConfig,
 };
 use jni::objects::JValue;
-use jni::{AttachGuard, JavaVM};
+use jni::{AttachGuard, JNIEnv, JavaVM};
 
 /// Returns a reference to the shared VM isolate
 /// Instead of creating a new VM for every tika call, we create a single VM that is shared
@@ -25,71 +32,148 @@ fn get_vm_attach_current_thread<'local>() -> ExtractResult<AttachGuard<'local>>
     Ok(env)
 }
 
+/// Attaches the current thread for an extraction of the source. The records logged by tika
+/// during the extraction carry the id of the extraction
+fn attach_for_extraction<'local>(source: &str) -> ExtractResult<AttachGuard<'local>> {
+    let mut env = get_vm_attach_current_thread()?;
+    start_extraction_log(&mut env, source)?;
+    Ok(env)
+}
+
+/// Describes bytes in the logs, with their resource name hint if any
+fn bytes_source(len: usize, metadata_hints: &Metadata) -> String {
+    match metadata_hints
+        .get("resourceName")
+        .and_then(|names| names.first())
+    {
+        Some(name) => format!("{} ({} bytes)", name, len),
+        None => format!("{} bytes", len),
+    }
+}
+
+/// The options of a parse: the parser configs, the page selection, the output options, the
+/// registered Rust parsers and the progress callback
+pub struct ParseOptions<'a> {
+    pub pdf_conf: &'a PdfParserConfig,
+    pub office_conf: &'a OfficeParserConfig,
+    pub ocr_conf: &'a TesseractOcrConfig,
+    /// The maximum number of pages, -1 for no limit. The pages are selected with the page
+    /// ranges of the pdf config and this maximum
+    pub max_pages: i32,
+    /// Whether the pages are followed by a page break in the text output
+    pub page_breaks: bool,
+    pub as_xml: bool,
+    pub parsers: &'a ParserRegistry,
+    pub progress: ProgressReporter,
+}
+
+/// The java objects of the [`ParseOptions`], passed in this order to the parse methods
+struct JParseOptions<'local> {
+    pdf_conf: JPDFParserConfig<'local>,
+    office_conf: JOfficeParserConfig<'local>,
+    ocr_conf: JTesseractOcrConfig<'local>,
+    page_selection: JPageSelection<'local>,
+    output_options: JOutputOptions<'local>,
+    rust_parsers: JRustParsers<'local>,
+    progress: JProgressReporter<'local>,
+}
+
+impl<'local> JParseOptions<'local> {
+    fn new(env: &mut JNIEnv<'local>, options: &ParseOptions) -> ExtractResult<Self> {
+        Ok(Self {
+            pdf_conf: JPDFParserConfig::new(env, options.pdf_conf)?,
+            office_conf: JOfficeParserConfig::new(env, options.office_conf)?,
+            ocr_conf: JTesseractOcrConfig::new(env, options.ocr_conf)?,
+            page_selection: JPageSelection::new(
+                env,
+                &options.pdf_conf.page_ranges,
+                options.max_pages,
+            )?,
+            output_options: JOutputOptions::new(
+                env,
+                options.page_breaks,
+                options.office_conf.include_comments,
+            )?,
+            rust_parsers: JRustParsers::new(env, options.parsers)?,
+            progress: JProgressReporter::new(env, &options.progress)?,
+        })
+    }
+
+    fn args(&self) -> [JValue<'local, '_>; 7] {
+        [
+            (&self.pdf_conf.internal).into(),
+            (&self.office_conf.internal).into(),
+            (&self.ocr_conf.internal).into(),
+            (&self.page_selection.internal).into(),
+            (&self.output_options.internal).into(),
+            (&self.rust_parsers.internal).into(),
+            (&self.progress.internal).into(),
+        ]
+    }
+}
+
 fn parse_to_stream(
     mut env: AttachGuard,
-    data_source_val: JValue,
+    data_source_vals: &[JValue],
     char_set: &CharSet,
-    pdf_conf: &PdfParserConfig,
-    office_conf: &OfficeParserConfig,
-    ocr_conf: &TesseractOcrConfig,
-    as_xml: bool,
+    options: &ParseOptions,
     method_name: &str,
     signature: &str,
 ) -> ExtractResult<(StreamReader, Metadata)> {
     let charset_name_val = jni_new_string_as_jvalue(&mut env, &char_set.to_string())?;
-    let j_pdf_conf = JPDFParserConfig::new(&mut env, pdf_conf)?;
-    let j_office_conf = JOfficeParserConfig::new(&mut env, office_conf)?;
-    let j_ocr_conf = JTesseractOcrConfig::new(&mut env, ocr_conf)?;
+    let j_options = JParseOptions::new(&mut env, options)?;
 
     // Make the java parse call
+    let mut args = data_source_vals.to_vec();
+    args.push((&charset_name_val).into());
+    args.extend_from_slice(&j_options.args());
+    args.push(JValue::Bool(if options.as_xml { 1 } else { 0 }));
     let call_result = jni_call_static_method(
         &mut env,
         "ai/yobix/TikaNativeMain",
         method_name,
         signature,
-        &[
-            data_source_val,
-            (&charset_name_val).into(),
-            (&j_pdf_conf.internal).into(),
-            (&j_office_conf.internal).into(),
-            (&j_ocr_conf.internal).into(),
-            JValue::Bool(if as_xml { 1 } else { 0 }),
-        ],
+        &args,
     );
     let call_result_obj = call_result?.l()?;
 
     // Create and process the JReaderResult
+    let copy_start = Instant::now();
     let result = JReaderResult::new(&mut env, call_result_obj)?;
-    let j_reader = JReaderInputStream::new(&mut env, result.java_reader)?;
+    let mut j_reader = JReaderInputStream::new(&mut env, result.java_reader)?;
+    if options.progress.collects_stats() {
+        j_reader.keep_metadata(&mut env, &result.java_metadata, copy_start.elapsed())?;
+    }
 
-    Ok((StreamReader { inner: j_reader }, result.metadata))
+    Ok((
+        StreamReader::tika(j_reader, options.parsers, &options.progress),
+        result.metadata,
+    ))
 }
 
 pub fn parse_file(
     file_path: &str,
     char_set: &CharSet,
-    pdf_conf: &PdfParserConfig,
-    office_conf: &OfficeParserConfig,
-    ocr_conf: &TesseractOcrConfig,
-    as_xml: bool
+    options: &ParseOptions,
 ) -> ExtractResult<(StreamReader, Metadata)> {
-    let mut env = get_vm_attach_current_thread()?;
+    let mut env = attach_for_extraction(file_path)?;
 
     let file_path_val = jni_new_string_as_jvalue(&mut env, file_path)?;
     parse_to_stream(
         env,
-        (&file_path_val).into(),
+        &[(&file_path_val).into()],
         char_set,
-        pdf_conf,
-        office_conf,
-        ocr_conf,
-        as_xml,
+        options,
         "parseFile",
         "(Ljava/lang/String;\
         Ljava/lang/String;\
         Lorg/apache/tika/parser/pdf/PDFParserConfig;\
         Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
         Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        Lai/yobix/PageSelection;\
+        Lai/yobix/OutputOptions;\
+        [Lai/yobix/RustParser;\
+        Lai/yobix/ProgressReporter;\
         Z\
         )Lai/yobix/ReaderResult;",
     )
@@ -97,34 +181,71 @@ pub fn parse_file(
 
 pub fn parse_bytes(
     buffer: &[u8],
+    metadata_hints: &Metadata,
     char_set: &CharSet,
-    pdf_conf: &PdfParserConfig,
-    office_conf: &OfficeParserConfig,
-    ocr_conf: &TesseractOcrConfig,
-    as_xml: bool,
+    options: &ParseOptions,
 ) -> ExtractResult<(StreamReader, Metadata)> {
-    let mut env = get_vm_attach_current_thread()?;
+    let mut env = attach_for_extraction(&bytes_source(buffer.len(), metadata_hints))?;
 
     // Because we know the buffer is used for reading only, cast it to *mut u8 to satisfy the
     // jni_new_direct_buffer call, which requires a mutable pointer
     let mut_ptr: *mut u8 = buffer.as_ptr() as *mut u8;
 
     let byte_buffer = jni_new_direct_buffer(&mut env, mut_ptr, buffer.len())?;
+    let j_metadata = jni_rust_metadata_to_tika_metadata(&mut env, metadata_hints)?;
 
     parse_to_stream(
         env,
-        (&byte_buffer).into(),
+        &[(&byte_buffer).into(), (&j_metadata).into()],
         char_set,
-        pdf_conf,
-        office_conf,
-        ocr_conf,
-        as_xml,
+        options,
         "parseBytes",
         "(Ljava/nio/ByteBuffer;\
+        Lorg/apache/tika/metadata/Metadata;\
         Ljava/lang/String;\
         Lorg/apache/tika/parser/pdf/PDFParserConfig;\
         Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
         Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        Lai/yobix/PageSelection;\
+        Lai/yobix/OutputOptions;\
+        [Lai/yobix/RustParser;\
+        Lai/yobix/ProgressReporter;\
+        Z\
+        )Lai/yobix/ReaderResult;",
+    )
+}
+
+/// Same as [`parse_bytes`] but the data is first copied to the java heap. Must be used when
+/// the data does not outlive the returned [`StreamReader`], because the stream is parsed
+/// lazily while reading
+pub fn parse_owned_bytes(
+    buffer: Vec<u8>,
+    metadata_hints: &Metadata,
+    char_set: &CharSet,
+    options: &ParseOptions,
+) -> ExtractResult<(StreamReader, Metadata)> {
+    let mut env = attach_for_extraction(&bytes_source(buffer.len(), metadata_hints))?;
+
+    let byte_buffer = jni_new_heap_buffer(&mut env, &buffer)?;
+    drop(buffer);
+    let j_metadata = jni_rust_metadata_to_tika_metadata(&mut env, metadata_hints)?;
+
+    parse_to_stream(
+        env,
+        &[(&byte_buffer).into(), (&j_metadata).into()],
+        char_set,
+        options,
+        "parseBytes",
+        "(Ljava/nio/ByteBuffer;\
+        Lorg/apache/tika/metadata/Metadata;\
+        Ljava/lang/String;\
+        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
+        Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
+        Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        Lai/yobix/PageSelection;\
+        Lai/yobix/OutputOptions;\
+        [Lai/yobix/RustParser;\
+        Lai/yobix/ProgressReporter;\
         Z\
         )Lai/yobix/ReaderResult;",
     )
@@ -133,28 +254,26 @@ pub fn parse_bytes(
 pub fn parse_url(
     url: &str,
     char_set: &CharSet,
-    pdf_conf: &PdfParserConfig,
-    office_conf: &OfficeParserConfig,
-    ocr_conf: &TesseractOcrConfig,
-    as_xml: bool,
+    options: &ParseOptions,
 ) -> ExtractResult<(StreamReader, Metadata)> {
-    let mut env = get_vm_attach_current_thread()?;
+    let mut env = attach_for_extraction(url)?;
 
     let url_val = jni_new_string_as_jvalue(&mut env, url)?;
     parse_to_stream(
         env,
-        (&url_val).into(),
+        &[(&url_val).into()],
         char_set,
-        pdf_conf,
-        office_conf,
-        ocr_conf,
-        as_xml,
+        options,
         "parseUrl",
         "(Ljava/lang/String;\
         Ljava/lang/String;\
         Lorg/apache/tika/parser/pdf/PDFParserConfig;\
         Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
         Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        Lai/yobix/PageSelection;\
+        Lai/yobix/OutputOptions;\
+        [Lai/yobix/RustParser;\
+        Lai/yobix/ProgressReporter;\
         Z\
         )Lai/yobix/ReaderResult;",
     )
@@ -163,37 +282,33 @@ pub fn parse_url(
 /// Parses a file to a JStringResult using the Apache Tika library.
 pub fn parse_to_string(
     mut env: AttachGuard,
-    data_source_val: JValue,
+    data_source_vals: &[JValue],
     max_length: i32,
-    pdf_conf: &PdfParserConfig,
-    office_conf: &OfficeParserConfig,
-    ocr_conf: &TesseractOcrConfig,
-    as_xml: bool,
+    options: &ParseOptions,
     method_name: &str,
     signature: &str,
 ) -> ExtractResult<(String, Metadata)> {
-    let j_pdf_conf = JPDFParserConfig::new(&mut env, pdf_conf)?;
-    let j_office_conf = JOfficeParserConfig::new(&mut env, office_conf)?;
-    let j_ocr_conf = JTesseractOcrConfig::new(&mut env, ocr_conf)?;
+    let j_options = JParseOptions::new(&mut env, options)?;
 
+    let mut args = data_source_vals.to_vec();
+    args.push(JValue::Int(max_length));
+    args.extend_from_slice(&j_options.args());
+    args.push(JValue::Bool(if options.as_xml { 1 } else { 0 }));
     let call_result = jni_call_static_method(
         &mut env,
         "ai/yobix/TikaNativeMain",
         method_name,
         signature,
-        &[
-            data_source_val,
-            JValue::Int(max_length),
-            (&j_pdf_conf.internal).into(),
-            (&j_office_conf.internal).into(),
-            (&j_ocr_conf.internal).into(),
-            JValue::Bool(if as_xml { 1 } else { 0 }),
-        ],
+        &args,
     );
     let call_result_obj = call_result?.l()?;
 
     // Create and process the JStringResult
-    let result = JStringResult::new(&mut env, call_result_obj)?;
+    let copy_start = Instant::now();
+    let mut result = JStringResult::new(&mut env, call_result_obj)?;
+    if options.progress.collects_stats() {
+        stats::add_jni_copy_time(&mut result.metadata, copy_start.elapsed());
+    }
     Ok((result.content, result.metadata))
 }
 
@@ -201,28 +316,26 @@ pub fn parse_to_string(
 pub fn parse_file_to_string(
     file_path: &str,
     max_length: i32,
-    pdf_conf: &PdfParserConfig,
-    office_conf: &OfficeParserConfig,
-    ocr_conf: &TesseractOcrConfig,
-    as_xml: bool,
+    options: &ParseOptions,
 ) -> ExtractResult<(String, Metadata)> {
-    let mut env = get_vm_attach_current_thread()?;
+    let mut env = attach_for_extraction(file_path)?;
 
     let file_path_val = jni_new_string_as_jvalue(&mut env, file_path)?;
     parse_to_string(
         env,
-        (&file_path_val).into(),
+        &[(&file_path_val).into()],
         max_length,
-        pdf_conf,
-        office_conf,
-        ocr_conf,
-        as_xml,
+        options,
         "parseFileToString",
         "(Ljava/lang/String;\
         I\
         Lorg/apache/tika/parser/pdf/PDFParserConfig;\
         Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
         Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        Lai/yobix/PageSelection;\
+        Lai/yobix/OutputOptions;\
+        [Lai/yobix/RustParser;\
+        Lai/yobix/ProgressReporter;\
         Z\
         )Lai/yobix/StringResult;",
     )
@@ -231,34 +344,35 @@ pub fn parse_file_to_string(
 /// Parses bytes to a string using the Apache Tika library.
 pub fn parse_bytes_to_string(
     buffer: &[u8],
+    metadata_hints: &Metadata,
     max_length: i32,
-    pdf_conf: &PdfParserConfig,
-    office_conf: &OfficeParserConfig,
-    ocr_conf: &TesseractOcrConfig,
-    as_xml: bool,
+    options: &ParseOptions,
 ) -> ExtractResult<(String, Metadata)> {
-    let mut env = get_vm_attach_current_thread()?;
+    let mut env = attach_for_extraction(&bytes_source(buffer.len(), metadata_hints))?;
 
     // Because we know the buffer is used for reading only, cast it to *mut u8 to satisfy the
     // jni_new_direct_buffer call, which requires a mutable pointer
     let mut_ptr: *mut u8 = buffer.as_ptr() as *mut u8;
 
     let byte_buffer = jni_new_direct_buffer(&mut env, mut_ptr, buffer.len())?;
+    let j_metadata = jni_rust_metadata_to_tika_metadata(&mut env, metadata_hints)?;
 
     parse_to_string(
         env,
-        (&byte_buffer).into(),
+        &[(&byte_buffer).into(), (&j_metadata).into()],
         max_length,
-        pdf_conf,
-        office_conf,
-        ocr_conf,
-        as_xml,
+        options,
         "parseBytesToString",
         "(Ljava/nio/ByteBuffer;\
+        Lorg/apache/tika/metadata/Metadata;\
         I\
         Lorg/apache/tika/parser/pdf/PDFParserConfig;\
         Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
         Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        Lai/yobix/PageSelection;\
+        Lai/yobix/OutputOptions;\
+        [Lai/yobix/RustParser;\
+        Lai/yobix/ProgressReporter;\
         Z\
         )Lai/yobix/StringResult;",
     )
@@ -268,29 +382,399 @@ pub fn parse_bytes_to_string(
 pub fn parse_url_to_string(
     url: &str,
     max_length: i32,
-    pdf_conf: &PdfParserConfig,
-    office_conf: &OfficeParserConfig,
-    ocr_conf: &TesseractOcrConfig,
-    as_xml: bool,
+    options: &ParseOptions,
 ) -> ExtractResult<(String, Metadata)> {
-    let mut env = get_vm_attach_current_thread()?;
+    let mut env = attach_for_extraction(url)?;
 
     let url_val = jni_new_string_as_jvalue(&mut env, url)?;
     parse_to_string(
         env,
-        (&url_val).into(),
+        &[(&url_val).into()],
         max_length,
-        pdf_conf,
-        office_conf,
-        ocr_conf,
-        as_xml,
+        options,
         "parseUrlToString",
         "(Ljava/lang/String;\
         I\
         Lorg/apache/tika/parser/pdf/PDFParserConfig;\
         Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
         Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        Lai/yobix/PageSelection;\
+        Lai/yobix/OutputOptions;\
+        [Lai/yobix/RustParser;\
+        Lai/yobix/ProgressReporter;\
         Z\
         )Lai/yobix/StringResult;",
     )
 }
+
+/// Parses bytes and all the documents embedded in them using the Apache Tika library.
+/// Returns the metadata of every document, with the content under the `X-TIKA:content` key.
+/// The page selection, page breaks and progress of the options are not used
+pub fn parse_bytes_recursive(
+    buffer: &[u8],
+    metadata_hints: &Metadata,
+    max_length: i32,
+    options: &ParseOptions,
+) -> ExtractResult<Vec<Metadata>> {
+    let mut env = attach_for_extraction(&bytes_source(buffer.len(), metadata_hints))?;
+
+    // Because we know the buffer is used for reading only, cast it to *mut u8 to satisfy the
+    // jni_new_direct_buffer call, which requires a mutable pointer
+    let mut_ptr: *mut u8 = buffer.as_ptr() as *mut u8;
+
+    let byte_buffer = jni_new_direct_buffer(&mut env, mut_ptr, buffer.len())?;
+    let j_metadata = jni_rust_metadata_to_tika_metadata(&mut env, metadata_hints)?;
+    let handler_type = if options.as_xml { "xml" } else { "text" };
+    let handler_type_val = jni_new_string_as_jvalue(&mut env, handler_type)?;
+    let j_pdf_conf = JPDFParserConfig::new(&mut env, options.pdf_conf)?;
+    let j_office_conf = JOfficeParserConfig::new(&mut env, options.office_conf)?;
+    let j_ocr_conf = JTesseractOcrConfig::new(&mut env, options.ocr_conf)?;
+    let j_rust_parsers = JRustParsers::new(&mut env, options.parsers)?;
+
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "parseBytesRecursive",
+        "(Ljava/nio/ByteBuffer;\
+        Lorg/apache/tika/metadata/Metadata;\
+        Ljava/lang/String;\
+        I\
+        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
+        Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
+        Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        [Lai/yobix/RustParser;\
+        )Lai/yobix/MetadataListResult;",
+        &[
+            (&byte_buffer).into(),
+            (&j_metadata).into(),
+            (&handler_type_val).into(),
+            JValue::Int(max_length),
+            (&j_pdf_conf.internal).into(),
+            (&j_office_conf.internal).into(),
+            (&j_ocr_conf.internal).into(),
+            (&j_rust_parsers.internal).into(),
+        ],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let result = JMetadataListResult::new(&mut env, call_result_obj)?;
+    Ok(result.metadata_list)
+}
+
+/// Parses bytes using the Apache Tika library and collects the raw bytes of the documents
+/// embedded in them. Returns the text and metadata of the container and the embedded documents.
+/// Only the parser configs and the Rust parsers of the options are used
+pub fn unpack_bytes(
+    buffer: &[u8],
+    metadata_hints: &Metadata,
+    max_length: i32,
+    options: &ParseOptions,
+) -> ExtractResult<(String, Metadata, Vec<EmbeddedDocument>)> {
+    let mut env = attach_for_extraction(&bytes_source(buffer.len(), metadata_hints))?;
+
+    // Because we know the buffer is used for reading only, cast it to *mut u8 to satisfy the
+    // jni_new_direct_buffer call, which requires a mutable pointer
+    let mut_ptr: *mut u8 = buffer.as_ptr() as *mut u8;
+
+    let byte_buffer = jni_new_direct_buffer(&mut env, mut_ptr, buffer.len())?;
+    let j_metadata = jni_rust_metadata_to_tika_metadata(&mut env, metadata_hints)?;
+    let j_pdf_conf = JPDFParserConfig::new(&mut env, options.pdf_conf)?;
+    let j_office_conf = JOfficeParserConfig::new(&mut env, options.office_conf)?;
+    let j_ocr_conf = JTesseractOcrConfig::new(&mut env, options.ocr_conf)?;
+    let j_rust_parsers = JRustParsers::new(&mut env, options.parsers)?;
+
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "unpackBytes",
+        "(Ljava/nio/ByteBuffer;\
+        Lorg/apache/tika/metadata/Metadata;\
+        I\
+        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
+        Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
+        Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        [Lai/yobix/RustParser;\
+        )Lai/yobix/UnpackResult;",
+        &[
+            (&byte_buffer).into(),
+            (&j_metadata).into(),
+            JValue::Int(max_length),
+            (&j_pdf_conf.internal).into(),
+            (&j_office_conf.internal).into(),
+            (&j_ocr_conf.internal).into(),
+            (&j_rust_parsers.internal).into(),
+        ],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let result = JUnpackResult::new(&mut env, call_result_obj)?;
+    let embedded = result
+        .names
+        .into_iter()
+        .zip(result.contents)
+        .zip(result.embedded_metadata)
+        .map(|((name, data), metadata)| EmbeddedDocument {
+            name,
+            metadata,
+            data,
+        })
+        .collect();
+    Ok((result.content, result.metadata, embedded))
+}
+
+fn parse_to_events(
+    mut env: AttachGuard,
+    data_source_vals: &[JValue],
+    options: &ParseOptions,
+    handler: &mut dyn ContentHandler,
+    method_name: &str,
+    signature: &str,
+) -> ExtractResult<Metadata> {
+    let j_options = JParseOptions::new(&mut env, options)?;
+    register_content_handler_natives(&mut env)?;
+
+    // The java content handler calls back the target until the parse returns
+    let mut target = EventTarget::new(handler);
+    let mut args = data_source_vals.to_vec();
+    args.extend_from_slice(&j_options.args());
+    args.push(JValue::Long(target.address()));
+    let result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        method_name,
+        signature,
+        &args,
+    )
+    .and_then(|call_result| {
+        let call_result_obj = call_result.l()?;
+        // The content of the StringResult is empty, the events were sent to the handler
+        let copy_start = Instant::now();
+        let mut result = JStringResult::new(&mut env, call_result_obj)?;
+        if options.progress.collects_stats() {
+            stats::add_jni_copy_time(&mut result.metadata, copy_start.elapsed());
+        }
+        Ok(result.metadata)
+    });
+    target.finish(result)
+}
+
+/// Parses a file using the Apache Tika library and sends its SAX events to the handler.
+/// Returns the metadata of the file
+pub fn parse_file_to_events(
+    file_path: &str,
+    options: &ParseOptions,
+    handler: &mut dyn ContentHandler,
+) -> ExtractResult<Metadata> {
+    let mut env = attach_for_extraction(file_path)?;
+
+    let file_path_val = jni_new_string_as_jvalue(&mut env, file_path)?;
+    parse_to_events(
+        env,
+        &[(&file_path_val).into()],
+        options,
+        handler,
+        "parseFileToEvents",
+        "(Ljava/lang/String;\
+        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
+        Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
+        Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        Lai/yobix/PageSelection;\
+        Lai/yobix/OutputOptions;\
+        [Lai/yobix/RustParser;\
+        Lai/yobix/ProgressReporter;\
+        J\
+        )Lai/yobix/StringResult;",
+    )
+}
+
+/// Parses bytes using the Apache Tika library and sends their SAX events to the handler.
+/// Returns the metadata of the bytes
+pub fn parse_bytes_to_events(
+    buffer: &[u8],
+    metadata_hints: &Metadata,
+    options: &ParseOptions,
+    handler: &mut dyn ContentHandler,
+) -> ExtractResult<Metadata> {
+    let mut env = attach_for_extraction(&bytes_source(buffer.len(), metadata_hints))?;
+
+    // Because we know the buffer is used for reading only, cast it to *mut u8 to satisfy the
+    // jni_new_direct_buffer call, which requires a mutable pointer
+    let mut_ptr: *mut u8 = buffer.as_ptr() as *mut u8;
+
+    let byte_buffer = jni_new_direct_buffer(&mut env, mut_ptr, buffer.len())?;
+    let j_metadata = jni_rust_metadata_to_tika_metadata(&mut env, metadata_hints)?;
+
+    parse_to_events(
+        env,
+        &[(&byte_buffer).into(), (&j_metadata).into()],
+        options,
+        handler,
+        "parseBytesToEvents",
+        "(Ljava/nio/ByteBuffer;\
+        Lorg/apache/tika/metadata/Metadata;\
+        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
+        Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
+        Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        Lai/yobix/PageSelection;\
+        Lai/yobix/OutputOptions;\
+        [Lai/yobix/RustParser;\
+        Lai/yobix/ProgressReporter;\
+        J\
+        )Lai/yobix/StringResult;",
+    )
+}
+
+/// Parses an url using the Apache Tika library and sends its SAX events to the handler.
+/// Returns the metadata of the url
+pub fn parse_url_to_events(
+    url: &str,
+    options: &ParseOptions,
+    handler: &mut dyn ContentHandler,
+) -> ExtractResult<Metadata> {
+    let mut env = attach_for_extraction(url)?;
+
+    let url_val = jni_new_string_as_jvalue(&mut env, url)?;
+    parse_to_events(
+        env,
+        &[(&url_val).into()],
+        options,
+        handler,
+        "parseUrlToEvents",
+        "(Ljava/lang/String;\
+        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
+        Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
+        Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        Lai/yobix/PageSelection;\
+        Lai/yobix/OutputOptions;\
+        [Lai/yobix/RustParser;\
+        Lai/yobix/ProgressReporter;\
+        J\
+        )Lai/yobix/StringResult;",
+    )
+}
+
+/// Detects the mime type of a file using the Apache Tika library.
+pub fn detect_file(file_path: &str) -> ExtractResult<(String, Metadata)> {
+    let mut env = attach_for_extraction(file_path)?;
+
+    let file_path_val = jni_new_string_as_jvalue(&mut env, file_path)?;
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "detect",
+        "(Ljava/lang/String;)Lai/yobix/StringResult;",
+        &[(&file_path_val).into()],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let result = JStringResult::new(&mut env, call_result_obj)?;
+    Ok((result.content, result.metadata))
+}
+
+/// Detects the mime type of bytes using the Apache Tika library.
+pub fn detect_bytes(buffer: &[u8], metadata_hints: &Metadata) -> ExtractResult<(String, Metadata)> {
+    let mut env = attach_for_extraction(&bytes_source(buffer.len(), metadata_hints))?;
+
+    // Because we know the buffer is used for reading only, cast it to *mut u8 to satisfy the
+    // jni_new_direct_buffer call, which requires a mutable pointer
+    let mut_ptr: *mut u8 = buffer.as_ptr() as *mut u8;
+
+    let byte_buffer = jni_new_direct_buffer(&mut env, mut_ptr, buffer.len())?;
+    let j_metadata = jni_rust_metadata_to_tika_metadata(&mut env, metadata_hints)?;
+
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "detectBytes",
+        "(Ljava/nio/ByteBuffer;\
+        Lorg/apache/tika/metadata/Metadata;\
+        )Lai/yobix/StringResult;",
+        &[(&byte_buffer).into(), (&j_metadata).into()],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let result = JStringResult::new(&mut env, call_result_obj)?;
+    Ok((result.content, result.metadata))
+}
+
+/// Reads the outline of a pdf file with pdfbox, without extracting its text. Returns a line per
+/// bookmark with its level, page and title separated by tabs
+pub fn parse_file_to_pdf_outline(file_path: &str) -> ExtractResult<String> {
+    let mut env = attach_for_extraction(file_path)?;
+
+    let file_path_val = jni_new_string_as_jvalue(&mut env, file_path)?;
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "parseFileToPdfOutline",
+        "(Ljava/lang/String;)Lai/yobix/StringResult;",
+        &[(&file_path_val).into()],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let result = JStringResult::new(&mut env, call_result_obj)?;
+    Ok(result.content)
+}
+
+/// Same as [`parse_file_to_pdf_outline`] for the bytes of a pdf
+pub fn parse_bytes_to_pdf_outline(buffer: &[u8]) -> ExtractResult<String> {
+    let mut env = attach_for_extraction(&format!("{} bytes", buffer.len()))?;
+
+    // Because we know the buffer is used for reading only, cast it to *mut u8 to satisfy the
+    // jni_new_direct_buffer call, which requires a mutable pointer
+    let mut_ptr: *mut u8 = buffer.as_ptr() as *mut u8;
+
+    let byte_buffer = jni_new_direct_buffer(&mut env, mut_ptr, buffer.len())?;
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "parseBytesToPdfOutline",
+        "(Ljava/nio/ByteBuffer;)Lai/yobix/StringResult;",
+        &[(&byte_buffer).into()],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let result = JStringResult::new(&mut env, call_result_obj)?;
+    Ok(result.content)
+}
+
+/// Reads the annotations of the pages of a pdf file with pdfbox. Returns a line per annotation
+/// with its subtype, page, author, date, anchor text and contents separated by tabs
+pub fn parse_file_to_pdf_annotations(file_path: &str) -> ExtractResult<String> {
+    let mut env = attach_for_extraction(file_path)?;
+
+    let file_path_val = jni_new_string_as_jvalue(&mut env, file_path)?;
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "parseFileToPdfAnnotations",
+        "(Ljava/lang/String;)Lai/yobix/StringResult;",
+        &[(&file_path_val).into()],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let result = JStringResult::new(&mut env, call_result_obj)?;
+    Ok(result.content)
+}
+
+/// Same as [`parse_file_to_pdf_annotations`] for the bytes of a pdf
+pub fn parse_bytes_to_pdf_annotations(buffer: &[u8]) -> ExtractResult<String> {
+    let mut env = attach_for_extraction(&format!("{} bytes", buffer.len()))?;
+
+    // Because we know the buffer is used for reading only, cast it to *mut u8 to satisfy the
+    // jni_new_direct_buffer call, which requires a mutable pointer
+    let mut_ptr: *mut u8 = buffer.as_ptr() as *mut u8;
+
+    let byte_buffer = jni_new_direct_buffer(&mut env, mut_ptr, buffer.len())?;
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "parseBytesToPdfAnnotations",
+        "(Ljava/nio/ByteBuffer;)Lai/yobix/StringResult;",
+        &[(&byte_buffer).into()],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let result = JStringResult::new(&mut env, call_result_obj)?;
+    Ok(result.content)
+}
@@ -1,14 +1,21 @@
 use crate::errors::{Error, ExtractResult};
+use crate::parser::ParserRegistry;
+use crate::progress::ProgressReporter;
+use crate::stats;
+use crate::tika::callbacks::{register_progress_natives, register_rust_parser_natives};
 use crate::tika::jni_utils::{
-    jni_call_method, jni_jobject_to_string, jni_new_string_as_jvalue,
+    jni_byte_array_array_to_vec, jni_call_method, jni_jobject_array_to_vec, jni_jobject_to_string,
+    jni_new_string, jni_new_string_as_jvalue, jni_tika_metadata_array_to_vec,
     jni_tika_metadata_to_rust_metadata,
 };
 use crate::tika::vm;
 use crate::{Metadata, OfficeParserConfig, PdfParserConfig, TesseractOcrConfig, DEFAULT_BUF_SIZE};
 use bytemuck::cast_slice_mut;
 use jni::objects::{GlobalRef, JByteArray, JObject, JValue};
-use jni::sys::jsize;
+use jni::sys::{jint, jsize};
 use jni::JNIEnv;
+use std::ops::RangeInclusive;
+use std::time::{Duration, Instant};
 
 /// Wrapper for [`JObject`]s that contain `org.apache.commons.io.input.ReaderInputStream`
 /// It saves a GlobalRef to the java object, which is cleared when the last GlobalRef is dropped
@@ -18,6 +25,10 @@ pub struct JReaderInputStream {
     internal: GlobalRef,
     buffer: GlobalRef,
     capacity: jsize,
+    /// The java metadata, completed with the stats of the parse once the stream is read
+    metadata: Option<GlobalRef>,
+    /// The time spent copying the read bytes from the java byte array
+    copy_time: Duration,
 }
 
 impl JReaderInputStream {
@@ -33,9 +44,40 @@ impl JReaderInputStream {
             internal: env.new_global_ref(obj)?,
             buffer: env.new_global_ref(jbyte_array)?,
             capacity,
+            metadata: None,
+            copy_time: Duration::ZERO,
         })
     }
 
+    /// Keeps the java metadata of the parse, to read its stats once the stream is read. The copy
+    /// time is the time spent copying the result of the parse call
+    pub(crate) fn keep_metadata<'local>(
+        &mut self,
+        env: &mut JNIEnv<'local>,
+        metadata: &JObject<'local>,
+        copy_time: Duration,
+    ) -> ExtractResult<()> {
+        self.metadata = Some(env.new_global_ref(metadata)?);
+        self.copy_time += copy_time;
+        Ok(())
+    }
+
+    /// Reads the java metadata kept by [`JReaderInputStream::keep_metadata`], with the time spent
+    /// copying the read bytes and this metadata added to it
+    pub(crate) fn read_metadata(&self) -> ExtractResult<Metadata> {
+        let start = Instant::now();
+        let mut env = vm().attach_current_thread()?;
+        let mut metadata = match &self.metadata {
+            Some(obj) => {
+                let obj = env.new_local_ref(obj)?;
+                jni_tika_metadata_to_rust_metadata(&mut env, obj)?
+            }
+            None => Metadata::new(),
+        };
+        stats::add_jni_copy_time(&mut metadata, self.copy_time + start.elapsed());
+        Ok(metadata)
+    }
+
     pub(crate) fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
         let mut env = vm().attach_current_thread().map_err(Error::JniError)?;
 
@@ -84,8 +126,10 @@ impl JReaderInputStream {
 
         // Get the bytes from the Java byte array to the Rust byte array
         // This is a copy or just memory reference. POTENTIAL performance improvement
+        let start = Instant::now();
         env.get_byte_array_region(JByteArray::from(obj_local), 0, buf_of_i8)
             .map_err(|_e| Error::JniEnvCall("Failed to get byte array region"))?;
+        self.copy_time += start.elapsed();
 
         if num_read_bytes == -1 {
             // End of stream reached
@@ -151,6 +195,7 @@ impl<'local> JStringResult {
 /// `org.apache.commons.io.input.ReaderInputStream` object, which later can be used for reading
 pub struct JReaderResult<'local> {
     pub java_reader: JObject<'local>,
+    pub java_metadata: JObject<'local>,
     pub metadata: Metadata,
 }
 
@@ -187,16 +232,120 @@ impl<'local> JReaderResult<'local> {
                     &[],
                 )?
                 .l()?;
-            let metadata = jni_tika_metadata_to_rust_metadata(env, tika_metadata_obj)?;
+            // The java metadata is kept, the parse completes it with its stats
+            let metadata_ref = env.new_local_ref(&tika_metadata_obj)?;
+            let metadata = jni_tika_metadata_to_rust_metadata(env, metadata_ref)?;
 
             Ok(Self {
                 java_reader: reader_obj,
+                java_metadata: tika_metadata_obj,
                 metadata,
             })
         }
     }
 }
 
+/// Converts the error status and message of the `ai.yobix` result objects to an [`Error`].
+/// Returns `Ok(())` when the result is not an error
+fn jni_check_result_status<'local>(
+    env: &mut JNIEnv<'local>,
+    obj: &JObject<'local>,
+) -> ExtractResult<()> {
+    let is_error = jni_call_method(env, obj, "isError", "()Z", &[])?.z()?;
+    if !is_error {
+        return Ok(());
+    }
+
+    let status = jni_call_method(env, obj, "getStatus", "()B", &[])?.b()?;
+    let msg_obj = env
+        .call_method(obj, "getErrorMessage", "()Ljava/lang/String;", &[])?
+        .l()?;
+    let msg = jni_jobject_to_string(env, msg_obj)?;
+    match status {
+        1 => Err(Error::IoError(msg)),
+        2 => Err(Error::ParseError(msg)),
+        _ => Err(Error::Unknown(msg)),
+    }
+}
+
+/// Wrapper for the Java class  `ai.yobix.MetadataListResult`
+/// Upon creation it converts the metadata of the container and every embedded document
+pub struct JMetadataListResult {
+    pub metadata_list: Vec<Metadata>,
+}
+
+impl<'local> JMetadataListResult {
+    pub(crate) fn new(env: &mut JNIEnv<'local>, obj: JObject<'local>) -> ExtractResult<Self> {
+        jni_check_result_status(env, &obj)?;
+
+        let metadata_array_obj = env
+            .call_method(
+                &obj,
+                "getMetadataList",
+                "()[Lorg/apache/tika/metadata/Metadata;",
+                &[],
+            )?
+            .l()?;
+        let metadata_list = jni_tika_metadata_array_to_vec(env, metadata_array_obj)?;
+        Ok(Self { metadata_list })
+    }
+}
+
+/// Wrapper for the Java class  `ai.yobix.UnpackResult`
+/// Upon creation it copies the text and metadata of the container document, and the names,
+/// raw bytes and metadata of the embedded documents
+pub struct JUnpackResult {
+    pub content: String,
+    pub metadata: Metadata,
+    pub names: Vec<String>,
+    pub contents: Vec<Vec<u8>>,
+    pub embedded_metadata: Vec<Metadata>,
+}
+
+impl<'local> JUnpackResult {
+    pub(crate) fn new(env: &mut JNIEnv<'local>, obj: JObject<'local>) -> ExtractResult<Self> {
+        jni_check_result_status(env, &obj)?;
+
+        let content_obj = env
+            .call_method(&obj, "getContent", "()Ljava/lang/String;", &[])?
+            .l()?;
+        let content = jni_jobject_to_string(env, content_obj)?;
+        let tika_metadata_obj = env
+            .call_method(
+                &obj,
+                "getMetadata",
+                "()Lorg/apache/tika/metadata/Metadata;",
+                &[],
+            )?
+            .l()?;
+        let metadata = jni_tika_metadata_to_rust_metadata(env, tika_metadata_obj)?;
+
+        let names_obj = env
+            .call_method(&obj, "getNames", "()[Ljava/lang/String;", &[])?
+            .l()?;
+        let names = jni_jobject_array_to_vec(env, names_obj)?;
+        let contents_obj = env.call_method(&obj, "getContents", "()[[B", &[])?.l()?;
+        let contents = jni_byte_array_array_to_vec(env, contents_obj)?;
+        let embedded_metadata_obj = env
+            .call_method(
+                &obj,
+                "getEmbeddedMetadata",
+                "()[Lorg/apache/tika/metadata/Metadata;",
+                &[],
+            )?
+            .l()?;
+        let embedded_metadata = jni_tika_metadata_array_to_vec(env, embedded_metadata_obj)?;
+
+        Ok(Self {
+            content,
+            metadata,
+            names,
+            contents,
+            embedded_metadata,
+        })
+    }
+}
+
 /// Wrapper for [`JObject`]s that contain `org.apache.tika.parser.pdf.PDFParserConfig`.
 /// Looks up the class and method IDs on creation rather than for every method call.
 pub(crate) struct JPDFParserConfig<'local> {
@@ -205,8 +354,11 @@ pub(crate) struct JPDFParserConfig<'local> {
 
 impl<'local> JPDFParserConfig<'local> {
     /// Creates a new object instance of `JPDFParserConfig` in the java world
-    /// keeps reference to the object and method IDs for later use
+    /// keeps reference to the object and method IDs for later use. The config is validated
+    /// first, because the page selection does not check the page ranges
     pub(crate) fn new(env: &mut JNIEnv<'local>, config: &PdfParserConfig) -> ExtractResult<Self> {
+        config.validate()?;
+
         // Create the java object
         let class = env.find_class("org/apache/tika/parser/pdf/PDFParserConfig")?;
         let obj = env.new_object(&class, "()V", &[])?;
@@ -252,6 +404,111 @@ impl<'local> JPDFParserConfig<'local> {
             "(Ljava/lang/String;)V",
             &[(&ocr_str_val).into()],
         )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setSortByPosition",
+            "(Z)V",
+            &[JValue::from(config.sort_by_position)],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setSpacingTolerance",
+            "(F)V",
+            &[JValue::Float(config.spacing_tolerance)],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setAverageCharTolerance",
+            "(F)V",
+            &[JValue::Float(config.average_char_tolerance)],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setEnableAutoSpace",
+            "(Z)V",
+            &[JValue::from(config.enable_auto_space)],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setSuppressDuplicateOverlappingText",
+            "(Z)V",
+            &[JValue::from(config.suppress_duplicate_overlapping_text)],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setExtractAcroFormContent",
+            "(Z)V",
+            &[JValue::from(config.extract_acro_form_content)],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setExtractBookmarksText",
+            "(Z)V",
+            &[JValue::from(config.extract_bookmarks_text)],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setExtractActions",
+            "(Z)V",
+            &[JValue::from(config.extract_actions)],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setOcrDPI",
+            "(I)V",
+            &[JValue::Int(config.ocr_dpi)],
+        )?;
+        // The java setters parse the names case-insensitively, except the image format names
+        // that must be lowercase: png, tiff or jpeg
+        let image_type_val = jni_new_string_as_jvalue(env, &config.ocr_image_type.to_string())?;
+        jni_call_method(
+            env,
+            &obj,
+            "setOcrImageType",
+            "(Ljava/lang/String;)V",
+            &[(&image_type_val).into()],
+        )?;
+        let image_format_val =
+            jni_new_string_as_jvalue(env, &config.ocr_image_format.to_string().to_lowercase())?;
+        jni_call_method(
+            env,
+            &obj,
+            "setOcrImageFormatName",
+            "(Ljava/lang/String;)V",
+            &[(&image_format_val).into()],
+        )?;
+        let rendering_strategy_val =
+            jni_new_string_as_jvalue(env, &config.ocr_rendering_strategy.to_string())?;
+        jni_call_method(
+            env,
+            &obj,
+            "setOcrRenderingStrategy",
+            "(Ljava/lang/String;)V",
+            &[(&rendering_strategy_val).into()],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setMaxMainMemoryBytes",
+            "(J)V",
+            &[JValue::Long(config.max_main_memory_bytes)],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setCatchIntermediateIOExceptions",
+            "(Z)V",
+            &[JValue::from(config.catch_intermediate_io_exceptions)],
+        )?;
 
         Ok(Self { internal: obj })
     }
@@ -351,19 +608,158 @@ impl<'local> JOfficeParserConfig<'local> {
     }
 }
 
-/// Wrapper for [`JObject`]s that contain `org.apache.tika.parser.ocr.TesseractOCRConfig`.
+/// Wrapper for [`JObject`]s that contain `ai.yobix.PageSelection`.
+pub(crate) struct JPageSelection<'local> {
+    pub(crate) internal: JObject<'local>,
+}
+
+impl<'local> JPageSelection<'local> {
+    /// Creates a new object instance of `JPageSelection` in the java world. The page ranges
+    /// are passed as a flat array of inclusive `[start, end]` pairs
+    pub(crate) fn new(
+        env: &mut JNIEnv<'local>,
+        page_ranges: &[RangeInclusive<u32>],
+        max_pages: i32,
+    ) -> ExtractResult<Self> {
+        let bounds: Vec<jint> = page_ranges
+            .iter()
+            .flat_map(|range| [*range.start(), *range.end()])
+            .map(|page| jint::try_from(page).unwrap_or(jint::MAX))
+            .collect();
+        let j_page_ranges = env.new_int_array(bounds.len() as jsize)?;
+        env.set_int_array_region(&j_page_ranges, 0, &bounds)?;
+
+        let class = env.find_class("ai/yobix/PageSelection")?;
+        let obj = env.new_object(
+            &class,
+            "([II)V",
+            &[(&j_page_ranges).into(), JValue::Int(max_pages)],
+        )?;
+
+        Ok(Self { internal: obj })
+    }
+}
+
+/// Wrapper for [`JObject`]s that contain `ai.yobix.OutputOptions`.
+pub(crate) struct JOutputOptions<'local> {
+    pub(crate) internal: JObject<'local>,
+}
+
+impl<'local> JOutputOptions<'local> {
+    /// Creates a new object instance of `JOutputOptions` in the java world. With `page_breaks`,
+    /// a form feed is written after every pdf page. Without `include_comments`, the comments of
+    /// the docx, pptx and xlsx documents are dropped
+    pub(crate) fn new(
+        env: &mut JNIEnv<'local>,
+        page_breaks: bool,
+        include_comments: bool,
+    ) -> ExtractResult<Self> {
+        let class = env.find_class("ai/yobix/OutputOptions")?;
+        let obj = env.new_object(
+            &class,
+            "(ZZ)V",
+            &[
+                JValue::Bool(page_breaks.into()),
+                JValue::Bool(include_comments.into()),
+            ],
+        )?;
+
+        Ok(Self { internal: obj })
+    }
+}
+
+/// Wrapper for [`JObject`]s that contain an `ai.yobix.RustParser[]`, with a java parser calling
+/// back every parser registered on the extractor.
+pub(crate) struct JRustParsers<'local> {
+    pub(crate) internal: JObject<'local>,
+}
+
+impl<'local> JRustParsers<'local> {
+    /// Creates a new object instance of `JRustParsers` in the java world. The native methods
+    /// called back by the java parsers are registered with the first parser
+    pub(crate) fn new(env: &mut JNIEnv<'local>, registry: &ParserRegistry) -> ExtractResult<Self> {
+        let parsers = registry.parsers();
+        let class = env.find_class("ai/yobix/RustParser")?;
+        let array = env.new_object_array(parsers.len() as jsize, &class, JObject::null())?;
+        if parsers.is_empty() {
+            return Ok(Self {
+                internal: array.into(),
+            });
+        }
+        register_rust_parser_natives(env)?;
+
+        for (index, parser) in parsers.iter().enumerate() {
+            let types = parser.supported_types();
+            let j_types =
+                env.new_object_array(types.len() as jsize, "java/lang/String", JObject::null())?;
+            for (i, mime_type) in types.iter().enumerate() {
+                let j_mime_type = jni_new_string(env, mime_type)?;
+                env.set_object_array_element(&j_types, i as jsize, &j_mime_type)?;
+            }
+            let obj = env.new_object(
+                &class,
+                "(JI[Ljava/lang/String;)V",
+                &[
+                    JValue::Long(registry.id()),
+                    JValue::Int(index as jint),
+                    (&j_types).into(),
+                ],
+            )?;
+            env.set_object_array_element(&array, index as jsize, &obj)?;
+        }
+
+        Ok(Self {
+            internal: array.into(),
+        })
+    }
+}
+
+/// Wrapper for [`JObject`]s that contain an `ai.yobix.ProgressReporter`, which reports the
+/// progress of the parse to the progress callback of the extractor
+pub(crate) struct JProgressReporter<'local> {
+    pub(crate) internal: JObject<'local>,
+}
+
+impl<'local> JProgressReporter<'local> {
+    /// Creates a new object instance of `JProgressReporter` in the java world. The reporter of an
+    /// extractor without progress callback has the id 0 and reports nothing. The java reporter
+    /// adds the stats of the parse to the metadata if they are collected
+    pub(crate) fn new(
+        env: &mut JNIEnv<'local>,
+        reporter: &ProgressReporter,
+    ) -> ExtractResult<Self> {
+        if reporter.is_enabled() {
+            register_progress_natives(env)?;
+        }
+        let obj = env.new_object(
+            "ai/yobix/ProgressReporter",
+            "(JZ)V",
+            &[
+                JValue::Long(reporter.id()),
+                JValue::Bool(reporter.collects_stats().into()),
+            ],
+        )?;
+        Ok(Self { internal: obj })
+    }
+}
+
+/// Wrapper for [`JObject`]s that contain `ai.yobix.TesseractPathsConfig`, a
+/// `org.apache.tika.parser.ocr.TesseractOCRConfig` with the tesseract and ImageMagick paths.
 pub(crate) struct JTesseractOcrConfig<'local> {
     pub(crate) internal: JObject<'local>,
 }
 impl<'local> JTesseractOcrConfig<'local> {
     /// Creates a new object instance of `JTesseractOcrConfig` in the java world
-    /// keeps reference to the object for later use
+    /// keeps reference to the object for later use. The config is validated first, because
+    /// the java setters throw on invalid values
     pub(crate) fn new(
         env: &mut JNIEnv<'local>,
         config: &TesseractOcrConfig,
     ) -> ExtractResult<Self> {
+        config.validate()?;
+
         // Create the java object
-        let class = env.find_class("org/apache/tika/parser/ocr/TesseractOCRConfig")?;
+        let class = env.find_class("ai/yobix/TesseractPathsConfig")?;
         let obj = env.new_object(&class, "()V", &[])?;
 
         // Call the setters
@@ -408,6 +804,100 @@ impl<'local> JTesseractOcrConfig<'local> {
             &[(&lang_string_val).into()],
         )?;
 
+        let page_seg_mode_val = jni_new_string_as_jvalue(env, &config.page_seg_mode.to_string())?;
+        jni_call_method(
+            env,
+            &obj,
+            "setPageSegMode",
+            "(Ljava/lang/String;)V",
+            &[(&page_seg_mode_val).into()],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setPreserveInterwordSpacing",
+            "(Z)V",
+            &[JValue::from(config.preserve_interword_spacing)],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setMinFileSizeToOcr",
+            "(J)V",
+            &[JValue::from(config.min_file_size_to_ocr)],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setMaxFileSizeToOcr",
+            "(J)V",
+            &[JValue::from(config.max_file_size_to_ocr)],
+        )?;
+        let colorspace_val = jni_new_string_as_jvalue(env, &config.colorspace)?;
+        jni_call_method(
+            env,
+            &obj,
+            "setColorspace",
+            "(Ljava/lang/String;)V",
+            &[(&colorspace_val).into()],
+        )?;
+        let filter_val = jni_new_string_as_jvalue(env, &config.filter)?;
+        jni_call_method(
+            env,
+            &obj,
+            "setFilter",
+            "(Ljava/lang/String;)V",
+            &[(&filter_val).into()],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setResize",
+            "(I)V",
+            &[JValue::from(config.resize)],
+        )?;
+        let output_type_val = jni_new_string_as_jvalue(env, &config.output_type.to_string())?;
+        jni_call_method(
+            env,
+            &obj,
+            "setOutputType",
+            "(Ljava/lang/String;)V",
+            &[(&output_type_val).into()],
+        )?;
+
+        if let Some(mode) = config.ocr_engine_mode {
+            jni_call_method(env, &obj, "setOcrEngineMode", "(I)V", &[JValue::from(mode)])?;
+        }
+        for (key, value) in config.other_tesseract_config.iter() {
+            let key_val = jni_new_string_as_jvalue(env, key)?;
+            let value_val = jni_new_string_as_jvalue(env, value)?;
+            jni_call_method(
+                env,
+                &obj,
+                "addOtherTesseractConfig",
+                "(Ljava/lang/String;Ljava/lang/String;)V",
+                &[(&key_val).into(), (&value_val).into()],
+            )?;
+        }
+
+        let paths = [
+            ("setTesseractPath", &config.tesseract_path),
+            ("setTessdataPath", &config.tessdata_path),
+            ("setImageMagickPath", &config.image_magick_path),
+        ];
+        for (setter, path) in paths {
+            if let Some(path) = path {
+                let path_val = jni_new_string_as_jvalue(env, path)?;
+                jni_call_method(
+                    env,
+                    &obj,
+                    setter,
+                    "(Ljava/lang/String;)V",
+                    &[(&path_val).into()],
+                )?;
+            }
+        }
+
         Ok(Self { internal: obj })
     }
 }
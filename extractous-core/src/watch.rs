@@ -0,0 +1,399 @@
+//! Incremental extraction of a directory tree. A [`Watcher`] polls the files of a directory and
+//! extracts the created and modified ones with its [`Extractor`], reporting them as
+//! [`WatchEvent`]s along with the deleted files.
+//!
+//! The modification time, size and content hash of every file are kept in a state file, set
+//! with [`Watcher::set_state_file`], so that after a restart only the files changed since the
+//! last scan are extracted again. Polling also works on network drives, whose changes are not
+//! reported by the file system notifications.
+//!
+//! ```no_run
+//! use extractous::watch::{WatchEvent, Watcher};
+//! use extractous::Extractor;
+//!
+//! let mut watcher = Watcher::new("documents", Extractor::new()).set_state_file("index.json");
+//! watcher
+//!     .watch(|event| match event {
+//!         WatchEvent::Created { path, content, .. } | WatchEvent::Modified { path, content, .. } => {
+//!             println!("{}: {} chars", path.display(), content.len())
+//!         }
+//!         WatchEvent::Deleted { path } => println!("{} deleted", path.display()),
+//!         WatchEvent::Failed { path, error } => eprintln!("{}: {}", path.display(), error),
+//!     })
+//!     .unwrap();
+//! ```
+
+use crate::cache::{file_hash, write_atomically};
+use crate::errors::{Error, ExtractResult};
+use crate::{Extractor, Metadata};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use walkdir::WalkDir;
+
+/// The default interval between two scans of [`Watcher::watch`], 5 seconds
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The version of the state file format
+const STATE_VERSION: u64 = 1;
+
+/// A change of the watched directory found by a scan
+#[derive(Debug)]
+pub enum WatchEvent {
+    /// A new file, with its extracted content and metadata
+    Created {
+        path: PathBuf,
+        content: String,
+        metadata: Metadata,
+    },
+    /// A file whose content changed, with its extracted content and metadata
+    Modified {
+        path: PathBuf,
+        content: String,
+        metadata: Metadata,
+    },
+    /// A file that was removed
+    Deleted { path: PathBuf },
+    /// A created or modified file that could not be extracted. It is not saved in the state, so
+    /// it is extracted again by the next scan
+    Failed { path: PathBuf, error: Error },
+}
+
+impl WatchEvent {
+    /// The path of the changed file, made of the watched directory and the path of the file in
+    /// the directory
+    pub fn path(&self) -> &Path {
+        match self {
+            WatchEvent::Created { path, .. }
+            | WatchEvent::Modified { path, .. }
+            | WatchEvent::Deleted { path }
+            | WatchEvent::Failed { path, .. } => path,
+        }
+    }
+}
+
+/// The state of a file at the last scan
+#[derive(Debug, Clone, PartialEq)]
+struct FileState {
+    /// Modification time in microseconds since the unix epoch
+    modified: u64,
+    size: u64,
+    hash: String,
+}
+
+/// The filter of [`Watcher::set_filter`]
+type WatchFilter = Arc<dyn Fn(&Path) -> bool + Send + Sync>;
+
+/// Polls a directory tree and extracts its created and modified files. See the
+/// [module documentation](self)
+#[derive(Clone)]
+pub struct Watcher {
+    root: PathBuf,
+    extractor: Extractor,
+    state_file: Option<PathBuf>,
+    poll_interval: Duration,
+    filter: Option<WatchFilter>,
+    /// The state of the files by their path relative to the root, loaded by the first scan
+    files: Option<BTreeMap<PathBuf, FileState>>,
+}
+
+impl fmt::Debug for Watcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Watcher")
+            .field("root", &self.root)
+            .field("extractor", &self.extractor)
+            .field("state_file", &self.state_file)
+            .field("poll_interval", &self.poll_interval)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Watcher {
+    /// Creates a watcher of the directory tree, extracting the files to string with the
+    /// extractor. Without a state file, the first scan reports all the files as created
+    pub fn new<P: AsRef<Path>>(root: P, extractor: Extractor) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+            extractor,
+            state_file: None,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            filter: None,
+            files: None,
+        }
+    }
+
+    /// Set the file keeping the modification time, size and content hash of the files between
+    /// the runs. It is read by the first scan, a missing file is an empty state, and written
+    /// after every scan that found changes
+    /// Default: None
+    pub fn set_state_file<P: AsRef<Path>>(mut self, state_file: P) -> Self {
+        self.state_file = Some(state_file.as_ref().to_path_buf());
+        self.files = None;
+        self
+    }
+
+    /// Set the interval between two scans of [`Watcher::watch`]
+    /// Default: 5 seconds
+    pub fn set_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Set a filter called with the path of the files relative to the watched directory. Only
+    /// the files it accepts are extracted, the others are ignored
+    /// Default: all the files
+    pub fn set_filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&Path) -> bool + Send + Sync + 'static,
+    {
+        self.filter = Some(Arc::new(filter));
+        self
+    }
+
+    /// Scans the directory tree once and calls the handler with the changes since the last
+    /// scan. Files whose modification time and size did not change are not read, files whose
+    /// content hash did not change are not extracted. The files of the directories that can
+    /// not be read are not reported as deleted
+    pub fn scan<F>(&mut self, mut handler: F) -> ExtractResult<()>
+    where
+        F: FnMut(WatchEvent),
+    {
+        if !self.root.is_dir() {
+            return Err(Error::IoError(format!(
+                "The watched directory {} does not exist",
+                self.root.display()
+            )));
+        }
+        let mut files = match self.files.take() {
+            Some(files) => files,
+            None => self.load_state()?,
+        };
+        let state_file = self.state_file_in_root();
+        let mut seen = HashSet::new();
+        let mut unreadable = Vec::new();
+        let mut changed = false;
+
+        for entry in WalkDir::new(&self.root).sort_by_file_name() {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    log::warn!("Could not scan {}", e);
+                    unreadable.extend(e.path().map(Path::to_path_buf));
+                    continue;
+                }
+            };
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let relative = entry
+                .path()
+                .strip_prefix(&self.root)
+                .unwrap_or(entry.path())
+                .to_path_buf();
+            if is_state_file(&relative, state_file.as_ref())
+                || self
+                    .filter
+                    .as_ref()
+                    .is_some_and(|filter| !filter(&relative))
+            {
+                continue;
+            }
+            seen.insert(relative.clone());
+
+            let Ok(metadata) = entry.metadata() else {
+                // The file was removed during the scan, it is deleted by the next one
+                continue;
+            };
+            let modified = metadata.modified().map_or(0, micros_since_epoch);
+            let size = metadata.len();
+            if let Some(file) = files.get(&relative) {
+                if file.modified == modified && file.size == size {
+                    continue;
+                }
+            }
+
+            let hash = match file_hash(entry.path()) {
+                Ok(hash) => hash,
+                Err(e) => {
+                    log::warn!("Could not read {}: {}", entry.path().display(), e);
+                    continue;
+                }
+            };
+            let state = FileState {
+                modified,
+                size,
+                hash,
+            };
+            let same_hash = files.get(&relative).map(|file| file.hash == state.hash);
+            if same_hash == Some(true) {
+                // Only the modification time changed
+                files.insert(relative, state);
+                changed = true;
+                continue;
+            }
+
+            let path = entry.into_path();
+            let event = match self
+                .extractor
+                .extract_file_to_string(&path.to_string_lossy())
+            {
+                Ok((content, metadata)) => {
+                    // Only the extracted files are saved, the failed ones are extracted again
+                    files.insert(relative, state);
+                    changed = true;
+                    if same_hash.is_some() {
+                        WatchEvent::Modified {
+                            path,
+                            content,
+                            metadata,
+                        }
+                    } else {
+                        WatchEvent::Created {
+                            path,
+                            content,
+                            metadata,
+                        }
+                    }
+                }
+                Err(error) => WatchEvent::Failed { path, error },
+            };
+            handler(event);
+        }
+
+        let deleted: Vec<PathBuf> = files
+            .keys()
+            .filter(|relative| !seen.contains(*relative))
+            .filter(|relative| {
+                let path = self.root.join(relative);
+                !unreadable.iter().any(|dir| path.starts_with(dir))
+            })
+            .cloned()
+            .collect();
+        for relative in deleted {
+            files.remove(&relative);
+            changed = true;
+            handler(WatchEvent::Deleted {
+                path: self.root.join(relative),
+            });
+        }
+
+        let saved = if changed {
+            self.save_state(&files)
+        } else {
+            Ok(())
+        };
+        self.files = Some(files);
+        saved
+    }
+
+    /// Scans the directory tree every poll interval, calling the handler with the changes.
+    /// Only returns when a scan fails, e.g. when the directory is removed or the state file
+    /// can not be written
+    pub fn watch<F>(&mut self, mut handler: F) -> ExtractResult<()>
+    where
+        F: FnMut(WatchEvent),
+    {
+        loop {
+            self.scan(&mut handler)?;
+            std::thread::sleep(self.poll_interval);
+        }
+    }
+
+    /// The directory of the state file relative to the root and the name of the state file,
+    /// when the state file is in the watched directory
+    fn state_file_in_root(&self) -> Option<(PathBuf, String)> {
+        let state_file = self.state_file.as_ref()?;
+        let dir = match state_file.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        let dir = std::fs::canonicalize(dir).ok()?;
+        let root = std::fs::canonicalize(&self.root).ok()?;
+        let relative_dir = dir.strip_prefix(root).ok()?.to_path_buf();
+        let name = state_file.file_name()?.to_string_lossy().into_owned();
+        Some((relative_dir, name))
+    }
+
+    /// Reads the state file, a json object with the state of the files by their path
+    fn load_state(&self) -> ExtractResult<BTreeMap<PathBuf, FileState>> {
+        let Some(state_file) = &self.state_file else {
+            return Ok(BTreeMap::new());
+        };
+        let state_error =
+            |e: &dyn fmt::Display| Error::IoError(format!("{}: {}", state_file.display(), e));
+        let state = match std::fs::read(state_file) {
+            Ok(state) => state,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+            Err(e) => return Err(state_error(&e)),
+        };
+
+        let state: Value = serde_json::from_slice(&state).map_err(|e| state_error(&e))?;
+        if state["version"] != STATE_VERSION {
+            return Err(state_error(&"unsupported watch state version"));
+        }
+        let files = state["files"]
+            .as_object()
+            .ok_or_else(|| state_error(&"missing files"))?;
+        let files = files
+            .iter()
+            .map(|(relative, file)| {
+                let state = FileState {
+                    modified: file["modified"].as_u64().unwrap_or_default(),
+                    size: file["size"].as_u64().unwrap_or_default(),
+                    hash: file["hash"].as_str().unwrap_or_default().to_string(),
+                };
+                (PathBuf::from(relative), state)
+            })
+            .collect();
+        Ok(files)
+    }
+
+    fn save_state(&self, files: &BTreeMap<PathBuf, FileState>) -> ExtractResult<()> {
+        let Some(state_file) = &self.state_file else {
+            return Ok(());
+        };
+        let files: serde_json::Map<String, Value> = files
+            .iter()
+            .map(|(relative, file)| {
+                let file = json!({
+                    "modified": file.modified,
+                    "size": file.size,
+                    "hash": file.hash,
+                });
+                (relative.to_string_lossy().into_owned(), file)
+            })
+            .collect();
+        let state = json!({
+            "version": STATE_VERSION,
+            "root": self.root.to_string_lossy(),
+            "files": files,
+        });
+        write_atomically(state_file, state.to_string().as_bytes()).map_err(|e| {
+            Error::IoError(format!(
+                "Could not write the watch state {}: {}",
+                state_file.display(),
+                e
+            ))
+        })
+    }
+}
+
+/// Whether the file is the state file or one of its temporary files, which are named
+/// `.{state file name}.*.tmp`
+fn is_state_file(relative: &Path, state_file: Option<&(PathBuf, String)>) -> bool {
+    let Some((dir, state_name)) = state_file else {
+        return false;
+    };
+    let name = relative.file_name().unwrap_or_default().to_string_lossy();
+    relative.parent() == Some(dir.as_path())
+        && (name == *state_name
+            || (name.starts_with(&format!(".{}.", state_name)) && name.ends_with(".tmp")))
+}
+
+fn micros_since_epoch(time: SystemTime) -> u64 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_micros() as u64)
+}
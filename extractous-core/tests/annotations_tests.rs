@@ -0,0 +1,190 @@
+use extractous::{Annotation, Extractor};
+use std::io::{Cursor, Write};
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+const RELS_NS: &str = "http://schemas.openxmlformats.org/package/2006/relationships";
+const TYPE_NS: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+
+fn archive(files: &[(&str, String)]) -> Vec<u8> {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, content) in files {
+        writer
+            .start_file(*name, SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(content.as_bytes()).unwrap();
+    }
+    writer.finish().unwrap().into_inner()
+}
+
+fn rels(relationships: &[(&str, &str, &str)]) -> String {
+    let relationships: String = relationships
+        .iter()
+        .map(|(id, kind, target)| {
+            format!(
+                r#"<Relationship Id="{}" Type="{}/{}" Target="{}"/>"#,
+                id, TYPE_NS, kind, target
+            )
+        })
+        .collect();
+    format!(
+        r#"<Relationships xmlns="{}">{}</Relationships>"#,
+        RELS_NS, relationships
+    )
+}
+
+fn comment(author: Option<&str>, date: Option<&str>, text: &str, page: Option<u32>) -> Annotation {
+    Annotation {
+        kind: "comment".to_string(),
+        author: author.map(str::to_string),
+        date: date.map(str::to_string),
+        text: text.to_string(),
+        anchor_text: None,
+        page,
+    }
+}
+
+#[test]
+fn test_extract_docx_comments() {
+    let docx = archive(&[
+        (
+            "word/document.xml",
+            r#"<w:document xmlns:w="w"><w:body><w:p>
+                <w:r><w:t>The supplier </w:t></w:r><w:commentRangeStart w:id="3"/>
+                <w:r><w:t>shall indemnify</w:t></w:r><w:commentRangeEnd w:id="3"/>
+                <w:r><w:commentReference w:id="3"/></w:r><w:r><w:t> the buyer.</w:t></w:r>
+                </w:p></w:body></w:document>"#
+                .to_string(),
+        ),
+        (
+            "word/_rels/document.xml.rels",
+            rels(&[("rId7", "comments", "comments.xml")]),
+        ),
+        (
+            "word/comments.xml",
+            r#"<w:comments xmlns:w="w">
+                <w:comment w:id="3" w:author="Jane Roe" w:date="2024-03-01T10:00:00Z" w:initials="JR">
+                <w:p><w:r><w:t>Too broad, limit to</w:t></w:r></w:p>
+                <w:p><w:r><w:t>direct damages &amp; costs</w:t></w:r></w:p>
+                </w:comment></w:comments>"#
+                .to_string(),
+        ),
+    ]);
+    let annotations = Extractor::new().extract_bytes_annotations(&docx).unwrap();
+    let mut expected = comment(
+        Some("Jane Roe"),
+        Some("2024-03-01T10:00:00Z"),
+        "Too broad, limit to\ndirect damages & costs",
+        None,
+    );
+    expected.anchor_text = Some("shall indemnify".to_string());
+    assert_eq!(annotations, vec![expected]);
+}
+
+#[test]
+fn test_extract_pptx_comments() {
+    let pptx = archive(&[
+        (
+            "ppt/presentation.xml",
+            r#"<p:presentation xmlns:p="p" xmlns:r="r"><p:sldIdLst>
+                <p:sldId id="256" r:id="rId3"/><p:sldId id="257" r:id="rId2"/>
+                </p:sldIdLst></p:presentation>"#
+                .to_string(),
+        ),
+        (
+            "ppt/_rels/presentation.xml.rels",
+            rels(&[
+                ("rId2", "slide", "slides/slide1.xml"),
+                ("rId3", "slide", "slides/slide2.xml"),
+                ("rId4", "slideMaster", "slideMasters/slideMaster1.xml"),
+            ]),
+        ),
+        (
+            "ppt/commentAuthors.xml",
+            r#"<p:cmAuthorLst xmlns:p="p"><p:cmAuthor id="0" name="John Doe" initials="JD"/>
+                </p:cmAuthorLst>"#
+                .to_string(),
+        ),
+        (
+            "ppt/slides/_rels/slide1.xml.rels",
+            rels(&[("rId1", "comments", "../comments/comment1.xml")]),
+        ),
+        (
+            "ppt/comments/comment1.xml",
+            r#"<p:cmLst xmlns:p="p"><p:cm authorId="0" dt="2024-03-02T09:30:00.000" idx="1">
+                <p:pos x="10" y="10"/><p:text>Update the revenue figures</p:text></p:cm>
+                </p:cmLst>"#
+                .to_string(),
+        ),
+    ]);
+    let annotations = Extractor::new().extract_bytes_annotations(&pptx).unwrap();
+    // slide1.xml is the second slide of the presentation
+    assert_eq!(
+        annotations,
+        vec![comment(
+            Some("John Doe"),
+            Some("2024-03-02T09:30:00.000"),
+            "Update the revenue figures",
+            Some(2),
+        )]
+    );
+}
+
+#[test]
+fn test_extract_xlsx_comments() {
+    let xlsx = archive(&[
+        (
+            "xl/workbook.xml",
+            r#"<workbook xmlns:r="r"><sheets>
+                <sheet name="Summary" sheetId="1" r:id="rId1"/>
+                <sheet name="Q1" sheetId="2" r:id="rId2"/></sheets></workbook>"#
+                .to_string(),
+        ),
+        (
+            "xl/_rels/workbook.xml.rels",
+            rels(&[
+                ("rId1", "worksheet", "worksheets/sheet1.xml"),
+                ("rId2", "worksheet", "worksheets/sheet2.xml"),
+            ]),
+        ),
+        (
+            "xl/worksheets/_rels/sheet2.xml.rels",
+            rels(&[("rId1", "comments", "../comments1.xml")]),
+        ),
+        (
+            "xl/comments1.xml",
+            r#"<comments><authors><author>Jane Roe</author></authors><commentList>
+                <comment ref="B2" authorId="0"><text><r><t>Jane Roe:</t></r>
+                <r><t xml:space="preserve">
+Check this total</t></r></text></comment></commentList></comments>"#
+                .to_string(),
+        ),
+    ]);
+    let annotations = Extractor::new().extract_bytes_annotations(&xlsx).unwrap();
+    let mut expected = comment(Some("Jane Roe"), None, "Check this total", Some(2));
+    expected.anchor_text = Some("Q1!B2".to_string());
+    assert_eq!(annotations, vec![expected]);
+}
+
+#[test]
+fn test_extract_annotations_without_comments() {
+    let extractor = Extractor::new();
+    let annotations = extractor
+        .extract_file_annotations("../test_files/documents/winter-sports.epub")
+        .unwrap();
+    assert!(annotations.is_empty());
+    assert!(extractor
+        .extract_bytes_annotations(b"plain text")
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_extract_pdf_annotations() {
+    let bytes = std::fs::read("../test_files/documents/2022_Q3_AAPL.pdf").unwrap();
+    let annotations = Extractor::new().extract_bytes_annotations(&bytes).unwrap();
+    // The links are not annotations, and every pdf annotation is on a page
+    assert!(annotations
+        .iter()
+        .all(|annotation| annotation.kind != "link" && annotation.page.is_some()));
+}
@@ -0,0 +1,37 @@
+use extractous::{ExtractionCache, Extractor};
+
+#[test]
+fn test_cached_extraction() {
+    let dir = std::env::temp_dir().join(format!("extractous-cache-{}", std::process::id()));
+    std::fs::remove_dir_all(&dir).ok();
+    let cache = ExtractionCache::new(&dir).unwrap();
+    let extractor = Extractor::new().set_cache(cache.clone());
+
+    let (content, metadata) = extractor.extract_file_to_string("README.md").unwrap();
+    let (cached_content, cached_metadata) = extractor.extract_file_to_string("README.md").unwrap();
+    assert_eq!(cached_content, content);
+    assert_eq!(cached_metadata, metadata);
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses, stats.entries), (1, 1, 1));
+
+    // A different configuration misses the cache
+    let extractor = extractor.set_extract_string_max_length(10);
+    let (content, _metadata) = extractor.extract_file_to_string("README.md").unwrap();
+    assert_eq!(content.len(), 10);
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses, stats.entries), (1, 2, 2));
+
+    // The bytes are cached with their hints
+    let bytes = std::fs::read("README.md").unwrap();
+    let hints =
+        extractous::Metadata::from([("resourceName".to_string(), vec!["README.md".to_string()])]);
+    extractor
+        .extract_bytes_to_string_with_hints(&bytes, &hints)
+        .unwrap();
+    extractor
+        .extract_bytes_to_string_with_hints(&bytes, &hints)
+        .unwrap();
+    assert_eq!(cache.stats().hits, 2);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
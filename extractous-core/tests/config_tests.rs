@@ -0,0 +1,90 @@
+#![cfg(feature = "serde")]
+
+use extractous::{
+    Error, Extractor, PdfOcrStrategy, PdfParserConfig, TesseractOcrConfig, UrlFetchConfig,
+};
+use test_case::test_case;
+
+fn expected_extractor() -> Extractor {
+    Extractor::new()
+        .set_extract_string_max_length(1000)
+        .set_xml_output(true)
+        .set_pdf_config(
+            PdfParserConfig::new()
+                .set_ocr_strategy(PdfOcrStrategy::OCR_ONLY)
+                .set_extract_annotation_text(false),
+        )
+        .set_ocr_config(
+            TesseractOcrConfig::new()
+                .set_language("deu")
+                .set_timeout_seconds(60),
+        )
+        .set_url_fetch_config(
+            UrlFetchConfig::new()
+                .set_header("Accept-Language", "de")
+                .set_max_redirects(3),
+        )
+}
+
+#[test_case("extractor.toml"; "Test TOML config")]
+#[test_case("extractor.json"; "Test JSON config")]
+fn test_from_config_file(file_name: &str) {
+    let extractor =
+        Extractor::from_config_file(format!("../test_files/configs/{}", file_name)).unwrap();
+    // Extractor does not implement PartialEq, compare all the fields through Debug
+    assert_eq!(
+        format!("{:?}", extractor),
+        format!("{:?}", expected_extractor())
+    );
+}
+
+#[test_case("invalid.toml"; "Test unknown field")]
+#[test_case("does-not-exist.toml"; "Test missing file")]
+#[test_case("../documents/simple.odt"; "Test unsupported extension")]
+fn test_from_invalid_config_file(file_name: &str) {
+    let result = Extractor::from_config_file(format!("../test_files/configs/{}", file_name));
+    assert!(matches!(result, Err(Error::ConfigError(_))));
+}
+
+#[test]
+fn test_config_serde_round_trip() {
+    let config = PdfParserConfig::new().set_ocr_strategy(PdfOcrStrategy::NO_OCR);
+    let json = serde_json::to_string(&config).unwrap();
+    assert!(json.contains("\"ocr_strategy\":\"NO_OCR\""));
+    assert_eq!(
+        serde_json::from_str::<PdfParserConfig>(&json).unwrap(),
+        config
+    );
+
+    // Missing fields are filled with their defaults
+    let config: TesseractOcrConfig = serde_json::from_str("{}").unwrap();
+    assert_eq!(config, TesseractOcrConfig::default());
+}
+
+#[test]
+fn test_url_credentials_are_not_serialized() {
+    let config = UrlFetchConfig::new()
+        .set_bearer_auth("secret-token")
+        .set_basic_auth("user", Some("secret-password"));
+    let extractor = Extractor::new().set_url_fetch_config(config.clone());
+    for json in [
+        serde_json::to_string(&config).unwrap(),
+        serde_json::to_string(&extractor).unwrap(),
+    ] {
+        assert!(!json.contains("secret"), "credentials serialized: {}", json);
+        assert!(!json.contains("bearer_token"));
+        assert!(!json.contains("basic_auth"));
+    }
+
+    // The credentials are still read from config files
+    let config: UrlFetchConfig = serde_json::from_str(
+        r#"{"bearer_token": "secret-token", "basic_auth": ["user", "secret-password"]}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        config,
+        UrlFetchConfig::new()
+            .set_bearer_auth("secret-token")
+            .set_basic_auth("user", Some("secret-password"))
+    );
+}
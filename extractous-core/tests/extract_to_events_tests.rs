@@ -0,0 +1,133 @@
+use extractous::{ContentHandler, Error, Event, ExtractResult, Extractor, Metadata};
+use std::fs;
+use test_case::test_case;
+use textdistance::nstr::cosine;
+
+// Declarers the shared test_utils code as module in this integration test
+mod test_utils;
+
+/// Collects the text of the body of the top level document, like the text output
+fn body_text(events: &[Event]) -> String {
+    let mut text = String::new();
+    let mut inside_body = false;
+    let mut embedded_depth = 0;
+    for event in events {
+        match event {
+            Event::StartElement { name, .. } if name == "body" => inside_body = true,
+            Event::EndElement { name } if name == "body" => inside_body = false,
+            Event::EmbeddedStart(_) => embedded_depth += 1,
+            Event::EmbeddedEnd => embedded_depth -= 1,
+            Event::Characters(characters) if inside_body && embedded_depth == 0 => {
+                text.push_str(characters)
+            }
+            _ => (),
+        }
+    }
+    text
+}
+
+#[test_case("2022_Q3_AAPL.pdf", 0.9; "Test PDF file")]
+#[test_case("science-exploration-1p.pptx", 0.9; "Test PPTX file")]
+#[test_case("simple.odt", 0.8; "Test ODT file")]
+#[test_case("table-multi-row-column-cells-actual.csv", 0.8; "Test CSV file")]
+#[test_case("category-level.docx", 0.8; "Test DOCX file")]
+#[test_case("winter-sports.epub", 0.8; "Test EPUB file")]
+fn test_extract_file_to_events(file_name: &str, target_dist: f64) {
+    let extractor = Extractor::new();
+    let mut events: Vec<Event> = Vec::new();
+    let metadata = extractor
+        .extract_file_events(
+            &format!("../test_files/documents/{}", file_name),
+            &mut events,
+        )
+        .unwrap();
+
+    let expected =
+        fs::read_to_string(format!("../test_files/expected_result/{}.txt", file_name)).unwrap();
+    let extracted = body_text(&events);
+    let dist = cosine(expected.trim(), extracted.trim());
+    assert!(
+        dist > target_dist,
+        "Cosine similarity is less than {} for file: {}, dist: {}",
+        target_dist,
+        file_name,
+        dist
+    );
+
+    // The events are balanced and end with the metadata of the document
+    let starts = events
+        .iter()
+        .filter(|e| matches!(e, Event::StartElement { .. }))
+        .count();
+    let ends = events
+        .iter()
+        .filter(|e| matches!(e, Event::EndElement { .. }))
+        .count();
+    assert_eq!(starts, ends);
+    assert_eq!(events.last(), Some(&Event::Metadata(metadata.clone())));
+    assert!(metadata.contains_key("Content-Type"));
+}
+
+#[test]
+fn test_extract_bytes_to_events_with_hints() {
+    let extractor = Extractor::new();
+    let hints = Metadata::from([("resourceName".to_string(), vec!["notes.md".to_string()])]);
+    let mut events: Vec<Event> = Vec::new();
+    let metadata = extractor
+        .extract_bytes_events(b"first line\nsecond line", &hints, &mut events)
+        .unwrap();
+
+    assert_eq!(body_text(&events), "first line\nsecond line\n");
+    assert!(events.contains(&Event::StartElement {
+        name: "p".to_string(),
+        attributes: Vec::new(),
+    }));
+    assert_eq!(
+        metadata.get("resourceName"),
+        Some(&vec!["notes.md".to_string()])
+    );
+}
+
+/// Stops the extraction at the first paragraph
+struct FirstParagraph {
+    text: String,
+    inside: bool,
+}
+
+impl ContentHandler for FirstParagraph {
+    fn start_element(&mut self, name: &str, _: &[(String, String)]) -> ExtractResult<()> {
+        self.inside = name == "p";
+        Ok(())
+    }
+
+    fn end_element(&mut self, name: &str) -> ExtractResult<()> {
+        if name == "p" {
+            return Err(Error::Unknown("first paragraph found".to_string()));
+        }
+        Ok(())
+    }
+
+    fn characters(&mut self, text: &str) -> ExtractResult<()> {
+        if self.inside {
+            self.text.push_str(text);
+        }
+        Ok(())
+    }
+}
+
+#[test_case("../test_files/documents/simple.odt"; "Test ODT file")]
+#[test_case("README.md"; "Test natively parsed file")]
+fn test_content_handler_stops_extraction(file_path: &str) {
+    let extractor = Extractor::new();
+    let mut handler = FirstParagraph {
+        text: String::new(),
+        inside: false,
+    };
+    let result = extractor.extract_file_events(file_path, &mut handler);
+
+    match result {
+        Err(Error::Unknown(message)) => assert_eq!(message, "first paragraph found"),
+        result => panic!("Unexpected result: {:?}", result),
+    }
+    assert!(!handler.text.is_empty());
+}
@@ -0,0 +1,23 @@
+use extractous::{Extractor, Fingerprints, Metadata};
+
+#[test]
+fn test_fingerprints_of_file_and_bytes() {
+    let extractor = Extractor::new().set_compute_fingerprints(true);
+    let (_content, metadata) = extractor.extract_file_to_string("README.md").unwrap();
+    let file = Fingerprints::from_metadata(&metadata).unwrap();
+
+    let bytes = std::fs::read("README.md").unwrap();
+    let hints = Metadata::from([("resourceName".to_string(), vec!["README.md".to_string()])]);
+    let (_content, metadata) = extractor
+        .extract_bytes_to_string_with_hints(&bytes, &hints)
+        .unwrap();
+    let from_bytes = Fingerprints::from_metadata(&metadata).unwrap();
+    assert_eq!(from_bytes, file);
+    assert_eq!(from_bytes.similarity(&file), 1.0);
+
+    // The fingerprints are not computed by default
+    let (_content, metadata) = Extractor::new()
+        .extract_file_to_string("README.md")
+        .unwrap();
+    assert_eq!(Fingerprints::from_metadata(&metadata), None);
+}
@@ -0,0 +1,49 @@
+use extractous::{Extractor, HeaderFooterConfig};
+
+const AAPL_PDF: &str = "../test_files/documents/2022_Q3_AAPL.pdf";
+
+#[test]
+fn test_remove_headers_and_footers() {
+    let (content, metadata) = Extractor::new()
+        .set_remove_headers_and_footers(true)
+        .extract_file_to_string(AAPL_PDF)
+        .unwrap();
+    assert!(!content.contains("Form 10-Q | 2"));
+    assert!(!content.contains('\u{C}'));
+    assert!(content.contains("See accompanying Notes to Condensed Consolidated Financial"));
+    let footers = &metadata["X-EXTRACTOUS:removed_footers"];
+    assert!(footers
+        .iter()
+        .any(|footer| footer.starts_with("Apple Inc. | Q3 2022 Form 10-Q |")));
+    assert!(metadata.contains_key("X-EXTRACTOUS:removed_page_numbers"));
+
+    // The text of the pdf is not changed by default
+    let (content, metadata) = Extractor::new().extract_file_to_string(AAPL_PDF).unwrap();
+    assert!(content.contains("Apple Inc. | Q3 2022 Form 10-Q | 2"));
+    assert!(!metadata.contains_key("X-EXTRACTOUS:removed_footers"));
+}
+
+#[test]
+fn test_header_footer_config() {
+    // The footer is repeated on most pages, the page numbers are kept
+    let (content, metadata) = Extractor::new()
+        .set_remove_headers_and_footers(true)
+        .set_header_footer_config(
+            HeaderFooterConfig::new()
+                .set_min_page_ratio(0.6)
+                .set_remove_page_numbers(false),
+        )
+        .extract_file_to_string(AAPL_PDF)
+        .unwrap();
+    assert!(!content.contains("Form 10-Q | 2"));
+    assert_eq!(metadata["X-EXTRACTOUS:removed_page_numbers"], vec!["0"]);
+
+    // The xml output keeps the headers and footers
+    let (content, metadata) = Extractor::new()
+        .set_remove_headers_and_footers(true)
+        .set_xml_output(true)
+        .extract_file_to_string(AAPL_PDF)
+        .unwrap();
+    assert!(content.contains("Form 10-Q | 2"));
+    assert!(!metadata.contains_key("X-EXTRACTOUS:removed_page_numbers"));
+}
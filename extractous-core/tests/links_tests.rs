@@ -0,0 +1,79 @@
+use extractous::{Error, Extractor, Link};
+use std::fs;
+
+const EPUB: &str = "../test_files/documents/winter-sports.epub";
+
+const INTRANET_PAGE: &str = "<html><head><title>Docs</title></head><body>\
+    <p>See the <a href=\"guides/setup.html\">setup\n guide</a> and the\
+    <a href=\"https://example.com/\" rel=\"nofollow external\">vendor site</a>.</p>\
+    <p><a name=\"bookmark\">Not a link</a><a href=\"#faq\">FAQ</a></p></body></html>";
+
+fn write_page() -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!("extractous-links-{}.html", std::process::id()));
+    fs::write(&path, INTRANET_PAGE).unwrap();
+    path
+}
+
+#[test]
+fn test_extract_html_links() {
+    let path = write_page();
+    let (links, metadata) = Extractor::new()
+        .extract_file_links(path.to_str().unwrap())
+        .unwrap();
+    assert_eq!(
+        links,
+        vec![
+            Link {
+                href: "guides/setup.html".to_string(),
+                anchor_text: "setup guide".to_string(),
+                rel: None,
+                source_page: None,
+            },
+            Link {
+                href: "https://example.com/".to_string(),
+                anchor_text: "vendor site".to_string(),
+                rel: Some("nofollow external".to_string()),
+                source_page: None,
+            },
+            Link {
+                href: "#faq".to_string(),
+                anchor_text: "FAQ".to_string(),
+                rel: None,
+                source_page: None,
+            },
+        ]
+    );
+    assert!(metadata.contains_key("Content-Type"));
+
+    // The relative links are resolved against the base url
+    let (links, _) = Extractor::new()
+        .set_link_base_url("https://intranet.example.com/docs/index.html")
+        .extract_file_links(path.to_str().unwrap())
+        .unwrap();
+    let hrefs: Vec<&str> = links.iter().map(|link| link.href.as_str()).collect();
+    assert_eq!(
+        hrefs,
+        vec![
+            "https://intranet.example.com/docs/guides/setup.html",
+            "https://example.com/",
+            "https://intranet.example.com/docs/index.html#faq",
+        ]
+    );
+
+    let result = Extractor::new()
+        .set_link_base_url("not an url")
+        .extract_file_links(path.to_str().unwrap());
+    assert!(matches!(result, Err(Error::ConfigError(_))));
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_extract_epub_links() {
+    let bytes = fs::read(EPUB).unwrap();
+    let (links, _metadata) = Extractor::new().extract_bytes_links(&bytes).unwrap();
+    assert!(links
+        .iter()
+        .any(|link| link.href == "https://www.gutenberg.org"
+            && link.anchor_text.starts_with("www.gutenberg.org")));
+    assert!(links.iter().all(|link| !link.href.is_empty()));
+}
@@ -0,0 +1,193 @@
+use extractous::{Extractor, Metadata};
+use std::fs;
+use std::io::Read;
+use test_case::test_case;
+use textdistance::nstr::cosine;
+
+// Declarers the shared test_utils code as module in this integration test
+mod test_utils;
+
+const CSV_FILE: &str = "table-multi-row-column-cells-actual.csv";
+
+fn expected_metadata(file_name: &str) -> Metadata {
+    test_utils::parse_metadata_file(&format!(
+        "../test_files/expected_result/{}.metadata.json",
+        file_name
+    ))
+}
+
+#[test]
+fn test_extract_csv_file_natively() {
+    let extractor = Extractor::new().set_extract_string_max_length(1000000);
+    let (extracted, metadata) = extractor
+        .extract_file_to_string(&format!("../test_files/documents/{}", CSV_FILE))
+        .unwrap();
+
+    let expected =
+        fs::read_to_string(format!("../test_files/expected_result/{}.txt", CSV_FILE)).unwrap();
+    let dist = cosine(expected.trim(), extracted.trim());
+    assert!(
+        dist > 0.8,
+        "Cosine similarity is less than 0.8, dist: {}",
+        dist
+    );
+
+    assert!(test_utils::is_expected_metadata_contained(
+        &expected_metadata(CSV_FILE),
+        &metadata
+    ));
+}
+
+#[test]
+fn test_extract_csv_bytes_natively_with_hints() {
+    let extractor = Extractor::new();
+    let bytes = fs::read(format!("../test_files/documents/{}", CSV_FILE)).unwrap();
+    let hints = Metadata::from([("resourceName".to_string(), vec![CSV_FILE.to_string()])]);
+    let (extracted, metadata) = extractor
+        .extract_bytes_to_string_with_hints(&bytes, &hints)
+        .unwrap();
+
+    assert!(extracted.contains("Low Vision"));
+    assert!(test_utils::is_expected_metadata_contained(
+        &expected_metadata(CSV_FILE),
+        &metadata
+    ));
+}
+
+#[test]
+fn test_extract_markdown_file_natively_to_stream() {
+    let extractor = Extractor::new();
+    let (mut stream, metadata) = extractor.extract_file("README.md").unwrap();
+
+    let mut buffer = Vec::new();
+    stream.read_to_end(&mut buffer).unwrap();
+    let expected = fs::read_to_string("README.md").unwrap();
+    assert_eq!(String::from_utf8(buffer).unwrap().trim(), expected.trim());
+    assert_eq!(
+        metadata.get("Content-Type"),
+        Some(&vec!["text/x-web-markdown; charset=ISO-8859-1".to_string()])
+    );
+    assert_eq!(
+        metadata.get("resourceName"),
+        Some(&vec!["README.md".to_string()])
+    );
+}
+
+#[test]
+fn test_extract_native_to_xml_and_truncate() {
+    let extractor = Extractor::new().set_xml_output(true);
+    let (xml, _metadata) = extractor
+        .extract_file_to_string(&format!("../test_files/documents/{}", CSV_FILE))
+        .unwrap();
+    assert!(xml.contains("<td>Disability Category</td>"));
+    assert!(xml.contains("<meta name=\"csv:num_rows\" content=\"6\"/>"));
+
+    let extractor = Extractor::new().set_extract_string_max_length(10);
+    let (extracted, _metadata) = extractor.extract_file_to_string("README.md").unwrap();
+    assert_eq!(extracted.chars().count(), 10);
+}
+
+#[test_case("../test_files/documents/table-multi-row-column-cells-actual.csv", 0.9; "Test CSV file")]
+#[test_case("README.md", 0.99; "Test markdown file")]
+#[test_case("../test_files/expected_result/simple.odt.txt", 0.99; "Test text file")]
+fn test_native_output_matches_tika(file_path: &str, target_dist: f64) {
+    let native = Extractor::new().set_extract_string_max_length(1000000);
+    let tika = native.clone().set_force_tika(true);
+
+    let (native_content, native_metadata) = native.extract_file_to_string(file_path).unwrap();
+    let (tika_content, tika_metadata) = tika.extract_file_to_string(file_path).unwrap();
+    let dist = cosine(tika_content.trim(), native_content.trim());
+    assert!(
+        dist > target_dist,
+        "Cosine similarity is less than {} for file: {}, dist: {}",
+        target_dist,
+        file_path,
+        dist
+    );
+    assert!(test_utils::is_expected_metadata_contained(
+        &tika_metadata,
+        &native_metadata
+    ));
+
+    let (native_xml, _metadata) = native
+        .set_xml_output(true)
+        .extract_file_to_string(file_path)
+        .unwrap();
+    let (tika_xml, _metadata) = tika
+        .set_xml_output(true)
+        .extract_file_to_string(file_path)
+        .unwrap();
+    let dist = cosine(&tika_xml, &native_xml);
+    assert!(
+        dist > target_dist,
+        "Cosine similarity of the xml is less than {} for file: {}, dist: {}",
+        target_dist,
+        file_path,
+        dist
+    );
+}
+
+const NEWS_PAGE: &str = "<!DOCTYPE html><html><head><title>Rates rise | The Daily</title>\
+    <meta property=\"article:published_time\" content=\"2024-05-01T08:00:00Z\"></head><body>\
+    <header><nav><a href=\"/\">Home</a> <a href=\"/economy\">Economy</a></nav></header>\
+    <div id=\"cookie-banner\"><p>We use cookies to improve your experience, please accept them.</p></div>\
+    <article><h1>Central bank raises rates</h1><p class=\"byline\">By Jane Doe</p>\
+    <p>The central bank raised its rates on Wednesday, for the third time this year.</p>\
+    <p>Analysts expect inflation to slow down, although prices are still rising.</p></article>\
+    <footer><p>Copyright The Daily, all rights reserved, since the first edition.</p></footer>\
+    </body></html>";
+
+fn assert_main_content(content: &str, metadata: &Metadata) {
+    assert!(content.contains("raised its rates on Wednesday"));
+    assert!(!content.contains("cookies"));
+    assert!(!content.contains("Economy"));
+    assert!(!content.contains("Copyright"));
+    assert_eq!(
+        metadata.get("dc:title"),
+        Some(&vec!["Central bank raises rates".to_string()])
+    );
+    assert_eq!(
+        metadata.get("dc:creator"),
+        Some(&vec!["By Jane Doe".to_string()])
+    );
+    assert_eq!(
+        metadata.get("dcterms:created"),
+        Some(&vec!["2024-05-01T08:00:00Z".to_string()])
+    );
+}
+
+#[test]
+fn test_extract_html_main_content() {
+    let path = std::env::temp_dir().join(format!("extractous-news-{}.html", std::process::id()));
+    fs::write(&path, NEWS_PAGE).unwrap();
+    // Html is parsed natively in main content mode, even with force tika
+    let extractor = Extractor::new()
+        .set_html_main_content(true)
+        .set_force_tika(true);
+
+    let (content, metadata) = extractor
+        .extract_file_to_string(path.to_str().unwrap())
+        .unwrap();
+    assert_main_content(&content, &metadata);
+
+    let (mut reader, metadata) = extractor.extract_file(path.to_str().unwrap()).unwrap();
+    let mut content = String::new();
+    reader.read_to_string(&mut content).unwrap();
+    assert_main_content(&content, &metadata);
+    fs::remove_file(&path).unwrap();
+
+    // The bytes without hints are sniffed
+    let (content, metadata) = extractor
+        .extract_bytes_to_string(NEWS_PAGE.as_bytes())
+        .unwrap();
+    assert_main_content(&content, &metadata);
+
+    // The whole page is extracted by default
+    let (content, _metadata) = Extractor::new()
+        .extract_bytes_to_string_with_hints(
+            NEWS_PAGE.as_bytes(),
+            &Metadata::from([("Content-Type".to_string(), vec!["text/html".to_string()])]),
+        )
+        .unwrap();
+    assert!(content.contains("cookies") && content.contains("Copyright"));
+}
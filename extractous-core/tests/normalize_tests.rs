@@ -0,0 +1,31 @@
+use extractous::{Extractor, TextNormalizer, UnicodeForm};
+use std::io::Read;
+
+#[test]
+fn test_normalized_string_and_stream() {
+    let file_path =
+        std::env::temp_dir().join(format!("extractous-normalize-{}.txt", std::process::id()));
+    std::fs::write(
+        &file_path,
+        "The \u{FB01}nal extrac-\ntion   of the\n\n\n\nsec\u{AD}tion.",
+    )
+    .unwrap();
+    let file_path = file_path.to_str().unwrap();
+    let extractor = Extractor::new().set_text_normalizer(
+        TextNormalizer::new()
+            .set_expand_ligatures(true)
+            .set_unicode_form(Some(UnicodeForm::NFC))
+            .set_dehyphenate(true)
+            .set_collapse_whitespace(true),
+    );
+    let expected = "The final extraction of the\n\nsection.";
+
+    let (content, _metadata) = extractor.extract_file_to_string(file_path).unwrap();
+    assert_eq!(content.trim(), expected);
+
+    let (mut reader, _metadata) = extractor.extract_file(file_path).unwrap();
+    let mut content = String::new();
+    reader.read_to_string(&mut content).unwrap();
+    assert_eq!(content.trim(), expected);
+    std::fs::remove_file(file_path).unwrap();
+}
@@ -0,0 +1,211 @@
+use extractous::{
+    Error, Extractor, OcrOutputType, PdfOcrStrategy, PdfParserConfig, TesseractOcrConfig,
+};
+use std::fs;
+use test_case::test_case;
+use textdistance::nstr::cosine;
+
+#[test_case(TesseractOcrConfig::new().set_density(100); "Test density too low")]
+#[test_case(TesseractOcrConfig::new().set_depth(3); "Test invalid depth")]
+#[test_case(TesseractOcrConfig::new().set_timeout_seconds(-1); "Test negative timeout")]
+#[test_case(TesseractOcrConfig::new().set_language("eng+"); "Test empty language")]
+#[test_case(TesseractOcrConfig::new().set_language("eng;rm"); "Test unsafe language")]
+#[test_case(TesseractOcrConfig::new().set_page_seg_mode(14); "Test invalid page seg mode")]
+#[test_case(TesseractOcrConfig::new().set_page_seg_mode(-1); "Test negative page seg mode")]
+#[test_case(TesseractOcrConfig::new().set_ocr_engine_mode(4); "Test invalid engine mode")]
+#[test_case(TesseractOcrConfig::new().set_ocr_engine_mode(-1); "Test negative engine mode")]
+#[test_case(TesseractOcrConfig::new().set_min_file_size_to_ocr(-1); "Test negative min file size")]
+#[test_case(TesseractOcrConfig::new().set_min_file_size_to_ocr(10).set_max_file_size_to_ocr(5); "Test inverted file sizes")]
+#[test_case(TesseractOcrConfig::new().set_tesseract_path(" "); "Test empty tesseract path")]
+#[test_case(TesseractOcrConfig::new().set_colorspace("gray rgb"); "Test invalid colorspace")]
+#[test_case(TesseractOcrConfig::new().set_filter("bilinear"); "Test invalid filter")]
+#[test_case(TesseractOcrConfig::new().set_resize(250); "Test invalid resize")]
+#[test_case(TesseractOcrConfig::new().add_other_tesseract_config("key", "a b"); "Test invalid config value")]
+#[test_case(TesseractOcrConfig::new().add_other_tesseract_config("key", "0,5"); "Test config value outside tika pattern")]
+#[test_case(TesseractOcrConfig::new().add_other_tesseract_config("key;rm", "1"); "Test invalid config key")]
+#[test_case(TesseractOcrConfig::new().add_other_tesseract_config("", "1"); "Test empty config key")]
+#[test_case(TesseractOcrConfig::new().add_other_tesseract_config("key", ""); "Test empty config value")]
+fn test_invalid_ocr_config(config: TesseractOcrConfig) {
+    assert!(matches!(config.validate(), Err(Error::ConfigError(_))));
+}
+
+#[test]
+fn test_valid_ocr_config() {
+    let config = TesseractOcrConfig::new()
+        .set_language("chi_tra+chi_sim+script/Arabic")
+        .set_page_seg_mode(6)
+        .set_ocr_engine_mode(1)
+        .set_preserve_interword_spacing(true)
+        .set_min_file_size_to_ocr(100)
+        .set_max_file_size_to_ocr(1_000_000)
+        .set_tessdata_path("/usr/share/tesseract-ocr/5/tessdata")
+        .set_colorspace("rgb")
+        .set_filter("Gaussian")
+        .set_resize(300)
+        .add_other_tesseract_config("textord_initialx_ile", "0.75");
+    assert!(config.validate().is_ok());
+    assert!(TesseractOcrConfig::new().validate().is_ok());
+}
+
+#[test_case(TesseractOcrConfig::new().set_page_seg_mode(0); "Test lowest page seg mode")]
+#[test_case(TesseractOcrConfig::new().set_page_seg_mode(13); "Test highest page seg mode")]
+#[test_case(TesseractOcrConfig::new().set_ocr_engine_mode(0); "Test lowest engine mode")]
+#[test_case(TesseractOcrConfig::new().set_ocr_engine_mode(3); "Test highest engine mode")]
+#[test_case(TesseractOcrConfig::new().add_other_tesseract_config("tessedit-char.list", "a-z/0.9_x"); "Test config with tika pattern characters")]
+#[test_case(TesseractOcrConfig::new().set_output_type(OcrOutputType::TXT); "Test txt output type")]
+#[test_case(TesseractOcrConfig::new().set_output_type(OcrOutputType::HOCR); "Test hocr output type")]
+fn test_valid_ocr_config_setting(config: TesseractOcrConfig) {
+    assert!(config.validate().is_ok());
+}
+
+#[test]
+fn test_ocr_output_type_names() {
+    // The names are passed to tika, which only knows these output types
+    assert_eq!("TXT".parse::<OcrOutputType>().unwrap(), OcrOutputType::TXT);
+    assert_eq!(
+        "HOCR".parse::<OcrOutputType>().unwrap(),
+        OcrOutputType::HOCR
+    );
+    assert!("PDF".parse::<OcrOutputType>().is_err());
+}
+
+#[test]
+fn test_invalid_ocr_config_extraction() {
+    // The config is rejected before the document is parsed
+    let result = Extractor::new()
+        .set_ocr_config(TesseractOcrConfig::new().set_page_seg_mode(20))
+        .extract_file_to_string("../test_files/documents/ara-ocr.png");
+    assert!(matches!(result, Err(Error::ConfigError(_))));
+}
+
+#[cfg(not(target_os = "macos"))]
+#[test_case(TesseractOcrConfig::new().set_page_seg_mode(3); "Test page seg mode")]
+#[test_case(TesseractOcrConfig::new().set_ocr_engine_mode(1); "Test lstm engine mode")]
+#[test_case(TesseractOcrConfig::new().set_preserve_interword_spacing(true); "Test preserve interword spacing")]
+#[test_case(TesseractOcrConfig::new().add_other_tesseract_config("tessedit_do_invert", "0"); "Test other config")]
+fn test_ocr_config(config: TesseractOcrConfig) {
+    let extractor = Extractor::new().set_ocr_config(config.set_language("ara"));
+    let (extracted, _metadata) = extractor
+        .extract_file_to_string("../test_files/documents/ara-ocr.png")
+        .unwrap();
+    let expected = fs::read_to_string("../test_files/expected_result/ara-ocr.png.txt").unwrap();
+
+    let dist = cosine(&expected, &extracted);
+    assert!(
+        dist > 0.9,
+        "Cosine similarity is less than 0.9, dist: {}",
+        dist
+    );
+}
+
+#[cfg(not(target_os = "macos"))]
+#[test]
+fn test_ocr_min_file_size() {
+    // Images smaller than the minimum size are not OCRed
+    let extractor = Extractor::new().set_ocr_config(
+        TesseractOcrConfig::new()
+            .set_language("ara")
+            .set_min_file_size_to_ocr(i32::MAX as i64),
+    );
+    let (extracted, _metadata) = extractor
+        .extract_file_to_string("../test_files/documents/ara-ocr.png")
+        .unwrap();
+    assert_eq!("", extracted.trim());
+}
+
+#[cfg(not(target_os = "macos"))]
+#[test]
+fn test_hocr_output_xml() {
+    let extractor = Extractor::new().set_xml_output(true).set_ocr_config(
+        TesseractOcrConfig::new()
+            .set_language("ara")
+            .set_output_type(OcrOutputType::HOCR),
+    );
+    let (extracted, _metadata) = extractor
+        .extract_file_to_string("../test_files/documents/ara-ocr.png")
+        .unwrap();
+    assert!(extracted.contains("ocrx_word"));
+    assert!(extracted.contains("x_wconf"));
+}
+
+#[cfg(not(target_os = "macos"))]
+#[test]
+fn test_ocr_words_image() {
+    let extractor = Extractor::new().set_ocr_config(TesseractOcrConfig::new().set_language("ara"));
+    let (text, words, _metadata) = extractor
+        .extract_file_to_ocr_words("../test_files/documents/ara-ocr.png")
+        .unwrap();
+    let expected = fs::read_to_string("../test_files/expected_result/ara-ocr.png.txt").unwrap();
+
+    let dist = cosine(&expected, &text);
+    assert!(
+        dist > 0.9,
+        "Cosine similarity is less than 0.9, dist: {}",
+        dist
+    );
+    assert!(!words.is_empty());
+    for word in words.iter() {
+        assert!(text.contains(&word.text));
+        assert_eq!(word.page, 1);
+        assert!(word.line >= 1);
+        assert!(word.bbox.x0 < word.bbox.x1 && word.bbox.y0 < word.bbox.y1);
+        assert!((0.0..=100.0).contains(&word.confidence));
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+#[test]
+fn test_ocr_words_pdf() {
+    let extractor = Extractor::new()
+        .set_ocr_config(TesseractOcrConfig::new().set_language("deu"))
+        .set_pdf_config(PdfParserConfig::new().set_ocr_strategy(PdfOcrStrategy::OCR_ONLY));
+    let bytes = fs::read("../test_files/documents/deu-ocr.pdf").unwrap();
+    let (text, words, _metadata) = extractor.extract_bytes_to_ocr_words(&bytes).unwrap();
+    let expected = fs::read_to_string("../test_files/expected_result/deu-ocr.pdf.txt").unwrap();
+
+    let dist = cosine(&expected, &text);
+    assert!(
+        dist > 0.9,
+        "Cosine similarity is less than 0.9, dist: {}",
+        dist
+    );
+    assert!(!words.is_empty());
+    assert!(words.iter().all(|w| w.page >= 1 && w.line >= 1));
+    // Lines are numbered in order within a page
+    assert!(words
+        .windows(2)
+        .all(|w| w[0].page < w[1].page || w[0].line <= w[1].line));
+}
+
+#[cfg(not(target_os = "macos"))]
+#[test]
+fn test_ocr_diagnose() {
+    let diagnostics = extractous::ocr::diagnose();
+    assert!(diagnostics.tesseract_found, "{:?}", diagnostics.error);
+    assert!(diagnostics.version.is_some());
+    for language in ["eng", "deu", "ara"] {
+        assert!(diagnostics.languages.iter().any(|l| l == language));
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+#[test]
+fn test_extractor_validate() {
+    let extractor =
+        Extractor::new().set_ocr_config(TesseractOcrConfig::new().set_language("deu+ara"));
+    assert!(extractor.validate().is_ok());
+
+    let extractor =
+        Extractor::new().set_ocr_config(TesseractOcrConfig::new().set_language("eng+xyz"));
+    match extractor.validate() {
+        Err(Error::ConfigError(msg)) => assert!(msg.contains("xyz"), "{}", msg),
+        result => panic!("expected a config error, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_extractor_validate_missing_tesseract() {
+    let extractor = Extractor::new()
+        .set_ocr_config(TesseractOcrConfig::new().set_tesseract_path("/extractous/missing"));
+    assert!(matches!(extractor.validate(), Err(Error::ConfigError(_))));
+}
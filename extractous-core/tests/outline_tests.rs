@@ -0,0 +1,62 @@
+use extractous::{Extractor, OutlineEntry};
+use std::fs;
+
+const EPUB: &str = "../test_files/documents/winter-sports.epub";
+const AAPL_PDF: &str = "../test_files/documents/2022_Q3_AAPL.pdf";
+
+const HANDBOOK_PAGE: &str = "<html><head><title>Handbook</title></head><body>\
+    <h1>Onboarding</h1><p>Welcome.</p>\
+    <h2>First day</h2><p>Badge.</p><h2>First week</h2>\
+    <h1>Benefits</h1><h3>Dental</h3></body></html>";
+
+fn titles(entries: &[OutlineEntry]) -> Vec<String> {
+    entries.iter().map(|entry| entry.title.clone()).collect()
+}
+
+#[test]
+fn test_extract_epub_outline() {
+    let outline = Extractor::new().extract_file_outline(EPUB).unwrap();
+    assert_eq!(
+        outline[0].title,
+        "The Project Gutenberg eBook of Winter Sports in Switzerland, by E. F. Benson"
+    );
+    let chapter = outline
+        .iter()
+        .find(|entry| entry.title == "CHAPTER II RINKS AND SKATERS")
+        .unwrap();
+    assert_eq!(chapter.level, 1);
+    assert_eq!(chapter.page, None);
+    assert_eq!(chapter.children[0].title, "THIRD-CLASS TEST");
+    assert_eq!(chapter.children[0].level, 2);
+
+    // The navigation document of the bytes is read as well
+    let bytes = fs::read(EPUB).unwrap();
+    assert_eq!(
+        Extractor::new().extract_bytes_outline(&bytes).unwrap(),
+        outline
+    );
+}
+
+#[test]
+fn test_extract_html_outline() {
+    let path = std::env::temp_dir().join(format!("extractous-outline-{}.html", std::process::id()));
+    fs::write(&path, HANDBOOK_PAGE).unwrap();
+    let outline = Extractor::new()
+        .extract_file_outline(path.to_str().unwrap())
+        .unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(titles(&outline), vec!["Onboarding", "Benefits"]);
+    assert_eq!(
+        titles(&outline[0].children),
+        vec!["First day", "First week"]
+    );
+    assert_eq!(outline[1].children[0].title, "Dental");
+    assert_eq!(outline[1].children[0].level, 3);
+}
+
+#[test]
+fn test_extract_pdf_without_outline() {
+    let outline = Extractor::new().extract_file_outline(AAPL_PDF).unwrap();
+    assert!(outline.is_empty());
+}
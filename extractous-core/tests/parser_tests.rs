@@ -0,0 +1,128 @@
+use extractous::{ContentSink, Error, ExtractResult, Extractor, Metadata, Parser};
+use std::io::Read;
+
+const CSV_FILE: &str = "../test_files/documents/table-multi-row-column-cells-actual.csv";
+
+/// Parses lines of `key: value` pairs to a definition list
+struct KeyValueParser;
+
+impl Parser for KeyValueParser {
+    fn supported_types(&self) -> Vec<String> {
+        vec![
+            "application/x-key-value".to_string(),
+            "text/csv".to_string(),
+        ]
+    }
+
+    fn parse(
+        &self,
+        input: &mut dyn Read,
+        sink: &mut ContentSink,
+        metadata: &mut Metadata,
+    ) -> ExtractResult<()> {
+        let mut text = String::new();
+        input
+            .read_to_string(&mut text)
+            .map_err(|e| Error::IoError(e.to_string()))?;
+
+        sink.start_element("dl", &[]);
+        let mut count = 0;
+        for (key, value) in text.lines().filter_map(|line| line.split_once(':')) {
+            sink.element("dt", key.trim());
+            sink.element("dd", value.trim());
+            count += 1;
+        }
+        sink.end_element("dl");
+        metadata.insert("kv:count".to_string(), vec![count.to_string()]);
+        Ok(())
+    }
+
+    fn name(&self) -> String {
+        "KeyValueParser".to_string()
+    }
+}
+
+fn key_value_hints() -> Metadata {
+    Metadata::from([(
+        "Content-Type".to_string(),
+        vec!["application/x-key-value".to_string()],
+    )])
+}
+
+#[test]
+fn test_registered_parser_to_string() {
+    let extractor = Extractor::new().register_parser(KeyValueParser);
+    let (content, metadata) = extractor
+        .extract_bytes_to_string_with_hints(b"name: extractous\nlang: rust", &key_value_hints())
+        .unwrap();
+
+    assert_eq!(content, "\tname\n\textractous\n\tlang\n\trust\n\n");
+    assert_eq!(metadata.get("kv:count"), Some(&vec!["2".to_string()]));
+    assert_eq!(
+        metadata.get("X-TIKA:Parsed-By"),
+        Some(&vec!["KeyValueParser".to_string()])
+    );
+}
+
+#[test]
+fn test_registered_parser_to_xml_and_stream() {
+    let extractor = Extractor::new()
+        .register_parser(KeyValueParser)
+        .set_xml_output(true);
+    let (xml, _metadata) = extractor
+        .extract_bytes_to_string_with_hints(b"name: extractous", &key_value_hints())
+        .unwrap();
+    assert!(xml.contains("<dt>name</dt>"));
+    assert!(xml.contains("<meta name=\"kv:count\" content=\"1\"/>"));
+
+    let (mut stream, metadata) = extractor
+        .set_xml_output(false)
+        .extract_file(CSV_FILE)
+        .unwrap();
+    let mut content = String::new();
+    stream.read_to_string(&mut content).unwrap();
+    assert_eq!(content, "\n");
+    assert_eq!(
+        metadata.get("X-TIKA:Parsed-By"),
+        Some(&vec!["KeyValueParser".to_string()])
+    );
+}
+
+#[test]
+fn test_registered_parser_wins_over_native_parser() {
+    let extractor = Extractor::new().register_parser(KeyValueParser);
+    let (_content, metadata) = extractor.extract_file_to_string(CSV_FILE).unwrap();
+
+    assert_eq!(
+        metadata.get("X-TIKA:Parsed-By"),
+        Some(&vec!["KeyValueParser".to_string()])
+    );
+    assert_eq!(
+        metadata.get("resourceName"),
+        Some(&vec!["table-multi-row-column-cells-actual.csv".to_string()])
+    );
+}
+
+#[test]
+fn test_registered_parser_error() {
+    struct FailingParser;
+
+    impl Parser for FailingParser {
+        fn supported_types(&self) -> Vec<String> {
+            vec!["application/x-key-value".to_string()]
+        }
+
+        fn parse(
+            &self,
+            _: &mut dyn Read,
+            _: &mut ContentSink,
+            _: &mut Metadata,
+        ) -> ExtractResult<()> {
+            Err(Error::ParseError("invalid document".to_string()))
+        }
+    }
+
+    let extractor = Extractor::new().register_parser(FailingParser);
+    let result = extractor.extract_bytes_to_string_with_hints(b"name", &key_value_hints());
+    assert!(matches!(result, Err(Error::ParseError(_))));
+}
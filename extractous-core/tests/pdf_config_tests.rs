@@ -0,0 +1,166 @@
+use extractous::{
+    Error, Extractor, PdfOcrImageFormat, PdfOcrImageType, PdfOcrRenderingStrategy, PdfOcrStrategy,
+    PdfParserConfig, TesseractOcrConfig,
+};
+use std::fs;
+use std::io::Read;
+use test_case::test_case;
+use textdistance::nstr::cosine;
+
+fn assert_similar_to_expected(
+    config: PdfParserConfig,
+    extractor: Extractor,
+    file_name: &str,
+    target_dist: f64,
+) {
+    let extractor = extractor
+        .set_extract_string_max_length(1000000)
+        .set_pdf_config(config);
+    // extract file with extractor
+    let (extracted, _metadata) = extractor
+        .extract_file_to_string(&format!("../test_files/documents/{}", file_name))
+        .unwrap();
+    // read expected string
+    let expected =
+        fs::read_to_string(format!("../test_files/expected_result/{}.txt", file_name)).unwrap();
+
+    let dist = cosine(expected.trim(), extracted.trim());
+    assert!(
+        dist > target_dist,
+        "Cosine similarity is less than {} for file: {}, dist: {}",
+        target_dist,
+        file_name,
+        dist
+    );
+}
+
+#[test_case(PdfParserConfig::new().set_sort_by_position(true), 0.9; "Test sort by position")]
+#[test_case(PdfParserConfig::new().set_spacing_tolerance(0.3), 0.9; "Test spacing tolerance")]
+#[test_case(PdfParserConfig::new().set_average_char_tolerance(0.5), 0.9; "Test average char tolerance")]
+#[test_case(PdfParserConfig::new().set_enable_auto_space(false), 0.5; "Test disable auto space")]
+#[test_case(PdfParserConfig::new().set_suppress_duplicate_overlapping_text(true), 0.9; "Test suppress duplicate text")]
+#[test_case(PdfParserConfig::new().set_extract_acro_form_content(false), 0.9; "Test no acro form content")]
+#[test_case(PdfParserConfig::new().set_extract_bookmarks_text(false), 0.9; "Test no bookmarks text")]
+#[test_case(PdfParserConfig::new().set_extract_actions(true), 0.9; "Test extract actions")]
+#[test_case(PdfParserConfig::new().set_max_main_memory_bytes(1024 * 1024), 0.9; "Test max main memory")]
+#[test_case(PdfParserConfig::new().set_catch_intermediate_io_exceptions(false), 0.9; "Test throw io exceptions")]
+fn test_pdf_layout_config(config: PdfParserConfig, target_dist: f64) {
+    assert_similar_to_expected(config, Extractor::new(), "2022_Q3_AAPL.pdf", target_dist);
+}
+
+#[cfg(not(target_os = "macos"))]
+#[test_case(PdfParserConfig::new().set_ocr_dpi(200); "Test ocr dpi")]
+#[test_case(PdfParserConfig::new().set_ocr_image_type(PdfOcrImageType::RGB); "Test ocr image type")]
+#[test_case(PdfParserConfig::new().set_ocr_image_format(PdfOcrImageFormat::TIFF); "Test ocr image format")]
+#[test_case(PdfParserConfig::new().set_ocr_rendering_strategy(PdfOcrRenderingStrategy::NO_TEXT); "Test ocr rendering strategy")]
+fn test_pdf_ocr_rendering_config(config: PdfParserConfig) {
+    let extractor = Extractor::new().set_ocr_config(TesseractOcrConfig::new().set_language("deu"));
+    assert_similar_to_expected(
+        config.set_ocr_strategy(PdfOcrStrategy::OCR_ONLY),
+        extractor,
+        "deu-ocr.pdf",
+        0.9,
+    );
+}
+
+#[test]
+fn test_pdf_config_defaults() {
+    // The defaults must match the defaults of the java PDFParserConfig
+    assert_eq!(
+        PdfParserConfig::new(),
+        PdfParserConfig::new()
+            .set_sort_by_position(false)
+            .set_spacing_tolerance(0.5)
+            .set_average_char_tolerance(0.3)
+            .set_enable_auto_space(true)
+            .set_suppress_duplicate_overlapping_text(false)
+            .set_extract_acro_form_content(true)
+            .set_extract_bookmarks_text(true)
+            .set_extract_actions(false)
+            .set_ocr_dpi(300)
+            .set_ocr_image_type(PdfOcrImageType::GRAY)
+            .set_ocr_image_format(PdfOcrImageFormat::PNG)
+            .set_ocr_rendering_strategy(PdfOcrRenderingStrategy::ALL)
+            .set_max_main_memory_bytes(512 * 1024 * 1024)
+            .set_catch_intermediate_io_exceptions(true)
+    );
+}
+
+fn count_pages(extractor: &Extractor, file_name: &str, page_class: &str) -> usize {
+    let (xml, _metadata) = extractor
+        .clone()
+        .set_xml_output(true)
+        .extract_file_to_string(&format!("../test_files/documents/{}", file_name))
+        .unwrap();
+    // The extraction stops after the last page, but the xml must still be complete
+    assert!(
+        xml.trim_end().ends_with("</html>"),
+        "Incomplete xml: {}",
+        xml
+    );
+    xml.matches(&format!("<div class=\"{}\">", page_class))
+        .count()
+}
+
+#[test_case(PdfParserConfig::new().set_page_range(1..=2), -1, 2; "Test page range")]
+#[test_case(PdfParserConfig::new().set_page_range(3..=3), -1, 1; "Test single page range")]
+#[test_case(PdfParserConfig::new().set_pages(&[1, 3, 5]), -1, 3; "Test page list")]
+#[test_case(PdfParserConfig::new().set_page_range(2..=10), 3, 2; "Test page range and max pages")]
+#[test_case(PdfParserConfig::new(), 1, 1; "Test max pages")]
+#[test_case(PdfParserConfig::new(), 0, 0; "Test zero max pages")]
+fn test_pdf_page_selection(config: PdfParserConfig, max_pages: i32, expected_pages: usize) {
+    let extractor = Extractor::new()
+        .set_pdf_config(config)
+        .set_max_pages(max_pages);
+    assert_eq!(
+        count_pages(&extractor, "2022_Q3_AAPL.pdf", "page"),
+        expected_pages
+    );
+}
+
+#[test_case(PdfParserConfig::new().set_page_range(0..=2); "Test page zero")]
+#[test_case(PdfParserConfig::new().set_pages(&[1, 0]); "Test page zero in list")]
+#[test_case(PdfParserConfig::new().set_page_range(5..=2); "Test inverted page range")]
+fn test_invalid_pdf_page_range(config: PdfParserConfig) {
+    assert!(matches!(config.validate(), Err(Error::ConfigError(_))));
+
+    // The config is rejected before the document is parsed
+    let result = Extractor::new()
+        .set_pdf_config(config)
+        .extract_file_to_string("../test_files/documents/2022_Q3_AAPL.pdf");
+    assert!(matches!(result, Err(Error::ConfigError(_))));
+}
+
+#[test]
+fn test_valid_pdf_page_range() {
+    assert!(PdfParserConfig::new().validate().is_ok());
+    assert!(PdfParserConfig::new()
+        .set_page_range(2..=2)
+        .validate()
+        .is_ok());
+    assert!(PdfParserConfig::new().set_pages(&[3, 1]).validate().is_ok());
+}
+
+#[test]
+fn test_pdf_page_selection_stream() {
+    let extractor = Extractor::new().set_pdf_config(PdfParserConfig::new().set_page_range(1..=1));
+    let (mut reader, _metadata) = extractor
+        .extract_file("../test_files/documents/2022_Q3_AAPL.pdf")
+        .unwrap();
+    let mut first_page = String::new();
+    reader.read_to_string(&mut first_page).unwrap();
+
+    let (all_pages, _metadata) = Extractor::new()
+        .set_extract_string_max_length(-1)
+        .extract_file_to_string("../test_files/documents/2022_Q3_AAPL.pdf")
+        .unwrap();
+    assert!(!first_page.trim().is_empty());
+    assert!(first_page.trim().len() < all_pages.trim().len());
+    assert!(all_pages.trim().starts_with(first_page.trim()));
+}
+
+#[test]
+fn test_max_pages_slides() {
+    let extractor = Extractor::new().set_max_pages(1);
+    assert_eq!(count_pages(&extractor, "simple.pptx", "slide-content"), 1);
+}
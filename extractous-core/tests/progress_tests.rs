@@ -0,0 +1,118 @@
+use extractous::{Extractor, PdfOcrStrategy, PdfParserConfig, Progress, TesseractOcrConfig};
+use std::io::Read;
+use std::sync::{Arc, Mutex};
+
+/// Returns an extractor collecting its progress
+fn collecting_extractor() -> (Extractor, Arc<Mutex<Vec<Progress>>>) {
+    let progress = Arc::new(Mutex::new(Vec::new()));
+    let collected = progress.clone();
+    let extractor = Extractor::new()
+        .set_progress_callback(move |p: &Progress| collected.lock().unwrap().push(p.clone()));
+    (extractor, progress)
+}
+
+#[test]
+fn test_progress_of_pdf_pages() {
+    let (extractor, progress) = collecting_extractor();
+    let (content, _metadata) = extractor
+        .extract_file_to_string("../test_files/documents/2022_Q3_AAPL.pdf")
+        .unwrap();
+
+    let progress = progress.lock().unwrap();
+    assert_eq!(
+        progress.first(),
+        Some(&Progress::DocumentStarted {
+            content_type: "application/pdf".to_string()
+        })
+    );
+    assert_eq!(progress.last(), Some(&Progress::DocumentFinished));
+
+    let pages: Vec<(u32, Option<u32>)> = progress
+        .iter()
+        .filter_map(|p| match p {
+            Progress::PageDone { page, total } => Some((*page, *total)),
+            _ => None,
+        })
+        .collect();
+    assert!(!pages.is_empty());
+    for (i, (page, total)) in pages.iter().enumerate() {
+        assert_eq!(*page, i as u32 + 1);
+        assert_eq!(*total, Some(pages.len() as u32));
+    }
+
+    // The output is counted from the tika content handler, before the string is truncated
+    let output: Vec<u64> = progress
+        .iter()
+        .filter_map(|p| match p {
+            Progress::OutputProduced { bytes } => Some(*bytes),
+            _ => None,
+        })
+        .collect();
+    assert!(output.windows(2).all(|w| w[0] <= w[1]));
+    assert!(*output.last().unwrap() >= content.trim().len() as u64);
+}
+
+#[test]
+fn test_progress_of_stream_with_max_pages() {
+    let (extractor, progress) = collecting_extractor();
+    let (mut reader, _metadata) = extractor
+        .set_max_pages(1)
+        .extract_file("../test_files/documents/2022_Q3_AAPL.pdf")
+        .unwrap();
+    let mut content = String::new();
+    reader.read_to_string(&mut content).unwrap();
+
+    // The stream is parsed on a background thread, which finished once the stream is read
+    let progress = progress.lock().unwrap();
+    let pages = progress
+        .iter()
+        .filter(|p| matches!(p, Progress::PageDone { .. }))
+        .count();
+    assert_eq!(pages, 1);
+    assert_eq!(progress.last(), Some(&Progress::DocumentFinished));
+}
+
+#[test]
+fn test_progress_of_ocr() {
+    let (extractor, progress) = collecting_extractor();
+    let extractor = extractor
+        .set_ocr_config(TesseractOcrConfig::new().set_language("deu"))
+        .set_pdf_config(PdfParserConfig::new().set_ocr_strategy(PdfOcrStrategy::OCR_ONLY));
+    extractor
+        .extract_file_to_string("../test_files/documents/deu-ocr.pdf")
+        .unwrap();
+
+    let progress = progress.lock().unwrap();
+    let ocr_started = progress
+        .iter()
+        .position(|p| *p == Progress::OcrStarted { page: 1 })
+        .expect("the OCR of the first page is reported");
+    let ocr_finished = progress
+        .iter()
+        .position(|p| *p == Progress::OcrFinished { page: 1 })
+        .expect("the end of the OCR of the first page is reported");
+    let page_done = progress
+        .iter()
+        .position(|p| matches!(p, Progress::PageDone { page: 1, .. }))
+        .unwrap();
+    assert!(ocr_started < ocr_finished && ocr_finished < page_done);
+}
+
+#[test]
+fn test_progress_of_natively_parsed_file() {
+    let (extractor, progress) = collecting_extractor();
+    let (content, metadata) = extractor.extract_file_to_string("README.md").unwrap();
+
+    assert_eq!(
+        *progress.lock().unwrap(),
+        vec![
+            Progress::DocumentStarted {
+                content_type: metadata["Content-Type"][0].clone()
+            },
+            Progress::OutputProduced {
+                bytes: content.len() as u64
+            },
+            Progress::DocumentFinished,
+        ]
+    );
+}
@@ -0,0 +1,69 @@
+use extractous::{Error, Extractor, RevisionKind};
+use std::io::{Cursor, Write};
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+const DOCUMENT: &str = r#"<w:document xmlns:w="w"><w:body>
+    <w:p><w:r><w:t xml:space="preserve">The supplier </w:t></w:r>
+    <w:del w:id="1" w:author="Jane Roe" w:date="2024-03-01T10:00:00Z"><w:r><w:delText>may</w:delText></w:r></w:del>
+    <w:ins w:id="2" w:author="Jane Roe" w:date="2024-03-01T10:00:00Z"><w:r><w:t>shall</w:t></w:r></w:ins>
+    <w:r><w:t xml:space="preserve"> indemnify the buyer.</w:t></w:r></w:p>
+    </w:body></w:document>"#;
+
+fn docx() -> Vec<u8> {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    writer
+        .start_file("word/document.xml", SimpleFileOptions::default())
+        .unwrap();
+    writer.write_all(DOCUMENT.as_bytes()).unwrap();
+    writer.finish().unwrap().into_inner()
+}
+
+#[test]
+fn test_extract_docx_revisions() {
+    let revisions = Extractor::new().extract_bytes_revisions(&docx()).unwrap();
+    assert_eq!(
+        revisions.text,
+        "The supplier mayshall indemnify the buyer.\n"
+    );
+    let kinds: Vec<(RevisionKind, &str, usize)> = revisions
+        .revisions
+        .iter()
+        .map(|revision| (revision.kind, revision.text.as_str(), revision.offset))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            (RevisionKind::Delete, "may", 13),
+            (RevisionKind::Insert, "shall", 16),
+        ]
+    );
+    assert_eq!(revisions.revisions[1].author.as_deref(), Some("Jane Roe"));
+    assert_eq!(
+        revisions.revisions[1].date.as_deref(),
+        Some("2024-03-01T10:00:00Z")
+    );
+    assert_eq!(
+        revisions.original_text(),
+        "The supplier may indemnify the buyer.\n"
+    );
+    assert_eq!(
+        revisions.final_text(),
+        "The supplier shall indemnify the buyer.\n"
+    );
+}
+
+#[test]
+fn test_extract_revisions_of_other_documents() {
+    let extractor = Extractor::new();
+    let result = extractor.extract_file_revisions("../test_files/documents/winter-sports.epub");
+    assert!(matches!(result, Err(Error::ParseError(_))));
+    let result = extractor.extract_bytes_revisions(b"plain text");
+    assert!(matches!(result, Err(Error::ParseError(_))));
+
+    let revisions = extractor
+        .extract_file_revisions("../test_files/documents/category-level.docx")
+        .unwrap();
+    assert!(revisions.revisions.is_empty());
+    assert_eq!(revisions.original_text(), revisions.text);
+}
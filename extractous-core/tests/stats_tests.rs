@@ -0,0 +1,56 @@
+use extractous::{ExtractionStats, Extractor};
+use std::io::Read;
+
+#[test]
+fn test_stats_of_pdf() {
+    let extractor = Extractor::new().set_collect_stats(true);
+    let file_path = "../test_files/documents/2022_Q3_AAPL.pdf";
+    let (content, metadata) = extractor.extract_file_to_string(file_path).unwrap();
+
+    let stats = ExtractionStats::from_metadata(&metadata).unwrap();
+    assert_eq!(stats.bytes_in, std::fs::metadata(file_path).unwrap().len());
+    assert_eq!(stats.chars_out, content.chars().count() as u64);
+    assert!(!stats.parse_time.is_zero());
+    assert!(
+        stats.detect_time + stats.parse_time + stats.ocr_time + stats.jni_copy_time
+            <= stats.total_time
+    );
+    assert!(stats.heap_used_before.is_some() && stats.heap_used_after.is_some());
+    assert!(!stats.ocr_ran);
+}
+
+#[test]
+fn test_stats_of_stream() {
+    let extractor = Extractor::new().set_collect_stats(true);
+    let (mut reader, metadata) = extractor
+        .extract_file("../test_files/documents/simple.odt")
+        .unwrap();
+    assert_eq!(ExtractionStats::from_metadata(&metadata), None);
+    assert_eq!(reader.stats(), None);
+
+    let mut content = String::new();
+    reader.read_to_string(&mut content).unwrap();
+    let stats = reader.stats().unwrap();
+    assert!(stats.chars_out > 0);
+    assert!(!stats.jni_copy_time.is_zero());
+}
+
+#[test]
+fn test_stats_of_natively_parsed_file() {
+    let (content, metadata) = Extractor::new()
+        .set_collect_stats(true)
+        .extract_file_to_string("README.md")
+        .unwrap();
+
+    let stats = ExtractionStats::from_metadata(&metadata).unwrap();
+    assert_eq!(stats.chars_out, content.chars().count() as u64);
+    assert_eq!(stats.heap_used_before, None);
+    assert!(stats.jni_copy_time.is_zero());
+    assert!(stats.parse_time <= stats.total_time);
+
+    // The stats are not collected by default
+    let (_content, metadata) = Extractor::new()
+        .extract_file_to_string("README.md")
+        .unwrap();
+    assert_eq!(ExtractionStats::from_metadata(&metadata), None);
+}
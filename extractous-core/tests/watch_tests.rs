@@ -0,0 +1,123 @@
+use extractous::watch::{WatchEvent, Watcher};
+use extractous::{ContentSink, Error, ExtractResult, Extractor, Metadata, Parser};
+use std::fs;
+use std::io::Read;
+use std::path::PathBuf;
+
+fn scan(watcher: &mut Watcher) -> Vec<(String, PathBuf)> {
+    let mut events = Vec::new();
+    watcher
+        .scan(|event| {
+            let kind = match &event {
+                WatchEvent::Created { .. } => "created",
+                WatchEvent::Modified { .. } => "modified",
+                WatchEvent::Deleted { .. } => "deleted",
+                WatchEvent::Failed { .. } => "failed",
+            };
+            events.push((kind.to_string(), event.path().to_path_buf()));
+        })
+        .unwrap();
+    events
+}
+
+#[test]
+fn test_watch_changes() {
+    let dir = std::env::temp_dir().join(format!("extractous-watch-{}", std::process::id()));
+    fs::remove_dir_all(&dir).ok();
+    let root = dir.join("documents");
+    fs::create_dir_all(root.join("notes")).unwrap();
+    fs::write(root.join("a.txt"), "first document").unwrap();
+    fs::write(root.join("notes/b.md"), "# Second document").unwrap();
+    let state_file = root.join("state.json");
+    let watcher = || Watcher::new(&root, Extractor::new()).set_state_file(&state_file);
+
+    let mut watcher1 = watcher();
+    assert_eq!(
+        scan(&mut watcher1),
+        vec![
+            ("created".to_string(), root.join("a.txt")),
+            ("created".to_string(), root.join("notes/b.md")),
+        ]
+    );
+    assert_eq!(scan(&mut watcher1), vec![]);
+
+    fs::write(root.join("a.txt"), "first document, modified").unwrap();
+    fs::remove_file(root.join("notes/b.md")).unwrap();
+    assert_eq!(
+        scan(&mut watcher1),
+        vec![
+            ("modified".to_string(), root.join("a.txt")),
+            ("deleted".to_string(), root.join("notes/b.md")),
+        ]
+    );
+
+    // A new watcher only reports the changes since the state file was written
+    fs::write(root.join("c.txt"), "third document").unwrap();
+    let mut watcher2 = watcher().set_filter(|path| path.extension().is_some_and(|e| e == "txt"));
+    assert_eq!(
+        scan(&mut watcher2),
+        vec![("created".to_string(), root.join("c.txt"))]
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Fails to parse the text documents containing "broken"
+struct FragileParser;
+
+impl Parser for FragileParser {
+    fn supported_types(&self) -> Vec<String> {
+        vec!["text/plain".to_string()]
+    }
+
+    fn parse(
+        &self,
+        input: &mut dyn Read,
+        sink: &mut ContentSink,
+        _: &mut Metadata,
+    ) -> ExtractResult<()> {
+        let mut text = String::new();
+        input
+            .read_to_string(&mut text)
+            .map_err(|e| Error::IoError(e.to_string()))?;
+        if text.contains("broken") {
+            return Err(Error::ParseError("broken document".to_string()));
+        }
+        sink.element("p", &text);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_watch_retries_failed_files() {
+    let dir = std::env::temp_dir().join(format!("extractous-watch-retry-{}", std::process::id()));
+    fs::remove_dir_all(&dir).ok();
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("a.txt"), "broken document").unwrap();
+    let state_file = dir.join("state.json");
+    let watcher = || {
+        Watcher::new(&dir, Extractor::new().register_parser(FragileParser))
+            .set_state_file(&state_file)
+    };
+
+    // The failed files are extracted again by every scan, also after a restart
+    let failed = vec![("failed".to_string(), dir.join("a.txt"))];
+    assert_eq!(scan(&mut watcher()), failed);
+    assert_eq!(scan(&mut watcher()), failed);
+
+    fs::write(dir.join("a.txt"), "fixed document").unwrap();
+    let mut watcher = watcher();
+    assert_eq!(
+        scan(&mut watcher),
+        vec![("created".to_string(), dir.join("a.txt"))]
+    );
+    assert_eq!(scan(&mut watcher), vec![]);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_watch_missing_directory() {
+    let mut watcher = Watcher::new("does-not-exist", Extractor::new());
+    assert!(watcher.scan(|_event| ()).is_err());
+}
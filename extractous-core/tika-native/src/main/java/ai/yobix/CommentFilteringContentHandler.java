@@ -0,0 +1,93 @@
+package ai.yobix;
+
+import org.apache.tika.sax.ContentHandlerDecorator;
+import org.xml.sax.Attributes;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+import java.util.ArrayDeque;
+import java.util.Deque;
+
+/**
+ * ContentHandler that drops the comments written by the office parsers in the text of the
+ * documents:
+ * <ul>
+ *     <li>the {@code <p class="slide-comment">} elements of the pptx slides</li>
+ *     <li>the {@code "\tComment by author: text"} characters written at the end of the docx
+ *     paragraphs</li>
+ *     <li>the cell comments of the xlsx sheets, written after a {@code <br>} in their
+ *     {@code <td>} cell</li>
+ * </ul>
+ */
+public class CommentFilteringContentHandler extends ContentHandlerDecorator {
+
+    private static final String SLIDE_COMMENT_CLASS = "slide-comment";
+    private static final String DOCX_COMMENT_PREFIX = "\tComment by ";
+
+    /**
+     * The local names of the open elements
+     */
+    private final Deque<String> openElements = new ArrayDeque<>();
+    /**
+     * Depth of the current element inside the dropped comment, 0 outside the comments
+     */
+    private int commentDepth = 0;
+
+    public CommentFilteringContentHandler(ContentHandler handler) {
+        super(handler);
+    }
+
+    @Override
+    public void startElement(String uri, String localName, String qName, Attributes atts)
+            throws SAXException {
+        if (commentDepth > 0) {
+            commentDepth++;
+            return;
+        }
+        if ("p".equals(localName) && SLIDE_COMMENT_CLASS.equals(atts.getValue("class"))) {
+            commentDepth = 1;
+            return;
+        }
+        if ("br".equals(localName) && "td".equals(openElements.peek())) {
+            // The comment of a cell follows the line break, up to the end of the cell. The
+            // cell itself is kept, the end of the comment is the end of its td
+            commentDepth = 2;
+            return;
+        }
+        openElements.push(localName);
+        super.startElement(uri, localName, qName, atts);
+    }
+
+    @Override
+    public void endElement(String uri, String localName, String qName) throws SAXException {
+        if (commentDepth > 0) {
+            commentDepth--;
+            // Only the td of a cell comment is forwarded
+            if (commentDepth > 0 || !"td".equals(localName)) {
+                return;
+            }
+        }
+        openElements.poll();
+        super.endElement(uri, localName, qName);
+    }
+
+    @Override
+    public void characters(char[] ch, int start, int length) throws SAXException {
+        if (commentDepth > 0 || isDocxComment(ch, start, length)) {
+            return;
+        }
+        super.characters(ch, start, length);
+    }
+
+    @Override
+    public void ignorableWhitespace(char[] ch, int start, int length) throws SAXException {
+        if (commentDepth == 0) {
+            super.ignorableWhitespace(ch, start, length);
+        }
+    }
+
+    private static boolean isDocxComment(char[] ch, int start, int length) {
+        return length >= DOCX_COMMENT_PREFIX.length()
+                && new String(ch, start, DOCX_COMMENT_PREFIX.length()).equals(DOCX_COMMENT_PREFIX);
+    }
+}
@@ -0,0 +1,75 @@
+package ai.yobix;
+
+import org.apache.tika.extractor.EmbeddedDocumentExtractor;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.metadata.TikaCoreProperties;
+import org.xml.sax.ContentHandler;
+
+import java.io.IOException;
+import java.io.InputStream;
+import java.util.ArrayList;
+import java.util.HashSet;
+import java.util.List;
+import java.util.Set;
+
+/**
+ * EmbeddedDocumentExtractor that keeps the raw bytes of every document embedded in the
+ * parsed document, e.g. email attachments, images of office documents or files of archives.
+ * Embedded documents are collected but not parsed.
+ */
+public class EmbeddedDocumentCollector implements EmbeddedDocumentExtractor {
+
+    private final List<String> names = new ArrayList<>();
+    private final List<byte[]> contents = new ArrayList<>();
+    private final List<Metadata> metadataList = new ArrayList<>();
+    private final Set<String> usedNames = new HashSet<>();
+
+    @Override
+    public boolean shouldParseEmbedded(Metadata metadata) {
+        return true;
+    }
+
+    @Override
+    public void parseEmbedded(
+            InputStream stream,
+            ContentHandler handler,
+            Metadata metadata,
+            boolean outputHtml
+    ) throws IOException {
+        names.add(uniqueName(metadata.get(TikaCoreProperties.RESOURCE_NAME_KEY)));
+        contents.add(stream.readAllBytes());
+        metadataList.add(metadata);
+    }
+
+    /**
+     * Returns a name without any directory, that was not returned before
+     */
+    private String uniqueName(String resourceName) {
+        String name = resourceName;
+        if (name != null) {
+            name = name.substring(Math.max(name.lastIndexOf('/'), name.lastIndexOf('\\')) + 1);
+        }
+        if (name == null || name.isEmpty() || name.equals(".") || name.equals("..")) {
+            name = "embedded-" + (names.size() + 1);
+        }
+
+        String unique = name;
+        for (int i = 1; usedNames.contains(unique); i++) {
+            unique = i + "-" + name;
+        }
+        usedNames.add(unique);
+        return unique;
+    }
+
+    public List<String> getNames() {
+        return names;
+    }
+
+    public List<byte[]> getContents() {
+        return contents;
+    }
+
+    public List<Metadata> getMetadataList() {
+        return metadataList;
+    }
+}
@@ -0,0 +1,41 @@
+package ai.yobix;
+
+import org.xml.sax.ContentHandler;
+
+/**
+ * The options of the content written by the parsers. The end of the pdf pages can be marked
+ * with a form feed, for the page-aware post-processing of the text output, and the comments
+ * of office documents can be left out.
+ */
+public class OutputOptions {
+
+    /**
+     * True to write a form feed after every pdf page
+     */
+    private final boolean pageBreaks;
+    /**
+     * False to drop the comments of docx, pptx and xlsx documents
+     */
+    private final boolean includeComments;
+
+    public OutputOptions(boolean pageBreaks, boolean includeComments) {
+        this.pageBreaks = pageBreaks;
+        this.includeComments = includeComments;
+    }
+
+    /**
+     * Wraps the handler to mark the end of the pages and drop the comments if requested
+     *
+     * @param handler the handler receiving the content
+     * @return the handler to pass to the parser, or to decorate further
+     */
+    public ContentHandler decorate(ContentHandler handler) {
+        if (!includeComments) {
+            handler = new CommentFilteringContentHandler(handler);
+        }
+        if (pageBreaks) {
+            handler = new PageBreakContentHandler(handler);
+        }
+        return handler;
+    }
+}
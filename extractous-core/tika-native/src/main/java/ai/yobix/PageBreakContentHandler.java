@@ -0,0 +1,47 @@
+package ai.yobix;
+
+import org.apache.tika.sax.ContentHandlerDecorator;
+import org.xml.sax.Attributes;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+/**
+ * ContentHandler that writes a form feed after every {@code <div class="page">} element of pdf
+ * documents, so that the pages can be told apart in the text output.
+ */
+public class PageBreakContentHandler extends ContentHandlerDecorator {
+
+    private static final String PDF_PAGE_CLASS = "page";
+    private static final char[] PAGE_BREAK = {'\f'};
+
+    /**
+     * Depth of the current element inside the current page, 0 outside the pages
+     */
+    private int pageDepth = 0;
+
+    public PageBreakContentHandler(ContentHandler handler) {
+        super(handler);
+    }
+
+    @Override
+    public void startElement(String uri, String localName, String qName, Attributes atts)
+            throws SAXException {
+        if (pageDepth > 0) {
+            pageDepth++;
+        } else if ("div".equals(localName) && PDF_PAGE_CLASS.equals(atts.getValue("class"))) {
+            pageDepth = 1;
+        }
+        super.startElement(uri, localName, qName, atts);
+    }
+
+    @Override
+    public void endElement(String uri, String localName, String qName) throws SAXException {
+        super.endElement(uri, localName, qName);
+        if (pageDepth > 0) {
+            pageDepth--;
+            if (pageDepth == 0) {
+                super.characters(PAGE_BREAK, 0, PAGE_BREAK.length);
+            }
+        }
+    }
+}
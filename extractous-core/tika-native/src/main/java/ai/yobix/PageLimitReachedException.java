@@ -0,0 +1,28 @@
+package ai.yobix;
+
+import org.xml.sax.SAXException;
+
+/**
+ * Thrown by the {@link PageSelectingContentHandler} to stop the parsing after the last
+ * selected page. It is not an error, the content extracted so far is complete.
+ */
+public class PageLimitReachedException extends SAXException {
+
+    public PageLimitReachedException(int pageNumber) {
+        super("Page limit reached after page " + pageNumber);
+    }
+
+    /**
+     * @param t the exception thrown by the parser
+     * @return true if the exception or one of its causes is a PageLimitReachedException
+     */
+    public static boolean isPageLimitReached(Throwable t) {
+        while (t != null) {
+            if (t instanceof PageLimitReachedException) {
+                return true;
+            }
+            t = t.getCause();
+        }
+        return false;
+    }
+}
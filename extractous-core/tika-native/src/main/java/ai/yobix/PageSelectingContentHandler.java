@@ -0,0 +1,136 @@
+package ai.yobix;
+
+import org.apache.tika.sax.ContentHandlerDecorator;
+import org.xml.sax.Attributes;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+import java.util.ArrayDeque;
+import java.util.Deque;
+
+/**
+ * ContentHandler that forwards the content of the selected pages only. Pages are the
+ * {@code <div class="page">} elements of pdf documents and the {@code <div class="slide-content">}
+ * elements of presentations. Once the last selected page is emitted, the open elements are
+ * closed, the document is ended and a {@link PageLimitReachedException} stops the parsing.
+ */
+public class PageSelectingContentHandler extends ContentHandlerDecorator {
+
+    private static final String PDF_PAGE_CLASS = "page";
+    private static final String SLIDE_CLASS = "slide-content";
+
+    private final PageSelection selection;
+    /**
+     * The forwarded elements that are not closed yet as [uri, localName, qName]
+     */
+    private final Deque<String[]> openElements = new ArrayDeque<>();
+
+    private int pageNumber = 0;
+    /**
+     * Depth of the current element inside the current page, 0 outside the pages
+     */
+    private int pageDepth = 0;
+    private boolean pdfPage = false;
+    private boolean selected = true;
+    private boolean stopped = false;
+
+    public PageSelectingContentHandler(ContentHandler handler, PageSelection selection) {
+        super(handler);
+        this.selection = selection;
+    }
+
+    @Override
+    public void startElement(String uri, String localName, String qName, Attributes atts)
+            throws SAXException {
+        if (stopped) {
+            return;
+        }
+        if (pageDepth > 0) {
+            pageDepth++;
+            if (selected) {
+                forwardStartElement(uri, localName, qName, atts);
+            }
+            return;
+        }
+
+        final String pageClass = "div".equals(localName) ? atts.getValue("class") : null;
+        if (PDF_PAGE_CLASS.equals(pageClass) || SLIDE_CLASS.equals(pageClass)) {
+            pdfPage = PDF_PAGE_CLASS.equals(pageClass);
+            pageNumber++;
+            if (pageNumber > selection.lastPage(pdfPage)) {
+                stop();
+            }
+            pageDepth = 1;
+            selected = selection.isSelected(pageNumber, pdfPage);
+            if (selected) {
+                forwardStartElement(uri, localName, qName, atts);
+            }
+            return;
+        }
+        forwardStartElement(uri, localName, qName, atts);
+    }
+
+    @Override
+    public void endElement(String uri, String localName, String qName) throws SAXException {
+        if (stopped) {
+            return;
+        }
+        if (pageDepth > 0) {
+            pageDepth--;
+            if (selected) {
+                forwardEndElement(uri, localName, qName);
+            }
+            if (pageDepth == 0 && pageNumber >= selection.lastPage(pdfPage)) {
+                stop();
+            }
+            return;
+        }
+        forwardEndElement(uri, localName, qName);
+    }
+
+    @Override
+    public void characters(char[] ch, int start, int length) throws SAXException {
+        if (!stopped && (pageDepth == 0 || selected)) {
+            super.characters(ch, start, length);
+        }
+    }
+
+    @Override
+    public void ignorableWhitespace(char[] ch, int start, int length) throws SAXException {
+        if (!stopped && (pageDepth == 0 || selected)) {
+            super.ignorableWhitespace(ch, start, length);
+        }
+    }
+
+    @Override
+    public void endDocument() throws SAXException {
+        if (!stopped) {
+            super.endDocument();
+        }
+    }
+
+    private void forwardStartElement(String uri, String localName, String qName, Attributes atts)
+            throws SAXException {
+        openElements.push(new String[]{uri, localName, qName});
+        super.startElement(uri, localName, qName, atts);
+    }
+
+    private void forwardEndElement(String uri, String localName, String qName) throws SAXException {
+        openElements.poll();
+        super.endElement(uri, localName, qName);
+    }
+
+    /**
+     * Closes the open elements and the document, so that the output is complete, and stops
+     * the parsing
+     */
+    private void stop() throws SAXException {
+        while (!openElements.isEmpty()) {
+            final String[] element = openElements.pop();
+            super.endElement(element[0], element[1], element[2]);
+        }
+        super.endDocument();
+        stopped = true;
+        throw new PageLimitReachedException(pageNumber);
+    }
+}
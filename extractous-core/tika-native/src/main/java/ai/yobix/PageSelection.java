@@ -0,0 +1,81 @@
+package ai.yobix;
+
+import org.xml.sax.ContentHandler;
+
+/**
+ * The pages to extract from paginated documents. Pages are numbered from 1.
+ * The page ranges apply to the pages of pdf documents only, the maximum number of pages
+ * applies to pdf pages and presentation slides.
+ */
+public class PageSelection {
+
+    /**
+     * Inclusive page ranges as [start, end] pairs, empty to select all the pages
+     */
+    private final int[] pageRanges;
+    /**
+     * Maximum number of pages to extract, negative for no limit
+     */
+    private final int maxPages;
+
+    public PageSelection(int[] pageRanges, int maxPages) {
+        this.pageRanges = pageRanges;
+        this.maxPages = maxPages;
+    }
+
+    /**
+     * @return true if all the pages of all the documents are selected
+     */
+    public boolean isAll() {
+        return pageRanges.length == 0 && maxPages < 0;
+    }
+
+    /**
+     * @param pageNumber the number of the page, starting at 1
+     * @param pdfPage true for a pdf page, false for a slide
+     * @return true if the page should be extracted
+     */
+    public boolean isSelected(int pageNumber, boolean pdfPage) {
+        if (maxPages >= 0 && pageNumber > maxPages) {
+            return false;
+        }
+        if (!pdfPage || pageRanges.length == 0) {
+            return true;
+        }
+        for (int i = 0; i + 1 < pageRanges.length; i += 2) {
+            if (pageNumber >= pageRanges[i] && pageNumber <= pageRanges[i + 1]) {
+                return true;
+            }
+        }
+        return false;
+    }
+
+    /**
+     * @param pdfPage true for a pdf page, false for a slide
+     * @return the number of the last selected page, parsing can stop after it
+     */
+    public int lastPage(boolean pdfPage) {
+        int last = maxPages >= 0 ? maxPages : Integer.MAX_VALUE;
+        if (pdfPage && pageRanges.length > 0) {
+            int lastInRanges = 0;
+            for (int i = 1; i < pageRanges.length; i += 2) {
+                lastInRanges = Math.max(lastInRanges, pageRanges[i]);
+            }
+            last = Math.min(last, lastInRanges);
+        }
+        return last;
+    }
+
+    /**
+     * Wraps the handler to drop the content of the pages that are not selected
+     *
+     * @param handler the handler receiving the selected content
+     * @return the handler to pass to the parser
+     */
+    public ContentHandler decorate(ContentHandler handler) {
+        if (isAll()) {
+            return handler;
+        }
+        return new PageSelectingContentHandler(handler, this);
+    }
+}
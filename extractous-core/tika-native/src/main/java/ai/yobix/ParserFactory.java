@@ -0,0 +1,94 @@
+package ai.yobix;
+
+import org.apache.tika.config.TikaConfig;
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.parser.AutoDetectParser;
+import org.apache.tika.parser.CompositeParser;
+import org.apache.tika.parser.DefaultParser;
+import org.apache.tika.parser.Parser;
+import org.apache.tika.parser.ocr.TesseractOCRConfig;
+import org.apache.tika.parser.ocr.TesseractOCRParser;
+
+import java.util.ArrayList;
+import java.util.Collections;
+import java.util.List;
+
+/**
+ * Creates the AutoDetectParser of a parse call, with the parsers registered on the Rust
+ * extractor, the tesseract paths of the {@link TesseractPathsConfig} and the progress reporting.
+ */
+public class ParserFactory {
+
+    /**
+     * Creates the AutoDetectParser used to parse documents without progress reporting
+     */
+    public static Parser newAutoDetectParser(
+            TikaConfig config,
+            TesseractOCRConfig tesseractConfig,
+            RustParser[] rustParsers
+    ) throws TikaException {
+        return newAutoDetectParser(config, tesseractConfig, rustParsers, new ProgressReporter(0));
+    }
+
+    /**
+     * Creates the AutoDetectParser used to parse documents. If the tesseract config sets a path,
+     * the default TesseractOCRParser is replaced by one using the configured paths. If the
+     * progress is reported or the stats collected, it is replaced by a ProgressTesseractOCRParser
+     * reporting the OCRs, and the detector is timed.
+     *
+     * @param config the tika config of the default parsers
+     * @param tesseractConfig the tesseract config of the parse call
+     * @param rustParsers the parsers registered on the Rust extractor, they win over the default
+     *                    parsers for their types
+     * @param progress the reporter of the progress of the parse
+     * @return the AutoDetectParser
+     */
+    public static Parser newAutoDetectParser(
+            TikaConfig config,
+            TesseractOCRConfig tesseractConfig,
+            RustParser[] rustParsers,
+            ProgressReporter progress
+    ) throws TikaException {
+        final List<Parser> parsers = new ArrayList<>();
+        if (tesseractConfig instanceof TesseractPathsConfig
+                && ((TesseractPathsConfig) tesseractConfig).hasPaths()) {
+            // Exclude the default tesseract parser, so that the pdf parser finds the new one
+            parsers.add(new DefaultParser(
+                    config.getMediaTypeRegistry(),
+                    config.getServiceLoader(),
+                    Collections.singleton(TesseractOCRParser.class)));
+            parsers.add(newTesseractParser((TesseractPathsConfig) tesseractConfig));
+        } else if (progress.isEnabled()) {
+            parsers.add(new DefaultParser(
+                    config.getMediaTypeRegistry(),
+                    config.getServiceLoader(),
+                    Collections.singleton(TesseractOCRParser.class)));
+            parsers.add(ProgressTesseractOCRParser.getDefault());
+        } else if (rustParsers.length == 0) {
+            return new AutoDetectParser(config);
+        } else {
+            parsers.add(config.getParser());
+        }
+        // The last parsers win for the types supported by several parsers
+        Collections.addAll(parsers, rustParsers);
+        final Parser parser = new CompositeParser(config.getMediaTypeRegistry(), parsers);
+        return new AutoDetectParser(progress.decorate(config.getDetector()), parser);
+    }
+
+    private static TesseractOCRParser newTesseractParser(TesseractPathsConfig pathsConfig)
+            throws TikaException {
+        final TesseractOCRParser parser = new ProgressTesseractOCRParser();
+        if (pathsConfig.getTesseractPath() != null) {
+            parser.setTesseractPath(pathsConfig.getTesseractPath());
+        }
+        if (pathsConfig.getTessdataPath() != null) {
+            parser.setTessdataPath(pathsConfig.getTessdataPath());
+        }
+        if (pathsConfig.getImageMagickPath() != null) {
+            parser.setImageMagickPath(pathsConfig.getImageMagickPath());
+        }
+        // Checks that tesseract and ImageMagick can be found at the new paths
+        parser.initialize(Collections.emptyMap());
+        return parser;
+    }
+}
@@ -20,16 +20,23 @@ public class ParsingReader extends Reader {
     private final InputStream stream;
     private final Metadata metadata;
     private final ParseContext context;
+    private final PageSelection pageSelection;
+    private final OutputOptions outputOptions;
+    private final ProgressReporter progress;
     private final boolean outputXml;
     private final String encoding;
     private transient Throwable throwable;
 
     public ParsingReader(Parser parser, InputStream stream, Metadata metadata,
-                            ParseContext context, boolean outputXml, String encoding) throws IOException {
+                            ParseContext context, PageSelection pageSelection, OutputOptions outputOptions,
+                            ProgressReporter progress, boolean outputXml, String encoding) throws IOException {
         this.parser = parser;
         this.stream = stream;
         this.metadata = metadata;
         this.context = context;
+        this.pageSelection = pageSelection;
+        this.outputOptions = outputOptions;
+        this.progress = progress;
         this.outputXml = outputXml;
         this.encoding = encoding;
 
@@ -78,10 +85,17 @@ public class ParsingReader extends Reader {
         public void run() {
             try {
                 ContentHandler handler = outputXml ? new ToXMLContentHandler(pipedOutputStream, encoding) : new BodyContentHandler(pipedOutputStream);
+                progress.parseStarted();
+                handler = progress.decorate(pageSelection.decorate(outputOptions.decorate(handler)), metadata);
                 parser.parse(stream, handler, metadata, context);
             } catch (Throwable t) {
-                throwable = t;
+                // Stopping after the last selected page is not an error
+                if (!PageLimitReachedException.isPageLimitReached(t)) {
+                    throwable = t;
+                }
             }
+            // Before closing the pipe, the stats are read once the stream is read
+            progress.parseFinished(metadata);
 
             try {
                 stream.close();
@@ -0,0 +1,112 @@
+package ai.yobix;
+
+import org.apache.pdfbox.pdmodel.PDDocument;
+import org.apache.pdfbox.pdmodel.PDPage;
+import org.apache.pdfbox.pdmodel.common.PDRectangle;
+import org.apache.pdfbox.pdmodel.interactive.annotation.PDAnnotation;
+import org.apache.pdfbox.pdmodel.interactive.annotation.PDAnnotationMarkup;
+import org.apache.pdfbox.pdmodel.interactive.annotation.PDAnnotationTextMarkup;
+import org.apache.pdfbox.text.PDFTextStripperByArea;
+import org.apache.pdfbox.util.DateConverter;
+
+import java.awt.geom.Rectangle2D;
+import java.io.IOException;
+import java.util.Calendar;
+import java.util.Set;
+
+/**
+ * Reads the annotations of the pages of a pdf document, such as the notes, highlights and free
+ * texts, without extracting the text of the pages. Every annotation is written on its own line
+ * as its subtype, its page starting at 1, its author, its modification date in ISO 8601, the
+ * text under it for the text markup annotations and its contents, separated by tabs. The
+ * backslashes, tabs and line breaks of the fields are escaped as {@code \\}, {@code \t},
+ * {@code \n} and {@code \r}.
+ */
+public class PdfAnnotations {
+
+    /**
+     * The annotations that are not comments: the links, the form fields and the popups, whose
+     * contents are the ones of their parent annotation
+     */
+    private static final Set<String> SKIPPED_SUBTYPES = Set.of("Link", "Widget", "Popup");
+    private static final String ANCHOR_REGION = "anchor";
+
+    public static String read(PDDocument document) throws IOException {
+        final StringBuilder lines = new StringBuilder();
+        int pageNumber = 0;
+        for (PDPage page : document.getPages()) {
+            pageNumber++;
+            for (PDAnnotation annotation : page.getAnnotations()) {
+                final String subtype = annotation.getSubtype();
+                if (subtype == null || SKIPPED_SUBTYPES.contains(subtype)) {
+                    continue;
+                }
+                final String author = annotation instanceof PDAnnotationMarkup
+                        ? ((PDAnnotationMarkup) annotation).getTitlePopup()
+                        : null;
+                lines.append(escape(subtype))
+                        .append('\t')
+                        .append(pageNumber)
+                        .append('\t')
+                        .append(escape(author))
+                        .append('\t')
+                        .append(escape(date(annotation.getModifiedDate())))
+                        .append('\t')
+                        .append(escape(anchorText(page, annotation)))
+                        .append('\t')
+                        .append(escape(annotation.getContents()))
+                        .append('\n');
+            }
+        }
+        return lines.toString();
+    }
+
+    /**
+     * @return the text under a highlight, underline, strikeout or squiggly annotation, null
+     * for the other annotations
+     */
+    private static String anchorText(PDPage page, PDAnnotation annotation) {
+        final PDRectangle rectangle = annotation.getRectangle();
+        if (!(annotation instanceof PDAnnotationTextMarkup) || rectangle == null) {
+            return null;
+        }
+        try {
+            // The regions of the stripper start at the top of the page, the pdf rectangles at
+            // its bottom
+            final float pageHeight = page.getMediaBox().getHeight();
+            final PDFTextStripperByArea stripper = new PDFTextStripperByArea();
+            stripper.addRegion(ANCHOR_REGION, new Rectangle2D.Float(
+                    rectangle.getLowerLeftX(),
+                    pageHeight - rectangle.getUpperRightY(),
+                    rectangle.getWidth(),
+                    rectangle.getHeight()));
+            stripper.extractRegions(page);
+            return stripper.getTextForRegion(ANCHOR_REGION).trim();
+        } catch (IOException e) {
+            // A page whose text cannot be read only loses the anchor text of its annotations
+            return null;
+        }
+    }
+
+    /**
+     * @return the pdf date, e.g. {@code D:20240102030405+01'00'}, in ISO 8601, or the pdf date
+     * as is if it is not valid
+     */
+    private static String date(String pdfDate) {
+        if (pdfDate == null) {
+            return null;
+        }
+        final Calendar calendar = DateConverter.toCalendar(pdfDate);
+        return calendar == null ? pdfDate : calendar.toInstant().toString();
+    }
+
+    private static String escape(String field) {
+        if (field == null) {
+            return "";
+        }
+        return field.replace("\\", "\\\\")
+                .replace("\t", "\\t")
+                .replace("\n", "\\n")
+                .replace("\r", "\\r");
+    }
+}
@@ -0,0 +1,74 @@
+package ai.yobix;
+
+import org.apache.pdfbox.cos.COSDictionary;
+import org.apache.pdfbox.pdmodel.PDDocument;
+import org.apache.pdfbox.pdmodel.PDPage;
+import org.apache.pdfbox.pdmodel.interactive.documentnavigation.outline.PDDocumentOutline;
+import org.apache.pdfbox.pdmodel.interactive.documentnavigation.outline.PDOutlineItem;
+import org.apache.pdfbox.pdmodel.interactive.documentnavigation.outline.PDOutlineNode;
+
+import java.io.IOException;
+import java.util.Collections;
+import java.util.IdentityHashMap;
+import java.util.Set;
+
+/**
+ * Reads the outline, i.e. the bookmarks, of a pdf document with the page every bookmark points
+ * to, without extracting the text of the pages. Every bookmark is written on its own line as
+ * its level, starting at 1, its page, starting at 1 or 0 when unknown, and its title, separated
+ * by tabs.
+ */
+public class PdfOutline {
+
+    /**
+     * Maximum depth of the outline, deeper bookmarks are skipped
+     */
+    private static final int MAX_DEPTH = 64;
+
+    public static String read(PDDocument document) {
+        final StringBuilder lines = new StringBuilder();
+        final PDDocumentOutline outline = document.getDocumentCatalog().getDocumentOutline();
+        if (outline != null) {
+            final Set<COSDictionary> visited = Collections.newSetFromMap(new IdentityHashMap<>());
+            append(document, outline, 1, visited, lines);
+        }
+        return lines.toString();
+    }
+
+    private static void append(
+            PDDocument document,
+            PDOutlineNode node,
+            int level,
+            Set<COSDictionary> visited,
+            StringBuilder lines
+    ) {
+        if (level > MAX_DEPTH) {
+            return;
+        }
+        for (PDOutlineItem item : node.children()) {
+            // Malformed outlines can link back to a visited bookmark
+            if (!visited.add(item.getCOSObject())) {
+                return;
+            }
+            final String title = item.getTitle() == null ? "" : item.getTitle();
+            lines.append(level)
+                    .append('\t')
+                    .append(page(document, item))
+                    .append('\t')
+                    .append(title.replaceAll("\\s+", " ").trim())
+                    .append('\n');
+            append(document, item, level + 1, visited, lines);
+        }
+    }
+
+    private static int page(PDDocument document, PDOutlineItem item) {
+        try {
+            final PDPage page = item.findDestinationPage(document);
+            // indexOf returns -1 for the pages that are not in the page tree
+            return page == null ? 0 : document.getPages().indexOf(page) + 1;
+        } catch (IOException e) {
+            // A broken destination only loses the page of its bookmark
+            return 0;
+        }
+    }
+}
@@ -0,0 +1,146 @@
+package ai.yobix;
+
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.metadata.TikaCoreProperties;
+import org.apache.tika.sax.ContentHandlerDecorator;
+import org.xml.sax.Attributes;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+/**
+ * ContentHandler that reports the start and end of the document, the parsed pages and the
+ * size of the produced output to a {@link ProgressReporter}. Pages are the
+ * {@code <div class="page">} elements of pdf documents and the {@code <div class="slide-content">}
+ * elements of presentations, like in {@link PageSelectingContentHandler}.
+ */
+public class ProgressContentHandler extends ContentHandlerDecorator {
+
+    private static final String PDF_PAGE_CLASS = "page";
+    private static final String SLIDE_CLASS = "slide-content";
+    private static final String[] PAGE_COUNT_KEYS = {"xmpTPg:NPages", "meta:page-count", "meta:slide-count"};
+    /**
+     * The produced output is reported every OUTPUT_STEP bytes
+     */
+    private static final long OUTPUT_STEP = 64 * 1024;
+
+    private final Metadata metadata;
+    private final ProgressReporter reporter;
+
+    private int pageNumber = 0;
+    /**
+     * Depth of the current element inside the current page, 0 outside the pages
+     */
+    private int pageDepth = 0;
+    private long bytes = 0;
+    private long chars = 0;
+    private long reportedBytes = 0;
+    private boolean finished = false;
+
+    public ProgressContentHandler(ContentHandler handler, Metadata metadata, ProgressReporter reporter) {
+        super(handler);
+        this.metadata = metadata;
+        this.reporter = reporter;
+    }
+
+    @Override
+    public void startDocument() throws SAXException {
+        reporter.documentStarted(metadata.get(TikaCoreProperties.TYPE));
+        super.startDocument();
+    }
+
+    @Override
+    public void startElement(String uri, String localName, String qName, Attributes atts)
+            throws SAXException {
+        if (pageDepth > 0) {
+            pageDepth++;
+        } else if ("div".equals(localName) && !reporter.isInEmbeddedDocument()) {
+            final String pageClass = atts.getValue("class");
+            if (PDF_PAGE_CLASS.equals(pageClass) || SLIDE_CLASS.equals(pageClass)) {
+                pageNumber++;
+                pageDepth = 1;
+                reporter.pageStarted(pageNumber);
+            }
+        }
+        super.startElement(uri, localName, qName, atts);
+    }
+
+    @Override
+    public void endElement(String uri, String localName, String qName) throws SAXException {
+        super.endElement(uri, localName, qName);
+        if (pageDepth > 0) {
+            pageDepth--;
+            if (pageDepth == 0) {
+                reporter.pageDone(pageNumber, pageCount());
+            }
+        }
+    }
+
+    @Override
+    public void characters(char[] ch, int start, int length) throws SAXException {
+        super.characters(ch, start, length);
+        addOutput(ch, start, length);
+    }
+
+    @Override
+    public void ignorableWhitespace(char[] ch, int start, int length) throws SAXException {
+        super.ignorableWhitespace(ch, start, length);
+        addOutput(ch, start, length);
+    }
+
+    @Override
+    public void endDocument() throws SAXException {
+        super.endDocument();
+        finish();
+    }
+
+    @Override
+    protected void handleException(SAXException exception) throws SAXException {
+        // Stopping after the last selected page ends the document
+        if (PageLimitReachedException.isPageLimitReached(exception)) {
+            finish();
+        }
+        super.handleException(exception);
+    }
+
+    /**
+     * Counts the UTF-8 bytes and the characters, the code points, of the output
+     */
+    private void addOutput(char[] ch, int start, int length) {
+        for (int i = start; i < start + length; i++) {
+            final char c = ch[i];
+            bytes += c < 0x80 ? 1 : c < 0x800 || Character.isSurrogate(c) ? 2 : 3;
+            if (!Character.isLowSurrogate(c)) {
+                chars++;
+            }
+        }
+        if (bytes - reportedBytes >= OUTPUT_STEP) {
+            reportedBytes = bytes;
+            reporter.outputProduced(bytes);
+        }
+    }
+
+    private void finish() {
+        if (!finished) {
+            finished = true;
+            reporter.outputProduced(bytes);
+            reporter.documentFinished(chars);
+        }
+    }
+
+    /**
+     * @return the number of pages from the metadata, 0 if unknown
+     */
+    private int pageCount() {
+        for (String key : PAGE_COUNT_KEYS) {
+            final String value = metadata.get(key);
+            if (value != null) {
+                try {
+                    return Integer.parseInt(value.trim());
+                } catch (NumberFormatException e) {
+                    // Try the next key
+                }
+            }
+        }
+        return 0;
+    }
+}
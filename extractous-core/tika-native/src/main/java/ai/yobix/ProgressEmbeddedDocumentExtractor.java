@@ -0,0 +1,45 @@
+package ai.yobix;
+
+import org.apache.tika.extractor.EmbeddedDocumentExtractor;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.metadata.TikaCoreProperties;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+import java.io.IOException;
+import java.io.InputStream;
+
+/**
+ * EmbeddedDocumentExtractor that reports the embedded documents to a {@link ProgressReporter}
+ * before handing them to the wrapped extractor.
+ */
+public class ProgressEmbeddedDocumentExtractor implements EmbeddedDocumentExtractor {
+
+    private final EmbeddedDocumentExtractor extractor;
+    private final ProgressReporter reporter;
+
+    public ProgressEmbeddedDocumentExtractor(EmbeddedDocumentExtractor extractor, ProgressReporter reporter) {
+        this.extractor = extractor;
+        this.reporter = reporter;
+    }
+
+    @Override
+    public boolean shouldParseEmbedded(Metadata metadata) {
+        return extractor.shouldParseEmbedded(metadata);
+    }
+
+    @Override
+    public void parseEmbedded(
+            InputStream stream,
+            ContentHandler handler,
+            Metadata metadata,
+            boolean outputHtml
+    ) throws SAXException, IOException {
+        reporter.embeddedDocumentStarted(metadata.get(TikaCoreProperties.RESOURCE_NAME_KEY));
+        try {
+            extractor.parseEmbedded(stream, handler, metadata, outputHtml);
+        } finally {
+            reporter.embeddedDocumentFinished();
+        }
+    }
+}
@@ -0,0 +1,225 @@
+package ai.yobix;
+
+import org.apache.tika.detect.Detector;
+import org.apache.tika.extractor.EmbeddedDocumentExtractor;
+import org.apache.tika.extractor.EmbeddedDocumentUtil;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.parser.ParseContext;
+import org.xml.sax.ContentHandler;
+
+/**
+ * Reports the progress of a parse to the progress callback of the Rust extractor. The reporter
+ * of an extractor without progress callback has the id 0 and reports nothing. If the stats are
+ * collected, it also times the detection, the parse and the OCRs, and adds the stats of the parse
+ * to the metadata once it finishes.
+ */
+public class ProgressReporter {
+
+    private static final int DOCUMENT_STARTED = 0;
+    private static final int PAGE_DONE = 1;
+    private static final int EMBEDDED_DOCUMENT_STARTED = 2;
+    private static final int OCR_STARTED = 3;
+    private static final int OCR_FINISHED = 4;
+    private static final int OUTPUT_PRODUCED = 5;
+    private static final int DOCUMENT_FINISHED = 6;
+
+    // The metadata keys of the stats, read by the Rust side
+    private static final String DETECT_TIME_KEY = "X-EXTRACTOUS:detect_time_micros";
+    private static final String PARSE_TIME_KEY = "X-EXTRACTOUS:parse_time_micros";
+    private static final String OCR_TIME_KEY = "X-EXTRACTOUS:ocr_time_micros";
+    private static final String CHARS_OUT_KEY = "X-EXTRACTOUS:chars_out";
+    private static final String EMBEDDED_DOCUMENTS_KEY = "X-EXTRACTOUS:embedded_documents";
+    private static final String HEAP_USED_BEFORE_KEY = "X-EXTRACTOUS:heap_used_before";
+    private static final String HEAP_USED_AFTER_KEY = "X-EXTRACTOUS:heap_used_after";
+    private static final String OCR_RAN_KEY = "X-EXTRACTOUS:ocr_ran";
+
+    /**
+     * Id of the Rust progress callback
+     */
+    private final long id;
+    private final boolean collectStats;
+    /**
+     * The page being parsed, 0 outside the pages
+     */
+    private int currentPage = 0;
+    private int ocrCount = 0;
+    private int embeddedDepth = 0;
+
+    // The stats of the parse, in nanoseconds for the times
+    private long parseStart = 0;
+    private long heapUsedBefore = 0;
+    private long detectTime = 0;
+    private long ocrStart = 0;
+    private long ocrTime = 0;
+    private long charsOut = 0;
+    private int embeddedDocuments = 0;
+
+    public ProgressReporter(long id) {
+        this(id, false);
+    }
+
+    public ProgressReporter(long id, boolean collectStats) {
+        this.id = id;
+        this.collectStats = collectStats;
+    }
+
+    /**
+     * @return true if the progress is reported or the stats are collected
+     */
+    public boolean isEnabled() {
+        return id != 0 || collectStats;
+    }
+
+    /**
+     * Wraps the handler to report the start and end of the document, the parsed pages and the
+     * produced output
+     *
+     * @param handler the handler receiving the content
+     * @param metadata the metadata of the parsed document
+     * @return the handler to pass to the parser
+     */
+    public ContentHandler decorate(ContentHandler handler, Metadata metadata) {
+        if (!isEnabled()) {
+            return handler;
+        }
+        return new ProgressContentHandler(handler, metadata, this);
+    }
+
+    /**
+     * Adds this reporter to the context, for the OCR parser, and wraps its embedded document
+     * extractor to report the embedded documents. Must be called once the parser and the
+     * embedded document extractor of the context are set.
+     *
+     * @param context the context of the parse
+     */
+    public void decorate(ParseContext context) {
+        if (!isEnabled()) {
+            return;
+        }
+        final EmbeddedDocumentExtractor extractor = EmbeddedDocumentUtil.getEmbeddedDocumentExtractor(context);
+        context.set(EmbeddedDocumentExtractor.class, new ProgressEmbeddedDocumentExtractor(extractor, this));
+        context.set(ProgressReporter.class, this);
+    }
+
+    /**
+     * Wraps the detector to time the detection of the document and of its embedded documents
+     *
+     * @param detector the detector of the parser
+     * @return the detector to pass to the parser
+     */
+    public Detector decorate(Detector detector) {
+        if (!collectStats) {
+            return detector;
+        }
+        return (input, metadata) -> {
+            final long start = System.nanoTime();
+            try {
+                return detector.detect(input, metadata);
+            } finally {
+                detectTime += System.nanoTime() - start;
+            }
+        };
+    }
+
+    /**
+     * Must be called right before the parse, to time it
+     */
+    public void parseStarted() {
+        if (collectStats) {
+            heapUsedBefore = heapUsed();
+            parseStart = System.nanoTime();
+        }
+    }
+
+    /**
+     * Must be called once the parse finished, even if it failed. Adds the stats of the parse to
+     * the metadata. The parse time excludes the detection and the OCR times
+     *
+     * @param metadata the metadata of the parsed document
+     */
+    public void parseFinished(Metadata metadata) {
+        if (!collectStats) {
+            return;
+        }
+        final long parseTime = System.nanoTime() - parseStart - detectTime - ocrTime;
+        metadata.set(DETECT_TIME_KEY, Long.toString(detectTime / 1000));
+        metadata.set(PARSE_TIME_KEY, Long.toString(Math.max(parseTime, 0) / 1000));
+        metadata.set(OCR_TIME_KEY, Long.toString(ocrTime / 1000));
+        metadata.set(CHARS_OUT_KEY, Long.toString(charsOut));
+        metadata.set(EMBEDDED_DOCUMENTS_KEY, Integer.toString(embeddedDocuments));
+        metadata.set(HEAP_USED_BEFORE_KEY, Long.toString(heapUsedBefore));
+        metadata.set(HEAP_USED_AFTER_KEY, Long.toString(heapUsed()));
+        metadata.set(OCR_RAN_KEY, Boolean.toString(ocrCount > 0));
+    }
+
+    private static long heapUsed() {
+        final Runtime runtime = Runtime.getRuntime();
+        return runtime.totalMemory() - runtime.freeMemory();
+    }
+
+    void documentStarted(String contentType) {
+        report(DOCUMENT_STARTED, 0, 0, contentType);
+    }
+
+    void pageStarted(int page) {
+        currentPage = page;
+    }
+
+    void pageDone(int page, int totalPages) {
+        currentPage = 0;
+        report(PAGE_DONE, page, totalPages, null);
+    }
+
+    void embeddedDocumentStarted(String resourceName) {
+        embeddedDepth++;
+        embeddedDocuments++;
+        report(EMBEDDED_DOCUMENT_STARTED, 0, 0, resourceName);
+    }
+
+    void embeddedDocumentFinished() {
+        embeddedDepth--;
+    }
+
+    boolean isInEmbeddedDocument() {
+        return embeddedDepth > 0;
+    }
+
+    /**
+     * @return the page of the OCR, the current pdf page or else the number of the OCRed image
+     */
+    int ocrStarted() {
+        ocrCount++;
+        final int page = currentPage > 0 ? currentPage : ocrCount;
+        ocrStart = System.nanoTime();
+        report(OCR_STARTED, page, 0, null);
+        return page;
+    }
+
+    void ocrFinished(int page) {
+        ocrTime += System.nanoTime() - ocrStart;
+        report(OCR_FINISHED, page, 0, null);
+    }
+
+    void outputProduced(long bytes) {
+        report(OUTPUT_PRODUCED, bytes, 0, null);
+    }
+
+    /**
+     * @param charsOut the number of characters of the output of the document
+     */
+    void documentFinished(long charsOut) {
+        this.charsOut = charsOut;
+        report(DOCUMENT_FINISHED, 0, 0, null);
+    }
+
+    private void report(int kind, long value, long total, String name) {
+        if (id != 0) {
+            report(id, kind, value, total, name);
+        }
+    }
+
+    /**
+     * Implemented on the Rust side, registered when the first enabled reporter is created
+     */
+    private static native void report(long id, int kind, long value, long total, String name);
+}
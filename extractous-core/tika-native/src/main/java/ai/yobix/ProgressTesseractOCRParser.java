@@ -0,0 +1,52 @@
+package ai.yobix;
+
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.exception.TikaConfigException;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.ocr.TesseractOCRParser;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+import java.io.IOException;
+import java.io.InputStream;
+import java.util.Collections;
+
+/**
+ * TesseractOCRParser that reports the start and end of every OCR to the {@link ProgressReporter}
+ * of the parse context. It is a TesseractOCRParser, so that the pdf parser finds it for the OCR
+ * of the pdf pages.
+ */
+public class ProgressTesseractOCRParser extends TesseractOCRParser {
+
+    private static ProgressTesseractOCRParser defaultParser = null;
+
+    /**
+     * @return the parser with the default tesseract paths
+     */
+    static synchronized ProgressTesseractOCRParser getDefault() throws TikaConfigException {
+        if (defaultParser == null) {
+            final ProgressTesseractOCRParser parser = new ProgressTesseractOCRParser();
+            parser.initialize(Collections.emptyMap());
+            defaultParser = parser;
+        }
+        return defaultParser;
+    }
+
+    @Override
+    public void parse(InputStream stream, ContentHandler handler, Metadata metadata, ParseContext context)
+            throws IOException, SAXException, TikaException {
+        final ProgressReporter reporter = context.get(ProgressReporter.class);
+        if (reporter == null) {
+            super.parse(stream, handler, metadata, context);
+            return;
+        }
+
+        final int page = reporter.ocrStarted();
+        try {
+            super.parse(stream, handler, metadata, context);
+        } finally {
+            reporter.ocrFinished(page);
+        }
+    }
+}
@@ -0,0 +1,105 @@
+package ai.yobix;
+
+import org.apache.tika.metadata.Metadata;
+import org.xml.sax.Attributes;
+import org.xml.sax.SAXException;
+import org.xml.sax.helpers.DefaultHandler;
+
+/**
+ * ContentHandler sending the SAX events of the parsed document to a Rust content handler,
+ * without writing the document to text or xml. Embedded documents are delimited by embedded
+ * start and end events, see {@link RustEmbeddedDocumentExtractor}.
+ */
+public class RustContentHandler extends DefaultHandler {
+
+    private static final int START_ELEMENT = 0;
+    private static final int END_ELEMENT = 1;
+    private static final int CHARACTERS = 2;
+    private static final int EMBEDDED_START = 3;
+    private static final int EMBEDDED_END = 4;
+    private static final int METADATA = 5;
+
+    /**
+     * Address of the Rust content handler, only valid during the parse
+     */
+    private final long address;
+
+    public RustContentHandler(long address) {
+        this.address = address;
+    }
+
+    @Override
+    public void startElement(String uri, String localName, String qName, Attributes atts) throws SAXException {
+        final String[] attributes = new String[atts.getLength() * 2];
+        for (int i = 0; i < atts.getLength(); i++) {
+            attributes[2 * i] = name(atts.getLocalName(i), atts.getQName(i));
+            attributes[2 * i + 1] = atts.getValue(i);
+        }
+        send(START_ELEMENT, name(localName, qName), attributes);
+    }
+
+    @Override
+    public void endElement(String uri, String localName, String qName) throws SAXException {
+        send(END_ELEMENT, name(localName, qName), null);
+    }
+
+    @Override
+    public void characters(char[] ch, int start, int length) throws SAXException {
+        if (length > 0) {
+            send(CHARACTERS, new String(ch, start, length), null);
+        }
+    }
+
+    @Override
+    public void ignorableWhitespace(char[] ch, int start, int length) throws SAXException {
+        characters(ch, start, length);
+    }
+
+    public void embeddedStart(Metadata metadata) throws SAXException {
+        send(EMBEDDED_START, null, pairs(metadata));
+    }
+
+    public void embeddedEnd() throws SAXException {
+        send(EMBEDDED_END, null, null);
+    }
+
+    public void metadata(Metadata metadata) throws SAXException {
+        send(METADATA, null, pairs(metadata));
+    }
+
+    private void send(int kind, String name, String[] values) throws SAXException {
+        if (!handleEvent(address, kind, name, values)) {
+            throw new SAXException("Parsing stopped by the content handler");
+        }
+    }
+
+    private static String name(String localName, String qName) {
+        return localName == null || localName.isEmpty() ? qName : localName;
+    }
+
+    /**
+     * The metadata as flat name, value pairs
+     */
+    private static String[] pairs(Metadata metadata) {
+        final String[] names = metadata.names();
+        int size = 0;
+        for (String name : names) {
+            size += metadata.getValues(name).length;
+        }
+
+        final String[] pairs = new String[size * 2];
+        int i = 0;
+        for (String name : names) {
+            for (String value : metadata.getValues(name)) {
+                pairs[i++] = name;
+                pairs[i++] = value;
+            }
+        }
+        return pairs;
+    }
+
+    /**
+     * Implemented on the Rust side, registered before the parse. Returns false to stop parsing
+     */
+    private static native boolean handleEvent(long address, int kind, String name, String[] values);
+}
@@ -0,0 +1,37 @@
+package ai.yobix;
+
+import org.apache.tika.extractor.ParsingEmbeddedDocumentExtractor;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.parser.ParseContext;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+import java.io.IOException;
+import java.io.InputStream;
+
+/**
+ * EmbeddedDocumentExtractor that parses the embedded documents like tika and sends their
+ * start, metadata and end to the Rust content handler around their events.
+ */
+public class RustEmbeddedDocumentExtractor extends ParsingEmbeddedDocumentExtractor {
+
+    private final RustContentHandler rustHandler;
+
+    public RustEmbeddedDocumentExtractor(ParseContext context, RustContentHandler rustHandler) {
+        super(context);
+        this.rustHandler = rustHandler;
+    }
+
+    @Override
+    public void parseEmbedded(
+            InputStream stream,
+            ContentHandler handler,
+            Metadata metadata,
+            boolean outputHtml
+    ) throws SAXException, IOException {
+        rustHandler.embeddedStart(metadata);
+        super.parseEmbedded(stream, handler, metadata, outputHtml);
+        rustHandler.metadata(metadata);
+        rustHandler.embeddedEnd();
+    }
+}
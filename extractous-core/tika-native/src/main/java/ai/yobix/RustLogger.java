@@ -0,0 +1,131 @@
+package ai.yobix;
+
+import org.slf4j.Marker;
+import org.slf4j.event.Level;
+import org.slf4j.helpers.LegacyAbstractLogger;
+import org.slf4j.helpers.MessageFormatter;
+
+import java.io.PrintWriter;
+import java.io.StringWriter;
+
+/**
+ * slf4j Logger sending the log records of tika and its dependencies, such as PDFBox and POI, to
+ * the Rust {@code log} crate. The name of the logger is the target of the Rust records, so that
+ * the records can be filtered per logger. Nothing is logged until the Rust side starts an
+ * extraction with logging enabled.
+ */
+public class RustLogger extends LegacyAbstractLogger {
+
+    // The values of the Rust log::Level
+    private static final int ERROR = 1;
+    private static final int WARN = 2;
+    private static final int INFO = 3;
+    private static final int DEBUG = 4;
+    private static final int TRACE = 5;
+
+    /**
+     * Set once the native methods are registered by the Rust side
+     */
+    private static volatile boolean enabled = false;
+
+    /**
+     * Id of the Rust extraction running on this thread, inherited by the threads it starts such
+     * as the thread of the {@link ParsingReader}
+     */
+    private static final InheritableThreadLocal<Long> extraction = new InheritableThreadLocal<>() {
+        @Override
+        protected Long initialValue() {
+            return 0L;
+        }
+    };
+
+    public RustLogger(String name) {
+        this.name = name;
+    }
+
+    /**
+     * Called by the Rust side before every extraction, once the native methods are registered
+     *
+     * @param id the id of the extraction, sent with the log records of this thread
+     */
+    public static void setExtraction(long id) {
+        enabled = true;
+        extraction.set(id);
+    }
+
+    @Override
+    public boolean isTraceEnabled() {
+        return isEnabled(TRACE);
+    }
+
+    @Override
+    public boolean isDebugEnabled() {
+        return isEnabled(DEBUG);
+    }
+
+    @Override
+    public boolean isInfoEnabled() {
+        return isEnabled(INFO);
+    }
+
+    @Override
+    public boolean isWarnEnabled() {
+        return isEnabled(WARN);
+    }
+
+    @Override
+    public boolean isErrorEnabled() {
+        return isEnabled(ERROR);
+    }
+
+    @Override
+    protected String getFullyQualifiedCallerName() {
+        return null;
+    }
+
+    @Override
+    protected void handleNormalizedLoggingCall(
+            Level level,
+            Marker marker,
+            String messagePattern,
+            Object[] arguments,
+            Throwable throwable
+    ) {
+        if (!enabled) {
+            return;
+        }
+        String message = MessageFormatter.basicArrayFormat(messagePattern, arguments);
+        if (throwable != null) {
+            final StringWriter trace = new StringWriter();
+            throwable.printStackTrace(new PrintWriter(trace));
+            message = message + "\n" + trace;
+        }
+        log(extraction.get(), toRustLevel(level), name, message);
+    }
+
+    private boolean isEnabled(int level) {
+        return enabled && isEnabled(level, name);
+    }
+
+    private static int toRustLevel(Level level) {
+        switch (level) {
+            case ERROR:
+                return ERROR;
+            case WARN:
+                return WARN;
+            case INFO:
+                return INFO;
+            case DEBUG:
+                return DEBUG;
+            default:
+                return TRACE;
+        }
+    }
+
+    /**
+     * Implemented on the Rust side, registered before the first extraction with logging enabled
+     */
+    private static native boolean isEnabled(int level, String target);
+
+    private static native void log(long extraction, int level, String target, String message);
+}
@@ -0,0 +1,20 @@
+package ai.yobix;
+
+import org.slf4j.ILoggerFactory;
+import org.slf4j.Logger;
+
+import java.util.concurrent.ConcurrentHashMap;
+import java.util.concurrent.ConcurrentMap;
+
+/**
+ * ILoggerFactory creating the {@link RustLogger}s, one per logger name
+ */
+public class RustLoggerFactory implements ILoggerFactory {
+
+    private final ConcurrentMap<String, Logger> loggers = new ConcurrentHashMap<>();
+
+    @Override
+    public Logger getLogger(String name) {
+        return loggers.computeIfAbsent(name, RustLogger::new);
+    }
+}
@@ -0,0 +1,101 @@
+package ai.yobix;
+
+import org.apache.commons.io.IOUtils;
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.mime.MediaType;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.Parser;
+import org.apache.tika.sax.OfflineContentHandler;
+import org.apache.tika.utils.XMLReaderUtils;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+import java.io.ByteArrayInputStream;
+import java.io.IOException;
+import java.io.InputStream;
+import java.nio.charset.StandardCharsets;
+import java.util.ArrayList;
+import java.util.Collections;
+import java.util.HashSet;
+import java.util.List;
+import java.util.Set;
+
+/**
+ * A tika parser calling back a parser registered on the Rust extractor, so that the registered
+ * parsers win over the tika parsers for their types, including for embedded documents.
+ * The Rust parser returns an xhtml document that is replayed to the content handler.
+ */
+public class RustParser implements Parser {
+
+    /**
+     * Id of the Rust parser registry
+     */
+    private final long registryId;
+    /**
+     * Index of the parser in the Rust parser registry
+     */
+    private final int index;
+    private final Set<MediaType> supportedTypes;
+
+    public RustParser(long registryId, int index, String[] types) {
+        this.registryId = registryId;
+        this.index = index;
+        final Set<MediaType> mediaTypes = new HashSet<>();
+        for (String type : types) {
+            final MediaType mediaType = MediaType.parse(type);
+            if (mediaType != null) {
+                mediaTypes.add(mediaType);
+            }
+        }
+        this.supportedTypes = Collections.unmodifiableSet(mediaTypes);
+    }
+
+    @Override
+    public Set<MediaType> getSupportedTypes(ParseContext context) {
+        return supportedTypes;
+    }
+
+    @Override
+    public void parse(InputStream stream, ContentHandler handler, Metadata metadata, ParseContext context)
+            throws IOException, SAXException, TikaException {
+        final byte[] data = IOUtils.toByteArray(stream);
+
+        // The metadata is passed as flat name, value pairs
+        final List<String> pairs = new ArrayList<>();
+        for (String name : metadata.names()) {
+            for (String value : metadata.getValues(name)) {
+                pairs.add(name);
+                pairs.add(value);
+            }
+        }
+
+        // The result is the error message or null, the xhtml and the metadata pairs
+        final String[] result = parseNative(registryId, index, data, pairs.toArray(new String[0]));
+        if (result == null || result.length < 2) {
+            throw new TikaException("Rust parser " + index + " returned no result");
+        }
+        if (result[0] != null) {
+            throw new TikaException(result[0]);
+        }
+
+        final Metadata parsed = new Metadata();
+        for (int i = 2; i + 1 < result.length; i += 2) {
+            parsed.add(result[i], result[i + 1]);
+        }
+        for (String name : parsed.names()) {
+            metadata.remove(name);
+            for (String value : parsed.getValues(name)) {
+                metadata.add(name, value);
+            }
+        }
+
+        final InputStream xhtml = new ByteArrayInputStream(result[1].getBytes(StandardCharsets.UTF_8));
+        XMLReaderUtils.parseSAX(xhtml, new OfflineContentHandler(handler), context);
+    }
+
+    /**
+     * Implemented on the Rust side, registered when the first Rust parser is created
+     */
+    private static native String[] parseNative(long registryId, int index, byte[] data, String[] metadata);
+}
@@ -0,0 +1,75 @@
+package ai.yobix;
+
+import org.apache.tika.parser.ocr.TesseractOCRConfig;
+
+/**
+ * TesseractOCRConfig with the paths of the tesseract and ImageMagick installations, and the
+ * OCR engine mode, which tika does not configure.
+ * Tika only reads these paths when the TesseractOCRParser is created, so when a path is set
+ * the {@link ParserFactory} replaces the default TesseractOCRParser by a dedicated one.
+ */
+public class TesseractPathsConfig extends TesseractOCRConfig {
+
+    private String tesseractPath = null;
+    private String tessdataPath = null;
+    private String imageMagickPath = null;
+    private int ocrEngineMode = -1;
+
+    /**
+     * @param tesseractPath the directory containing the tesseract executable
+     */
+    public void setTesseractPath(String tesseractPath) {
+        this.tesseractPath = tesseractPath;
+    }
+
+    /**
+     * @param tessdataPath the directory containing the tessdata language files
+     */
+    public void setTessdataPath(String tessdataPath) {
+        this.tessdataPath = tessdataPath;
+    }
+
+    /**
+     * @param imageMagickPath the directory containing the ImageMagick executables
+     */
+    public void setImageMagickPath(String imageMagickPath) {
+        this.imageMagickPath = imageMagickPath;
+    }
+
+    /**
+     * The engine mode is an init only tesseract variable, so it is passed with the other
+     * tesseract config as {@code -c tessedit_ocr_engine_mode=mode}.
+     *
+     * @param ocrEngineMode the tesseract OCR engine mode, from 0 to 3
+     */
+    public void setOcrEngineMode(int ocrEngineMode) {
+        if (ocrEngineMode < 0 || ocrEngineMode > 3) {
+            throw new IllegalArgumentException("OCR engine mode must be between 0 and 3");
+        }
+        this.ocrEngineMode = ocrEngineMode;
+        addOtherTesseractConfig("tessedit_ocr_engine_mode", Integer.toString(ocrEngineMode));
+    }
+
+    /**
+     * @return the tesseract OCR engine mode, -1 if it is not set
+     */
+    public int getOcrEngineMode() {
+        return ocrEngineMode;
+    }
+
+    String getTesseractPath() {
+        return tesseractPath;
+    }
+
+    String getTessdataPath() {
+        return tessdataPath;
+    }
+
+    String getImageMagickPath() {
+        return imageMagickPath;
+    }
+
+    boolean hasPaths() {
+        return tesseractPath != null || tessdataPath != null || imageMagickPath != null;
+    }
+}
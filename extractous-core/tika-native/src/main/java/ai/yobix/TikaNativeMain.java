@@ -1,20 +1,24 @@
 package ai.yobix;
 
 import org.apache.commons.io.input.ReaderInputStream;
+import org.apache.pdfbox.pdmodel.PDDocument;
 import org.apache.tika.Tika;
 import org.apache.tika.config.TikaConfig;
 import org.apache.tika.exception.TikaException;
 import org.apache.tika.exception.WriteLimitReachedException;
+import org.apache.tika.extractor.EmbeddedDocumentExtractor;
 import org.apache.tika.io.TemporaryResources;
 import org.apache.tika.io.TikaInputStream;
 import org.apache.tika.metadata.Metadata;
-import org.apache.tika.parser.AutoDetectParser;
 import org.apache.tika.parser.ParseContext;
 import org.apache.tika.parser.Parser;
+import org.apache.tika.parser.RecursiveParserWrapper;
 import org.apache.tika.parser.microsoft.OfficeParserConfig;
 import org.apache.tika.parser.ocr.TesseractOCRConfig;
 import org.apache.tika.parser.pdf.PDFParserConfig;
+import org.apache.tika.sax.BasicContentHandlerFactory;
 import org.apache.tika.sax.BodyContentHandler;
+import org.apache.tika.sax.RecursiveParserWrapperHandler;
 import org.apache.tika.sax.ToXMLContentHandler;
 import org.apache.tika.sax.WriteOutContentHandler;
 import org.graalvm.nativeimage.IsolateThread;
@@ -25,6 +29,7 @@ import org.graalvm.nativeimage.c.type.CTypeConversion;
 import org.xml.sax.ContentHandler;
 import org.xml.sax.SAXException;
 
+import java.io.File;
 import java.io.IOException;
 import java.io.InputStream;
 import java.io.Reader;
@@ -61,6 +66,25 @@ public class TikaNativeMain {
         }
     }
 
+    /**
+     * Detects the mime type of the given bytes
+     *
+     * @param data an array of bytes
+     * @param metadata initial metadata such as the resource name or content type hints
+     * @return StringResult
+     */
+    public static StringResult detectBytes(ByteBuffer data, Metadata metadata) {
+        final ByteBufferInputStream inStream = new ByteBufferInputStream(data);
+
+        try (final InputStream stream = TikaInputStream.get(inStream, new TemporaryResources(), metadata)) {
+            final String result = tika.detect(stream, metadata);
+            return new StringResult(result, metadata);
+
+        } catch (java.io.IOException e) {
+            return new StringResult((byte) 1, e.getMessage());
+        }
+    }
+
     /**
      * Parses the given file and returns its content as String.
      * To avoid unpredictable excess memory use, the returned string contains only up to maxLength
@@ -76,6 +100,10 @@ public class TikaNativeMain {
             PDFParserConfig pdfConfig,
             OfficeParserConfig officeConfig,
             TesseractOCRConfig tesseractConfig,
+            PageSelection pageSelection,
+            OutputOptions outputOptions,
+            RustParser[] rustParsers,
+            ProgressReporter progress,
             boolean asXML
             // maybe replace with a single config class
     ) {
@@ -85,7 +113,8 @@ public class TikaNativeMain {
             final InputStream stream = TikaInputStream.get(path, metadata);
 
             String result = parseToStringWithConfig(
-                    stream, metadata, maxLength, pdfConfig, officeConfig, tesseractConfig, asXML);
+                    stream, metadata, maxLength, pdfConfig, officeConfig, tesseractConfig, pageSelection,
+                    outputOptions, rustParsers, progress, asXML);
             // No need to close the stream because parseToString does so
             return new StringResult(result, metadata);
         } catch (java.io.IOException e) {
@@ -107,6 +136,10 @@ public class TikaNativeMain {
             PDFParserConfig pdfConfig,
             OfficeParserConfig officeConfig,
             TesseractOCRConfig tesseractConfig,
+            PageSelection pageSelection,
+            OutputOptions outputOptions,
+            RustParser[] rustParsers,
+            ProgressReporter progress,
             boolean asXML
     ) {
         try {
@@ -115,7 +148,8 @@ public class TikaNativeMain {
             final TikaInputStream stream = TikaInputStream.get(url, metadata);
 
             String result = parseToStringWithConfig(
-                    stream, metadata, maxLength, pdfConfig, officeConfig, tesseractConfig, asXML);
+                    stream, metadata, maxLength, pdfConfig, officeConfig, tesseractConfig, pageSelection,
+                    outputOptions, rustParsers, progress, asXML);
             // No need to close the stream because parseToString does so
             return new StringResult(result, metadata);
 
@@ -134,23 +168,29 @@ public class TikaNativeMain {
      * Parses the given array of bytes and return its content as String.
      *
      * @param data an array of bytes
+     * @param metadata initial metadata such as the resource name or content type hints
      * @return StringResult
      */
     public static StringResult parseBytesToString(
             ByteBuffer data,
+            Metadata metadata,
             int maxLength,
             PDFParserConfig pdfConfig,
             OfficeParserConfig officeConfig,
             TesseractOCRConfig tesseractConfig,
+            PageSelection pageSelection,
+            OutputOptions outputOptions,
+            RustParser[] rustParsers,
+            ProgressReporter progress,
             boolean asXML
     ) {
-        final Metadata metadata = new Metadata();
         final ByteBufferInputStream inStream = new ByteBufferInputStream(data);
         final TikaInputStream stream = TikaInputStream.get(inStream, new TemporaryResources(), metadata);
 
         try {
             String result = parseToStringWithConfig(
-                    stream, metadata, maxLength, pdfConfig, officeConfig, tesseractConfig, asXML);
+                    stream, metadata, maxLength, pdfConfig, officeConfig, tesseractConfig, pageSelection,
+                    outputOptions, rustParsers, progress, asXML);
             // No need to close the stream because parseToString does so
             return new StringResult(result, metadata);
         } catch (java.io.IOException e) {
@@ -167,6 +207,10 @@ public class TikaNativeMain {
             PDFParserConfig pdfConfig,
             OfficeParserConfig officeConfig,
             TesseractOCRConfig tesseractConfig,
+            PageSelection pageSelection,
+            OutputOptions outputOptions,
+            RustParser[] rustParsers,
+            ProgressReporter progress,
             boolean asXML
     ) throws IOException, TikaException {
         ContentHandler handler;
@@ -178,24 +222,30 @@ public class TikaNativeMain {
             handler = new WriteOutContentHandler(maxLength);
             handlerForParser = new BodyContentHandler(handler);
         }
+        handlerForParser = progress.decorate(
+                pageSelection.decorate(outputOptions.decorate(handlerForParser)), metadata);
 
         try {
             final TikaConfig config = TikaConfig.getDefaultConfig();
             final ParseContext parsecontext = new ParseContext();
-            final Parser parser = new AutoDetectParser(config);
+            final Parser parser = ParserFactory.newAutoDetectParser(config, tesseractConfig, rustParsers, progress);
 
             parsecontext.set(Parser.class, parser);
             parsecontext.set(PDFParserConfig.class, pdfConfig);
             parsecontext.set(OfficeParserConfig.class, officeConfig);
             parsecontext.set(TesseractOCRConfig.class, tesseractConfig);
+            progress.decorate(parsecontext);
 
+            progress.parseStarted();
             parser.parse(stream, handlerForParser, metadata, parsecontext);
         } catch (SAXException e) {
-            if (!WriteLimitReachedException.isWriteLimitReached(e)) {
+            if (!WriteLimitReachedException.isWriteLimitReached(e)
+                    && !PageLimitReachedException.isPageLimitReached(e)) {
                 // This should never happen with BodyContentHandler...
                 throw new TikaException("Unexpected SAX processing failure", e);
             }
         } finally {
+            progress.parseFinished(metadata);
             stream.close();
         }
         return handler.toString();
@@ -215,6 +265,10 @@ public class TikaNativeMain {
             PDFParserConfig pdfConfig,
             OfficeParserConfig officeConfig,
             TesseractOCRConfig tesseractConfig,
+            PageSelection pageSelection,
+            OutputOptions outputOptions,
+            RustParser[] rustParsers,
+            ProgressReporter progress,
             boolean asXML
     ) {
         try {
@@ -230,7 +284,8 @@ public class TikaNativeMain {
             final Metadata metadata = new Metadata();
             final TikaInputStream stream = TikaInputStream.get(path, metadata);
 
-            return parse(stream, metadata, charsetName, pdfConfig, officeConfig, tesseractConfig, asXML);
+            return parse(stream, metadata, charsetName, pdfConfig, officeConfig, tesseractConfig, pageSelection,
+                    outputOptions, rustParsers, progress, asXML);
 
         } catch (java.io.IOException e) {
             return new ReaderResult((byte) 1, "Could not open file: " + e.getMessage());
@@ -250,6 +305,10 @@ public class TikaNativeMain {
             PDFParserConfig pdfConfig,
             OfficeParserConfig officeConfig,
             TesseractOCRConfig tesseractConfig,
+            PageSelection pageSelection,
+            OutputOptions outputOptions,
+            RustParser[] rustParsers,
+            ProgressReporter progress,
             boolean asXML
     ) {
         try {
@@ -257,7 +316,8 @@ public class TikaNativeMain {
             final Metadata metadata = new Metadata();
             final TikaInputStream stream = TikaInputStream.get(url, metadata);
 
-            return parse(stream, metadata, charsetName, pdfConfig, officeConfig, tesseractConfig, asXML);
+            return parse(stream, metadata, charsetName, pdfConfig, officeConfig, tesseractConfig, pageSelection,
+                    outputOptions, rustParsers, progress, asXML);
 
         } catch (MalformedURLException e) {
             return new ReaderResult((byte) 2, "Malformed URL error occurred " + e.getMessage());
@@ -273,23 +333,275 @@ public class TikaNativeMain {
      * to read chunks and must be closed when reading is finished
      *
      * @param data an array of bytes
+     * @param metadata initial metadata such as the resource name or content type hints
      * @return ReaderResult
      */
     public static ReaderResult parseBytes(
             ByteBuffer data,
+            Metadata metadata,
             String charsetName,
             PDFParserConfig pdfConfig,
             OfficeParserConfig officeConfig,
             TesseractOCRConfig tesseractConfig,
+            PageSelection pageSelection,
+            OutputOptions outputOptions,
+            RustParser[] rustParsers,
+            ProgressReporter progress,
             boolean asXML
     ) {
+        final ByteBufferInputStream inStream = new ByteBufferInputStream(data);
+        final TikaInputStream stream = TikaInputStream.get(inStream, new TemporaryResources(), metadata);
 
+        return parse(stream, metadata, charsetName, pdfConfig, officeConfig, tesseractConfig, pageSelection,
+                outputOptions, rustParsers, progress, asXML);
+    }
 
-        final Metadata metadata = new Metadata();
+    /**
+     * Parses the given array of bytes and all the documents embedded in it, such as email
+     * attachments or the files of an archive. The content of every document is stored in
+     * its metadata under the X-TIKA:content key.
+     *
+     * @param data an array of bytes
+     * @param metadata initial metadata such as the resource name or content type hints
+     * @param handlerType the format of the content: text, html, xml or ignore
+     * @param maxLength maximum length of the content of each document, -1 for no limit
+     * @return MetadataListResult with the container document first
+     */
+    public static MetadataListResult parseBytesRecursive(
+            ByteBuffer data,
+            Metadata metadata,
+            String handlerType,
+            int maxLength,
+            PDFParserConfig pdfConfig,
+            OfficeParserConfig officeConfig,
+            TesseractOCRConfig tesseractConfig,
+            RustParser[] rustParsers
+    ) {
+        final ByteBufferInputStream inStream = new ByteBufferInputStream(data);
+
+        try (final TikaInputStream stream = TikaInputStream.get(inStream, new TemporaryResources(), metadata)) {
+            final TikaConfig config = TikaConfig.getDefaultConfig();
+            final ParseContext parsecontext = new ParseContext();
+            final Parser parser = new RecursiveParserWrapper(
+                    ParserFactory.newAutoDetectParser(config, tesseractConfig, rustParsers));
+
+            parsecontext.set(PDFParserConfig.class, pdfConfig);
+            parsecontext.set(OfficeParserConfig.class, officeConfig);
+            parsecontext.set(TesseractOCRConfig.class, tesseractConfig);
+
+            final BasicContentHandlerFactory.HANDLER_TYPE type = BasicContentHandlerFactory.parseHandlerType(
+                    handlerType, BasicContentHandlerFactory.HANDLER_TYPE.XML);
+            final RecursiveParserWrapperHandler handler = new RecursiveParserWrapperHandler(
+                    new BasicContentHandlerFactory(type, maxLength));
+
+            try {
+                parser.parse(stream, handler, metadata, parsecontext);
+            } catch (SAXException e) {
+                if (!WriteLimitReachedException.isWriteLimitReached(e)) {
+                    throw new TikaException("Unexpected SAX processing failure", e);
+                }
+            }
+            return new MetadataListResult(handler.getMetadataList());
+
+        } catch (java.io.IOException e) {
+            return new MetadataListResult((byte) 1, "IO error occurred: " + e.getMessage());
+        } catch (TikaException e) {
+            return new MetadataListResult((byte) 2, "Parse error occurred : " + e.getMessage());
+        }
+    }
+
+    /**
+     * Parses the given array of bytes and collects the raw bytes of the documents embedded
+     * in it, such as email attachments, images of office documents or the files of an archive.
+     *
+     * @param data an array of bytes
+     * @param metadata initial metadata such as the resource name or content type hints
+     * @param maxLength maximum length of the text content of the container document
+     * @return UnpackResult
+     */
+    public static UnpackResult unpackBytes(
+            ByteBuffer data,
+            Metadata metadata,
+            int maxLength,
+            PDFParserConfig pdfConfig,
+            OfficeParserConfig officeConfig,
+            TesseractOCRConfig tesseractConfig,
+            RustParser[] rustParsers
+    ) {
+        final ByteBufferInputStream inStream = new ByteBufferInputStream(data);
+
+        try (final TikaInputStream stream = TikaInputStream.get(inStream, new TemporaryResources(), metadata)) {
+            final TikaConfig config = TikaConfig.getDefaultConfig();
+            final ParseContext parsecontext = new ParseContext();
+            final Parser parser = ParserFactory.newAutoDetectParser(config, tesseractConfig, rustParsers);
+            final EmbeddedDocumentCollector collector = new EmbeddedDocumentCollector();
+
+            parsecontext.set(Parser.class, parser);
+            parsecontext.set(EmbeddedDocumentExtractor.class, collector);
+            parsecontext.set(PDFParserConfig.class, pdfConfig);
+            parsecontext.set(OfficeParserConfig.class, officeConfig);
+            parsecontext.set(TesseractOCRConfig.class, tesseractConfig);
+
+            final WriteOutContentHandler handler = new WriteOutContentHandler(maxLength);
+            try {
+                parser.parse(stream, new BodyContentHandler(handler), metadata, parsecontext);
+            } catch (SAXException e) {
+                if (!WriteLimitReachedException.isWriteLimitReached(e)) {
+                    throw new TikaException("Unexpected SAX processing failure", e);
+                }
+            }
+            return new UnpackResult(handler.toString(), metadata, collector);
+
+        } catch (java.io.IOException e) {
+            return new UnpackResult((byte) 1, "IO error occurred: " + e.getMessage());
+        } catch (TikaException e) {
+            return new UnpackResult((byte) 2, "Parse error occurred : " + e.getMessage());
+        }
+    }
+
+    /**
+     * Parses the given file and sends its SAX events to a Rust content handler
+     *
+     * @param filePath the path of the file
+     * @param handlerAddress the address of the Rust content handler, only valid during the call
+     * @return StringResult with an empty content
+     */
+    public static StringResult parseFileToEvents(
+            String filePath,
+            PDFParserConfig pdfConfig,
+            OfficeParserConfig officeConfig,
+            TesseractOCRConfig tesseractConfig,
+            PageSelection pageSelection,
+            OutputOptions outputOptions,
+            RustParser[] rustParsers,
+            ProgressReporter progress,
+            long handlerAddress
+    ) {
+        try {
+            final Path path = Paths.get(filePath);
+            final Metadata metadata = new Metadata();
+            final InputStream stream = TikaInputStream.get(path, metadata);
+
+            parseToEvents(stream, metadata, pdfConfig, officeConfig, tesseractConfig, pageSelection, outputOptions,
+                    rustParsers, progress, handlerAddress);
+            return new StringResult("", metadata);
+        } catch (java.io.IOException e) {
+            return new StringResult((byte) 1, "Could not open file: " + e.getMessage());
+        } catch (TikaException e) {
+            return new StringResult((byte) 2, "Parse error occurred : " + e.getMessage());
+        }
+    }
+
+    /**
+     * Parses the given Url and sends its SAX events to a Rust content handler
+     *
+     * @param urlString the url to be parsed
+     * @param handlerAddress the address of the Rust content handler, only valid during the call
+     * @return StringResult with an empty content
+     */
+    public static StringResult parseUrlToEvents(
+            String urlString,
+            PDFParserConfig pdfConfig,
+            OfficeParserConfig officeConfig,
+            TesseractOCRConfig tesseractConfig,
+            PageSelection pageSelection,
+            OutputOptions outputOptions,
+            RustParser[] rustParsers,
+            ProgressReporter progress,
+            long handlerAddress
+    ) {
+        try {
+            final URL url = new URI(urlString).toURL();
+            final Metadata metadata = new Metadata();
+            final TikaInputStream stream = TikaInputStream.get(url, metadata);
+
+            parseToEvents(stream, metadata, pdfConfig, officeConfig, tesseractConfig, pageSelection, outputOptions,
+                    rustParsers, progress, handlerAddress);
+            return new StringResult("", metadata);
+        } catch (MalformedURLException e) {
+            return new StringResult((byte) 2, "Malformed URL error occurred " + e.getMessage());
+        } catch (URISyntaxException e) {
+            return new StringResult((byte) 2, "Malformed URI error occurred: " + e.getMessage());
+        } catch (java.io.IOException e) {
+            return new StringResult((byte) 1, "IO error occurred: " + e.getMessage());
+        } catch (TikaException e) {
+            return new StringResult((byte) 2, "Parse error occurred : " + e.getMessage());
+        }
+    }
+
+    /**
+     * Parses the given array of bytes and sends its SAX events to a Rust content handler
+     *
+     * @param data an array of bytes
+     * @param metadata initial metadata such as the resource name or content type hints
+     * @param handlerAddress the address of the Rust content handler, only valid during the call
+     * @return StringResult with an empty content
+     */
+    public static StringResult parseBytesToEvents(
+            ByteBuffer data,
+            Metadata metadata,
+            PDFParserConfig pdfConfig,
+            OfficeParserConfig officeConfig,
+            TesseractOCRConfig tesseractConfig,
+            PageSelection pageSelection,
+            OutputOptions outputOptions,
+            RustParser[] rustParsers,
+            ProgressReporter progress,
+            long handlerAddress
+    ) {
         final ByteBufferInputStream inStream = new ByteBufferInputStream(data);
         final TikaInputStream stream = TikaInputStream.get(inStream, new TemporaryResources(), metadata);
 
-        return parse(stream, metadata, charsetName, pdfConfig, officeConfig, tesseractConfig, asXML);
+        try {
+            parseToEvents(stream, metadata, pdfConfig, officeConfig, tesseractConfig, pageSelection, outputOptions,
+                    rustParsers, progress, handlerAddress);
+            return new StringResult("", metadata);
+        } catch (java.io.IOException e) {
+            return new StringResult((byte) 1, "IO error occurred: " + e.getMessage());
+        } catch (TikaException e) {
+            return new StringResult((byte) 2, "Parse error occurred : " + e.getMessage());
+        }
+    }
+
+    private static void parseToEvents(
+            InputStream stream,
+            Metadata metadata,
+            PDFParserConfig pdfConfig,
+            OfficeParserConfig officeConfig,
+            TesseractOCRConfig tesseractConfig,
+            PageSelection pageSelection,
+            OutputOptions outputOptions,
+            RustParser[] rustParsers,
+            ProgressReporter progress,
+            long handlerAddress
+    ) throws IOException, TikaException {
+        final RustContentHandler handler = new RustContentHandler(handlerAddress);
+
+        try {
+            final TikaConfig config = TikaConfig.getDefaultConfig();
+            final ParseContext parsecontext = new ParseContext();
+            final Parser parser = ParserFactory.newAutoDetectParser(config, tesseractConfig, rustParsers, progress);
+
+            parsecontext.set(Parser.class, parser);
+            parsecontext.set(EmbeddedDocumentExtractor.class, new RustEmbeddedDocumentExtractor(parsecontext, handler));
+            parsecontext.set(PDFParserConfig.class, pdfConfig);
+            parsecontext.set(OfficeParserConfig.class, officeConfig);
+            parsecontext.set(TesseractOCRConfig.class, tesseractConfig);
+            progress.decorate(parsecontext);
+
+            progress.parseStarted();
+            final ContentHandler handlerForParser = progress.decorate(
+                    pageSelection.decorate(outputOptions.decorate(handler)), metadata);
+            parser.parse(stream, handlerForParser, metadata, parsecontext);
+        } catch (SAXException e) {
+            if (!PageLimitReachedException.isPageLimitReached(e)) {
+                // The Rust side reports the error of its content handler
+                throw new TikaException("Content handler failure", e);
+            }
+        } finally {
+            progress.parseFinished(metadata);
+            stream.close();
+        }
     }
 
     private static ReaderResult parse(
@@ -299,22 +611,29 @@ public class TikaNativeMain {
             PDFParserConfig pdfConfig,
             OfficeParserConfig officeConfig,
             TesseractOCRConfig tesseractConfig,
+            PageSelection pageSelection,
+            OutputOptions outputOptions,
+            RustParser[] rustParsers,
+            ProgressReporter progress,
             boolean asXML
     ) {
         try {
 
             final TikaConfig config = TikaConfig.getDefaultConfig();
             final ParseContext parsecontext = new ParseContext();
-            final Parser parser = new AutoDetectParser(config);
+            final Parser parser = ParserFactory.newAutoDetectParser(config, tesseractConfig, rustParsers, progress);
             final Charset charset = Charset.forName(charsetName, StandardCharsets.UTF_8);
 
             parsecontext.set(Parser.class, parser);
             parsecontext.set(PDFParserConfig.class, pdfConfig);
             parsecontext.set(OfficeParserConfig.class, officeConfig);
             parsecontext.set(TesseractOCRConfig.class, tesseractConfig);
+            progress.decorate(parsecontext);
 
             //final Reader reader = new org.apache.tika.parser.ParsingReader(parser, inputStream, metadata, parsecontext);
-            final Reader reader = new ParsingReader(parser, inputStream, metadata, parsecontext, asXML, charset.name());
+            final Reader reader = new ParsingReader(
+                    parser, inputStream, metadata, parsecontext, pageSelection, outputOptions, progress, asXML,
+                    charset.name());
 
             // Convert Reader which works with chars to ReaderInputStream which works with bytes
             ReaderInputStream readerInputStream = ReaderInputStream.builder()
@@ -326,10 +645,68 @@ public class TikaNativeMain {
 
         } catch (java.io.IOException e) {
             return new ReaderResult((byte) 1, "IO error occurred: " + e.getMessage());
+        } catch (TikaException e) {
+            return new ReaderResult((byte) 2, "Parse error occurred : " + e.getMessage());
         }
 
     }
 
+    /**
+     * Reads the outline of the given pdf file, see {@link PdfOutline}
+     *
+     * @param filePath the path of the pdf file
+     * @return StringResult with a line per bookmark
+     */
+    public static StringResult parseFileToPdfOutline(String filePath) {
+        try (PDDocument document = PDDocument.load(new File(filePath))) {
+            return new StringResult(PdfOutline.read(document), new Metadata());
+        } catch (java.io.IOException e) {
+            return new StringResult((byte) 1, "IO error occurred: " + e.getMessage());
+        }
+    }
+
+    /**
+     * Reads the outline of the given pdf bytes, see {@link PdfOutline}
+     *
+     * @param data the bytes of a pdf document
+     * @return StringResult with a line per bookmark
+     */
+    public static StringResult parseBytesToPdfOutline(ByteBuffer data) {
+        try (PDDocument document = PDDocument.load(new ByteBufferInputStream(data))) {
+            return new StringResult(PdfOutline.read(document), new Metadata());
+        } catch (java.io.IOException e) {
+            return new StringResult((byte) 1, "IO error occurred: " + e.getMessage());
+        }
+    }
+
+    /**
+     * Reads the annotations of the given pdf file, see {@link PdfAnnotations}
+     *
+     * @param filePath the path of the pdf file
+     * @return StringResult with a line per annotation
+     */
+    public static StringResult parseFileToPdfAnnotations(String filePath) {
+        try (PDDocument document = PDDocument.load(new File(filePath))) {
+            return new StringResult(PdfAnnotations.read(document), new Metadata());
+        } catch (java.io.IOException e) {
+            return new StringResult((byte) 1, "IO error occurred: " + e.getMessage());
+        }
+    }
+
+    /**
+     * Reads the annotations of the given pdf bytes, see {@link PdfAnnotations}
+     *
+     * @param data the bytes of a pdf document
+     * @return StringResult with a line per annotation
+     */
+    public static StringResult parseBytesToPdfAnnotations(ByteBuffer data) {
+        try (PDDocument document = PDDocument.load(new ByteBufferInputStream(data))) {
+            return new StringResult(PdfAnnotations.read(document), new Metadata());
+        } catch (java.io.IOException e) {
+            return new StringResult((byte) 1, "IO error occurred: " + e.getMessage());
+        }
+    }
+
     /**
      * This is the main entry point of the native image build. @CEntryPoint is used
      * because we do not want to build an executable with a main method. The gradle nativeImagePlugin
@@ -0,0 +1,98 @@
+package ai.yobix;
+
+import org.apache.tika.metadata.Metadata;
+
+public class UnpackResult {
+
+    private final String content;
+    private final Metadata metadata;
+    private final String[] names;
+    private final byte[][] contents;
+    private final Metadata[] embeddedMetadata;
+    private final byte status;
+    private final String errorMessage;
+
+    public UnpackResult(String content, Metadata metadata, EmbeddedDocumentCollector collector) {
+        this.content = content;
+        this.metadata = metadata;
+        this.names = collector.getNames().toArray(new String[0]);
+        this.contents = collector.getContents().toArray(new byte[0][]);
+        this.embeddedMetadata = collector.getMetadataList().toArray(new Metadata[0]);
+        this.status = 0;
+        this.errorMessage = null;
+    }
+
+    public UnpackResult(byte status, String errorMessage) {
+        this.content = null;
+        this.metadata = null;
+        this.names = null;
+        this.contents = null;
+        this.embeddedMetadata = null;
+        this.status = status;
+        this.errorMessage = errorMessage;
+    }
+
+    /**
+     * Returns the text content of the container document or null if there is an error
+     * @return String content
+     */
+    public String getContent() {
+        return content;
+    }
+
+    /**
+     * Returns the tika metadata of the container document or null if there is an error
+     * @return tika metadata
+     */
+    public Metadata getMetadata() {
+        return metadata;
+    }
+
+    /**
+     * Returns the unique file names of the embedded documents
+     * @return array of names, in the same order as the contents
+     */
+    public String[] getNames() {
+        return names;
+    }
+
+    /**
+     * Returns the raw bytes of the embedded documents
+     * @return array of byte arrays, in the same order as the names
+     */
+    public byte[][] getContents() {
+        return contents;
+    }
+
+    /**
+     * Returns the tika metadata of the embedded documents
+     * @return array of tika metadata, in the same order as the names
+     */
+    public Metadata[] getEmbeddedMetadata() {
+        return embeddedMetadata;
+    }
+
+    public boolean isError() {
+        return status != 0;
+    }
+
+    /**
+     * Returns the status of the call
+     * @return
+     * 0: OK
+     * 1: IOException
+     * 2: TikaException
+     */
+    public byte getStatus() {
+        return status;
+    }
+
+    /**
+     * Returns the error message in case of error
+     * @return  String representing the error message or
+     * null if there is no error
+     */
+    public String getErrorMessage() {
+        return errorMessage;
+    }
+}
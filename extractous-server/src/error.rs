@@ -0,0 +1,46 @@
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+
+/// Errors returned by the request handlers. Converted to a plain text response with the
+/// same status codes tika-server uses
+#[derive(thiserror::Error, Debug)]
+pub enum ServerError {
+    #[error("{0}")]
+    BadRequest(String),
+    #[error("{0}")]
+    NotFound(String),
+    #[error("The server is busy, no extraction slot became free in time")]
+    Busy,
+    #[error(transparent)]
+    Extract(#[from] extractous::Error),
+    #[error("Failed to write the zip archive: {0}")]
+    Zip(#[from] zip::result::ZipError),
+    #[error("Extraction task failed: {0}")]
+    Task(#[from] tokio::task::JoinError),
+}
+
+pub type ServerResult<T> = Result<T, ServerError>;
+
+impl ServerError {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ServerError::BadRequest(_)
+            | ServerError::Extract(extractous::Error::ConfigError(_)) => StatusCode::BAD_REQUEST,
+            ServerError::NotFound(_) => StatusCode::NOT_FOUND,
+            ServerError::Busy => StatusCode::SERVICE_UNAVAILABLE,
+            // Like tika-server, documents that can not be parsed are unprocessable entities
+            ServerError::Extract(extractous::Error::ParseError(_)) => {
+                StatusCode::UNPROCESSABLE_ENTITY
+            }
+            ServerError::Extract(_) | ServerError::Zip(_) | ServerError::Task(_) => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        }
+    }
+}
+
+impl IntoResponse for ServerError {
+    fn into_response(self) -> Response {
+        (self.status(), format!("{}\n", self)).into_response()
+    }
+}
@@ -0,0 +1,391 @@
+use std::str::FromStr;
+
+use axum::http::header::{ACCEPT, CONTENT_DISPOSITION, CONTENT_TYPE};
+use axum::http::HeaderMap;
+use extractous::{Extractor, Metadata, PdfOcrStrategy, PdfParserConfig, TesseractOcrConfig};
+
+use crate::error::{ServerError, ServerResult};
+
+/// Prefix of the headers overriding the `PdfParserConfig`, e.g. `X-Tika-PDFOcrStrategy`
+const PDF_HEADER_PREFIX: &str = "x-tika-pdf";
+/// Prefix of the headers overriding the `TesseractOcrConfig`, e.g. `X-Tika-OCRLanguage`
+const OCR_HEADER_PREFIX: &str = "x-tika-ocr";
+/// tika-server header limiting the length of the extracted text
+const WRITE_LIMIT_HEADER: &str = "writelimit";
+/// Header with the file name of the document, used as a detection hint
+const FILE_NAME_HEADER: &str = "file-name";
+
+/// The per request extraction configuration, built from the tika-server request headers
+#[derive(Debug, Clone)]
+pub struct RequestConfig {
+    pub pdf_config: PdfParserConfig,
+    pub ocr_config: TesseractOcrConfig,
+    pub max_length: i32,
+    /// The file name and content type of the document, used to detect its type
+    pub hints: Metadata,
+}
+
+impl RequestConfig {
+    /// Builds the configuration from the `X-Tika-PDF*`, `X-Tika-OCR*` and `writeLimit` headers.
+    /// Header names are matched case-insensitively, unknown `X-Tika-PDF*` and `X-Tika-OCR*`
+    /// headers are rejected
+    pub fn from_headers(headers: &HeaderMap, default_max_length: i32) -> ServerResult<Self> {
+        let mut pdf_config = PdfParserConfig::new();
+        let mut ocr_config = TesseractOcrConfig::new();
+        let mut max_length = default_max_length;
+
+        for (name, value) in headers.iter() {
+            // Header names are always lowercase
+            let name = name.as_str();
+            let is_config_header = name.starts_with(PDF_HEADER_PREFIX)
+                || name.starts_with(OCR_HEADER_PREFIX)
+                || name == WRITE_LIMIT_HEADER;
+            if !is_config_header {
+                continue;
+            }
+            let value = value
+                .to_str()
+                .map_err(|_| invalid_header(name, "the value is not valid ascii"))?
+                .trim();
+
+            if let Some(property) = name.strip_prefix(PDF_HEADER_PREFIX) {
+                pdf_config = apply_pdf_header(pdf_config, property, value)
+                    .map_err(|reason| invalid_header(name, &reason))?;
+            } else if let Some(property) = name.strip_prefix(OCR_HEADER_PREFIX) {
+                ocr_config = apply_ocr_header(ocr_config, property, value)
+                    .map_err(|reason| invalid_header(name, &reason))?;
+            } else {
+                max_length = parse_int(value).map_err(|reason| invalid_header(name, &reason))?;
+            }
+        }
+
+        // Reject invalid tesseract values before the extraction starts
+        ocr_config
+            .validate()
+            .map_err(|e| ServerError::BadRequest(e.to_string()))?;
+
+        Ok(Self {
+            pdf_config,
+            ocr_config,
+            max_length,
+            hints: metadata_hints(headers),
+        })
+    }
+
+    /// Returns an extractor configured for this request
+    pub fn extractor(&self) -> Extractor {
+        Extractor::new()
+            .set_pdf_config(self.pdf_config.clone())
+            .set_ocr_config(self.ocr_config.clone())
+            .set_extract_string_max_length(self.max_length)
+    }
+}
+
+fn invalid_header(name: &str, reason: &str) -> ServerError {
+    ServerError::BadRequest(format!("Invalid header {}: {}", name, reason))
+}
+
+fn parse_bool(value: &str) -> Result<bool, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "true" => Ok(true),
+        "false" => Ok(false),
+        _ => Err(format!("expected true or false, got {:?}", value)),
+    }
+}
+
+fn parse_int(value: &str) -> Result<i32, String> {
+    value
+        .parse()
+        .map_err(|_| format!("expected an integer, got {:?}", value))
+}
+
+fn parse_long(value: &str) -> Result<i64, String> {
+    value
+        .parse()
+        .map_err(|_| format!("expected an integer, got {:?}", value))
+}
+
+fn parse_number<T: FromStr>(value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("expected a number, got {:?}", value))
+}
+
+/// Parses a case-insensitive enum value, `expected` lists the accepted values
+fn parse_enum<T: FromStr>(value: &str, expected: &str) -> Result<T, String> {
+    T::from_str(&value.to_ascii_uppercase())
+        .map_err(|_| format!("expected {}, got {:?}", expected, value))
+}
+
+fn parse_ocr_strategy(value: &str) -> Result<PdfOcrStrategy, String> {
+    let name = match value.to_ascii_uppercase().as_str() {
+        // tika-server accepts the short name as well
+        "OCR_AND_TEXT" => "OCR_AND_TEXT_EXTRACTION".to_string(),
+        name => name.to_string(),
+    };
+    PdfOcrStrategy::from_str(&name).map_err(|_| {
+        format!(
+            "expected no_ocr, ocr_only, ocr_and_text_extraction or auto, got {:?}",
+            value
+        )
+    })
+}
+
+/// Applies a `X-Tika-PDF<property>` header. The property is lowercase
+fn apply_pdf_header(
+    config: PdfParserConfig,
+    property: &str,
+    value: &str,
+) -> Result<PdfParserConfig, String> {
+    Ok(match property {
+        "ocrstrategy" => config.set_ocr_strategy(parse_ocr_strategy(value)?),
+        "extractinlineimages" => config.set_extract_inline_images(parse_bool(value)?),
+        "extractuniqueinlineimagesonly" => {
+            config.set_extract_unique_inline_images_only(parse_bool(value)?)
+        }
+        "extractmarkedcontent" => config.set_extract_marked_content(parse_bool(value)?),
+        "extractannotationtext" => config.set_extract_annotation_text(parse_bool(value)?),
+        "sortbyposition" => config.set_sort_by_position(parse_bool(value)?),
+        "spacingtolerance" => config.set_spacing_tolerance(parse_number(value)?),
+        "averagechartolerance" => config.set_average_char_tolerance(parse_number(value)?),
+        "enableautospace" => config.set_enable_auto_space(parse_bool(value)?),
+        "suppressduplicateoverlappingtext" => {
+            config.set_suppress_duplicate_overlapping_text(parse_bool(value)?)
+        }
+        "extractacroformcontent" => config.set_extract_acro_form_content(parse_bool(value)?),
+        "extractbookmarkstext" => config.set_extract_bookmarks_text(parse_bool(value)?),
+        "extractactions" => config.set_extract_actions(parse_bool(value)?),
+        "ocrdpi" => config.set_ocr_dpi(parse_int(value)?),
+        "ocrimagetype" => {
+            config.set_ocr_image_type(parse_enum(value, "binary, gray, rgb or argb")?)
+        }
+        // tika-server names the setting after the java setter setOcrImageFormatName
+        "ocrimageformat" | "ocrimageformatname" => {
+            config.set_ocr_image_format(parse_enum(value, "png, tiff or jpeg")?)
+        }
+        "ocrrenderingstrategy" => config.set_ocr_rendering_strategy(parse_enum(
+            value,
+            "no_text, text_only, vector_graphics_only or all",
+        )?),
+        "maxmainmemorybytes" => config.set_max_main_memory_bytes(parse_number(value)?),
+        "catchintermediateioexceptions" => {
+            config.set_catch_intermediate_io_exceptions(parse_bool(value)?)
+        }
+        _ => return Err("unsupported pdf parser setting".to_string()),
+    })
+}
+
+/// Applies a `X-Tika-OCR<property>` header. The property is lowercase
+fn apply_ocr_header(
+    config: TesseractOcrConfig,
+    property: &str,
+    value: &str,
+) -> Result<TesseractOcrConfig, String> {
+    Ok(match property {
+        "language" => config.set_language(value),
+        "density" => config.set_density(parse_int(value)?),
+        "depth" => config.set_depth(parse_int(value)?),
+        "timeoutseconds" => config.set_timeout_seconds(parse_int(value)?),
+        "enableimagepreprocessing" => config.set_enable_image_preprocessing(parse_bool(value)?),
+        "applyrotation" => config.set_apply_rotation(parse_bool(value)?),
+        "pagesegmode" => config.set_page_seg_mode(parse_int(value)?),
+        "preserveinterwordspacing" => config.set_preserve_interword_spacing(parse_bool(value)?),
+        "minfilesizetoocr" => config.set_min_file_size_to_ocr(parse_long(value)?),
+        "maxfilesizetoocr" => config.set_max_file_size_to_ocr(parse_long(value)?),
+        "colorspace" => config.set_colorspace(value),
+        "filter" => config.set_filter(value),
+        "resize" => config.set_resize(parse_int(value)?),
+        "outputtype" => config.set_output_type(parse_enum(value, "txt or hocr")?),
+        // Like tika-server, the executable paths can not be changed per request
+        "tesseractpath" | "tessdatapath" | "imagemagickpath" => {
+            return Err(
+                "the tesseract and ImageMagick paths can not be set per request".to_string(),
+            )
+        }
+        _ => return Err("unsupported ocr setting".to_string()),
+    })
+}
+
+/// Returns the file name from a `Content-Disposition` header value, e.g.
+/// `attachment; filename="report.pdf"`
+fn content_disposition_file_name(value: &str) -> Option<String> {
+    value
+        .split(';')
+        .filter_map(|part| part.trim().strip_prefix("filename="))
+        .map(|name| name.trim().trim_matches('"').to_string())
+        .find(|name| !name.is_empty())
+}
+
+/// Returns the detection hints of the request: the `resourceName` from the
+/// `Content-Disposition` or `File-Name` headers and the `Content-Type`, unless it is the
+/// generic `application/octet-stream`
+pub fn metadata_hints(headers: &HeaderMap) -> Metadata {
+    let mut hints = Metadata::new();
+
+    let file_name = headers
+        .get(CONTENT_DISPOSITION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(content_disposition_file_name)
+        .or_else(|| {
+            headers
+                .get(FILE_NAME_HEADER)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        });
+    if let Some(file_name) = file_name {
+        hints.insert("resourceName".to_string(), vec![file_name]);
+    }
+
+    let content_type = headers
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|v| !v.is_empty() && !v.starts_with("application/octet-stream"));
+    if let Some(content_type) = content_type {
+        hints.insert("Content-Type".to_string(), vec![content_type.to_string()]);
+    }
+    hints
+}
+
+/// Returns true if the `Accept` header contains one of the given mime types
+pub fn accepts(headers: &HeaderMap, mime_types: &[&str]) -> bool {
+    headers
+        .get_all(ACCEPT)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .map(|v| v.split(';').next().unwrap_or_default().trim())
+        .any(|v| mime_types.iter().any(|m| v.eq_ignore_ascii_case(m)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+    use extractous::{OcrOutputType, PdfOcrImageFormat, PdfOcrImageType, PdfOcrRenderingStrategy};
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.append(*name, HeaderValue::from_static(value));
+        }
+        headers
+    }
+
+    #[test]
+    fn pdf_and_ocr_headers_test() {
+        let config = RequestConfig::from_headers(
+            &headers(&[
+                ("X-Tika-PDFOcrStrategy", "ocr_only"),
+                ("X-Tika-PDFextractAnnotationText", "FALSE"),
+                ("x-tika-ocrlanguage", "deu+eng"),
+                ("X-Tika-OCRTimeoutSeconds", "60"),
+                ("X-Tika-OCRPageSegMode", "6"),
+                ("X-Tika-OCRFilter", "gaussian"),
+                ("X-Tika-OCROutputType", "hocr"),
+                ("writeLimit", "100"),
+            ]),
+            -1,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.pdf_config,
+            PdfParserConfig::new()
+                .set_ocr_strategy(PdfOcrStrategy::OCR_ONLY)
+                .set_extract_annotation_text(false)
+        );
+        assert_eq!(
+            config.ocr_config,
+            TesseractOcrConfig::new()
+                .set_language("deu+eng")
+                .set_timeout_seconds(60)
+                .set_page_seg_mode(6)
+                .set_filter("gaussian")
+                .set_output_type(OcrOutputType::HOCR)
+        );
+        assert_eq!(config.max_length, 100);
+
+        let config = RequestConfig::from_headers(
+            &headers(&[
+                ("X-Tika-PDFsortByPosition", "true"),
+                ("X-Tika-PDFspacingTolerance", "0.4"),
+                ("X-Tika-PDFocrImageType", "rgb"),
+                ("X-Tika-PDFocrImageFormatName", "tiff"),
+                ("X-Tika-PDFocrRenderingStrategy", "no_text"),
+                ("X-Tika-PDFmaxMainMemoryBytes", "-1"),
+            ]),
+            -1,
+        )
+        .unwrap();
+        assert_eq!(
+            config.pdf_config,
+            PdfParserConfig::new()
+                .set_sort_by_position(true)
+                .set_spacing_tolerance(0.4)
+                .set_ocr_image_type(PdfOcrImageType::RGB)
+                .set_ocr_image_format(PdfOcrImageFormat::TIFF)
+                .set_ocr_rendering_strategy(PdfOcrRenderingStrategy::NO_TEXT)
+                .set_max_main_memory_bytes(-1)
+        );
+
+        let config =
+            RequestConfig::from_headers(&headers(&[("X-Tika-PDFOcrStrategy", "ocr_and_text")]), -1)
+                .unwrap();
+        assert_eq!(
+            config.pdf_config,
+            PdfParserConfig::new().set_ocr_strategy(PdfOcrStrategy::OCR_AND_TEXT_EXTRACTION)
+        );
+        assert_eq!(config.max_length, -1);
+    }
+
+    #[test]
+    fn invalid_headers_test() {
+        for pairs in [
+            [("X-Tika-PDFOcrStrategy", "sometimes")],
+            [("X-Tika-PDFextractInlineImages", "yes")],
+            [("X-Tika-PDFsortByPosition", "1")],
+            [("X-Tika-PDFocrImageType", "cmyk")],
+            [("X-Tika-PDFunknownSetting", "true")],
+            [("X-Tika-OCRDensity", "high")],
+            [("X-Tika-OCRDensity", "50")],
+            [("X-Tika-OCRPageSegMode", "14")],
+            [("X-Tika-OCRTesseractPath", "/tmp")],
+            [("writeLimit", "all")],
+        ] {
+            let result = RequestConfig::from_headers(&headers(&pairs), -1);
+            assert!(
+                matches!(result, Err(ServerError::BadRequest(_))),
+                "{:?} should be rejected",
+                pairs
+            );
+        }
+    }
+
+    #[test]
+    fn metadata_hints_test() {
+        let hints = metadata_hints(&headers(&[
+            ("Content-Disposition", "attachment; filename=\"report.pdf\""),
+            ("File-Name", "ignored.pdf"),
+            ("Content-Type", "application/pdf"),
+        ]));
+        assert_eq!(hints["resourceName"], vec!["report.pdf"]);
+        assert_eq!(hints["Content-Type"], vec!["application/pdf"]);
+
+        let hints = metadata_hints(&headers(&[
+            ("File-Name", "notes.docx"),
+            ("Content-Type", "application/octet-stream"),
+        ]));
+        assert_eq!(hints["resourceName"], vec!["notes.docx"]);
+        assert!(!hints.contains_key("Content-Type"));
+    }
+
+    #[test]
+    fn accepts_test() {
+        let headers = headers(&[("Accept", "text/html;q=0.9, application/json")]);
+        assert!(accepts(&headers, &["application/json"]));
+        assert!(accepts(&headers, &["text/xml", "text/html"]));
+        assert!(!accepts(&headers, &["text/plain"]));
+    }
+}
@@ -0,0 +1,87 @@
+mod error;
+mod headers;
+mod metadata;
+mod routes;
+
+use std::net::SocketAddr;
+use std::process::ExitCode;
+use std::thread;
+use std::time::Duration;
+
+use clap::Parser;
+
+use crate::routes::AppState;
+
+/// Apache Tika server compatible HTTP server for Extractous
+#[derive(Parser, Debug)]
+#[command(name = "extractous-server", version, about)]
+struct Args {
+    /// Address to listen on
+    #[arg(long, env = "EXTRACTOUS_SERVER_HOST", default_value = "127.0.0.1")]
+    host: String,
+
+    /// Port to listen on, the default is the tika-server port
+    #[arg(short, long, env = "EXTRACTOUS_SERVER_PORT", default_value_t = 9998)]
+    port: u16,
+
+    /// Maximum size in bytes of a request body, larger requests are rejected with 413
+    #[arg(long, default_value_t = 100 * 1024 * 1024)]
+    max_body_bytes: usize,
+
+    /// Maximum number of documents extracted at the same time. Default: number of cpus
+    #[arg(long)]
+    max_concurrency: Option<usize>,
+
+    /// Seconds a request waits for a free extraction slot before it is rejected with 503.
+    /// By default requests wait until a slot is free
+    #[arg(long)]
+    queue_timeout_seconds: Option<u64>,
+
+    /// Default maximum length of the extracted text of a document, -1 for no limit.
+    /// Can be overridden per request with the `writeLimit` header
+    #[arg(long, default_value_t = -1, allow_negative_numbers = true)]
+    max_length: i32,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+
+    let max_concurrency = args
+        .max_concurrency
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()))
+        .max(1);
+    let state = AppState::new(
+        max_concurrency,
+        args.queue_timeout_seconds.map(Duration::from_secs),
+        args.max_length,
+    );
+    let app = routes::router(state, args.max_body_bytes);
+
+    let addr = format!("{}:{}", args.host, args.port);
+    let listener = match tokio::net::TcpListener::bind(&addr).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("extractous-server: failed to listen on {}: {}", addr, e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let local_addr = listener
+        .local_addr()
+        .map_or(addr, |a: SocketAddr| a.to_string());
+    eprintln!(
+        "extractous-server {} listening on http://{} with {} extraction slots",
+        env!("CARGO_PKG_VERSION"),
+        local_addr,
+        max_concurrency
+    );
+
+    let server = axum::serve(listener, app).with_graceful_shutdown(async {
+        tokio::signal::ctrl_c().await.ok();
+    });
+    if let Err(e) = server.await {
+        eprintln!("extractous-server: {}", e);
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,95 @@
+use extractous::Metadata;
+use serde_json::{Map, Value};
+
+/// The metadata key of the extracted content in the tika-server json responses
+pub const CONTENT_KEY: &str = "X-TIKA:content";
+
+/// Converts the metadata to a json object like tika-server does: keys with a single value
+/// are strings, keys with multiple values are arrays. Keys are sorted
+pub fn metadata_to_json(metadata: &Metadata) -> Map<String, Value> {
+    let mut keys: Vec<_> = metadata.keys().collect();
+    keys.sort();
+
+    let mut object = Map::new();
+    for key in keys {
+        let values = &metadata[key];
+        let value = match values.as_slice() {
+            [value] => Value::String(value.clone()),
+            values => Value::from(values.to_vec()),
+        };
+        object.insert(key.clone(), value);
+    }
+    object
+}
+
+/// Same as [`metadata_to_json`] with the extracted content added under [`CONTENT_KEY`]
+pub fn metadata_with_content_to_json(metadata: &Metadata, content: &str) -> Value {
+    let mut object = metadata_to_json(metadata);
+    object.insert(CONTENT_KEY.to_string(), Value::String(content.to_string()));
+    Value::Object(object)
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Converts the metadata to csv like tika-server does: one line per key, the key followed
+/// by its values. Keys are sorted
+pub fn metadata_to_csv(metadata: &Metadata) -> String {
+    let mut keys: Vec<_> = metadata.keys().collect();
+    keys.sort();
+
+    let mut csv = String::new();
+    for key in keys {
+        let fields: Vec<_> = std::iter::once(key)
+            .chain(metadata[key].iter())
+            .map(|f| csv_field(f))
+            .collect();
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn metadata() -> Metadata {
+        Metadata::from([
+            (
+                "dc:title".to_string(),
+                vec!["Report, \"final\"".to_string()],
+            ),
+            (
+                "dc:creator".to_string(),
+                vec!["Alice".to_string(), "Bob".to_string()],
+            ),
+        ])
+    }
+
+    #[test]
+    fn metadata_to_json_test() {
+        assert_eq!(
+            metadata_with_content_to_json(&metadata(), "text"),
+            json!({
+                "dc:creator": ["Alice", "Bob"],
+                "dc:title": "Report, \"final\"",
+                "X-TIKA:content": "text",
+            })
+        );
+    }
+
+    #[test]
+    fn metadata_to_csv_test() {
+        assert_eq!(
+            metadata_to_csv(&metadata()),
+            "dc:creator,Alice,Bob\ndc:title,\"Report, \"\"final\"\"\"\n"
+        );
+    }
+}
@@ -0,0 +1,512 @@
+use std::io::{Cursor, Write};
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::body::Bytes;
+use axum::extract::{DefaultBodyLimit, Path, State};
+use axum::http::header::CONTENT_TYPE;
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, put};
+use axum::{Json, Router};
+use extractous::{EmbeddedDocument, Metadata};
+use serde_json::{json, Value};
+use tokio::sync::Semaphore;
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+use crate::error::{ServerError, ServerResult};
+use crate::headers::{accepts, RequestConfig};
+use crate::metadata::{
+    metadata_to_csv, metadata_to_json, metadata_with_content_to_json, CONTENT_KEY,
+};
+
+const TEXT_PLAIN: &str = "text/plain; charset=UTF-8";
+const TEXT_HTML: &str = "text/html; charset=UTF-8";
+const APPLICATION_XML: &str = "application/xml; charset=UTF-8";
+const TEXT_CSV: &str = "text/csv; charset=UTF-8";
+
+/// State shared by all the requests
+#[derive(Clone)]
+pub struct AppState {
+    /// One permit per extraction that can run at the same time
+    slots: Arc<Semaphore>,
+    max_concurrency: usize,
+    queue_timeout: Option<Duration>,
+    default_max_length: i32,
+}
+
+impl AppState {
+    pub fn new(max_concurrency: usize, queue_timeout: Option<Duration>, max_length: i32) -> Self {
+        Self {
+            slots: Arc::new(Semaphore::new(max_concurrency)),
+            max_concurrency,
+            queue_timeout,
+            default_max_length: max_length,
+        }
+    }
+
+    fn request_config(&self, headers: &HeaderMap) -> ServerResult<RequestConfig> {
+        RequestConfig::from_headers(headers, self.default_max_length)
+    }
+
+    /// Runs an extraction on the blocking thread pool once an extraction slot is free.
+    /// Fails with [`ServerError::Busy`] if no slot is free within the queue timeout
+    async fn run<T, F>(&self, f: F) -> ServerResult<T>
+    where
+        F: FnOnce() -> ServerResult<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let acquire = self.slots.clone().acquire_owned();
+        let permit = match self.queue_timeout {
+            Some(timeout) => tokio::time::timeout(timeout, acquire)
+                .await
+                .map_err(|_| ServerError::Busy)?,
+            None => acquire.await,
+        }
+        .map_err(|_| ServerError::Busy)?;
+
+        tokio::task::spawn_blocking(move || {
+            // The slot is released when the extraction is done
+            let _permit = permit;
+            f()
+        })
+        .await?
+    }
+}
+
+/// Builds the tika-server compatible routes
+pub fn router(state: AppState, max_body_bytes: usize) -> Router {
+    Router::new()
+        .route("/health", get(health))
+        .route("/version", get(version))
+        .route("/tika", get(tika_greeting).put(tika))
+        .route("/tika/main", put(tika_main))
+        .route("/tika/{format}", put(tika_json))
+        .route("/meta", put(meta))
+        .route("/meta/{field}", put(meta_field))
+        .route("/detect/stream", put(detect))
+        .route("/rmeta", put(rmeta))
+        .route("/rmeta/{format}", put(rmeta_with_format))
+        .route("/unpack", put(unpack))
+        .route("/unpack/all", put(unpack_all))
+        // Larger bodies are rejected with 413 Payload Too Large
+        .layer(DefaultBodyLimit::max(max_body_bytes))
+        .with_state(state)
+}
+
+/// The format of the extracted content selected by the `/tika/{format}` and
+/// `/rmeta/{format}` paths
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ContentFormat {
+    Text,
+    Xml,
+    Ignore,
+}
+
+impl ContentFormat {
+    fn from_path(format: &str) -> ServerResult<Self> {
+        match format {
+            "text" => Ok(ContentFormat::Text),
+            "html" | "xml" => Ok(ContentFormat::Xml),
+            "ignore" => Ok(ContentFormat::Ignore),
+            _ => Err(ServerError::NotFound(format!(
+                "Unknown content format {:?}, expected text, html, xml or ignore",
+                format
+            ))),
+        }
+    }
+}
+
+async fn health(State(state): State<AppState>) -> Json<Value> {
+    Json(json!({
+        "status": "ok",
+        "max_concurrency": state.max_concurrency,
+        "available_slots": state.slots.available_permits(),
+    }))
+}
+
+async fn version() -> &'static str {
+    concat!("Extractous Server ", env!("CARGO_PKG_VERSION"))
+}
+
+async fn tika_greeting() -> &'static str {
+    concat!(
+        "This is Tika Server (extractous-server ",
+        env!("CARGO_PKG_VERSION"),
+        "). Please PUT\n"
+    )
+}
+
+/// Extracts the content and metadata of the request body
+async fn extract(
+    state: &AppState,
+    config: RequestConfig,
+    body: Bytes,
+    as_xml: bool,
+) -> ServerResult<(String, Metadata)> {
+    state
+        .run(move || {
+            let extractor = config.extractor().set_xml_output(as_xml);
+            Ok(extractor.extract_bytes_to_string_with_hints(&body, &config.hints)?)
+        })
+        .await
+}
+
+/// `PUT /tika`: the content as text, or as xhtml when the `Accept` header asks for html or
+/// xml. The content and metadata are returned as json when the `Accept` header asks for json
+async fn tika(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> ServerResult<Response> {
+    let config = state.request_config(&headers)?;
+    let as_json = accepts(&headers, &["application/json"]);
+    let as_html = accepts(&headers, &["text/html", "application/xhtml+xml"]);
+    let as_xml = as_html || accepts(&headers, &["text/xml", "application/xml"]);
+
+    let (content, metadata) = extract(&state, config, body, as_xml).await?;
+    if as_json {
+        return Ok(Json(metadata_with_content_to_json(&metadata, &content)).into_response());
+    }
+    let content_type = match (as_html, as_xml) {
+        (true, _) => TEXT_HTML,
+        (_, true) => APPLICATION_XML,
+        _ => TEXT_PLAIN,
+    };
+    Ok(([(CONTENT_TYPE, content_type)], content).into_response())
+}
+
+/// `PUT /tika/main`: the main content of html documents as text, without the navigation,
+/// banners and footers of the page
+async fn tika_main(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> ServerResult<Response> {
+    let config = state.request_config(&headers)?;
+    let (content, _metadata) = state
+        .run(move || {
+            let extractor = config.extractor().set_html_main_content(true);
+            Ok(extractor.extract_bytes_to_string_with_hints(&body, &config.hints)?)
+        })
+        .await?;
+    Ok(([(CONTENT_TYPE, TEXT_PLAIN)], content).into_response())
+}
+
+/// `PUT /tika/{text,html,xml}`: the metadata as json with the content under `X-TIKA:content`
+async fn tika_json(
+    State(state): State<AppState>,
+    Path(format): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> ServerResult<Json<Value>> {
+    let format = ContentFormat::from_path(&format)?;
+    if format == ContentFormat::Ignore {
+        return Err(ServerError::NotFound(
+            "The ignore format is only supported by /rmeta".to_string(),
+        ));
+    }
+    let config = state.request_config(&headers)?;
+
+    let (content, metadata) = extract(&state, config, body, format == ContentFormat::Xml).await?;
+    Ok(Json(metadata_with_content_to_json(&metadata, &content)))
+}
+
+/// `PUT /meta`: the metadata as json, or as csv when the `Accept` header asks for csv
+async fn meta(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> ServerResult<Response> {
+    let config = state.request_config(&headers)?;
+    let (_content, metadata) = extract(&state, config, body, false).await?;
+
+    if accepts(&headers, &["text/csv"]) {
+        return Ok(([(CONTENT_TYPE, TEXT_CSV)], metadata_to_csv(&metadata)).into_response());
+    }
+    Ok(Json(Value::Object(metadata_to_json(&metadata))).into_response())
+}
+
+/// `PUT /meta/{field}`: a single metadata field as json, 404 if the document does not have it
+async fn meta_field(
+    State(state): State<AppState>,
+    Path(field): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> ServerResult<Json<Value>> {
+    let config = state.request_config(&headers)?;
+    let (_content, mut metadata) = extract(&state, config, body, false).await?;
+
+    let values = metadata
+        .remove(&field)
+        .ok_or_else(|| ServerError::NotFound(format!("Metadata field {} not found", field)))?;
+    let field_metadata = Metadata::from([(field, values)]);
+    Ok(Json(Value::Object(metadata_to_json(&field_metadata))))
+}
+
+/// `PUT /detect/stream`: the detected mime type as text. The `Content-Disposition` and
+/// `File-Name` headers are used as detection hints
+async fn detect(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> ServerResult<Response> {
+    let config = state.request_config(&headers)?;
+    let (mime_type, _metadata) = state
+        .run(move || {
+            Ok(config
+                .extractor()
+                .detect_bytes_with_hints(&body, &config.hints)?)
+        })
+        .await?;
+    Ok(([(CONTENT_TYPE, TEXT_PLAIN)], mime_type).into_response())
+}
+
+async fn recursive_metadata(
+    state: AppState,
+    headers: HeaderMap,
+    body: Bytes,
+    format: ContentFormat,
+) -> ServerResult<Json<Value>> {
+    let config = state.request_config(&headers)?;
+    let mut metadata_list = state
+        .run(move || {
+            let extractor = config
+                .extractor()
+                .set_xml_output(format == ContentFormat::Xml);
+            Ok(extractor.extract_bytes_recursive(&body, &config.hints)?)
+        })
+        .await?;
+
+    if format == ContentFormat::Ignore {
+        for metadata in metadata_list.iter_mut() {
+            metadata.remove(CONTENT_KEY);
+        }
+    }
+    let documents = metadata_list
+        .iter()
+        .map(|metadata| Value::Object(metadata_to_json(metadata)))
+        .collect();
+    Ok(Json(Value::Array(documents)))
+}
+
+/// `PUT /rmeta`: a json array with the metadata and xhtml content of the document and of
+/// every document embedded in it
+async fn rmeta(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> ServerResult<Json<Value>> {
+    recursive_metadata(state, headers, body, ContentFormat::Xml).await
+}
+
+/// `PUT /rmeta/{text,html,xml,ignore}`: same as `/rmeta` with the given content format
+async fn rmeta_with_format(
+    State(state): State<AppState>,
+    Path(format): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> ServerResult<Json<Value>> {
+    let format = ContentFormat::from_path(&format)?;
+    recursive_metadata(state, headers, body, format).await
+}
+
+/// Writes the embedded documents to a zip archive. With `all`, the text and metadata of the
+/// container document are added as `__TEXT__` and `__METADATA__`
+fn write_zip(
+    content: &str,
+    metadata: &Metadata,
+    embedded: &[EmbeddedDocument],
+    all: bool,
+) -> ServerResult<Vec<u8>> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default();
+
+    for document in embedded.iter() {
+        zip.start_file(document.name.as_str(), options)?;
+        zip.write_all(&document.data)
+            .map_err(zip::result::ZipError::from)?;
+    }
+    if all {
+        zip.start_file("__TEXT__", options)?;
+        zip.write_all(content.as_bytes())
+            .map_err(zip::result::ZipError::from)?;
+        zip.start_file("__METADATA__", options)?;
+        zip.write_all(metadata_to_csv(metadata).as_bytes())
+            .map_err(zip::result::ZipError::from)?;
+    }
+    Ok(zip.finish()?.into_inner())
+}
+
+async fn unpack_to_zip(
+    state: AppState,
+    headers: HeaderMap,
+    body: Bytes,
+    all: bool,
+) -> ServerResult<Response> {
+    let config = state.request_config(&headers)?;
+    let archive = state
+        .run(move || {
+            let (content, metadata, embedded) =
+                config.extractor().unpack_bytes(&body, &config.hints)?;
+            if embedded.is_empty() && !all {
+                return Ok(None);
+            }
+            write_zip(&content, &metadata, &embedded, all).map(Some)
+        })
+        .await?;
+
+    match archive {
+        Some(archive) => Ok(([(CONTENT_TYPE, "application/zip")], archive).into_response()),
+        // Like tika-server, documents without embedded documents have no content
+        None => Ok(StatusCode::NO_CONTENT.into_response()),
+    }
+}
+
+/// `PUT /unpack`: a zip archive with the raw bytes of the embedded documents, or 204 No
+/// Content if there are none
+async fn unpack(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> ServerResult<Response> {
+    unpack_to_zip(state, headers, body, false).await
+}
+
+/// `PUT /unpack/all`: same as `/unpack` with the text and metadata of the document
+async fn unpack_all(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> ServerResult<Response> {
+    unpack_to_zip(state, headers, body, true).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{to_bytes, Body};
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    const TEST_FILE: &str = "../README.md";
+
+    fn app() -> Router {
+        router(AppState::new(2, None, -1), 1024 * 1024)
+    }
+
+    async fn send(app: Router, request: Request<Body>) -> (StatusCode, String) {
+        let response = app.oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, String::from_utf8_lossy(&body).into_owned())
+    }
+
+    fn put_file(uri: &str) -> Request<Body> {
+        let data = std::fs::read(TEST_FILE).unwrap();
+        Request::put(uri)
+            .header("File-Name", "README.md")
+            .body(Body::from(data))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn health_test() {
+        let request = Request::get("/health").body(Body::empty()).unwrap();
+        let (status, body) = send(app(), request).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let health: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(health["status"], "ok");
+        assert_eq!(health["available_slots"], 2);
+    }
+
+    #[tokio::test]
+    async fn request_limits_test() {
+        let limited_app = router(AppState::new(1, None, -1), 16);
+        let request = Request::put("/tika")
+            .body(Body::from(vec![b'a'; 64]))
+            .unwrap();
+        let (status, _body) = send(limited_app, request).await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+
+        let request = Request::put("/tika")
+            .header("X-Tika-PDFOcrStrategy", "sometimes")
+            .body(Body::from("text"))
+            .unwrap();
+        let (status, body) = send(app(), request).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body.contains("x-tika-pdfocrstrategy"));
+    }
+
+    #[tokio::test]
+    async fn tika_main_test() {
+        let page = "<html><body><nav><a href=\"/\">Home</a></nav><article>\
+                    <p>The article of the page, long enough to be its main content.</p>\
+                    </article><footer>Contact us</footer></body></html>";
+        let request = Request::put("/tika/main")
+            .header("File-Name", "news.html")
+            .body(Body::from(page))
+            .unwrap();
+        let (status, body) = send(app(), request).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.contains("The article of the page"));
+        assert!(!body.contains("Home") && !body.contains("Contact us"));
+    }
+
+    #[tokio::test]
+    async fn busy_test() {
+        let state = AppState::new(1, Some(Duration::from_millis(10)), -1);
+        let _slot = state.slots.clone().acquire_owned().await.unwrap();
+        let result = state.run(|| Ok(())).await;
+        assert!(matches!(result, Err(ServerError::Busy)));
+    }
+
+    #[tokio::test]
+    async fn tika_test() {
+        let (status, body) = send(app(), put_file("/tika")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.contains("Extractous"));
+
+        let request = put_file("/tika/text");
+        let (status, body) = send(app(), request).await;
+        assert_eq!(status, StatusCode::OK);
+        let document: Value = serde_json::from_str(&body).unwrap();
+        assert!(document[CONTENT_KEY]
+            .as_str()
+            .unwrap()
+            .contains("Extractous"));
+        assert_eq!(document["resourceName"], "README.md");
+    }
+
+    #[tokio::test]
+    async fn meta_and_detect_test() {
+        let (status, body) = send(app(), put_file("/meta/Content-Type")).await;
+        assert_eq!(status, StatusCode::OK);
+        let field: Value = serde_json::from_str(&body).unwrap();
+        assert!(field["Content-Type"].as_str().unwrap().starts_with("text/"));
+
+        let (status, _body) = send(app(), put_file("/meta/does-not-exist")).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, body) = send(app(), put_file("/detect/stream")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "text/x-web-markdown");
+    }
+
+    #[tokio::test]
+    async fn rmeta_and_unpack_test() {
+        let (status, body) = send(app(), put_file("/rmeta/ignore")).await;
+        assert_eq!(status, StatusCode::OK);
+        let documents: Vec<Value> = serde_json::from_str(&body).unwrap();
+        assert_eq!(documents.len(), 1);
+        assert!(documents[0].get(CONTENT_KEY).is_none());
+
+        let (status, _body) = send(app(), put_file("/unpack")).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+
+        let (status, _body) = send(app(), put_file("/unpack/all")).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+}